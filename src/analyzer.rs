@@ -1,6 +1,11 @@
 use crate::util;
 use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::process::Command;
+use std::sync::Mutex;
+use walkdir::WalkDir;
 
 /// Shared helper: validates the feature name and runs `code_analyse` with the
 /// assembled argument list: `<pre_args...> --feature <feature> <post_args...>`.
@@ -13,11 +18,12 @@ fn run_code_analyse(pre_args: &[&str], feature: &str, post_args: &[&str]) -> Res
     args.push(feature);
     args.extend_from_slice(post_args);
 
-    let output = Command::new("code_analyse")
-        .current_dir(&project_root)
-        .args(&args)
-        .output()
-        .with_context(|| format!("Failed to execute code_analyse {:?}", args))?;
+    let output = crate::interrupt::output(
+        Command::new("code_analyse")
+            .current_dir(&project_root)
+            .args(&args),
+    )
+    .with_context(|| format!("Failed to execute code_analyse {:?}", args))?;
 
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -33,18 +39,170 @@ fn run_code_analyse(pre_args: &[&str], feature: &str, post_args: &[&str]) -> Res
     Ok(())
 }
 
+/// Feature and source fingerprint of the last successful `code_analyse --update`.
+static LAST_UPDATE: Mutex<Option<(String, u64)>> = Mutex::new(None);
+
+/// Hash of the relative path, size and mtime of every file under `rust_dir` except
+/// `target/`: the C sources and the Rust files generated next to them.
+fn source_fingerprint(rust_dir: &Path) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    let walker = WalkDir::new(rust_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !(e.file_type().is_dir() && e.file_name() == "target"));
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        entry
+            .path()
+            .strip_prefix(rust_dir)
+            .unwrap_or(entry.path())
+            .hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok().hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+fn set_last_update(update: Option<(String, u64)>) {
+    *LAST_UPDATE.lock().unwrap_or_else(|e| e.into_inner()) = update;
+}
+
 /// Initialize code analysis for a feature.
 pub fn initialize_feature(feature: &str) -> Result<()> {
     println!("Running code_analyse --init --feature {}", feature);
+    set_last_update(None);
     run_code_analyse(&["--init"], feature, &[])
 }
 
 /// Update code analysis for a feature.
-pub fn update_code_analysis(feature: &str) -> Result<()> {
-    run_code_analyse(&["--update"], feature, &[])
+///
+/// Skipped when no file under `.c2rust/<feature>/rust` changed since the last update of the
+/// same feature, so the clean/build/test steps of one verification share a single run.
+/// Returns whether `code_analyse --update` actually ran; see [`update_status_message`].
+pub fn update_code_analysis(feature: &str) -> Result<bool> {
+    util::validate_feature_name(feature)?;
+    let rust_dir = util::find_project_root()?
+        .join(".c2rust")
+        .join(feature)
+        .join("rust");
+    // An unreadable tree is never treated as unchanged.
+    let fingerprint = source_fingerprint(&rust_dir).ok();
+    let last = LAST_UPDATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if fingerprint.is_some()
+        && last.as_ref() == fingerprint.map(|f| (feature.to_string(), f)).as_ref()
+    {
+        return Ok(false);
+    }
+
+    set_last_update(None);
+    run_code_analyse(&["--update"], feature, &[])?;
+    set_last_update(fingerprint.map(|f| (feature.to_string(), f)));
+    Ok(true)
+}
+
+/// Progress message for the result of [`update_code_analysis`]
+pub fn update_status_message(updated: bool) -> &'static str {
+    if updated {
+        "Code analysis updated"
+    } else {
+        "Code analysis unchanged since the last update (skipped)"
+    }
 }
 
 /// Notify code_analyse of build success after tests pass.
 pub fn update_code_analysis_build_success(feature: &str) -> Result<()> {
+    // A plain update after this must not be skipped as a repeat of the previous one.
+    set_last_update(None);
     run_code_analyse(&["--update"], feature, &["--build-success"])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_fingerprint_tracks_files_outside_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rust_dir = temp_dir.path();
+        let src = rust_dir.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(rust_dir.join("target").join("debug")).unwrap();
+        std::fs::write(
+            src.join("fun_add.c"),
+            "int add(int a, int b) { return a + b; }",
+        )
+        .unwrap();
+        std::fs::File::create(src.join("fun_add.rs")).unwrap();
+
+        let initial = source_fingerprint(rust_dir).unwrap();
+        assert_eq!(source_fingerprint(rust_dir).unwrap(), initial);
+
+        // Build artifacts do not affect the fingerprint.
+        std::fs::write(rust_dir.join("target").join("debug").join("out.o"), "obj").unwrap();
+        assert_eq!(source_fingerprint(rust_dir).unwrap(), initial);
+
+        // Writing a translation changes it.
+        std::fs::write(src.join("fun_add.rs"), "pub fn add() {}").unwrap();
+        assert_ne!(source_fingerprint(rust_dir).unwrap(), initial);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_unchanged_update_does_not_rerun_code_analyse() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path();
+        let src = project_root.join(".c2rust/default/rust/src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("fun_a.rs"), "").unwrap();
+
+        // A fake code_analyse that records each call.
+        let bin_dir = project_root.join("bin");
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        let calls = project_root.join("calls.txt");
+        let script = bin_dir.join("code_analyse");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\necho \"$*\" >> '{}'\n", calls.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let prior_path = std::env::var_os("PATH");
+        let mut path = std::ffi::OsString::from(&bin_dir);
+        if let Some(prior) = &prior_path {
+            path.push(":");
+            path.push(prior);
+        }
+        std::env::set_var("PATH", path);
+        let prior_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(project_root).unwrap();
+        let _restore = scopeguard::guard((prior_path, prior_dir), |(path, dir)| {
+            match path {
+                Some(path) => std::env::set_var("PATH", path),
+                None => std::env::remove_var("PATH"),
+            }
+            let _ = std::env::set_current_dir(dir);
+        });
+        set_last_update(None);
+
+        let call_count = || std::fs::read_to_string(&calls).unwrap().lines().count();
+        assert!(update_code_analysis("default").unwrap());
+        assert!(!update_code_analysis("default").unwrap());
+        assert_eq!(call_count(), 1);
+
+        std::fs::write(src.join("fun_a.rs"), "pub fn a() {}").unwrap();
+        assert!(update_code_analysis("default").unwrap());
+        assert_eq!(call_count(), 2);
+        set_last_update(None);
+    }
+}
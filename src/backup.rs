@@ -0,0 +1,252 @@
+//! 从头重试翻译前备份上一次的翻译结果
+//!
+//! 重试会覆盖 `.rs` 文件；覆盖前把现有内容保存到
+//! `rust/src/.bak/<相对 src 的目录>/<文件名>.<尝试次数>.<运行开始时间>.rs`，
+//! 以免一次能用但未通过构建的翻译被之后更差的结果替换后无法找回。尝试次数每次运行从 1 开始，
+//! 文件名中的运行开始时间（Unix 秒）避免覆盖之前运行的备份。每个文件最多保留
+//! [`MAX_BACKUPS_PER_FILE`] 份（超出时删除最旧的）。`translate --file ... --restore-attempt N`
+//! 可恢复最近一次运行中第 N 次尝试的备份。`.bak` 目录中的文件不参与文件扫描，也不会被提交。
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 备份目录名（位于 `rust/src/` 下）
+pub const BACKUP_DIR_NAME: &str = ".bak";
+
+/// 每个文件最多保留的备份数
+pub const MAX_BACKUPS_PER_FILE: usize = 5;
+
+/// 路径是否位于备份目录中
+pub fn is_backup_path(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == BACKUP_DIR_NAME)
+}
+
+/// 一份备份：第几次尝试、在哪次运行（开始时间，Unix 秒）中产生
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Backup {
+    pub run: u64,
+    pub attempt: usize,
+    pub path: PathBuf,
+}
+
+/// 本次运行（进程）的开始时间，用于区分不同运行中相同尝试次数的备份
+fn current_run() -> u64 {
+    static RUN: OnceLock<u64> = OnceLock::new();
+    *RUN.get_or_init(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    })
+}
+
+/// `rs_file` 的备份目录；`rs_file` 不在 `rust_dir/src` 下时返回 `None`
+fn backup_dir(rust_dir: &Path, rs_file: &Path) -> Option<PathBuf> {
+    let src_dir = rust_dir.join("src");
+    let relative = rs_file.strip_prefix(&src_dir).ok()?;
+    Some(
+        src_dir
+            .join(BACKUP_DIR_NAME)
+            .join(relative.parent().unwrap_or(Path::new(""))),
+    )
+}
+
+/// `rs_file` 在第 `run` 次运行中第 `attempt` 次翻译结果的备份路径
+fn backup_path(rust_dir: &Path, rs_file: &Path, attempt: usize, run: u64) -> Option<PathBuf> {
+    let stem = rs_file.file_stem()?.to_str()?;
+    Some(backup_dir(rust_dir, rs_file)?.join(format!("{}.{}.{}.rs", stem, attempt, run)))
+}
+
+/// 列出 `rs_file` 的备份，按运行先后、尝试次数排序
+pub fn list_backups(rust_dir: &Path, rs_file: &Path) -> Vec<Backup> {
+    let Some(dir) = backup_dir(rust_dir, rs_file) else {
+        return Vec::new();
+    };
+    let Some(stem) = rs_file.file_stem().and_then(|s| s.to_str()) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", stem);
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let (attempt, run) = name
+                .strip_prefix(&prefix)?
+                .strip_suffix(".rs")?
+                .split_once('.')?;
+            Some(Backup {
+                run: run.parse().ok()?,
+                attempt: attempt.parse().ok()?,
+                path: entry.path(),
+            })
+        })
+        .collect();
+    backups.sort();
+    backups
+}
+
+/// 重试前备份 `rs_file` 的当前内容（第 `attempt` 次翻译的结果）；文件为空时不备份
+///
+/// 返回备份文件路径。
+pub fn backup_before_retry(
+    rust_dir: &Path,
+    rs_file: &Path,
+    attempt: usize,
+) -> Result<Option<PathBuf>> {
+    let content = match std::fs::read(rs_file) {
+        Ok(content) if !content.is_empty() => content,
+        Ok(_) => return Ok(None),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", rs_file.display())),
+    };
+    let Some(path) = backup_path(rust_dir, rs_file, attempt, current_run()) else {
+        return Ok(None);
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write backup: {}", path.display()))?;
+    prune_backups(rust_dir, rs_file);
+    Ok(Some(path))
+}
+
+/// 超出 [`MAX_BACKUPS_PER_FILE`] 时删除最旧（修改时间最早）的备份
+fn prune_backups(rust_dir: &Path, rs_file: &Path) {
+    let mut backups = list_backups(rust_dir, rs_file);
+    if backups.len() <= MAX_BACKUPS_PER_FILE {
+        return;
+    }
+    backups.sort_by_key(|backup| {
+        let modified = std::fs::metadata(&backup.path)
+            .and_then(|m| m.modified())
+            .unwrap_or(UNIX_EPOCH);
+        (modified, backup.run, backup.attempt)
+    });
+    let excess = backups.len() - MAX_BACKUPS_PER_FILE;
+    for backup in backups.into_iter().take(excess) {
+        let _ = std::fs::remove_file(backup.path);
+    }
+}
+
+/// 用最近一次运行中第 `attempt` 次翻译的备份覆盖 `rs_file`；备份不存在时报错并列出可用的尝试次数
+pub fn restore_backup(rust_dir: &Path, rs_file: &Path, attempt: usize) -> Result<PathBuf> {
+    let backups = list_backups(rust_dir, rs_file);
+    let Some(path) = backups
+        .iter()
+        .rev()
+        .find(|backup| backup.attempt == attempt)
+        .map(|backup| &backup.path)
+    else {
+        let mut attempts: Vec<usize> = backups.iter().map(|backup| backup.attempt).collect();
+        attempts.sort_unstable();
+        attempts.dedup();
+        let available = if attempts.is_empty() {
+            "none".to_string()
+        } else {
+            attempts
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        anyhow::bail!(
+            "No backup of attempt {} for {} (available attempts: {})",
+            attempt,
+            rs_file.display(),
+            available
+        );
+    };
+    std::fs::copy(path, rs_file).with_context(|| {
+        format!(
+            "Failed to restore {} from {}",
+            rs_file.display(),
+            path.display()
+        )
+    })?;
+    Ok(path.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_prune_and_restore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rust_dir = temp_dir.path();
+        let rs_file = rust_dir.join("src/net/fun_a.rs");
+        std::fs::create_dir_all(rs_file.parent().unwrap()).unwrap();
+
+        std::fs::write(&rs_file, "").unwrap();
+        assert_eq!(backup_before_retry(rust_dir, &rs_file, 1).unwrap(), None);
+
+        for attempt in 1..=MAX_BACKUPS_PER_FILE + 1 {
+            std::fs::write(&rs_file, format!("// attempt {}\n", attempt)).unwrap();
+            let path = backup_before_retry(rust_dir, &rs_file, attempt)
+                .unwrap()
+                .unwrap();
+            assert!(is_backup_path(&path));
+            // 让修改时间可区分
+            let mtime = std::time::SystemTime::UNIX_EPOCH
+                + std::time::Duration::from_secs(1_000 + attempt as u64);
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        }
+        assert_eq!(
+            rust_dir.join(format!("src/.bak/net/fun_a.2.{}.rs", current_run())),
+            backup_path(rust_dir, &rs_file, 2, current_run()).unwrap()
+        );
+
+        // 超出上限时删除最旧的备份
+        std::fs::write(&rs_file, "// attempt 7\n").unwrap();
+        backup_before_retry(rust_dir, &rs_file, 7).unwrap();
+        let attempts: Vec<usize> = list_backups(rust_dir, &rs_file)
+            .into_iter()
+            .map(|backup| backup.attempt)
+            .collect();
+        assert_eq!(attempts.len(), MAX_BACKUPS_PER_FILE);
+        assert!(!attempts.contains(&1) && attempts.contains(&7));
+
+        restore_backup(rust_dir, &rs_file, 3).unwrap();
+        assert_eq!(std::fs::read_to_string(&rs_file).unwrap(), "// attempt 3\n");
+        let err = restore_backup(rust_dir, &rs_file, 1)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("available attempts: "), "{}", err);
+    }
+
+    #[test]
+    fn test_backups_from_an_earlier_run_are_kept() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rust_dir = temp_dir.path();
+        let rs_file = rust_dir.join("src/fun_b.rs");
+        std::fs::create_dir_all(rust_dir.join("src/.bak")).unwrap();
+        let earlier = rust_dir.join("src/.bak/fun_b.1.1000.rs");
+        std::fs::write(&earlier, "// earlier run\n").unwrap();
+
+        std::fs::write(&rs_file, "// this run\n").unwrap();
+        let path = backup_before_retry(rust_dir, &rs_file, 1).unwrap().unwrap();
+        assert_ne!(path, earlier);
+        assert_eq!(
+            std::fs::read_to_string(&earlier).unwrap(),
+            "// earlier run\n"
+        );
+
+        // 恢复时使用最近一次运行的备份
+        std::fs::write(&rs_file, "// attempt 2\n").unwrap();
+        restore_backup(rust_dir, &rs_file, 1).unwrap();
+        assert_eq!(std::fs::read_to_string(&rs_file).unwrap(), "// this run\n");
+    }
+}
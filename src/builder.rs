@@ -1,10 +1,617 @@
 use crate::analyzer;
+use crate::ui;
 use crate::util;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::env;
 use std::process::Command;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 构建 profile（对应 `build.profile`，决定 cargo 产物目录与混合构建链接的 `librust.a`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuildProfile {
+    #[default]
+    Debug,
+    Release,
+}
+
+impl BuildProfile {
+    /// profile 名称，同时也是 `target/` 下的产物目录名
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BuildProfile::Debug => "debug",
+            BuildProfile::Release => "release",
+        }
+    }
+}
+
+impl std::str::FromStr for BuildProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "debug" | "dev" => Ok(BuildProfile::Debug),
+            "release" => Ok(BuildProfile::Release),
+            other => Err(format!(
+                "`{other}` is not a valid build profile (expected debug or release)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for BuildProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 当前生效的构建 profile
+static ACTIVE_BUILD_PROFILE: Mutex<BuildProfile> = Mutex::new(BuildProfile::Debug);
+
+/// 接受翻译前需要额外校验的 profile（不含当前生效的主 profile）
+static EXTRA_VERIFY_PROFILES: Mutex<Vec<BuildProfile>> = Mutex::new(Vec::new());
+
+/// 获取当前生效的构建 profile
+pub fn active_build_profile() -> BuildProfile {
+    *ACTIVE_BUILD_PROFILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 设置当前生效的构建 profile
+pub fn set_active_build_profile(profile: BuildProfile) {
+    *ACTIVE_BUILD_PROFILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = profile;
+}
+
+/// 配置 `--verify-profiles` 的 profile 矩阵
+///
+/// 第一个 profile 作为主 profile（常规构建/测试流程使用），其余 profile 会在接受翻译前
+/// 额外各执行一次混合构建/测试。空列表等价于仅 `debug`。重复项会被忽略。
+pub fn set_verify_profiles(profiles: &[BuildProfile]) {
+    let mut unique: Vec<BuildProfile> = Vec::new();
+    for profile in profiles {
+        if !unique.contains(profile) {
+            unique.push(*profile);
+        }
+    }
+    let primary = unique.first().copied().unwrap_or_default();
+    set_active_build_profile(primary);
+    *EXTRA_VERIFY_PROFILES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = unique.into_iter().skip(1).collect();
+}
+
+/// 获取主 profile 之外需要额外校验的 profile
+pub fn extra_verify_profiles() -> Vec<BuildProfile> {
+    EXTRA_VERIFY_PROFILES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// 在指定 profile 下执行 `f`，结束后（包括出错时）恢复原来的 profile
+pub fn with_build_profile<T>(profile: BuildProfile, f: impl FnOnce() -> T) -> T {
+    let previous = active_build_profile();
+    set_active_build_profile(profile);
+    let _restore = ProfileRestoreGuard(previous);
+    f()
+}
+
+/// 作用域结束时恢复 profile 的守卫
+struct ProfileRestoreGuard(BuildProfile);
+
+impl Drop for ProfileRestoreGuard {
+    fn drop(&mut self) {
+        set_active_build_profile(self.0);
+    }
+}
+
+/// 通过 `--extra-rustflags` 追加的 RUSTFLAGS（整个运行期间有效）
+static EXTRA_RUSTFLAGS: Mutex<Option<String>> = Mutex::new(None);
+
+/// 返回 RUSTFLAGS 参数中第一个修改 `warnings` lint 级别（或 `--cap-lints`）的参数
+fn find_warnings_level_flag(parts: &[String]) -> Option<&String> {
+    let mut iter = parts.iter().peekable();
+    while let Some(part) = iter.next() {
+        let lint_target = match part.as_str() {
+            "-A" | "-W" | "-D" | "-F" | "--allow" | "--warn" | "--deny" | "--forbid" => {
+                iter.peek().map(|next| next.as_str())
+            }
+            other => ["-A", "-W", "-D", "-F"]
+                .iter()
+                .find_map(|prefix| other.strip_prefix(prefix))
+                .or_else(|| {
+                    ["--allow=", "--warn=", "--deny=", "--forbid="]
+                        .iter()
+                        .find_map(|prefix| other.strip_prefix(prefix))
+                }),
+        };
+        if lint_target == Some("warnings") || part.starts_with("--cap-lints") {
+            return Some(part);
+        }
+    }
+    None
+}
+
+/// 检查额外的 RUSTFLAGS 是否会改变 `warnings` lint 的级别
+///
+/// 抑制告警（`-A warnings`）由工具自身控制，额外参数不能覆盖它。
+fn validate_extra_rustflags(flags: &str) -> Result<()> {
+    let parts = shell_words::split(flags)
+        .with_context(|| format!("Failed to parse --extra-rustflags: {}", flags))?;
+    if let Some(part) = find_warnings_level_flag(&parts) {
+        anyhow::bail!(
+            "--extra-rustflags must not change the `warnings` lint level (found `{}`); \
+             warning handling is controlled by --warnings",
+            part
+        );
+    }
+    Ok(())
+}
+
+/// 环境变量 RUSTFLAGS 是否自行设置了 `warnings` lint 级别（此时不再追加 `-A warnings`）
+fn environment_sets_warnings_level(environment: &str) -> bool {
+    shell_words::split(environment)
+        .map(|parts| find_warnings_level_flag(&parts).is_some())
+        .unwrap_or(false)
+}
+
+/// 是否以 `-D warnings` 构建（`--deny-warnings`，整个运行期间有效）
+static DENY_WARNINGS: AtomicBool = AtomicBool::new(false);
+
+/// 设置 `--deny-warnings`
+pub fn set_deny_warnings(deny: bool) {
+    DENY_WARNINGS.store(deny, Ordering::Relaxed);
+}
+
+/// 启动时环境变量中的 RUSTFLAGS（空值等价于未设置）
+fn environment_rustflags() -> Option<String> {
+    env::var("RUSTFLAGS")
+        .ok()
+        .filter(|flags| !flags.trim().is_empty())
+}
+
+/// 设置 `--extra-rustflags`（空字符串等价于未设置）
+pub fn set_extra_rustflags(flags: Option<&str>) -> Result<()> {
+    let flags = flags.map(str::trim).filter(|f| !f.is_empty());
+    if let Some(flags) = flags {
+        validate_extra_rustflags(flags)?;
+    }
+    *EXTRA_RUSTFLAGS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = flags.map(str::to_string);
+    Ok(())
+}
+
+fn extra_rustflags() -> Option<String> {
+    EXTRA_RUSTFLAGS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// 构建命令（cargo check/build 以及 clean/build/test）默认的超时时间（秒）
+pub const DEFAULT_BUILD_TIMEOUT_SECS: u64 = 600;
+
+/// 通过 `--build-timeout` 指定的超时时间（秒），优先于配置的 `build.timeout`
+static BUILD_TIMEOUT_OVERRIDE: Mutex<Option<u64>> = Mutex::new(None);
+
+/// 设置 `--build-timeout`（`None` 表示使用配置的 `build.timeout` 或默认值）
+pub fn set_build_timeout(secs: Option<u64>) {
+    *BUILD_TIMEOUT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = secs;
+}
+
+/// 解析 `build.timeout` 配置值（正整数秒数）
+fn parse_build_timeout(value: &str) -> Result<u64> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(secs),
+        _ => anyhow::bail!(
+            "build.timeout must be a positive number of seconds, got: {}",
+            value
+        ),
+    }
+}
+
+/// 单个构建命令的超时时间：`--build-timeout` > `build.timeout` > [`DEFAULT_BUILD_TIMEOUT_SECS`]
+fn build_timeout(feature: &str) -> Duration {
+    let override_secs = *BUILD_TIMEOUT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let secs = override_secs.unwrap_or_else(|| {
+        // build.timeout 为可选配置，未设置时使用默认值
+        match get_config_value("build.timeout", feature) {
+            Ok(value) => parse_build_timeout(&value).unwrap_or_else(|e| {
+                eprintln!("Warning: {}; using {}s", e, DEFAULT_BUILD_TIMEOUT_SECS);
+                DEFAULT_BUILD_TIMEOUT_SECS
+            }),
+            Err(_) => DEFAULT_BUILD_TIMEOUT_SECS,
+        }
+    });
+    Duration::from_secs(secs)
+}
+
+/// 构建失败时错误消息中 stderr/stdout 各自保留的默认行数
+pub const DEFAULT_ERROR_OUTPUT_LINES: usize = 200;
+
+/// 错误消息中 stderr/stdout 各自保留的行数：配置的 `build.error_lines` > [`DEFAULT_ERROR_OUTPUT_LINES`]
+fn error_output_lines(feature: &str) -> usize {
+    // build.error_lines 为可选配置，未设置时使用默认值
+    match get_config_value("build.error_lines", feature) {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(lines) if lines > 0 => lines,
+            _ => {
+                eprintln!(
+                    "Warning: build.error_lines must be a positive number of lines, got: {}; using {}",
+                    value, DEFAULT_ERROR_OUTPUT_LINES
+                );
+                DEFAULT_ERROR_OUTPUT_LINES
+            }
+        },
+        Err(_) => DEFAULT_ERROR_OUTPUT_LINES,
+    }
+}
+
+/// 默认的构建机故障特征（磁盘满、内存不足、进程被 OOM killer 杀掉），可用 `build.transient_errors` 替换
+///
+/// 只与 cargo 自身输出的 stderr 行比较（见 [`cargo_own_lines`]），不匹配 rustc 诊断及其引用的源码。
+const DEFAULT_TRANSIENT_BUILD_ERRORS: &[&str] = &[
+    "(signal: 9, SIGKILL",
+    "os error 28",
+    "os error 12",
+    "memory allocation of",
+];
+
+/// 构建机故障导致失败时默认的重试次数（`build.transient_retries`）
+pub const DEFAULT_TRANSIENT_BUILD_RETRIES: usize = 2;
+
+/// 构建机故障重试前默认的等待时间（秒，`build.transient_cooldown`）
+pub const DEFAULT_TRANSIENT_BUILD_COOLDOWN_SECS: u64 = 30;
+
+/// 构建机故障（而非代码错误）导致的 cargo 失败，重试后仍然失败
+///
+/// 与 [`crate::diagnostics::CargoBuildError`] 区分开，修复流程不会把它交给修复器。
+#[derive(Debug)]
+pub struct TransientBuildError {
+    pub label: String,
+    /// 匹配到的故障特征
+    pub signature: String,
+    pub retries: usize,
+    pub output: String,
+}
+
+impl std::fmt::Display for TransientBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: build machine failure ({}) persisted after {} retr{}; not a code error: {}",
+            self.label,
+            self.signature,
+            self.retries,
+            if self.retries == 1 { "y" } else { "ies" },
+            self.output
+        )
+    }
+}
+
+impl std::error::Error for TransientBuildError {}
+
+/// 构建机故障特征：配置的 `build.transient_errors`（shell 风格分词）> [`DEFAULT_TRANSIENT_BUILD_ERRORS`]
+fn transient_build_errors(feature: &str) -> Vec<String> {
+    let defaults = || {
+        DEFAULT_TRANSIENT_BUILD_ERRORS
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    };
+    // build.transient_errors 为可选配置，未设置时使用默认值
+    match get_config_value("build.transient_errors", feature) {
+        Ok(value) => shell_words::split(&value).unwrap_or_else(|e| {
+            eprintln!(
+                "Warning: invalid build.transient_errors value ({}): {}; using the defaults",
+                e, value
+            );
+            defaults()
+        }),
+        Err(_) => defaults(),
+    }
+}
+
+/// 构建机故障时的重试次数与每次重试前的等待时间
+fn transient_build_retry_policy(feature: &str) -> (usize, Duration) {
+    let retries = match get_config_value("build.transient_retries", feature) {
+        Ok(value) => value.trim().parse::<usize>().unwrap_or_else(|_| {
+            eprintln!(
+                "Warning: build.transient_retries must be a non-negative integer, got: {}; using {}",
+                value, DEFAULT_TRANSIENT_BUILD_RETRIES
+            );
+            DEFAULT_TRANSIENT_BUILD_RETRIES
+        }),
+        Err(_) => DEFAULT_TRANSIENT_BUILD_RETRIES,
+    };
+    let cooldown = match get_config_value("build.transient_cooldown", feature) {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+            eprintln!(
+                "Warning: build.transient_cooldown must be a non-negative number of seconds, got: {}; using {}",
+                value, DEFAULT_TRANSIENT_BUILD_COOLDOWN_SECS
+            );
+            DEFAULT_TRANSIENT_BUILD_COOLDOWN_SECS
+        }),
+        Err(_) => DEFAULT_TRANSIENT_BUILD_COOLDOWN_SECS,
+    };
+    (retries, Duration::from_secs(cooldown))
+}
+
+/// 终止进程的信号（仅 Unix）
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+/// cargo stderr 中 cargo 自身输出的行：跳过 rustc 诊断（带 `-->` 位置的 `error`/`warning` 块，
+/// 到空行为止）以及引用源码、标注的 `N | ...` 行
+fn cargo_own_lines(stderr: &str) -> Vec<&str> {
+    let lines: Vec<&str> = stderr.lines().collect();
+    let mut own = Vec::new();
+    let mut in_diagnostic = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            in_diagnostic = false;
+            continue;
+        }
+        if in_diagnostic {
+            continue;
+        }
+        if (line.starts_with("error") || line.starts_with("warning"))
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| next.trim_start().starts_with("--> "))
+        {
+            in_diagnostic = true;
+            continue;
+        }
+        let gutter = line
+            .trim_start()
+            .trim_start_matches(|c: char| c.is_ascii_digit());
+        if gutter.trim_start().starts_with('|') {
+            continue;
+        }
+        own.push(*line);
+    }
+    own
+}
+
+/// 判断失败是否由构建机故障引起，返回匹配到的特征；cargo 被 SIGKILL 杀掉时也视为故障
+///
+/// 只检查 cargo 自身的 stderr 行，避免 rustc 诊断或其中引用的源码恰好包含特征时被误判。
+fn classify_transient_failure(
+    stderr: &str,
+    signal: Option<i32>,
+    signatures: &[String],
+) -> Option<String> {
+    if signal == Some(9) {
+        return Some("signal 9 (SIGKILL)".to_string());
+    }
+    let own_lines = cargo_own_lines(stderr);
+    signatures
+        .iter()
+        .find(|signature| {
+            !signature.is_empty()
+                && own_lines
+                    .iter()
+                    .any(|line| line.contains(signature.as_str()))
+        })
+        .cloned()
+}
+
+/// 只保留前 `max_lines` 行，截断时注明省略的行数（`show_full_output` 时不截断）
+fn truncate_output(text: &str, max_lines: usize, show_full_output: bool) -> String {
+    let total = text.lines().count();
+    if show_full_output || total <= max_lines {
+        return text.to_string();
+    }
+    let mut truncated: String = text
+        .lines()
+        .take(max_lines)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    truncated.push_str(&format!(
+        "... ({} more lines truncated; use --show-full-output to see everything)\n",
+        total - max_lines
+    ));
+    truncated
+}
+
+/// 组合构建失败时的输出：有 stdout 时分别标注 stderr 与 stdout，各自按行数截断
+fn format_failure_output(
+    stderr: &str,
+    stdout: &str,
+    max_lines: usize,
+    show_full_output: bool,
+) -> String {
+    let stderr = truncate_output(stderr, max_lines, show_full_output);
+    if stdout.trim().is_empty() {
+        return stderr;
+    }
+    format!(
+        "\n--- stderr ---\n{}\n--- stdout ---\n{}",
+        stderr.trim_end(),
+        truncate_output(stdout, max_lines, show_full_output)
+    )
+}
+
+/// 执行命令并收集输出，超过 `timeout` 时终止整个进程组并返回超时错误
+///
+/// 子进程放在独立的进程组中，超时后连同它启动的子进程（如 cargo 调用的 rustc、
+/// 测试二进制）一起终止。stdout/stderr 由后台线程读取，避免管道写满导致子进程阻塞。
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+    command_desc: &str,
+) -> Result<std::process::Output> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    let _tracked = crate::interrupt::track_child(child.id());
+    let mut stdout_pipe = child.stdout.take();
+    let mut stderr_pipe = child.stderr.take();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            anyhow::bail!(
+                "Command '{}' timed out after {} seconds",
+                command_desc,
+                timeout.as_secs()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+
+    Ok(std::process::Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+#[cfg(unix)]
+pub(crate) fn kill_process_group(child: &mut std::process::Child) {
+    // 子进程是自身进程组的组长，向负的 pid 发送信号即终止整个进程组
+    let pgid = child.id() as libc::pid_t;
+    // SAFETY: 只向我们自己创建的进程组发送信号
+    if unsafe { libc::kill(-pgid, libc::SIGKILL) } != 0 {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn kill_process_group(child: &mut std::process::Child) {
+    let _ = child.kill();
+}
+
+/// 组合最终的 RUSTFLAGS：环境变量中已有的 RUSTFLAGS、配置的 `build.rustflags`、
+/// `--extra-rustflags`，最后追加告警级别（放在最后以保证生效）：
+/// `--deny-warnings` 时为 `-D warnings`；抑制告警时为 `-A warnings`，
+/// 但环境变量已自行设置 `warnings` 级别时尊重用户的设置，不再追加
+fn compose_rustflags(
+    suppress_warnings: bool,
+    deny_warnings: bool,
+    environment: Option<&str>,
+    configured: Option<&str>,
+    extra: Option<&str>,
+) -> Option<String> {
+    let mut parts: Vec<&str> = [environment, configured, extra]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .collect();
+    if deny_warnings {
+        parts.push("-D warnings");
+    } else if suppress_warnings && !environment.is_some_and(environment_sets_warnings_level) {
+        parts.push("-A warnings");
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+/// 在 cargo 命令上设置组合后的 RUSTFLAGS（无任何参数时不设置）
+fn apply_rustflags(
+    cmd: &mut Command,
+    suppress_warnings: bool,
+    configured: Option<&str>,
+    extra: Option<&str>,
+) {
+    if let Some(rustflags) = compose_rustflags(
+        suppress_warnings,
+        DENY_WARNINGS.load(Ordering::Relaxed),
+        environment_rustflags().as_deref(),
+        configured,
+        extra,
+    ) {
+        cmd.env("RUSTFLAGS", rustflags);
+    }
+}
+
+/// 生成 cargo 子命令的展示字符串（`--dry-run` 时打印，不实际执行）
+pub(crate) fn describe_cargo_command(
+    feature: &str,
+    subcommand: &str,
+    suppress_warnings: bool,
+) -> String {
+    let configured_rustflags = get_config_value("build.rustflags", feature).ok();
+    let rustflags = compose_rustflags(
+        suppress_warnings,
+        DENY_WARNINGS.load(Ordering::Relaxed),
+        environment_rustflags().as_deref(),
+        configured_rustflags.as_deref(),
+        extra_rustflags().as_deref(),
+    );
+    format_cargo_command(subcommand, rustflags.as_deref(), active_build_profile())
+}
+
+fn format_cargo_command(
+    subcommand: &str,
+    rustflags: Option<&str>,
+    profile: BuildProfile,
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(rustflags) = rustflags {
+        parts.push(format!("RUSTFLAGS={}", shell_words::quote(rustflags)));
+    }
+    parts.push(format!("cargo {}", subcommand));
+    if profile == BuildProfile::Release {
+        parts.push("--release".to_string());
+    }
+    parts.join(" ")
+}
 
 /// 内部辅助函数：执行 cargo 子命令（build 或 check），处理公共逻辑
 ///
@@ -12,9 +619,13 @@ use std::time::Instant;
 /// - `exec_error_msg`: `cmd.output()` 失败时的错误提示
 /// - `failure_label`: 命令执行失败时 bail 消息的前缀（如 `"Build error"` / `"Check error"`）
 /// - `success_label`: 命令执行成功时的提示文字（如 `"Build completed"` / `"Check completed"`）
+///
+/// 失败时错误中包含 stderr（以及 JSON 之外的 stdout），按 `build.error_lines` 截断，
+/// `show_full_output` 时保留完整输出；结构化诊断不受截断影响。
 fn run_cargo_subcommand(
     feature: &str,
     suppress_warnings: bool,
+    show_full_output: bool,
     subcommand: &str,
     exec_error_msg: &str,
     failure_label: &str,
@@ -25,24 +636,121 @@ fn run_cargo_subcommand(
     let project_root = util::find_project_root()?;
     let build_dir = project_root.join(".c2rust").join(feature).join("rust");
 
-    let start_time = Instant::now();
-
     let mut cmd = Command::new("cargo");
-    cmd.arg(subcommand).current_dir(&build_dir);
+    // JSON 诊断用于按文件精确定位错误；渲染文本仍从中取出，输出与普通模式一致
+    cmd.arg(subcommand)
+        .arg("--message-format=json")
+        .current_dir(&build_dir);
+    if active_build_profile() == BuildProfile::Release {
+        cmd.arg("--release");
+    }
     // Required because translated Rust code may use unstable (nightly-only) features.
     cmd.env("RUSTC_BOOTSTRAP", "1");
 
-    if suppress_warnings {
-        cmd.env("RUSTFLAGS", "-A warnings");
-    }
+    // build.rustflags 为可选配置，读取失败（未设置或 c2rust-config 不可用）时忽略
+    let configured_rustflags = get_config_value("build.rustflags", feature).ok();
+    apply_rustflags(
+        &mut cmd,
+        suppress_warnings,
+        configured_rustflags.as_deref(),
+        extra_rustflags().as_deref(),
+    );
 
-    let output = cmd.output().with_context(|| exec_error_msg.to_string())?;
-    let duration = start_time.elapsed();
+    let command_desc = format!("cargo {}", subcommand);
+    // 构建机故障（磁盘满、OOM 等）不是代码错误：等待一段时间后重试，仍失败时不交给修复流程
+    let mut transient_retries = 0;
+    let (output, duration) = loop {
+        let start_time = Instant::now();
+        let output = output_with_timeout(&mut cmd, build_timeout(feature), &command_desc)
+            .with_context(|| exec_error_msg.to_string())?;
+        let duration = start_time.elapsed();
+        crate::summary::add_build_time(duration);
+        crate::events::emit(&crate::events::Event::Build {
+            command: &command_desc,
+            result: crate::events::EventResult::from_ok(output.status.success()),
+            duration_ms: duration.as_millis() as u64,
+        });
+        if output.status.success() {
+            break (output, duration);
+        }
 
+        let Some(signature) = classify_transient_failure(
+            &String::from_utf8_lossy(&output.stderr),
+            exit_signal(&output.status),
+            &transient_build_errors(feature),
+        ) else {
+            if transient_retries > 0 {
+                println!(
+                    "  {}",
+                    "No build machine failure signature matched; treating it as a code error"
+                        .bright_blue()
+                );
+            }
+            break (output, duration);
+        };
+        let (max_retries, cooldown) = transient_build_retry_policy(feature);
+        if transient_retries >= max_retries {
+            println!(
+                "  {}",
+                format!(
+                    "⚠ {} still fails because of the build machine ({}); not sending it to the fixer",
+                    command_desc, signature
+                )
+                .yellow()
+            );
+            return Err(TransientBuildError {
+                label: failure_label.to_string(),
+                signature,
+                retries: transient_retries,
+                output: format_failure_output(
+                    &String::from_utf8_lossy(&output.stderr),
+                    &String::from_utf8_lossy(&output.stdout),
+                    error_output_lines(feature),
+                    show_full_output,
+                ),
+            }
+            .into());
+        }
+        transient_retries += 1;
+        println!(
+            "  {}",
+            format!(
+                "⚠ {} failed because of the build machine, not the code (matched \"{}\"); \
+                 retrying in {}s ({}/{})",
+                command_desc,
+                signature,
+                cooldown.as_secs(),
+                transient_retries,
+                max_retries
+            )
+            .yellow()
+        );
+        std::thread::sleep(cooldown);
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    // JSON 无法解析时回退到 stderr 原文，由调用方按文本解析；此时 stdout 也是普通文本
+    let (stderr, stdout, diagnostics) = match crate::diagnostics::parse_cargo_json(&stdout, &stderr)
+    {
+        Some(parsed) => (parsed.text, String::new(), parsed.diagnostics),
+        None => (stderr, stdout.into_owned(), Vec::new()),
+    };
 
     if !output.status.success() {
-        anyhow::bail!("{}: {}", failure_label, stderr);
+        // 修复器与按文本定位文件需要完整输出，只有显示时才截断
+        return Err(crate::diagnostics::CargoBuildError {
+            label: failure_label.to_string(),
+            output: format_failure_output(&stderr, &stdout, usize::MAX, true),
+            display_output: format_failure_output(
+                &stderr,
+                &stdout,
+                error_output_lines(feature),
+                show_full_output,
+            ),
+            diagnostics,
+        }
+        .into());
     }
 
     println!(
@@ -69,7 +777,7 @@ fn run_cargo_subcommand(
 /// # 参数
 /// - `feature`: 特性名称
 /// - `suppress_warnings`: true=抑制警告(-A warnings), false=显示警告
-/// - `_show_full_output`: 保留参数，暂未实现，传入值不影响当前输出行为
+/// - `show_full_output`: true=失败时显示完整输出，false=显示时按 `build.error_lines` 截断（错误本身始终完整）
 ///
 /// # 返回
 /// - `Ok(None)`: 构建成功且无警告（或警告被抑制）
@@ -78,11 +786,12 @@ fn run_cargo_subcommand(
 pub fn cargo_build(
     feature: &str,
     suppress_warnings: bool,
-    _show_full_output: bool,
+    show_full_output: bool,
 ) -> Result<Option<String>> {
     run_cargo_subcommand(
         feature,
         suppress_warnings,
+        show_full_output,
         "build",
         "Failed to execute cargo build",
         "Build error",
@@ -99,7 +808,7 @@ pub fn cargo_build(
 /// # 参数
 /// - `feature`: 特性名称
 /// - `suppress_warnings`: true=抑制警告(-A warnings), false=显示警告
-/// - `_show_full_output`: 保留参数，暂未实现，传入值不影响当前输出行为
+/// - `show_full_output`: true=失败时显示完整输出，false=显示时按 `build.error_lines` 截断（错误本身始终完整）
 ///
 /// # 返回
 /// - `Ok(None)`: 检查成功且无警告（或警告被抑制）
@@ -108,11 +817,12 @@ pub fn cargo_build(
 pub fn cargo_check(
     feature: &str,
     suppress_warnings: bool,
-    _show_full_output: bool,
+    show_full_output: bool,
 ) -> Result<Option<String>> {
     run_cargo_subcommand(
         feature,
         suppress_warnings,
+        show_full_output,
         "check",
         "Failed to execute cargo check",
         "Check error",
@@ -120,19 +830,83 @@ pub fn cargo_check(
     )
 }
 
+/// 本工具依赖的 c2rust-config 行为
+const C2RUST_CONFIG_REQUIRED_USAGE: &str =
+    "c2rust-config config --make --feature <feature> --list <key>";
+
+/// 判断 c2rust-config 的错误输出是否表示不认识所用的子命令或选项（通常是版本过旧）
+fn is_unsupported_c2rust_config_usage(stderr: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "unknown option",
+        "unknown subcommand",
+        "unknown command",
+        "unknown argument",
+        "unrecognized option",
+        "unrecognized subcommand",
+        "unrecognized argument",
+        "unexpected argument",
+        "invalid choice",
+        "no such command",
+        "no such option",
+    ];
+    let stderr = stderr.to_ascii_lowercase();
+    PATTERNS.iter().any(|pattern| stderr.contains(pattern))
+}
+
+/// c2rust-config 版本不匹配时的错误：说明需要的最低行为，而不是直接输出原始 stderr
+fn c2rust_config_version_mismatch(key: &str, stderr: &str) -> anyhow::Error {
+    let detail = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    anyhow::anyhow!(
+        "c2rust-config version mismatch while reading {}: this tool requires `{}` support; \
+         upgrade c2rust-config to a version that provides `config --make` with `--feature` and `--list` \
+         (c2rust-config reported: {})",
+        key,
+        C2RUST_CONFIG_REQUIRED_USAGE,
+        detail
+    )
+}
+
+/// 把 `--config` 指定的文件复制为临时目录中的 `.c2rust/config.toml`，供 c2rust-config 读取
+///
+/// c2rust-config 只读取所在 `.c2rust` 目录中的 config.toml，没有指定其他路径的选项。
+fn stage_config_override(config: &std::path::Path) -> Result<tempfile::TempDir> {
+    let staging = tempfile::tempdir().context("Failed to create a directory for --config")?;
+    let c2rust_dir = staging.path().join(".c2rust");
+    std::fs::create_dir(&c2rust_dir)
+        .with_context(|| format!("Failed to create {}", c2rust_dir.display()))?;
+    std::fs::copy(config, c2rust_dir.join("config.toml"))
+        .with_context(|| format!("Failed to read config file {}", config.display()))?;
+    Ok(staging)
+}
+
 /// 从 c2rust-config 获取特定的配置值
+///
+/// 指定了 `--config` 时从该文件读取，否则从 `<project_root>/.c2rust/config.toml` 读取。
 pub(crate) fn get_config_value(key: &str, feature: &str) -> Result<String> {
-    let project_root = util::find_project_root()?;
-    let c2rust_dir = project_root.join(".c2rust");
+    let staged = util::config_path_override()
+        .map(|config| stage_config_override(&config))
+        .transpose()?;
+    let c2rust_dir = match &staged {
+        Some(staging) => staging.path().join(".c2rust"),
+        None => util::find_project_root()?.join(".c2rust"),
+    };
 
-    let output = Command::new("c2rust-config")
-        .current_dir(&c2rust_dir)
-        .args(["config", "--make", "--feature", feature, "--list", key])
-        .output()
+    let output = crate::interrupt::output(
+        Command::new("c2rust-config")
+            .current_dir(&c2rust_dir)
+            .args(["config", "--make", "--feature", feature, "--list", key]),
+    )
         .with_context(|| format!("Failed to get {} from config", key))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_unsupported_c2rust_config_usage(&stderr) {
+            return Err(c2rust_config_version_mismatch(key, &stderr));
+        }
         anyhow::bail!("Failed to retrieve {}: {}", key, stderr);
     }
 
@@ -146,25 +920,50 @@ pub(crate) fn get_config_value(key: &str, feature: &str) -> Result<String> {
 }
 
 /// 如果启用了 LD_PRELOAD，则设置混合构建环境变量
+///
+/// `C2RUST_HYBRID_BUILD_LIB` 指向的文件不存在时报错；`librust.a` 不存在时先执行一次
+/// cargo build，仍不存在则报错，避免到链接阶段才出现难以理解的失败。
 fn setup_hybrid_env(
     command: &mut Command,
     project_root: &std::path::Path,
     feature: &str,
     set_ld_preload: bool,
     build_target: Option<&str>,
-) -> Option<std::path::PathBuf> {
+) -> Result<Option<std::path::PathBuf>> {
     if !set_ld_preload {
-        return None;
+        return Ok(None);
     }
 
-    let hybrid_lib = env::var("C2RUST_HYBRID_BUILD_LIB").ok()?;
+    let Ok(hybrid_lib) = env::var("C2RUST_HYBRID_BUILD_LIB") else {
+        return Ok(None);
+    };
+    validate_hybrid_build_lib(std::path::Path::new(&hybrid_lib))?;
+
     let c2rust_dir = project_root.join(".c2rust");
     let feature_root_path = c2rust_dir.join(feature);
     let rust_lib_path = feature_root_path
         .join("rust")
         .join("target")
-        .join("debug")
+        .join(active_build_profile().as_str())
         .join("librust.a");
+    if !rust_lib_path.is_file() {
+        println!(
+            "│ {}",
+            format!(
+                "librust.a not found at {}, running cargo build first...",
+                rust_lib_path.display()
+            )
+            .yellow()
+        );
+        cargo_build(feature, true, false)?;
+        if !rust_lib_path.is_file() {
+            anyhow::bail!(
+                "Rust static library not found: {} (needed by C2RUST_RUST_LIB for the hybrid build). \
+                 Make sure the Rust crate builds a staticlib named `rust`.",
+                rust_lib_path.display()
+            );
+        }
+    }
 
     command.env("LD_PRELOAD", &hybrid_lib);
     command.env("C2RUST_PROJECT_ROOT", project_root);
@@ -176,7 +975,25 @@ fn setup_hybrid_env(
         command.env("C2RUST_LD_TARGET", target);
     }
 
-    Some(feature_root_path)
+    Ok(Some(feature_root_path))
+}
+
+/// 检查 `C2RUST_HYBRID_BUILD_LIB` 指向的混合构建库是否为存在的文件
+fn validate_hybrid_build_lib(hybrid_lib: &std::path::Path) -> Result<()> {
+    if !hybrid_lib.exists() {
+        anyhow::bail!(
+            "Hybrid build library not found: {} (from C2RUST_HYBRID_BUILD_LIB). \
+             Set C2RUST_HYBRID_BUILD_LIB to the path of the hybrid build library, or unset it.",
+            hybrid_lib.display()
+        );
+    }
+    if !hybrid_lib.is_file() {
+        anyhow::bail!(
+            "Hybrid build library is not a file: {} (from C2RUST_HYBRID_BUILD_LIB)",
+            hybrid_lib.display()
+        );
+    }
+    Ok(())
 }
 
 /// 打印命令执行详情
@@ -205,7 +1022,7 @@ fn print_command_details(
                 .map(|f| {
                     f.join("rust")
                         .join("target")
-                        .join("debug")
+                        .join(active_build_profile().as_str())
                         .join("librust.a")
                 })
                 .unwrap_or_default();
@@ -241,38 +1058,172 @@ fn print_command_details(
     );
 }
 
+/// 验证配置中的目录路径：必须是相对路径且不含 `..`
+fn validate_config_dir(dir_str: &str) -> Result<()> {
+    if std::path::Path::new(dir_str).is_absolute() {
+        anyhow::bail!(
+            "Directory path from config must be relative, got: {}",
+            dir_str
+        );
+    }
+    if dir_str.contains("..") {
+        anyhow::bail!(
+            "Directory path from config cannot contain '..', got: {}",
+            dir_str
+        );
+    }
+    Ok(())
+}
+
+/// 混合构建需要的 (命令, 目录) 配置键
+const REQUIRED_COMMAND_KEYS: [(&str, &str); 3] = [
+    ("build.cmd", "build.dir"),
+    ("test.cmd", "test.dir"),
+    ("clean.cmd", "clean.dir"),
+];
+
+/// 在翻译循环开始前一次性检查 build/test/clean 的命令和目录配置，以及配置了的
+/// `translator.command` / `translator.fix_command` 命令模板
+///
+/// 所有问题汇总为一个错误返回，而不是在循环中途才逐个暴露。`test.cmd` 和 `test.dir`
+/// 都未配置时不算错误（由调用方提示是否跳过测试阶段）。
+pub fn validate_config(feature: &str) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let problems = collect_config_problems(&project_root, |key| get_config_value(key, feature));
+    if problems.is_empty() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Invalid configuration for feature '{}' ({} problem(s)):\n  - {}",
+        feature,
+        problems.len(),
+        problems.join("\n  - ")
+    )
+}
+
+fn collect_config_problems(
+    project_root: &std::path::Path,
+    lookup: impl Fn(&str) -> Result<String>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (cmd_key, dir_key) in REQUIRED_COMMAND_KEYS {
+        let cmd = lookup(cmd_key);
+        let dir = lookup(dir_key);
+        if cmd_key == "test.cmd" && cmd.is_err() && dir.is_err() {
+            continue;
+        }
+
+        match cmd {
+            Ok(cmd) => match shell_words::split(&cmd) {
+                Ok(parts) if parts.first().is_some_and(|p| !p.is_empty()) => {}
+                Ok(_) => problems.push(format!("{}: command is empty", cmd_key)),
+                Err(e) => problems.push(format!("{}: cannot parse '{}': {}", cmd_key, cmd, e)),
+            },
+            Err(e) => problems.push(format!("{}: {:#}", cmd_key, e)),
+        }
+
+        match dir {
+            Ok(dir) => {
+                if let Err(e) = validate_config_dir(&dir) {
+                    problems.push(format!("{}: {}", dir_key, e));
+                } else if !project_root.join(&dir).is_dir() {
+                    problems.push(format!(
+                        "{}: directory does not exist: {}",
+                        dir_key,
+                        project_root.join(&dir).display()
+                    ));
+                }
+            }
+            Err(e) => problems.push(format!("{}: {:#}", dir_key, e)),
+        }
+    }
+    problems.extend(crate::translator::command_template_problems(&lookup));
+    problems
+}
+
+/// 一次 clean/build/test 命令的执行结果
+#[derive(Debug, Clone)]
+pub struct CommandOutcome {
+    pub status: std::process::ExitStatus,
+    pub duration: Duration,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutcome {
+    fn from_output(output: &std::process::Output, duration: Duration) -> Self {
+        Self {
+            status: output.status,
+            duration,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    }
+}
+
+/// 命令以非零状态退出的错误，携带执行结果
+///
+/// 显示文本与原先的 bail 消息一致；需要退出码或耗时时通过 `downcast_ref` 取出。
+#[derive(Debug)]
+pub struct CommandFailed {
+    pub command: String,
+    pub outcome: CommandOutcome,
+}
+
+impl std::fmt::Display for CommandFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut summary_lines: Vec<&str> = self.outcome.stderr.lines().take(3).collect();
+        // 链接错误通常出现在输出末尾，单独保留以便定位出错的符号
+        for line in crate::error_handler::collect_link_error_lines(&self.outcome.stderr) {
+            if !summary_lines.contains(&line) {
+                summary_lines.push(line);
+            }
+        }
+        let stderr_summary = summary_lines.join("\n");
+
+        if stderr_summary.is_empty() {
+            write!(
+                f,
+                "Command '{}' failed with non-zero exit status",
+                self.command
+            )
+        } else {
+            write!(
+                f,
+                "Command '{}' failed with non-zero exit status. Stderr (first lines):\n{}",
+                self.command, stderr_summary
+            )
+        }
+    }
+}
+
+impl std::error::Error for CommandFailed {}
+
 /// 在配置的目录中执行命令
 /// 此函数被 hybrid_build 模块使用，因此是公开的
+///
+/// 成功时返回执行结果；命令为空（未执行任何命令）时返回 `None`。
+/// 命令以非零状态退出时返回 [`CommandFailed`] 错误。
 pub fn execute_command_in_dir_with_type(
     command_str: &str,
     dir_key: &str,
     feature: &str,
     set_ld_preload: bool,
     command_type: &str,
-) -> Result<()> {
+) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     let dir_str = get_config_value(dir_key, feature)?;
 
     // 验证路径安全性
-    if std::path::Path::new(&dir_str).is_absolute() {
-        anyhow::bail!(
-            "Directory path from config must be relative, got: {}",
-            dir_str
-        );
-    }
-    if dir_str.contains("..") {
-        anyhow::bail!(
-            "Directory path from config cannot contain '..', got: {}",
-            dir_str
-        );
-    }
+    validate_config_dir(&dir_str)?;
 
     let parts = shell_words::split(command_str)
         .with_context(|| format!("Failed to parse command: {}", command_str))?;
 
     if parts.is_empty() {
-        return Ok(());
+        return Ok(None);
     }
 
     if parts[0].is_empty() {
@@ -308,6 +1259,7 @@ pub fn execute_command_in_dir_with_type(
 
     let mut command = Command::new(&parts[0]);
     command.current_dir(&exec_dir);
+    command.env("C2RUST_BUILD_PROFILE", active_build_profile().as_str());
 
     if parts.len() > 1 {
         command.args(&parts[1..]);
@@ -319,7 +1271,7 @@ pub fn execute_command_in_dir_with_type(
         feature,
         set_ld_preload,
         build_target.as_deref(),
-    );
+    )?;
     print_command_details(
         command_type,
         &parts,
@@ -331,31 +1283,34 @@ pub fn execute_command_in_dir_with_type(
     );
 
     let start_time = Instant::now();
-    let output = command
-        .output()
+    let output = output_with_timeout(&mut command, build_timeout(feature), command_str)
         .with_context(|| format!("Failed to execute command: {}", command_str))?;
     let duration = start_time.elapsed();
+    if command_type == "test" {
+        crate::summary::add_test_time(duration);
+    } else {
+        crate::summary::add_build_time(duration);
+    }
+    crate::events::emit(&crate::events::Event::Build {
+        command: command_str,
+        result: crate::events::EventResult::from_ok(output.status.success()),
+        duration_ms: duration.as_millis() as u64,
+    });
 
+    let outcome = CommandOutcome::from_output(&output, duration);
     if !output.status.success() {
         print_command_failure(command_type, &output, duration);
 
-        // 在 bail 消息中包含错误详情以便更好地调试
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stderr_summary = stderr.lines().take(3).collect::<Vec<_>>().join("\n");
-
-        if stderr_summary.is_empty() {
-            anyhow::bail!("Command '{}' failed with non-zero exit status", command_str);
-        } else {
-            anyhow::bail!(
-                "Command '{}' failed with non-zero exit status. Stderr (first lines):\n{}",
-                command_str,
-                stderr_summary
-            );
+        // 错误消息中包含 stderr 摘要以便更好地调试
+        return Err(CommandFailed {
+            command: command_str.to_string(),
+            outcome,
         }
+        .into());
     }
 
     print_command_success(command_type, duration);
-    Ok(())
+    Ok(Some(outcome))
 }
 
 /// 打印命令失败消息
@@ -411,12 +1366,15 @@ fn print_command_success(command_type: &str, duration: std::time::Duration) {
 }
 
 /// 为给定特性运行清理命令
-pub fn c2rust_clean(feature: &str) -> Result<()> {
+pub fn c2rust_clean(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     let clean_cmd = get_config_value("clean.cmd", feature)?;
 
@@ -426,7 +1384,7 @@ pub fn c2rust_clean(feature: &str) -> Result<()> {
 /// 为给定特性运行清理命令（不更新代码分析）
 ///
 /// 在 clean/build/test 序列中使用，此时代码分析已在序列开始时统一更新一次。
-pub(crate) fn c2rust_clean_no_analysis(feature: &str) -> Result<()> {
+pub(crate) fn c2rust_clean_no_analysis(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     let clean_cmd = get_config_value("clean.cmd", feature)?;
@@ -436,12 +1394,15 @@ pub(crate) fn c2rust_clean_no_analysis(feature: &str) -> Result<()> {
 
 /// 为给定特性运行构建命令
 /// 如果设置了 C2RUST_HYBRID_BUILD_LIB，则自动检测并设置 LD_PRELOAD
-pub fn c2rust_build(feature: &str) -> Result<()> {
+pub fn c2rust_build(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     println!("{}", "Rebuilding Rust static library for hybrid link...".bright_blue());
     cargo_build(feature, true, false)?;
@@ -455,7 +1416,7 @@ pub fn c2rust_build(feature: &str) -> Result<()> {
 /// 为给定特性运行构建命令（不更新代码分析）
 ///
 /// 在 clean/build/test 序列中使用，此时代码分析已在序列开始时统一更新一次。
-pub(crate) fn c2rust_build_no_analysis(feature: &str) -> Result<()> {
+pub(crate) fn c2rust_build_no_analysis(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     println!("{}", "Rebuilding Rust static library for hybrid link...".bright_blue());
@@ -468,12 +1429,15 @@ pub(crate) fn c2rust_build_no_analysis(feature: &str) -> Result<()> {
 }
 
 /// 为给定特性运行测试命令
-pub fn c2rust_test(feature: &str) -> Result<()> {
+pub fn c2rust_test(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     let test_cmd = get_config_value("test.cmd", feature)?;
 
@@ -483,7 +1447,7 @@ pub fn c2rust_test(feature: &str) -> Result<()> {
 /// 为给定特性运行测试命令（不更新代码分析）
 ///
 /// 在 clean/build/test 序列中使用，此时代码分析已在序列开始时统一更新一次。
-pub(crate) fn c2rust_test_no_analysis(feature: &str) -> Result<()> {
+pub(crate) fn c2rust_test_no_analysis(feature: &str) -> Result<Option<CommandOutcome>> {
     util::validate_feature_name(feature)?;
 
     let test_cmd = get_config_value("test.cmd", feature)?;
@@ -505,8 +1469,7 @@ pub fn run_hybrid_build_interactive(
     rs_file: Option<&std::path::Path>,
 ) -> Result<()> {
     // 从配置获取构建命令
-    let project_root = util::find_project_root()?;
-    let config_path = project_root.join(".c2rust/config.toml");
+    let config_path = util::config_path()?;
 
     if !config_path.exists() {
         eprintln!(
@@ -525,9 +1488,9 @@ pub fn run_hybrid_build_interactive(
     }
 
     // 在整个序列开始前统一更新一次代码分析，避免 clean/build/test 各自重复更新
-    println!("│ {}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("│ {}", "✓ Code analysis updated".bright_green());
+    ui::info("Updating code analysis...");
+    let updated = analyzer::update_code_analysis(feature)?;
+    ui::success(analyzer::update_status_message(updated));
 
     // 执行命令
     println!("│ {}", "Running hybrid build tests...".bright_blue().bold());
@@ -667,7 +1630,7 @@ pub(crate) fn handle_build_failure_interactive(
     use crate::interaction;
     use crate::suggestion;
 
-    println!("│");
+    ui::blank();
     println!("│ {}", "⚠ Build failed!".red().bold());
     println!("│ {}", "The build process did not succeed.".yellow());
 
@@ -678,7 +1641,10 @@ pub(crate) fn handle_build_failure_interactive(
     interaction::display_file_paths(Some(&c_file), rs_file);
 
     // 使用差异显示进行更好的比较
-    let error_message = format!("✗ Build Error:\n{}", build_error);
+    let error_message = format!(
+        "✗ Build Error:\n{}",
+        crate::diagnostics::display_text(&build_error)
+    );
     if let Err(e) = diff_display::display_code_comparison(
         &c_file,
         rs_file,
@@ -698,7 +1664,10 @@ pub(crate) fn handle_build_failure_interactive(
         translator::display_code(rs_file, "─ Rust Code ─", usize::MAX, true);
 
         println!("│ {}", "═══ Build Error ═══".bright_red().bold());
-        println!("│ {}", build_error);
+        println!("│ {}", crate::diagnostics::display_text(&build_error));
+    }
+    if let Some(diagnostics) = crate::diagnostics::diagnostics_of(&build_error) {
+        crate::diagnostics::display_snippets(diagnostics, rs_file);
     }
 
     // 使用新提示获取用户选择
@@ -706,7 +1675,7 @@ pub(crate) fn handle_build_failure_interactive(
 
     match choice {
         interaction::FailureChoice::RetryDirectly => {
-            println!("│");
+            ui::blank();
             println!(
                 "│ {}",
                 "You chose: Retry directly without suggestion".bright_cyan()
@@ -716,17 +1685,14 @@ pub(crate) fn handle_build_failure_interactive(
             suggestion::clear_suggestions()?;
 
             println!("│ {}", "Retrying translation from scratch...".bright_cyan());
-            println!(
-                "│ {}",
-                "Note: The translator will overwrite the existing file content.".bright_blue()
-            );
-            println!("│ {}", "✓ Retry scheduled".bright_green());
+            ui::info("Note: The translator will overwrite the existing file content.");
+            ui::success("Retry scheduled");
 
             // 返回 false 以信号重试翻译
             Ok(false)
         }
         interaction::FailureChoice::AddSuggestion => {
-            println!("│");
+            ui::blank();
             println!(
                 "│ {}",
                 "You chose: Add fix suggestion for AI to modify".bright_cyan()
@@ -750,11 +1716,8 @@ pub(crate) fn handle_build_failure_interactive(
                 suggestion::append_suggestion(&suggestion_text)?;
 
                 // 应用带有建议的修复
-                println!("│");
-                println!(
-                    "│ {}",
-                    "Applying fix based on your suggestion...".bright_blue()
-                );
+                ui::blank();
+                ui::info("Applying fix based on your suggestion...");
 
                 let format_progress = |op: &str| format!("Fix for build failure - {}", op);
                 crate::apply_error_fix(
@@ -767,7 +1730,7 @@ pub(crate) fn handle_build_failure_interactive(
                 )?;
 
                 // 再次尝试构建和测试
-                println!("│");
+                ui::blank();
                 println!(
                     "│ {}",
                     "Running full build and test...".bright_blue().bold()
@@ -778,13 +1741,13 @@ pub(crate) fn handle_build_failure_interactive(
                         return Ok(true);
                     }
                     Err(e) => {
-                        println!("│ {}", "✗ Build or tests still failing".red());
+                        ui::error("Build or tests still failing");
 
                         // 使用最新失败更新 current_error
                         current_error = e;
 
                         // 询问用户是否想再试一次
-                        println!("│");
+                        ui::blank();
                         println!(
                             "│ {}",
                             "Build or tests still have errors. What would you like to do?".yellow()
@@ -802,11 +1765,11 @@ pub(crate) fn handle_build_failure_interactive(
                                 continue;
                             }
                             interaction::FailureChoice::ManualFix => {
-                                println!("│");
+                                ui::blank();
                                 println!("│ {}", "You chose: Manually edit the code".bright_cyan());
-                                println!("│ {}", "Opening vim for manual fixes...".bright_blue());
+                                ui::info("Opening editor for manual fixes...");
 
-                                // 打开 vim 允许用户手动编辑代码（支持多文件选择）
+                                // 打开编辑器允许用户手动编辑代码（支持多文件选择）
                                 let fix_files = get_manual_fix_files(
                                     feature,
                                     rs_file,
@@ -814,7 +1777,7 @@ pub(crate) fn handle_build_failure_interactive(
                                 );
                                 match interaction::open_files_for_manual_fix(&fix_files) {
                                     Ok(_) => {
-                                        println!("│");
+                                        ui::blank();
                                         println!(
                                             "│ {}",
                                             "Running full build and test after manual fix..."
@@ -830,10 +1793,12 @@ pub(crate) fn handle_build_failure_interactive(
                                                 return Ok(true);
                                             }
                                             Err(e) => {
-                                                println!("│ {}", "✗ Build or tests still failing after manual fix".red());
+                                                ui::error(
+                                                    "Build or tests still failing after manual fix",
+                                                );
 
                                                 // 询问用户是否想再试一次
-                                                println!("│");
+                                                ui::blank();
                                                 println!("│ {}", "Build or tests still have errors. What would you like to do?".yellow());
                                                 let nested_retry_choice =
                                                     interaction::prompt_build_failure_choice()?;
@@ -854,11 +1819,11 @@ pub(crate) fn handle_build_failure_interactive(
                                                         continue;
                                                     }
                                                     interaction::FailureChoice::ManualFix => {
-                                                        // 重新打开 vim
-                                                        println!("│ {}", "Reopening Vim for another manual fix attempt...".bright_blue());
+                                                        // 重新打开编辑器
+                                                        ui::info("Reopening editor for another manual fix attempt...");
                                                         let fix_files = get_manual_fix_files(feature, rs_file, &e.to_string());
                                                         interaction::open_files_for_manual_fix(&fix_files)
-                                                            .context("Failed to reopen vim for additional manual fix")?;
+                                                            .context("Failed to reopen editor for additional manual fix")?;
                                                         // 更新错误并继续外部循环以重新构建
                                                         current_error = e;
                                                         continue;
@@ -879,14 +1844,14 @@ pub(crate) fn handle_build_failure_interactive(
                                     Err(open_err) => {
                                         println!(
                                             "│ {}",
-                                            format!("Failed to open vim: {}", open_err).red()
+                                            format!("Failed to open editor: {}", open_err).red()
                                         );
                                         println!(
                                             "│ {}",
                                             "Cannot continue manual fix flow; exiting.".yellow()
                                         );
                                         return Err(open_err).context(
-                                            "Build failed and could not open vim for manual fix",
+                                            "Build failed and could not open editor for manual fix",
                                         );
                                     }
                                 }
@@ -905,33 +1870,27 @@ pub(crate) fn handle_build_failure_interactive(
             }
         }
         interaction::FailureChoice::ManualFix => {
-            println!("│");
+            ui::blank();
             println!("│ {}", "You chose: Manual fix".bright_cyan());
 
-            // 尝试打开 vim
+            // 尝试打开编辑器
             let fix_files = get_manual_fix_files(feature, rs_file, &build_error.to_string());
             match interaction::open_files_for_manual_fix(&fix_files) {
                 Ok(_) => {
                     loop {
-                        println!("│");
-                        println!(
-                            "│ {}",
-                            "Vim editing completed. Running full build and test...".bright_blue()
-                        );
+                        ui::blank();
+                        ui::info("Editing completed. Running full build and test...");
 
-                        // Vim 编辑后尝试使用混合构建流程进行构建和测试
+                        // 编辑器编辑后尝试使用混合构建流程进行构建和测试
                         match run_full_build_and_test_interactive(feature, file_type, rs_file, skip_test) {
                             Ok(_) => {
                                 return Ok(true);
                             }
                             Err(e) => {
-                                println!(
-                                    "│ {}",
-                                    "✗ Build or tests still failing after manual fix".red()
-                                );
+                                ui::error("Build or tests still failing after manual fix");
 
                                 // 询问用户是否想再试一次
-                                println!("│");
+                                ui::blank();
                                 println!(
                                     "│ {}",
                                     "Build or tests still have errors. What would you like to do?"
@@ -951,14 +1910,16 @@ pub(crate) fn handle_build_failure_interactive(
                                     interaction::FailureChoice::ManualFix => {
                                         println!(
                                             "│ {}",
-                                            "Reopening Vim for another manual fix attempt..."
+                                            "Reopening editor for another manual fix attempt..."
                                                 .bright_blue()
                                         );
-                                        let fix_files = get_manual_fix_files(feature, rs_file, &e.to_string());
-                                        interaction::open_files_for_manual_fix(&fix_files).context(
-                                            "Failed to reopen vim for additional manual fix",
-                                        )?;
-                                        // Vim 关闭后，继续循环重新构建和重新测试
+                                        let fix_files =
+                                            get_manual_fix_files(feature, rs_file, &e.to_string());
+                                        interaction::open_files_for_manual_fix(&fix_files)
+                                            .context(
+                                                "Failed to reopen editor for additional manual fix",
+                                            )?;
+                                        // 编辑器关闭后，继续循环重新构建和重新测试
                                         continue;
                                     }
                                     interaction::FailureChoice::AddSuggestion => {
@@ -992,10 +1953,10 @@ pub(crate) fn handle_build_failure_interactive(
                     }
                 }
                 Err(e) => {
-                    println!("│ {}", format!("Failed to open vim: {}", e).red());
+                    println!("│ {}", format!("Failed to open editor: {}", e).red());
                     println!("│ {}", "Falling back to exit.".yellow());
                     Err(e).context(format!(
-                        "Build failed (original error: {}) and could not open vim",
+                        "Build failed (original error: {}) and could not open editor",
                         build_error
                     ))
                 }
@@ -1008,7 +1969,7 @@ pub(crate) fn handle_build_failure_interactive(
             unreachable!("FixOtherFile is not offered in this context")
         }
         interaction::FailureChoice::Exit => {
-            println!("│");
+            ui::blank();
             println!("│ {}", "You chose: Exit".yellow());
             println!("│ {}", "Exiting due to build failures.".yellow());
             Err(build_error).context("Build failed and user chose to exit")
@@ -1034,7 +1995,7 @@ pub(crate) fn handle_test_failure_interactive(
     use crate::interaction;
     use crate::suggestion;
 
-    println!("│");
+    ui::blank();
     println!("│ {}", "⚠ Hybrid build tests failed!".red().bold());
     println!("│ {}", "The test suite did not pass.".yellow());
 
@@ -1073,7 +2034,7 @@ pub(crate) fn handle_test_failure_interactive(
 
     match choice {
         interaction::FailureChoice::RetryDirectly => {
-            println!("│");
+            ui::blank();
             println!(
                 "│ {}",
                 "You chose: Retry directly without suggestion".bright_cyan()
@@ -1085,17 +2046,14 @@ pub(crate) fn handle_test_failure_interactive(
             suggestion::clear_suggestions()?;
 
             println!("│ {}", "Retrying translation from scratch...".bright_cyan());
-            println!(
-                "│ {}",
-                "Note: The translator will overwrite the existing file content.".bright_blue()
-            );
-            println!("│ {}", "✓ Retry scheduled".bright_green());
+            ui::info("Note: The translator will overwrite the existing file content.");
+            ui::success("Retry scheduled");
 
             // 返回 false 以信号重试翻译
             Ok(false)
         }
         interaction::FailureChoice::AddSuggestion => {
-            println!("│");
+            ui::blank();
             println!(
                 "│ {}",
                 "You chose: Add fix suggestion for AI to modify".bright_cyan()
@@ -1119,11 +2077,8 @@ pub(crate) fn handle_test_failure_interactive(
                 suggestion::append_suggestion(&suggestion_text)?;
 
                 // 应用带有建议的修复
-                println!("│");
-                println!(
-                    "│ {}",
-                    "Applying fix based on your suggestion...".bright_blue()
-                );
+                ui::blank();
+                ui::info("Applying fix based on your suggestion...");
 
                 let format_progress = |op: &str| format!("Fix for test failure - {}", op);
                 crate::apply_error_fix(
@@ -1136,7 +2091,7 @@ pub(crate) fn handle_test_failure_interactive(
                 )?;
 
                 // 再次尝试构建和测试
-                println!("│");
+                ui::blank();
                 println!(
                     "│ {}",
                     "Running full build and test...".bright_blue().bold()
@@ -1147,13 +2102,13 @@ pub(crate) fn handle_test_failure_interactive(
                         return Ok(true);
                     }
                     Err(e) => {
-                        println!("│ {}", "✗ Tests still failing".red());
+                        ui::error("Tests still failing");
 
                         // 使用最新失败更新 current_error
                         current_error = e;
 
                         // 询问用户是否想再试一次
-                        println!("│");
+                        ui::blank();
                         println!(
                             "│ {}",
                             "Tests still have errors. What would you like to do?".yellow()
@@ -1171,11 +2126,11 @@ pub(crate) fn handle_test_failure_interactive(
                                 continue;
                             }
                             interaction::FailureChoice::ManualFix => {
-                                println!("│");
+                                ui::blank();
                                 println!("│ {}", "You chose: Manually edit the code".bright_cyan());
-                                println!("│ {}", "Opening vim for manual fixes...".bright_blue());
+                                ui::info("Opening editor for manual fixes...");
 
-                                // 打开 vim 允许用户手动编辑代码
+                                // 打开编辑器允许用户手动编辑代码
                                 let fix_files = get_manual_fix_files(
                                     feature,
                                     rs_file,
@@ -1183,7 +2138,7 @@ pub(crate) fn handle_test_failure_interactive(
                                 );
                                 match interaction::open_files_for_manual_fix(&fix_files) {
                                     Ok(_) => {
-                                        println!("│");
+                                        ui::blank();
                                         println!(
                                             "│ {}",
                                             "Running full build and test after manual fix..."
@@ -1198,10 +2153,7 @@ pub(crate) fn handle_test_failure_interactive(
                                                 return Ok(true);
                                             }
                                             Err(e) => {
-                                                println!(
-                                                    "│ {}",
-                                                    "✗ Tests still failing after manual fix".red()
-                                                );
+                                                ui::error("Tests still failing after manual fix");
                                                 // 更新 current_error 并继续外部循环
                                                 current_error = e;
                                                 continue;
@@ -1211,14 +2163,14 @@ pub(crate) fn handle_test_failure_interactive(
                                     Err(open_err) => {
                                         println!(
                                             "│ {}",
-                                            format!("Failed to open vim: {}", open_err).red()
+                                            format!("Failed to open editor: {}", open_err).red()
                                         );
                                         println!(
                                             "│ {}",
                                             "Cannot continue manual fix flow; exiting.".yellow()
                                         );
                                         return Err(open_err).context(
-                                            "Tests failed and could not open vim for manual fix",
+                                            "Tests failed and could not open editor for manual fix",
                                         );
                                     }
                                 }
@@ -1237,30 +2189,27 @@ pub(crate) fn handle_test_failure_interactive(
             }
         }
         interaction::FailureChoice::ManualFix => {
-            println!("│");
+            ui::blank();
             println!("│ {}", "You chose: Manual fix".bright_cyan());
 
-            // 尝试打开 vim
+            // 尝试打开编辑器
             let fix_files = get_manual_fix_files(feature, rs_file, &test_error.to_string());
             match interaction::open_files_for_manual_fix(&fix_files) {
                 Ok(_) => {
                     loop {
-                        println!("│");
-                        println!(
-                            "│ {}",
-                            "Vim editing completed. Running full build and test...".bright_blue()
-                        );
+                        ui::blank();
+                        ui::info("Editing completed. Running full build and test...");
 
-                        // Vim 编辑后尝试使用混合构建流程进行构建和测试
+                        // 编辑器编辑后尝试使用混合构建流程进行构建和测试
                         match run_full_build_and_test_interactive(feature, file_type, rs_file, skip_test) {
                             Ok(_) => {
                                 return Ok(true);
                             }
                             Err(e) => {
-                                println!("│ {}", "✗ Tests still failing after manual fix".red());
+                                ui::error("Tests still failing after manual fix");
 
                                 // 询问用户是否想再试一次
-                                println!("│");
+                                ui::blank();
                                 println!(
                                     "│ {}",
                                     "Tests still have errors. What would you like to do?".yellow()
@@ -1279,14 +2228,16 @@ pub(crate) fn handle_test_failure_interactive(
                                     interaction::FailureChoice::ManualFix => {
                                         println!(
                                             "│ {}",
-                                            "Reopening Vim for another manual fix attempt..."
+                                            "Reopening editor for another manual fix attempt..."
                                                 .bright_blue()
                                         );
-                                        let fix_files = get_manual_fix_files(feature, rs_file, &e.to_string());
-                                        interaction::open_files_for_manual_fix(&fix_files).context(
-                                            "Failed to reopen vim for additional manual fix",
-                                        )?;
-                                        // Vim 关闭后，继续循环重新构建和重新测试
+                                        let fix_files =
+                                            get_manual_fix_files(feature, rs_file, &e.to_string());
+                                        interaction::open_files_for_manual_fix(&fix_files)
+                                            .context(
+                                                "Failed to reopen editor for additional manual fix",
+                                            )?;
+                                        // 编辑器关闭后，继续循环重新构建和重新测试
                                         continue;
                                     }
                                     interaction::FailureChoice::AddSuggestion => {
@@ -1311,10 +2262,10 @@ pub(crate) fn handle_test_failure_interactive(
                     }
                 }
                 Err(e) => {
-                    println!("│ {}", format!("Failed to open vim: {}", e).red());
+                    println!("│ {}", format!("Failed to open editor: {}", e).red());
                     println!("│ {}", "Falling back to exit.".yellow());
                     Err(e).context(format!(
-                        "Tests failed (original error: {}) and could not open vim",
+                        "Tests failed (original error: {}) and could not open editor",
                         test_error
                     ))
                 }
@@ -1325,7 +2276,7 @@ pub(crate) fn handle_test_failure_interactive(
             unreachable!("Skip and FixOtherFile are not offered in this context")
         }
         interaction::FailureChoice::Exit => {
-            println!("│");
+            ui::blank();
             println!("│ {}", "You chose: Exit".yellow());
             println!("│ {}", "Exiting due to test failures.".yellow());
             Err(test_error).context("Tests failed and user chose to exit")
@@ -1355,7 +2306,7 @@ pub fn run_full_build_and_test_interactive(
     _rs_file: &std::path::Path,
     skip_test: bool,
 ) -> Result<()> {
-    println!("│");
+    ui::blank();
     println!(
         "│ {}",
         "Running full build and test flow...".bright_blue().bold()
@@ -1363,80 +2314,71 @@ pub fn run_full_build_and_test_interactive(
 
     // 1. 更新代码分析，然后检查 Rust 代码（快速失败检查：Rust 代码无法编译则提前返回）
     // 使用 cargo check 而非 cargo build，跳过代码生成以提升速度；实际产物由步骤3生成。
-    println!(
-        "│ {}",
-        "→ Step 1/4: Updating code analysis and checking Rust code (cargo check)...".bright_blue()
-    );
-    println!("│ {}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("│ {}", "✓ Code analysis updated".bright_green());
+    ui::info("→ Step 1/4: Updating code analysis and checking Rust code (cargo check)...");
+    ui::info("Updating code analysis...");
+    let updated = analyzer::update_code_analysis(feature)?;
+    ui::success(analyzer::update_status_message(updated));
     match cargo_check(feature, true, false) {
         Ok(_) => {
             println!("│ {}", "  ✓ Rust check successful".bright_green());
         }
         Err(e) => {
             println!("│ {}", "  ✗ Rust check failed".red());
-            println!("│");
+            ui::blank();
             println!("│ {}", "Error details:".red().bold());
             println!("│ {}", format!("{:#}", e).red());
-            println!("│");
+            ui::blank();
             return Err(e).context("Rust check failed in full build flow");
         }
     }
 
     // 2. 清理混合构建环境（代码分析已在步骤 1 更新，此处跳过）
-    println!("│ {}", "→ Step 2/4: Cleaning hybrid build...".bright_blue());
+    ui::info("→ Step 2/4: Cleaning hybrid build...");
     match c2rust_clean_no_analysis(feature) {
         Ok(_) => {
             println!("│ {}", "  ✓ Clean successful".bright_green());
         }
         Err(e) => {
             println!("│ {}", "  ✗ Clean failed".red());
-            println!("│");
+            ui::blank();
             println!("│ {}", "Error details:".red().bold());
             println!("│ {}", format!("{:#}", e).red());
-            println!("│");
+            ui::blank();
             return Err(e).context("Clean failed in full build flow");
         }
     }
 
     // 3. 混合构建（代码分析已在步骤 1 更新，此处跳过；不调用交互式处理器以避免递归）
-    println!(
-        "│ {}",
-        "→ Step 3/4: Running hybrid build (C + Rust)...".bright_blue()
-    );
+    ui::info("→ Step 3/4: Running hybrid build (C + Rust)...");
     match c2rust_build_no_analysis(feature) {
         Ok(_) => {
             println!("│ {}", "  ✓ Hybrid build successful".bright_green());
         }
         Err(e) => {
             println!("│ {}", "  ✗ Hybrid build failed".red());
-            println!("│");
+            ui::blank();
             println!("│ {}", "Error details:".red().bold());
             println!("│ {}", format!("{:#}", e).red());
-            println!("│");
+            ui::blank();
             return Err(e).context("Hybrid build failed in full build flow");
         }
     }
 
     // 4. 运行测试（代码分析已在步骤 1 更新，此处跳过；不调用交互式处理器以避免递归）
     if skip_test {
-        println!(
-            "│ {}",
-            "⚠ Skipping test phase (test configuration not available)".yellow()
-        );
+        ui::warning("Skipping test phase (test configuration not available)");
     } else {
-        println!("│ {}", "→ Step 4/4: Running tests...".bright_blue());
+        ui::info("→ Step 4/4: Running tests...");
         match c2rust_test_no_analysis(feature) {
             Ok(_) => {
                 println!("│ {}", "  ✓ All tests passed".bright_green().bold());
             }
             Err(e) => {
                 println!("│ {}", "  ✗ Tests failed".red());
-                println!("│");
+                ui::blank();
                 println!("│ {}", "Error details:".red().bold());
                 println!("│ {}", format!("{:#}", e).red());
-                println!("│");
+                ui::blank();
                 if crate::should_continue_on_test_error() {
                     println!(
                         "│ {}",
@@ -1455,6 +2397,348 @@ pub fn run_full_build_and_test_interactive(
 
 #[cfg(test)]
 mod tests {
+    use super::BuildProfile;
+
+    #[test]
+    fn test_collect_config_problems_aggregates_every_issue() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("build")).unwrap();
+        let config = |entries: &[(&str, &str)]| {
+            let entries: std::collections::HashMap<String, String> = entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            move |key: &str| {
+                entries
+                    .get(key)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Empty {} value from config", key))
+            }
+        };
+
+        // 完整且合法；test.* 都未配置时不报错
+        let ok = config(&[
+            ("build.cmd", "make"),
+            ("build.dir", "build"),
+            ("clean.cmd", "make clean"),
+            ("clean.dir", "build"),
+        ]);
+        assert!(super::collect_config_problems(temp_dir.path(), ok).is_empty());
+
+        let bad = config(&[
+            ("build.cmd", "make"),
+            ("build.dir", "/abs/build"),
+            ("test.cmd", "make test"),
+            ("test.dir", "missing"),
+            ("clean.cmd", "'unterminated"),
+            ("clean.dir", "../outside"),
+        ]);
+        let problems = super::collect_config_problems(temp_dir.path(), bad);
+        assert_eq!(problems.len(), 4, "{problems:#?}");
+        assert!(problems[0].starts_with("build.dir: Directory path from config must be relative"));
+        assert!(problems[1].starts_with("test.dir: directory does not exist"));
+        assert!(problems[2].starts_with("clean.cmd: cannot parse"));
+        assert!(
+            problems[3].starts_with("clean.dir: Directory path from config cannot contain '..'")
+        );
+
+        let partial_test = config(&[
+            ("build.cmd", "make"),
+            ("build.dir", "build"),
+            ("test.cmd", "make test"),
+            ("clean.cmd", "make clean"),
+            ("clean.dir", "build"),
+        ]);
+        let problems = super::collect_config_problems(temp_dir.path(), partial_test);
+        assert_eq!(problems, ["test.dir: Empty test.dir value from config"]);
+    }
+
+    #[test]
+    fn test_c2rust_config_unknown_option_maps_to_version_mismatch() {
+        let stderr = "error: unknown option '--make'\nusage: c2rust-config config [--list KEY]\n";
+        assert!(super::is_unsupported_c2rust_config_usage(stderr));
+        let message = super::c2rust_config_version_mismatch("build.dir", stderr).to_string();
+        assert!(message.contains("c2rust-config version mismatch while reading build.dir"));
+        assert!(message.contains(super::C2RUST_CONFIG_REQUIRED_USAGE));
+        assert!(message.contains("c2rust-config reported: error: unknown option '--make'"));
+        assert!(!message.contains("usage:"));
+
+        assert!(super::is_unsupported_c2rust_config_usage(
+            "error: unrecognized subcommand 'config'"
+        ));
+        assert!(!super::is_unsupported_c2rust_config_usage(
+            "Error: key build.target not found"
+        ));
+    }
+
+    #[test]
+    fn test_parse_build_timeout() {
+        assert_eq!(super::parse_build_timeout("900").unwrap(), 900);
+        assert_eq!(super::parse_build_timeout(" 30 ").unwrap(), 30);
+        assert!(super::parse_build_timeout("0").is_err());
+        assert!(super::parse_build_timeout("10m").is_err());
+    }
+
+    #[test]
+    fn test_classify_transient_failure() {
+        let defaults: Vec<String> = super::DEFAULT_TRANSIENT_BUILD_ERRORS
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            super::classify_transient_failure(
+                "error: failed to write target/debug/deps/x.rlib: No space left on device (os error 28)",
+                None,
+                &defaults
+            )
+            .as_deref(),
+            Some("os error 28")
+        );
+        assert_eq!(
+            super::classify_transient_failure(
+                "error: could not compile `demo` (lib)\n\nCaused by:\n  process didn't exit successfully: `rustc ...` (signal: 9, SIGKILL: kill)\n",
+                Some(101),
+                &defaults
+            )
+            .as_deref(),
+            Some("(signal: 9, SIGKILL")
+        );
+        assert_eq!(
+            super::classify_transient_failure("", Some(9), &defaults).as_deref(),
+            Some("signal 9 (SIGKILL)")
+        );
+        assert_eq!(
+            super::classify_transient_failure(
+                "error[E0308]: mismatched types",
+                Some(101),
+                &defaults
+            ),
+            None
+        );
+        assert_eq!(
+            super::classify_transient_failure("Killed", None, &["".to_string()]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_classify_transient_failure_ignores_rustc_diagnostics() {
+        let defaults: Vec<String> = super::DEFAULT_TRANSIENT_BUILD_ERRORS
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let stderr = "\
+error[E0425]: cannot find value `x` in this scope
+ --> src/fun_a.rs:3:5
+  |
+3 |     panic!(\"memory allocation of {} bytes failed (os error 12)\", x);
+  |                                                                   ^ not found in this scope
+
+error: could not compile `demo` (lib) due to 1 previous error
+";
+        assert_eq!(
+            super::classify_transient_failure(stderr, Some(101), &defaults),
+            None
+        );
+        assert_eq!(
+            super::cargo_own_lines(stderr),
+            vec!["error: could not compile `demo` (lib) due to 1 previous error"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_with_timeout_collects_output() {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.args(["-c", "echo out; echo err >&2; exit 3"]);
+        let output =
+            super::output_with_timeout(&mut cmd, std::time::Duration::from_secs(10), "sh").unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "err\n");
+    }
+
+    #[test]
+    fn test_validate_hybrid_build_lib_reports_missing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let missing = temp_dir.path().join("libhybrid.so");
+
+        let err = super::validate_hybrid_build_lib(&missing)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("libhybrid.so"), "{}", err);
+        assert!(err.contains("C2RUST_HYBRID_BUILD_LIB"), "{}", err);
+        assert!(super::validate_hybrid_build_lib(temp_dir.path()).is_err());
+
+        std::fs::write(&missing, b"").unwrap();
+        assert!(super::validate_hybrid_build_lib(&missing).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_failed_keeps_outcome_and_message() {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.args(["-c", "echo out; printf 'e1\\ne2\\ne3\\ne4\\n' >&2; exit 2"]);
+        let output =
+            super::output_with_timeout(&mut cmd, std::time::Duration::from_secs(10), "sh").unwrap();
+        let error = anyhow::Error::new(super::CommandFailed {
+            command: "make".to_string(),
+            outcome: super::CommandOutcome::from_output(
+                &output,
+                std::time::Duration::from_millis(7),
+            ),
+        })
+        .context("Build failed");
+
+        let failed = error.downcast_ref::<super::CommandFailed>().unwrap();
+        assert_eq!(failed.outcome.status.code(), Some(2));
+        assert_eq!(failed.outcome.duration, std::time::Duration::from_millis(7));
+        assert_eq!(failed.outcome.stdout, "out\n");
+        assert_eq!(
+            failed.to_string(),
+            "Command 'make' failed with non-zero exit status. Stderr (first lines):\ne1\ne2\ne3"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_with_timeout_kills_hung_command() {
+        let mut cmd = std::process::Command::new("sh");
+        // The inner sleep keeps the pipes open, so this only returns if the whole group is killed.
+        cmd.args(["-c", "sleep 30 & sleep 30"]);
+        let start = std::time::Instant::now();
+        let err =
+            super::output_with_timeout(&mut cmd, std::time::Duration::from_millis(200), "sleep 30")
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Command 'sleep 30' timed out after 0 seconds"));
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_format_cargo_command() {
+        use super::format_cargo_command;
+
+        assert_eq!(
+            format_cargo_command("check", Some("-A warnings"), BuildProfile::Debug),
+            "RUSTFLAGS='-A warnings' cargo check"
+        );
+        assert_eq!(
+            format_cargo_command("build", None, BuildProfile::Release),
+            "cargo build --release"
+        );
+    }
+
+    #[test]
+    fn test_build_profile_from_str() {
+        assert_eq!("debug".parse::<BuildProfile>(), Ok(BuildProfile::Debug));
+        assert_eq!("Release".parse::<BuildProfile>(), Ok(BuildProfile::Release));
+        assert!("fast".parse::<BuildProfile>().is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_set_verify_profiles_splits_primary_and_extra() {
+        super::set_verify_profiles(&[
+            BuildProfile::Release,
+            BuildProfile::Debug,
+            BuildProfile::Release,
+        ]);
+        assert_eq!(super::active_build_profile(), BuildProfile::Release);
+        assert_eq!(super::extra_verify_profiles(), vec![BuildProfile::Debug]);
+
+        // 作用域内临时切换 profile，结束后恢复
+        super::with_build_profile(BuildProfile::Debug, || {
+            assert_eq!(super::active_build_profile(), BuildProfile::Debug);
+        });
+        assert_eq!(super::active_build_profile(), BuildProfile::Release);
+
+        super::set_verify_profiles(&[]);
+        assert_eq!(super::active_build_profile(), BuildProfile::Debug);
+        assert!(super::extra_verify_profiles().is_empty());
+    }
+
+    #[test]
+    fn test_format_failure_output_labels_and_truncates() {
+        use super::format_failure_output;
+
+        // 没有 stdout 时保持原样
+        assert_eq!(format_failure_output("e1\ne2\n", "", 5, false), "e1\ne2\n");
+
+        let output = format_failure_output("e1\ne2\ne3\n", "o1\n", 2, false);
+        assert_eq!(
+            output,
+            "\n--- stderr ---\ne1\ne2\n\
+             ... (1 more lines truncated; use --show-full-output to see everything)\n\
+             --- stdout ---\no1\n"
+        );
+        assert!(!format_failure_output("e1\ne2\ne3\n", "o1\n", 2, true).contains("truncated"));
+    }
+
+    #[test]
+    fn test_compose_rustflags_combines_config_extra_and_suppression() {
+        use super::compose_rustflags;
+
+        assert_eq!(
+            compose_rustflags(
+                true,
+                false,
+                None,
+                Some("--cfg c2rust"),
+                Some("-Zmacro-backtrace --verbose"),
+            )
+            .as_deref(),
+            Some("--cfg c2rust -Zmacro-backtrace --verbose -A warnings")
+        );
+        assert_eq!(
+            compose_rustflags(false, false, None, None, Some("-Zmacro-backtrace")).as_deref(),
+            Some("-Zmacro-backtrace")
+        );
+        assert_eq!(compose_rustflags(false, false, None, None, None), None);
+    }
+
+    #[test]
+    fn test_compose_rustflags_keeps_environment_and_deny_warnings() {
+        use super::compose_rustflags;
+
+        // 环境变量中的 RUSTFLAGS 保留在最前面
+        assert_eq!(
+            compose_rustflags(
+                true,
+                false,
+                Some("-C target-cpu=native"),
+                Some("--cfg c2rust"),
+                None
+            )
+            .as_deref(),
+            Some("-C target-cpu=native --cfg c2rust -A warnings")
+        );
+        // 环境变量自行设置了 warnings 级别时不再抑制告警
+        assert_eq!(
+            compose_rustflags(true, false, Some("-W warnings"), None, None).as_deref(),
+            Some("-W warnings")
+        );
+        assert_eq!(
+            compose_rustflags(true, true, Some("--cfg x"), None, None).as_deref(),
+            Some("--cfg x -D warnings")
+        );
+        assert_eq!(
+            compose_rustflags(false, true, None, None, None).as_deref(),
+            Some("-D warnings")
+        );
+    }
+
+    #[test]
+    fn test_validate_extra_rustflags_rejects_warning_overrides() {
+        assert!(super::validate_extra_rustflags("-Zmacro-backtrace --verbose").is_ok());
+        assert!(super::validate_extra_rustflags("-A dead_code").is_ok());
+        assert!(super::validate_extra_rustflags("-D warnings").is_err());
+        assert!(super::validate_extra_rustflags("-Wwarnings").is_err());
+        assert!(super::validate_extra_rustflags("--deny=warnings").is_err());
+        assert!(super::validate_extra_rustflags("--cap-lints allow").is_err());
+    }
+
     /// Test that warning detection recognises `warning[code]:` patterns
     #[test]
     fn test_detect_warning_code_format() {
@@ -1545,9 +2829,9 @@ mod tests {
         fs::write(&rs_file_path, "// test").unwrap();
 
         // Error message referencing the same file
-        let error_str = format!(
+        let error_str =
             "error[E0308]: mismatched types\n  --> src/fun_test.rs:10:5\n  |\n10 |     x\n"
-        );
+                .to_string();
 
         let files =
             super::get_manual_fix_files(feature, &rs_file_path, &error_str);
@@ -1566,4 +2850,53 @@ mod tests {
             .count();
         assert_eq!(count, 1, "rs_file should appear exactly once in the result");
     }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_get_config_value_reads_the_config_override() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join(".c2rust")).unwrap();
+        std::fs::write(project_root.join(".c2rust/config.toml"), "project\n").unwrap();
+        let fixture = project_root.join("fixture.toml");
+        std::fs::write(&fixture, "fixture\n").unwrap();
+
+        // A fake c2rust-config that prints the config.toml of its working directory.
+        let bin_dir = project_root.join("bin");
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        let script = bin_dir.join("c2rust-config");
+        std::fs::write(&script, "#!/bin/sh\ncat config.toml\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let prior_path = std::env::var_os("PATH");
+        let mut path = std::ffi::OsString::from(&bin_dir);
+        if let Some(prior) = &prior_path {
+            path.push(":");
+            path.push(prior);
+        }
+        std::env::set_var("PATH", path);
+        let prior_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(project_root).unwrap();
+        let _restore = scopeguard::guard((prior_path, prior_dir), |(path, dir)| {
+            match path {
+                Some(path) => std::env::set_var("PATH", path),
+                None => std::env::remove_var("PATH"),
+            }
+            let _ = std::env::set_current_dir(dir);
+            let _ = crate::util::set_config_path(None);
+        });
+
+        assert_eq!(
+            super::get_config_value("build.cmd", "default").unwrap(),
+            "project"
+        );
+        crate::util::set_config_path(Some(&fixture)).unwrap();
+        assert_eq!(
+            super::get_config_value("build.cmd", "default").unwrap(),
+            "fixture"
+        );
+    }
 }
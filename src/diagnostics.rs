@@ -0,0 +1,253 @@
+//! cargo `--message-format=json` 结构化诊断的解析
+//!
+//! `cargo build` / `cargo check` 以 JSON 格式输出诊断，解析出 `compiler-message` 中每条诊断的
+//! 文件、行列、级别、错误码和消息，供修复流程按文件精确分组，交互界面只展示出错位置附近的代码。
+//! 输出无法按 JSON 解析时返回 `None`，调用方回退到对文本输出的正则解析。
+
+use colored::Colorize;
+use std::path::Path;
+
+/// 一条编译器诊断（取主 span 的位置）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerDiagnostic {
+    /// 相对 crate 根目录的文件路径（如 `src/fun_add.rs`）
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// `error`、`warning` 等
+    pub level: String,
+    /// 错误码（如 `E0308`），没有时为 `None`
+    pub code: Option<String>,
+    pub message: String,
+    /// rustc 渲染的完整文本（与非 JSON 模式下 stderr 中的内容一致）
+    pub rendered: String,
+}
+
+impl CompilerDiagnostic {
+    /// 是否为 error 或 warning 级别（note、help 等辅助信息不用于定位文件）
+    pub fn is_error_or_warning(&self) -> bool {
+        self.level == "error" || self.level == "warning"
+    }
+}
+
+/// 解析后的 cargo 输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedCargoOutput {
+    pub diagnostics: Vec<CompilerDiagnostic>,
+    /// 人类可读的输出：cargo 自身的 stderr 中插入各条诊断的渲染文本
+    pub text: String,
+}
+
+/// 解析 `cargo --message-format=json` 的 stdout，并与 stderr 合成人类可读的输出
+///
+/// stdout 中任意一行不是 JSON 对象时返回 `None`。
+pub fn parse_cargo_json(stdout: &str, stderr: &str) -> Option<ParsedCargoOutput> {
+    let mut diagnostics = Vec::new();
+    let mut rendered = String::new();
+
+    for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+        let value: serde_json::Value = serde_json::from_str(line).ok()?;
+        if !value.is_object() {
+            return None;
+        }
+        if value["reason"] != "compiler-message" {
+            continue;
+        }
+        let message = &value["message"];
+        if let Some(text) = message["rendered"].as_str() {
+            rendered.push_str(text);
+        }
+        if let Some(diagnostic) = parse_compiler_message(message) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    // cargo 的 "Compiling ..." 等进度行在诊断之前，"error: could not compile ..." 等总结在之后
+    let split = stderr
+        .lines()
+        .position(|line| line.starts_with("error") || line.starts_with("warning"))
+        .unwrap_or(stderr.lines().count());
+    let mut text = String::new();
+    for line in stderr.lines().take(split) {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.push_str(&rendered);
+    for line in stderr.lines().skip(split) {
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    Some(ParsedCargoOutput { diagnostics, text })
+}
+
+/// 从一条 rustc 诊断 JSON 中取出主 span；没有位置信息的诊断（如 "aborting due to ..."）返回 `None`
+fn parse_compiler_message(message: &serde_json::Value) -> Option<CompilerDiagnostic> {
+    let spans = message["spans"].as_array()?;
+    let span = spans
+        .iter()
+        .find(|span| span["is_primary"].as_bool() == Some(true))
+        .or_else(|| spans.first())?;
+    Some(CompilerDiagnostic {
+        file: span["file_name"].as_str()?.to_string(),
+        line: span["line_start"].as_u64()? as usize,
+        column: span["column_start"].as_u64().unwrap_or(1) as usize,
+        level: message["level"].as_str().unwrap_or("error").to_string(),
+        code: message["code"]["code"].as_str().map(str::to_string),
+        message: message["message"].as_str().unwrap_or("").to_string(),
+        rendered: message["rendered"].as_str().unwrap_or("").to_string(),
+    })
+}
+
+/// cargo build / check 失败的错误，携带解析出的诊断
+///
+/// 显示文本与原先的 `"<label>: <stderr>"` 一致且不截断，因此按文本处理错误的代码（修复器、
+/// 按文本定位文件）拿到的是完整输出；需要结构化信息时通过 [`diagnostics_of`] 取出。
+/// 给用户看的文本用 [`display_text`]，按 `build.error_lines` 截断。
+#[derive(Debug)]
+pub struct CargoBuildError {
+    pub label: String,
+    /// 完整输出
+    pub output: String,
+    /// 按 `build.error_lines` 截断后用于显示的输出
+    pub display_output: String,
+    pub diagnostics: Vec<CompilerDiagnostic>,
+}
+
+impl std::fmt::Display for CargoBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.label, self.output)
+    }
+}
+
+impl std::error::Error for CargoBuildError {}
+
+/// 显示给用户的错误文本：cargo 构建错误使用截断后的输出，其他错误原样显示
+pub fn display_text(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<CargoBuildError>() {
+        Some(e) if error.chain().count() == 1 => format!("{}: {}", e.label, e.display_output),
+        _ => error.to_string(),
+    }
+}
+
+/// 取出错误中携带的诊断；不是 cargo 构建错误或 JSON 解析失败（没有诊断）时返回 `None`
+pub fn diagnostics_of(error: &anyhow::Error) -> Option<&[CompilerDiagnostic]> {
+    error
+        .downcast_ref::<CargoBuildError>()
+        .map(|e| e.diagnostics.as_slice())
+        .filter(|diagnostics| !diagnostics.is_empty())
+}
+
+/// 出错位置前后展示的行数
+const SNIPPET_CONTEXT_LINES: usize = 2;
+
+/// 只展示 `rs_file` 中各条 error/warning 所在位置附近的代码
+pub fn display_snippets(diagnostics: &[CompilerDiagnostic], rs_file: &Path) {
+    let Some(file_name) = rs_file.file_name().and_then(|n| n.to_str()) else {
+        return;
+    };
+    let Ok(content) = std::fs::read_to_string(rs_file) else {
+        return;
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let relevant: Vec<&CompilerDiagnostic> = diagnostics
+        .iter()
+        .filter(|d| {
+            d.is_error_or_warning() && Path::new(&d.file).file_name() == Some(file_name.as_ref())
+        })
+        .collect();
+    if relevant.is_empty() {
+        return;
+    }
+
+    println!("│");
+    println!("│ {}", "═══ Error Locations ═══".bright_red().bold());
+    for diagnostic in relevant {
+        let code = diagnostic
+            .code
+            .as_ref()
+            .map(|code| format!("[{}]", code))
+            .unwrap_or_default();
+        println!("│");
+        println!(
+            "│ {} {}",
+            format!(
+                "{}:{}:{}",
+                diagnostic.file, diagnostic.line, diagnostic.column
+            )
+            .bright_cyan(),
+            format!("{}{}: {}", diagnostic.level, code, diagnostic.message).red()
+        );
+        let first = diagnostic.line.saturating_sub(SNIPPET_CONTEXT_LINES).max(1);
+        let last = (diagnostic.line + SNIPPET_CONTEXT_LINES).min(lines.len());
+        for number in first..=last {
+            let text = lines[number - 1];
+            if number == diagnostic.line {
+                println!("│ {} {}", format!("{:>5} >", number).bright_red(), text);
+            } else {
+                println!("│ {} {}", format!("{:>5} |", number).dimmed(), text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STDOUT: &str = r#"{"reason":"compiler-artifact","package_id":"dep 0.1.0","target":{"name":"dep"}}
+{"reason":"compiler-message","package_id":"demo 0.1.0","message":{"rendered":"error[E0308]: mismatched types\n --> src/fun_add.rs:3:18\n\n","level":"error","message":"mismatched types","code":{"code":"E0308","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":1,"column_start":1,"is_primary":false},{"file_name":"src/fun_add.rs","line_start":3,"column_start":18,"is_primary":true}]}}
+{"reason":"compiler-message","package_id":"demo 0.1.0","message":{"rendered":"error: aborting due to 1 previous error\n\n","level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}
+{"reason":"build-finished","success":false}
+"#;
+
+    #[test]
+    fn test_parse_cargo_json_extracts_primary_spans() {
+        let stderr = "   Compiling demo v0.1.0\nerror: could not compile `demo` (lib) due to 1 previous error\n";
+        let parsed = parse_cargo_json(STDOUT, stderr).unwrap();
+
+        assert_eq!(
+            parsed.diagnostics,
+            vec![CompilerDiagnostic {
+                file: "src/fun_add.rs".to_string(),
+                line: 3,
+                column: 18,
+                level: "error".to_string(),
+                code: Some("E0308".to_string()),
+                message: "mismatched types".to_string(),
+                rendered: "error[E0308]: mismatched types\n --> src/fun_add.rs:3:18\n\n"
+                    .to_string(),
+            }]
+        );
+        assert_eq!(
+            parsed.text,
+            "   Compiling demo v0.1.0\n\
+             error[E0308]: mismatched types\n --> src/fun_add.rs:3:18\n\n\
+             error: aborting due to 1 previous error\n\n\
+             error: could not compile `demo` (lib) due to 1 previous error\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_cargo_json_rejects_text_output() {
+        assert_eq!(
+            parse_cargo_json("error[E0308]: mismatched types\n", ""),
+            None
+        );
+
+        let error = anyhow::Error::new(CargoBuildError {
+            label: "Build error".to_string(),
+            output: "raw\nmore".to_string(),
+            display_output: "raw".to_string(),
+            diagnostics: Vec::new(),
+        });
+        assert_eq!(error.to_string(), "Build error: raw\nmore");
+        assert_eq!(display_text(&error), "Build error: raw");
+        let error = error.context("Build failed");
+        assert!(diagnostics_of(&error).is_none());
+        assert_eq!(
+            error.downcast_ref::<CargoBuildError>().unwrap().to_string(),
+            "Build error: raw\nmore"
+        );
+    }
+}
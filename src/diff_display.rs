@@ -1,8 +1,9 @@
-//! 代码比较显示工具，用于并排显示 C 和 Rust 代码
+//! 代码比较显示工具，用于并排（或上下堆叠）显示 C 和 Rust 代码
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::path::Path;
+use std::sync::Mutex;
 use terminal_size::{terminal_size, Width};
 
 // 默认列宽常量（当无法检测终端大小时使用）
@@ -19,6 +20,82 @@ const SEPARATOR_SPACING: usize = 2;
 // 终端宽度的最小要求：保证每列至少有 MIN_COLUMN_WIDTH 字符宽度
 const MIN_TERMINAL_WIDTH: usize =
     (MIN_COLUMN_WIDTH * 2) + ((LINE_NUM_WIDTH + 1) * 2) + SEPARATOR_CHAR_COUNT + SEPARATOR_SPACING;
+// 无法检测终端大小（非 TTY）时假定的终端宽度
+const FALLBACK_TERMINAL_WIDTH: usize = 80;
+
+/// 代码比较的布局（`--diff-layout`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffLayout {
+    /// 终端足够宽时并排显示，否则上下堆叠（默认）
+    #[default]
+    Auto,
+    /// C 代码在上、Rust 代码在下
+    Stacked,
+    /// 左右两列；`width` 为总宽度，`None` 时按终端宽度计算
+    SideBySide { width: Option<usize> },
+}
+
+impl std::str::FromStr for DiffLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let value = s.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "auto" => return Ok(DiffLayout::Auto),
+            "stacked" => return Ok(DiffLayout::Stacked),
+            "side-by-side" => return Ok(DiffLayout::SideBySide { width: None }),
+            _ => {}
+        }
+        match value.strip_prefix("side-by-side=") {
+            Some(width) => match width.parse::<usize>() {
+                Ok(width) if width > 0 => Ok(DiffLayout::SideBySide { width: Some(width) }),
+                _ => Err(format!("invalid side-by-side width `{width}`")),
+            },
+            None => Err(format!(
+                "unknown diff layout `{s}` (expected `auto`, `stacked`, `side-by-side` or `side-by-side=<COLS>`)"
+            )),
+        }
+    }
+}
+
+/// 本次运行的代码比较布局，在 `translate_feature` 开始时设置
+static DIFF_LAYOUT: Mutex<DiffLayout> = Mutex::new(DiffLayout::Auto);
+
+/// 设置 `--diff-layout`
+pub fn set_diff_layout(layout: DiffLayout) {
+    *DIFF_LAYOUT.lock().unwrap_or_else(|e| e.into_inner()) = layout;
+}
+
+fn diff_layout() -> DiffLayout {
+    *DIFF_LAYOUT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 实际使用的布局及总宽度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResolvedLayout {
+    SideBySide(usize),
+    Stacked(usize),
+}
+
+/// 根据布局选项和终端宽度确定实际布局；并排显示的宽度至少为 `MIN_TERMINAL_WIDTH`
+fn resolve_layout(layout: DiffLayout, term_width: usize) -> ResolvedLayout {
+    match layout {
+        DiffLayout::Auto if term_width >= MIN_TERMINAL_WIDTH => {
+            ResolvedLayout::SideBySide(term_width)
+        }
+        DiffLayout::Auto | DiffLayout::Stacked => ResolvedLayout::Stacked(term_width),
+        DiffLayout::SideBySide { width } => {
+            ResolvedLayout::SideBySide(width.unwrap_or(term_width).max(MIN_TERMINAL_WIDTH))
+        }
+    }
+}
+
+/// 当前终端宽度，非 TTY 时为 `FALLBACK_TERMINAL_WIDTH`
+fn terminal_width() -> usize {
+    terminal_size()
+        .map(|(Width(width), _)| width as usize)
+        .unwrap_or(FALLBACK_TERMINAL_WIDTH)
+}
 
 /// 根据给定的终端宽度计算列宽
 ///
@@ -48,70 +125,78 @@ fn compute_column_widths(term_width: usize) -> (usize, usize) {
     (column_width, column_width)
 }
 
-/// 获取适配终端大小的列宽
-///
-/// 根据当前终端宽度动态计算 C 和 Rust 代码列的宽度
-///
-/// # Returns
-/// 返回 (c_column_width, rust_column_width) 元组
-fn get_adaptive_column_widths() -> (usize, usize) {
-    if let Some((Width(terminal_width), _)) = terminal_size() {
-        compute_column_widths(terminal_width as usize)
-    } else {
-        // 无法检测终端大小，使用默认值
-        (DEFAULT_C_COLUMN_WIDTH, DEFAULT_RUST_COLUMN_WIDTH)
-    }
-}
-
-/// 并排显示 C 和 Rust 代码及测试/构建结果
+/// 显示 C 和 Rust 代码及测试/构建结果（布局见 [`DiffLayout`]）
 pub fn display_code_comparison(
     c_file: &Path,
     rust_file: &Path,
     result_message: &str,
     result_type: ResultType,
 ) -> Result<()> {
-    // 获取适配终端大小的列宽
-    let (c_column_width, rust_column_width) = get_adaptive_column_widths();
+    // 读取文件内容
+    let c_content = std::fs::read_to_string(c_file)
+        .with_context(|| format!("Failed to read C file: {}", c_file.display()))?;
+    let rust_content = std::fs::read_to_string(rust_file)
+        .with_context(|| format!("Failed to read Rust file: {}", rust_file.display()))?;
+
+    let c_lines: Vec<&str> = c_content.lines().collect();
+    let rust_lines: Vec<&str> = rust_content.lines().collect();
+
+    let (lines, total_width) = match resolve_layout(diff_layout(), terminal_width()) {
+        ResolvedLayout::SideBySide(term_width) => {
+            let (c_column_width, rust_column_width) = compute_column_widths(term_width);
+            render_side_by_side(&c_lines, &rust_lines, c_column_width, rust_column_width)
+        }
+        ResolvedLayout::Stacked(term_width) => (
+            render_stacked(&c_lines, &rust_lines, term_width),
+            term_width,
+        ),
+    };
 
     println!("│");
+    print_title("C vs Rust Code Comparison", total_width);
+    for line in lines {
+        println!("{}", line);
+    }
 
-    // 根据计算出的列宽动态生成分隔线
-    // total_width 包括: 两个代码列 + 行号列 + 分隔符
-    let total_width = (LINE_NUM_WIDTH + 1 + c_column_width + 1)
-        + (LINE_NUM_WIDTH + 1 + rust_column_width + 1)
-        + SEPARATOR_CHAR_COUNT;
-    println!("{}", "═".repeat(total_width).bright_cyan());
+    // 显示结果部分
+    display_result_section(result_message, result_type, total_width);
 
-    // 居中显示标题
-    let title = "C vs Rust Code Comparison";
+    Ok(())
+}
+
+/// 居中打印带双线上下边框的标题
+fn print_title(title: &str, total_width: usize) {
+    println!("{}", "═".repeat(total_width).bright_cyan());
     let padding = (total_width.saturating_sub(title.len())) / 2;
     println!(
         "{}{}{}",
         " ".repeat(padding),
         title.bright_cyan().bold(),
-        " ".repeat(total_width - padding - title.len())
+        " ".repeat(total_width.saturating_sub(padding + title.len()))
     );
     println!("{}", "═".repeat(total_width).bright_cyan());
+}
 
-    // 读取文件内容
-    let c_content = std::fs::read_to_string(c_file)
-        .with_context(|| format!("Failed to read C file: {}", c_file.display()))?;
-    let rust_content = std::fs::read_to_string(rust_file)
-        .with_context(|| format!("Failed to read Rust file: {}", rust_file.display()))?;
-
-    let c_lines: Vec<&str> = c_content.lines().collect();
-    let rust_lines: Vec<&str> = rust_content.lines().collect();
-
-    // 显示表头
+/// 生成左右两列的代码表格，返回 (各行, 总宽度)
+fn render_side_by_side(
+    c_lines: &[&str],
+    rust_lines: &[&str],
+    c_column_width: usize,
+    rust_column_width: usize,
+) -> (Vec<String>, usize) {
+    // total_width 包括: 两个代码列 + 行号列 + 分隔符
     let c_total_width = LINE_NUM_WIDTH + 1 + c_column_width + 1;
     let rust_total_width = LINE_NUM_WIDTH + 1 + rust_column_width + 1;
-    println!(
+    let total_width = c_total_width + rust_total_width + SEPARATOR_CHAR_COUNT;
+
+    // 显示表头
+    let mut lines = vec![format!(
         "┌{:─<width1$}┬{:─<width2$}┐",
         "─ C Source Code ",
         "─ Rust Code ─",
         width1 = c_total_width,
         width2 = rust_total_width
-    );
+    )];
 
     // 并排显示行
     let max_lines = std::cmp::max(c_lines.len(), rust_lines.len());
@@ -133,7 +218,7 @@ pub fn display_code_comparison(
             let c_line_num = format_line_number(j, i + 1);
             let rust_line_num = format_line_number(j, i + 1);
 
-            println!(
+            lines.push(format!(
                 "│ {} {:<c_width$}│ {} {:<r_width$}│",
                 c_line_num,
                 c_display,
@@ -141,24 +226,42 @@ pub fn display_code_comparison(
                 rust_display,
                 c_width = c_column_width,
                 r_width = rust_column_width
-            );
+            ));
         }
     }
 
-    let c_total_width = LINE_NUM_WIDTH + 1 + c_column_width + 1;
-    let rust_total_width = LINE_NUM_WIDTH + 1 + rust_column_width + 1;
-    println!(
+    lines.push(format!(
         "└{:─<width1$}┴{:─<width2$}┘",
         "",
         "",
         width1 = c_total_width,
         width2 = rust_total_width
-    );
-
-    // 显示结果部分
-    display_result_section(result_message, result_type, total_width);
+    ));
+    (lines, total_width)
+}
 
-    Ok(())
+/// 生成上下堆叠的两个代码块（C 在上、Rust 在下），每行不超过 `total_width`
+fn render_stacked(c_lines: &[&str], rust_lines: &[&str], total_width: usize) -> Vec<String> {
+    // 格式："│ num code│"
+    let inner_width = total_width.saturating_sub(2);
+    let code_width = total_width.saturating_sub(LINE_NUM_WIDTH + 4).max(1);
+
+    let mut lines = Vec::new();
+    for (title, source) in [("─ C Source Code ", c_lines), ("─ Rust Code ", rust_lines)] {
+        lines.push(format!("┌{:─<width$}┐", title, width = inner_width));
+        for (i, line) in source.iter().enumerate() {
+            for (j, segment) in wrap_line(line, code_width).iter().enumerate() {
+                lines.push(format!(
+                    "│ {} {:<width$}│",
+                    format_line_number(j, i + 1),
+                    segment,
+                    width = code_width
+                ));
+            }
+        }
+        lines.push(format!("└{:─<width$}┘", "", width = inner_width));
+    }
+    lines
 }
 
 /// 格式化行号显示
@@ -361,15 +464,100 @@ mod tests {
     }
 
     #[test]
-    fn test_get_adaptive_column_widths() {
-        // 测试获取自适应列宽不会 panic（环境相关测试）
-        let (c_width, rust_width) = get_adaptive_column_widths();
+    fn test_terminal_width_resolves_to_a_usable_layout() {
+        // 环境相关测试：无论是否为 TTY，都应得到合理的宽度
+        match resolve_layout(DiffLayout::Auto, terminal_width()) {
+            ResolvedLayout::SideBySide(width) => {
+                let (c_width, rust_width) = compute_column_widths(width);
+                assert!(c_width > 0);
+                assert_eq!(c_width, rust_width);
+            }
+            ResolvedLayout::Stacked(width) => assert!(width > 0),
+        }
+    }
 
-        // 列宽应该是合理的值（大于0）
-        assert!(c_width > 0);
-        assert!(rust_width > 0);
+    #[test]
+    fn test_diff_layout_parsing() {
+        assert_eq!("auto".parse::<DiffLayout>().unwrap(), DiffLayout::Auto);
+        assert_eq!(
+            "Stacked".parse::<DiffLayout>().unwrap(),
+            DiffLayout::Stacked
+        );
+        assert_eq!(
+            "side-by-side".parse::<DiffLayout>().unwrap(),
+            DiffLayout::SideBySide { width: None }
+        );
+        assert_eq!(
+            "side-by-side=140".parse::<DiffLayout>().unwrap(),
+            DiffLayout::SideBySide { width: Some(140) }
+        );
+        assert!("side-by-side=0".parse::<DiffLayout>().is_err());
+        assert!("columns".parse::<DiffLayout>().is_err());
+        assert_eq!(DiffLayout::default(), DiffLayout::Auto);
+    }
 
-        // 两列宽度应该相同（平均分配）
-        assert_eq!(c_width, rust_width);
+    #[test]
+    fn test_resolve_layout() {
+        // 窄终端（包括非 TTY 的 80 列）默认上下堆叠
+        assert_eq!(
+            resolve_layout(DiffLayout::Auto, FALLBACK_TERMINAL_WIDTH),
+            ResolvedLayout::Stacked(80)
+        );
+        assert_eq!(
+            resolve_layout(DiffLayout::Auto, 120),
+            ResolvedLayout::SideBySide(120)
+        );
+        assert_eq!(
+            resolve_layout(DiffLayout::Stacked, 200),
+            ResolvedLayout::Stacked(200)
+        );
+        // 显式宽度优先于终端宽度，但不小于并排显示的最小宽度
+        assert_eq!(
+            resolve_layout(DiffLayout::SideBySide { width: Some(150) }, 80),
+            ResolvedLayout::SideBySide(150)
+        );
+        assert_eq!(
+            resolve_layout(DiffLayout::SideBySide { width: None }, 80),
+            ResolvedLayout::SideBySide(MIN_TERMINAL_WIDTH)
+        );
+    }
+
+    fn strip_ansi(line: &str) -> String {
+        let mut out = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\u{1b}' {
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_render_wraps_long_lines_within_columns() {
+        let long_line = "x".repeat(300);
+        let c_lines = ["int main(void) {", long_line.as_str(), "}"];
+        let rust_lines = ["fn main() {", long_line.as_str(), "}"];
+
+        let stacked = render_stacked(&c_lines, &rust_lines, 80);
+        assert!(stacked[0].starts_with("┌─ C Source Code "));
+        assert!(stacked.iter().any(|l| l.starts_with("┌─ Rust Code ")));
+        for line in &stacked {
+            assert_eq!(strip_ansi(line).chars().count(), 80, "{}", line);
+        }
+
+        let (c_width, rust_width) = compute_column_widths(120);
+        let (side_by_side, total_width) =
+            render_side_by_side(&c_lines, &rust_lines, c_width, rust_width);
+        assert!(side_by_side.len() > c_lines.len() + 2);
+        for line in &side_by_side {
+            assert_eq!(strip_ansi(line).chars().count(), total_width, "{}", line);
+        }
     }
 }
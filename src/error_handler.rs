@@ -3,9 +3,9 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::{builder, file_scanner, interaction, suggestion, translator, util};
+use crate::{builder, file_scanner, interaction, suggestion, translator, ui, util};
 
 /// 从错误消息中按出现顺序提取文件路径字符串
 /// 只提取 error 和 warning 级别诊断中引用的文件路径
@@ -20,9 +20,12 @@ fn collect_error_level_file_paths(error_msg: &str) -> Vec<String> {
         static ref NOTE_HELP_LEVEL_RE: regex::Regex =
             regex::Regex::new(r"^(?:note|help|suggestion)(?:\[.*?\])?[:\s]")
                 .expect("Failed to compile note/help level regex");
-        static ref FILE_PATH_LINE_RE: regex::Regex =
-            regex::Regex::new(r"^\s*(?:-->|at)\s+([^\s:]+\.rs)(?::\d+:\d+)?")
-                .expect("Failed to compile file path line regex");
+        // 路径可能是：带引号的路径（可含空格）、带盘符的 Windows 绝对路径
+        // （`C:\proj\src\fun_a.rs`）或普通相对/Unix 绝对路径，后面可跟 `:行:列`
+        static ref FILE_PATH_LINE_RE: regex::Regex = regex::Regex::new(
+            r#"^\s*(?:-->|at)\s+(?:"([^"]+?\.rs)(?::\d+(?::\d+)?)?"|'([^']+?\.rs)(?::\d+(?::\d+)?)?'|((?:[A-Za-z]:[\\/])?[^:"'\r\n]+?\.rs))(?::\d+(?::\d+)?)?(?:\s|$)"#
+        )
+        .expect("Failed to compile file path line regex");
     }
 
     let mut paths: Vec<String> = Vec::new();
@@ -39,13 +42,15 @@ fn collect_error_level_file_paths(error_msg: &str) -> Vec<String> {
 
         if in_error_or_warning {
             if let Some(cap) = FILE_PATH_LINE_RE.captures(line) {
-                if let Some(path_match) = cap.get(1) {
+                if let Some(path_match) = cap.get(1).or_else(|| cap.get(2)).or_else(|| cap.get(3)) {
                     let path_str = path_match.as_str().to_string();
                     // 只提取文件名以 fun_ 或 var_ 开头的文件
-                    let file_stem = std::path::Path::new(&path_str)
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("");
+                    // （同时按 `/` 和 `\` 切分，以便在任何平台上识别 Windows 路径的文件名）
+                    let file_stem = path_str
+                        .rsplit(['/', '\\'])
+                        .next()
+                        .unwrap_or("")
+                        .trim_end_matches(".rs");
                     if !file_stem.starts_with("fun_") && !file_stem.starts_with("var_") {
                         continue;
                     }
@@ -60,45 +65,53 @@ fn collect_error_level_file_paths(error_msg: &str) -> Vec<String> {
     paths
 }
 
-/// 解析错误消息以提取 Rust 文件路径
-/// 返回在错误消息中找到的文件路径列表
-/// 过滤为仅包含项目内的文件
-pub(crate) fn parse_error_for_files(error_msg: &str, feature: &str) -> Result<Vec<PathBuf>> {
+/// 按首次出现顺序定位错误消息中引用的、位于 `.c2rust/<feature>/rust` 内的文件（规范化路径，去重）
+///
+/// [`parse_error_for_files`] 与 [`group_errors_by_file`] 共用这一逻辑，
+/// 特性名称校验和路径过滤因此对所有调用方一致。
+fn locate_error_files(error_msg: &str, feature: &str) -> Result<Vec<PathBuf>> {
     // 验证特性名称以防止路径遍历
     util::validate_feature_name(feature)?;
 
     let project_root = util::find_project_root()?;
-    let feature_path = project_root.join(".c2rust").join(feature);
-    let rust_dir = feature_path.join("rust");
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    let Ok(rust_canonical) = rust_dir.canonicalize() else {
+        return Ok(Vec::new());
+    };
 
-    let mut file_paths = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut ordered_files: Vec<PathBuf> = Vec::new();
 
     for path_str in collect_error_level_file_paths(error_msg) {
         let path = PathBuf::from(&path_str);
 
         // 尝试原样路径和相对于 rust_dir 的路径
-        let candidates = vec![path.clone(), rust_dir.join(&path)];
-
-        for candidate in candidates {
-            // 检查文件是否存在且在我们的项目内
-            if candidate.exists() && candidate.is_file() {
-                // 确保文件在 rust 目录内
-                if let Ok(canonical) = candidate.canonicalize() {
-                    if let Ok(rust_canonical) = rust_dir.canonicalize() {
-                        if canonical.starts_with(&rust_canonical) {
-                            file_paths.insert(canonical);
-                            break;
-                        }
+        for candidate in [path.clone(), rust_dir.join(&path)] {
+            // 检查文件是否存在且在 rust 目录内
+            if !candidate.is_file() {
+                continue;
+            }
+            if let Ok(canonical) = candidate.canonicalize() {
+                if canonical.starts_with(&rust_canonical) {
+                    if seen.insert(canonical.clone()) {
+                        ordered_files.push(canonical);
                     }
+                    break;
                 }
             }
         }
     }
 
-    // 转换为 Vec 并排序以保持一致的顺序
-    let mut result: Vec<PathBuf> = file_paths.into_iter().collect();
-    result.sort();
+    Ok(ordered_files)
+}
 
+/// 解析错误消息以提取 Rust 文件路径
+/// 返回在错误消息中找到的文件路径列表（已排序）
+/// 过滤为仅包含项目内的文件
+pub(crate) fn parse_error_for_files(error_msg: &str, feature: &str) -> Result<Vec<PathBuf>> {
+    let mut result = locate_error_files(error_msg, feature)?;
+    // 排序以保持一致的顺序
+    result.sort();
     Ok(result)
 }
 
@@ -107,7 +120,7 @@ pub(crate) fn parse_error_for_files(error_msg: &str, feature: &str) -> Result<Ve
 /// 将错误消息拆分为由空行分隔的块，并返回引用指定文件的块。
 /// 匹配时使用路径分隔符或行首/行尾边界，避免 "test.rs" 误匹配 "my_test.rs"。
 /// 如果没有找到匹配的块，则返回完整的错误消息作为后备。
-pub(crate) fn extract_errors_for_file(error_msg: &str, file_path: &PathBuf) -> String {
+pub(crate) fn extract_errors_for_file(error_msg: &str, file_path: &Path) -> String {
     let file_name = match file_path.file_name().and_then(|n| n.to_str()) {
         Some(s) => s,
         None => return error_msg.to_string(),
@@ -139,47 +152,161 @@ pub(crate) fn extract_errors_for_file(error_msg: &str, file_path: &PathBuf) -> S
     }
 }
 
-/// 按文件分组错误信息，保持文件首次出现的顺序
+/// 链接错误的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LinkErrorKind {
+    /// 未定义符号（`undefined reference to` / `undefined symbol:`）
+    Undefined,
+    /// 重复定义（`multiple definition of` / `duplicate symbol:`）
+    Duplicate,
+}
+
+/// 从链接错误中解析出的符号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LinkSymbolError {
+    pub symbol: String,
+    pub kind: LinkErrorKind,
+}
+
+lazy_static::lazy_static! {
+    static ref UNDEFINED_SYMBOL_RE: regex::Regex = regex::Regex::new(
+        r#"(?:undefined reference to|undefined symbol:)\s*[`'"]?([A-Za-z_$][A-Za-z0-9_$.@]*)"#
+    )
+    .expect("Failed to compile undefined symbol regex");
+    static ref DUPLICATE_SYMBOL_RE: regex::Regex = regex::Regex::new(
+        r#"(?:multiple definition of|duplicate symbol:)\s*[`'"]?([A-Za-z_$][A-Za-z0-9_$.@]*)"#
+    )
+    .expect("Failed to compile duplicate symbol regex");
+}
+
+/// 从构建输出中提取包含链接错误的行（用于在截断的错误摘要中保留关键信息）
+pub(crate) fn collect_link_error_lines(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .filter(|line| UNDEFINED_SYMBOL_RE.is_match(line) || DUPLICATE_SYMBOL_RE.is_match(line))
+        .collect()
+}
+
+/// 从链接错误中提取未定义/重复定义的符号（按首次出现顺序去重）
 ///
-/// 解析错误消息中的文件路径，按出现顺序去重，
-/// 并为每个文件提取其相关的错误块。
+/// 符号版本后缀（如 `foo@GLIBC_2.2.5`）会被去除。
+pub(crate) fn extract_link_errors(error_msg: &str) -> Vec<LinkSymbolError> {
+    let mut result: Vec<LinkSymbolError> = Vec::new();
+
+    for line in error_msg.lines() {
+        let matched = UNDEFINED_SYMBOL_RE
+            .captures(line)
+            .map(|cap| (cap, LinkErrorKind::Undefined))
+            .or_else(|| {
+                DUPLICATE_SYMBOL_RE
+                    .captures(line)
+                    .map(|cap| (cap, LinkErrorKind::Duplicate))
+            });
+        let Some((cap, kind)) = matched else {
+            continue;
+        };
+        let symbol = cap[1].split('@').next().unwrap_or("").to_string();
+        if symbol.is_empty() {
+            continue;
+        }
+        let entry = LinkSymbolError { symbol, kind };
+        if !result.contains(&entry) {
+            result.push(entry);
+        }
+    }
+
+    result
+}
+
+/// 将链接错误中的符号映射回对应的 `fun_<symbol>.rs` / `var_<symbol>.rs` 文件
 ///
-/// 返回 (file_path, error_message) 对列表，按文件在错误中首次出现的顺序排列。
-pub(crate) fn group_errors_by_file(
-    error_msg: &str,
+/// 在 `rust_dir` 下递归查找；同时尝试去掉 Mach-O 风格的前导 `_`。
+/// 返回去重后的文件列表，按符号出现顺序排列。
+pub(crate) fn map_link_errors_to_files(
+    errors: &[LinkSymbolError],
+    rust_dir: &Path,
+) -> Vec<PathBuf> {
+    let mut candidates: Vec<(String, PathBuf)> = Vec::new();
+    for entry in walkdir::WalkDir::new(rust_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file()
+            || path.extension().is_none_or(|ext| ext != "rs")
+            || crate::backup::is_backup_path(path)
+        {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            if let Some((_, name)) = file_scanner::extract_file_type(stem) {
+                candidates.push((name.to_string(), path.to_path_buf()));
+            }
+        }
+    }
+    candidates.sort();
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for error in errors {
+        let names = [error.symbol.as_str(), error.symbol.trim_start_matches('_')];
+        for (name, path) in &candidates {
+            if names.contains(&name.as_str()) && !files.contains(path) {
+                files.push(path.clone());
+            }
+        }
+    }
+    files
+}
+
+/// 按文件分组 JSON 诊断（error 与 warning 级别，仅 fun_/var_ 文件），保持文件首次出现的顺序
+///
+/// 每个文件对应的消息只包含该文件诊断的渲染文本，不需要从整段输出中按文本切分。
+pub(crate) fn group_diagnostics_by_file(
+    diagnostics: &[crate::diagnostics::CompilerDiagnostic],
     feature: &str,
 ) -> Result<Vec<(PathBuf, String)>> {
-    // 验证特性名称以防止路径遍历
     util::validate_feature_name(feature)?;
 
     let project_root = util::find_project_root()?;
-    let feature_path = project_root.join(".c2rust").join(feature);
-    let rust_dir = feature_path.join("rust");
-
-    // 按出现顺序收集文件路径（保留首次出现顺序，去重）
-    let mut seen = HashSet::new();
-    let mut ordered_files: Vec<PathBuf> = Vec::new();
-
-    for path_str in collect_error_level_file_paths(error_msg) {
-        let path = PathBuf::from(&path_str);
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    let Ok(rust_canonical) = rust_dir.canonicalize() else {
+        return Ok(Vec::new());
+    };
 
-        // 尝试原样路径和相对于 rust_dir 的路径
-        let candidates = vec![path.clone(), rust_dir.join(&path)];
-
-        for candidate in candidates {
-            if candidate.exists() && candidate.is_file() {
-                if let Ok(canonical) = candidate.canonicalize() {
-                    if let Ok(rust_canonical) = rust_dir.canonicalize() {
-                        if canonical.starts_with(&rust_canonical) && seen.insert(canonical.clone())
-                        {
-                            ordered_files.push(canonical);
-                            break;
-                        }
-                    }
-                }
-            }
+    let mut result: Vec<(PathBuf, String)> = Vec::new();
+    for diagnostic in diagnostics.iter().filter(|d| d.is_error_or_warning()) {
+        let is_translated_file = Path::new(&diagnostic.file)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|stem| stem.starts_with("fun_") || stem.starts_with("var_"));
+        if !is_translated_file {
+            continue;
+        }
+        let Ok(canonical) = rust_dir.join(&diagnostic.file).canonicalize() else {
+            continue;
+        };
+        if !canonical.starts_with(&rust_canonical) {
+            continue;
+        }
+        match result.iter_mut().find(|(file, _)| *file == canonical) {
+            Some((_, messages)) => messages.push_str(&diagnostic.rendered),
+            None => result.push((canonical, diagnostic.rendered.clone())),
         }
     }
+    Ok(result)
+}
+
+/// 按文件分组错误信息，保持文件首次出现的顺序
+///
+/// 解析错误消息中的文件路径，按出现顺序去重，
+/// 并为每个文件提取其相关的错误块。
+///
+/// 返回 (file_path, error_message) 对列表，按文件在错误中首次出现的顺序排列。
+pub(crate) fn group_errors_by_file(
+    error_msg: &str,
+    feature: &str,
+) -> Result<Vec<(PathBuf, String)>> {
+    let ordered_files = locate_error_files(error_msg, feature)?;
 
     // 为每个文件提取其相关的错误块
     let result = ordered_files
@@ -193,14 +320,65 @@ pub(crate) fn group_errors_by_file(
     Ok(result)
 }
 
+/// 启动测试失败修复流程最多处理的轮数（每轮处理一组出错文件）
+const MAX_STARTUP_FIX_ROUNDS: usize = 10;
+
+/// 同一组出错文件最多出现的次数（首次加上两次原样重复）
+const MAX_SAME_FILE_SET_ROUNDS: usize = 3;
+
+/// 记录修复流程每轮的出错文件集合，在没有进展时中止，避免无限循环
+#[derive(Debug, Default)]
+struct FixRoundTracker {
+    rounds: usize,
+    seen: Vec<(Vec<PathBuf>, usize)>,
+}
+
+impl FixRoundTracker {
+    /// 记录新一轮的出错文件；轮数超限或同一组文件重复出现过多时返回错误
+    fn record(&mut self, files: &[PathBuf]) -> Result<()> {
+        self.rounds += 1;
+        let mut set = files.to_vec();
+        set.sort();
+        set.dedup();
+        let count = match self.seen.iter_mut().find(|(seen, _)| *seen == set) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                self.seen.push((set.clone(), 1));
+                1
+            }
+        };
+        if count >= MAX_SAME_FILE_SET_ROUNDS {
+            let names: Vec<String> = set.iter().map(|f| f.display().to_string()).collect();
+            anyhow::bail!(
+                "Unable to make progress: the same files keep failing after {} rounds: {}",
+                count,
+                names.join(", ")
+            );
+        }
+        if self.rounds > MAX_STARTUP_FIX_ROUNDS {
+            anyhow::bail!(
+                "Unable to make progress: build or tests still failing after {} rounds of fixes",
+                MAX_STARTUP_FIX_ROUNDS
+            );
+        }
+        Ok(())
+    }
+}
+
 /// 当可以定位文件时处理启动测试失败
-#[allow(dead_code)]
+///
+/// 每轮处理错误中的第一个文件，修复后错误涉及新的文件时进入下一轮；
+/// 同一组文件反复失败或轮数过多时中止（见 [`FixRoundTracker`]）。
 pub(crate) fn handle_startup_test_failure_with_files(
     feature: &str,
     test_error: anyhow::Error,
     mut files: Vec<PathBuf>,
 ) -> Result<()> {
     let mut current_error = test_error;
+    let mut tracker = FixRoundTracker::default();
 
     // 使用循环迭代处理文件，避免深度递归
     'outer: loop {
@@ -208,8 +386,12 @@ pub(crate) fn handle_startup_test_failure_with_files(
             // 没有要处理的文件，返回当前错误
             return Err(current_error).context("No files found to fix");
         }
+        if let Err(stuck) = tracker.record(&files) {
+            ui::error(&stuck);
+            return Err(current_error).context(stuck.to_string());
+        }
 
-        println!("│");
+        ui::blank();
         println!(
             "│ {}",
             format!("Found {} file(s) in error message:", files.len()).bright_cyan()
@@ -220,7 +402,7 @@ pub(crate) fn handle_startup_test_failure_with_files(
 
         // 处理第一个文件（每次外层循环迭代只处理一个文件）
         let file = &files[0];
-        println!("│");
+        ui::blank();
         let file_display_name = file
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -266,7 +448,7 @@ pub(crate) fn handle_startup_test_failure_with_files(
 
         match choice {
             interaction::FailureChoice::Skip => {
-                println!("│");
+                ui::blank();
                 println!(
                     "│ {}",
                     "You chose: Continue trying with a new suggestion".bright_cyan()
@@ -299,10 +481,7 @@ pub(crate) fn handle_startup_test_failure_with_files(
                         return Ok(());
                     }
                     Err(e) => {
-                        println!(
-                            "│ {}",
-                            "✗ Build or tests still failing after fix attempt".red()
-                        );
+                        ui::error("Build or tests still failing after fix attempt");
 
                         // 尝试解析新错误并查看是否有更多文件
                         match parse_error_for_files(&e.to_string(), feature) {
@@ -326,18 +505,18 @@ pub(crate) fn handle_startup_test_failure_with_files(
                 }
             }
             interaction::FailureChoice::ManualFix => {
-                println!("│");
+                ui::blank();
                 println!("│ {}", "You chose: Manual fix".bright_cyan());
 
-                // 尝试打开 vim（支持多文件选择）
+                // 尝试打开编辑器（支持多文件选择）
                 match interaction::open_files_for_manual_fix(&files) {
                     Ok(_) => {
-                        // Vim 编辑后，重复尝试构建和测试
+                        // 编辑器编辑后，重复尝试构建和测试
                         loop {
-                            println!("│");
+                            ui::blank();
                             println!(
                                 "│ {}",
-                                "Vim editing completed. Running full build and test flow..."
+                                "Editing completed. Running full build and test flow..."
                                     .bright_blue()
                             );
 
@@ -352,10 +531,7 @@ pub(crate) fn handle_startup_test_failure_with_files(
                                     return Ok(());
                                 }
                                 Err(e) => {
-                                    println!(
-                                        "│ {}",
-                                        "✗ Build or tests still failing after manual fix".red()
-                                    );
+                                    ui::error("Build or tests still failing after manual fix");
 
                                     // 尝试解析新错误并查看是否有更多文件
                                     match parse_error_for_files(&e.to_string(), feature) {
@@ -368,7 +544,7 @@ pub(crate) fn handle_startup_test_failure_with_files(
                                         }
                                         _ => {
                                             // 没有更多文件需要处理，询问用户是否想再试一次
-                                            println!("│");
+                                            ui::blank();
                                             println!(
                                                 "│ {}",
                                                 format!(
@@ -381,14 +557,14 @@ pub(crate) fn handle_startup_test_failure_with_files(
                                             for (idx, f) in files.iter().enumerate() {
                                                 println!("│   {}. {}", idx + 1, f.display());
                                             }
-                                            println!("│");
+                                            ui::blank();
                                             println!("│ {}", "Build or tests still have errors. What would you like to do?".yellow());
                                             let retry_choice =
                                                 interaction::prompt_after_manual_fix_choice()?;
 
                                             match retry_choice {
                                                 interaction::FailureChoice::ManualFix => {
-                                                    println!("│ {}", "Reopening file in Vim for additional manual fixes...".bright_blue());
+                                                    ui::info("Reopening file in editor for additional manual fixes...");
                                                     match interaction::open_files_for_manual_fix(&files) {
                                                         Ok(_) => {
                                                             // 循环将重试构建
@@ -398,13 +574,13 @@ pub(crate) fn handle_startup_test_failure_with_files(
                                                             println!(
                                                                 "│ {}",
                                                                 format!(
-                                                                    "Failed to reopen vim: {}",
+                                                                    "Failed to reopen editor: {}",
                                                                     open_err
                                                                 )
                                                                 .red()
                                                             );
                                                             return Err(open_err).context(format!(
-                                                                "Build/tests still failing and could not reopen vim for {} file(s)",
+                                                                "Build/tests still failing and could not reopen editor for {} file(s)",
                                                                 files.len()
                                                             ));
                                                         }
@@ -439,16 +615,16 @@ pub(crate) fn handle_startup_test_failure_with_files(
                         }
                     }
                     Err(e) => {
-                        println!("│ {}", format!("Failed to open vim: {}", e).red());
+                        println!("│ {}", format!("Failed to open editor: {}", e).red());
                         return Err(e).context(format!(
-                            "Initial test failed and could not open vim for {} file(s)",
+                            "Initial test failed and could not open editor for {} file(s)",
                             files.len()
                         ));
                     }
                 }
             }
             interaction::FailureChoice::Exit => {
-                println!("│");
+                ui::blank();
                 println!("│ {}", "You chose: Exit".yellow());
                 return Err(current_error)
                     .context("User chose to exit during startup test failure handling");
@@ -466,6 +642,87 @@ pub(crate) fn handle_startup_test_failure_with_files(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fix_round_tracker_stops_when_same_files_repeat() {
+        let a = PathBuf::from("src/fun_a.rs");
+        let b = PathBuf::from("src/fun_b.rs");
+        let mut tracker = FixRoundTracker::default();
+        tracker.record(&[a.clone(), b.clone()]).unwrap();
+        tracker.record(std::slice::from_ref(&a)).unwrap();
+        // 顺序不同视为同一组文件
+        tracker.record(&[b.clone(), a.clone()]).unwrap();
+        let err = tracker.record(&[a.clone(), b.clone()]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("the same files keep failing after 3 rounds"));
+
+        let mut tracker = FixRoundTracker::default();
+        for i in 0..MAX_STARTUP_FIX_ROUNDS {
+            tracker
+                .record(&[PathBuf::from(format!("src/fun_{}.rs", i))])
+                .unwrap();
+        }
+        assert!(tracker.record(&[b]).is_err());
+    }
+
+    #[test]
+    fn test_extract_link_errors_gnu_and_lld_formats() {
+        let stderr = "\
+/usr/bin/ld: main.o: in function `main':
+main.c:(.text+0x15): undefined reference to `compute_sum'
+/usr/bin/ld: librust.a(rust.o): multiple definition of `g_counter'; counter.o:(.bss+0x0): first defined here
+ld.lld: error: undefined symbol: parse_header
+ld.lld: error: undefined symbol: compute_sum
+main.c:(.text+0x30): undefined reference to `memcpy@GLIBC_2.14'
+collect2: error: ld returned 1 exit status";
+
+        let errors = extract_link_errors(stderr);
+        assert_eq!(
+            errors,
+            vec![
+                LinkSymbolError {
+                    symbol: "compute_sum".to_string(),
+                    kind: LinkErrorKind::Undefined,
+                },
+                LinkSymbolError {
+                    symbol: "g_counter".to_string(),
+                    kind: LinkErrorKind::Duplicate,
+                },
+                LinkSymbolError {
+                    symbol: "parse_header".to_string(),
+                    kind: LinkErrorKind::Undefined,
+                },
+                LinkSymbolError {
+                    symbol: "memcpy".to_string(),
+                    kind: LinkErrorKind::Undefined,
+                },
+            ]
+        );
+        assert_eq!(collect_link_error_lines(stderr).len(), 5);
+    }
+
+    #[test]
+    fn test_map_link_errors_to_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir_all(src.join("nested")).unwrap();
+        std::fs::write(src.join("fun_compute_sum.rs"), "").unwrap();
+        std::fs::write(src.join("nested").join("var_g_counter.rs"), "").unwrap();
+        std::fs::write(src.join("fun_unrelated.rs"), "").unwrap();
+
+        let errors = extract_link_errors(
+            "undefined reference to `g_counter'\nduplicate symbol: _compute_sum\nundefined reference to `memcpy'",
+        );
+        let files = map_link_errors_to_files(&errors, temp_dir.path());
+        assert_eq!(
+            files,
+            vec![
+                src.join("nested").join("var_g_counter.rs"),
+                src.join("fun_compute_sum.rs"),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_error_pattern_extraction() {
         // 测试我们可以从错误消息中提取文件路径
@@ -615,6 +872,88 @@ note: some note about outside file
         assert!(result[0] < result[1], "Files should be sorted");
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_group_diagnostics_by_file_uses_json_locations() {
+        use crate::diagnostics::CompilerDiagnostic;
+        use std::env;
+        use std::fs;
+        use tempfile::tempdir;
+
+        let temp_dir = tempdir().unwrap();
+        let original_dir = env::current_dir().unwrap();
+        let src_dir = temp_dir.path().join(".c2rust/test_feature/rust/src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("fun_a.rs"), "").unwrap();
+        fs::write(src_dir.join("var_b.rs"), "").unwrap();
+        fs::write(src_dir.join("lib.rs"), "").unwrap();
+        env::set_current_dir(temp_dir.path()).unwrap();
+
+        let diagnostic = |file: &str, level: &str, rendered: &str| CompilerDiagnostic {
+            file: file.to_string(),
+            line: 1,
+            column: 1,
+            level: level.to_string(),
+            code: None,
+            message: String::new(),
+            rendered: rendered.to_string(),
+        };
+        let diagnostics = vec![
+            diagnostic("src/var_b.rs", "error", "b1\n"),
+            diagnostic("src/lib.rs", "error", "lib\n"),
+            diagnostic("src/fun_a.rs", "note", "note\n"),
+            diagnostic("src/fun_a.rs", "warning", "a1\n"),
+            diagnostic("src/var_b.rs", "error", "b2\n"),
+        ];
+        let result = group_diagnostics_by_file(&diagnostics, "test_feature");
+        env::set_current_dir(&original_dir).unwrap();
+
+        let result = result.unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result[0].0.ends_with("var_b.rs"));
+        assert_eq!(result[0].1, "b1\nb2\n");
+        assert!(result[1].0.ends_with("fun_a.rs"));
+        assert_eq!(result[1].1, "a1\n");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_error_for_files_absolute_paths_respect_rust_dir() {
+        use std::env;
+        use std::fs;
+        use tempfile::tempdir;
+
+        let temp_dir = tempdir().unwrap();
+        let project_root = temp_dir.path();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(project_root).unwrap();
+
+        let feature = "test_feature";
+        let src_dir = project_root
+            .join(".c2rust")
+            .join(feature)
+            .join("rust")
+            .join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let inside = src_dir.join("fun_inside.rs");
+        fs::write(&inside, "// inside").unwrap();
+        let outside_dir = project_root.join("elsewhere");
+        fs::create_dir_all(&outside_dir).unwrap();
+        let outside = outside_dir.join("fun_outside.rs");
+        fs::write(&outside, "// outside").unwrap();
+
+        let error_msg = format!(
+            "error[E0308]: mismatched types\n  --> {}:3:1\nerror[E0308]: mismatched types\n  --> {}:4:2",
+            inside.display(),
+            outside.display()
+        );
+        let result = parse_error_for_files(&error_msg, feature).unwrap();
+
+        env::set_current_dir(&original_dir).unwrap();
+
+        assert_eq!(result, vec![inside.canonicalize().unwrap()]);
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_parse_error_for_files_deduplication() {
@@ -659,6 +998,15 @@ note: note about same file
         assert!(result[0].ends_with("var_test.rs"));
     }
 
+    #[test]
+    fn test_error_file_lookups_reject_traversal_feature_names() {
+        let error_msg = "error: test\n  --> src/fun_a.rs:1:1";
+        for feature in ["../bad", "good/bad"] {
+            assert!(parse_error_for_files(error_msg, feature).is_err());
+            assert!(group_errors_by_file(error_msg, feature).is_err());
+        }
+    }
+
     #[test]
     fn test_parse_error_for_files_validates_feature_name() {
         // 测试无效的特性名称被拒绝
@@ -951,6 +1299,31 @@ note: expected signature from here
         );
     }
 
+    #[test]
+    fn test_collect_error_level_file_paths_windows_and_absolute_paths() {
+        let error_msg = r#"error[E0425]: cannot find value `a`
+ --> C:\Users\me\proj\src\fun_a.rs:3:1
+error[E0308]: mismatched types
+  --> /home/me/proj/.c2rust/default/rust/src/var_b.rs:10:5
+error: unused variable
+  --> D:/proj/src/fun_c.rs:7
+error: mismatched types
+  --> "C:\My Projects\src\fun_d.rs:12:3"
+error: mismatched types
+  --> src/mod a/var_e.rs:1:1"#;
+        let paths = collect_error_level_file_paths(error_msg);
+        assert_eq!(
+            paths,
+            vec![
+                r"C:\Users\me\proj\src\fun_a.rs",
+                "/home/me/proj/.c2rust/default/rust/src/var_b.rs",
+                "D:/proj/src/fun_c.rs",
+                r"C:\My Projects\src\fun_d.rs",
+                "src/mod a/var_e.rs",
+            ]
+        );
+    }
+
     #[test]
     fn test_collect_error_level_file_paths_filters_non_fun_var_prefix() {
         // error 级别引用的文件，但文件名不以 fun_ 或 var_ 开头，不应被提取
@@ -0,0 +1,306 @@
+//! 机器可读的工作流事件输出（`--format json`）
+//!
+//! JSON 模式下每个事件以一行 JSON（NDJSON）写到标准输出，便于 CI 或编辑器插件解析；
+//! 原本面向人的彩色输出（带 `│` 前缀的装饰行等）会被转到标准错误，
+//! 保证标准输出中只有事件流。默认的 human 模式下 [`emit`] 不输出任何内容。
+//!
+//! 在非 unix 平台上无法重定向进程的标准输出，事件与人类可读输出会交错出现在标准输出中。
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::Mutex;
+
+/// 输出格式（`--format`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Human => "human",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "human" | "text" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!(
+                "unknown output format `{other}` (expected `human` or `json`)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 事件结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventResult {
+    Ok,
+    Error,
+}
+
+impl EventResult {
+    pub fn from_ok(ok: bool) -> Self {
+        if ok {
+            EventResult::Ok
+        } else {
+            EventResult::Error
+        }
+    }
+}
+
+/// 单个文件的最终处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileOutcome {
+    Completed,
+    Skipped,
+    Failed,
+}
+
+/// 工作流事件，序列化为 `{"event":"<snake_case 名称>", ...}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    TranslationStart {
+        feature: &'a str,
+    },
+    FileStart {
+        file: &'a str,
+        index: usize,
+        total: usize,
+    },
+    Translate {
+        file: &'a str,
+        result: EventResult,
+        duration_ms: u64,
+    },
+    Build {
+        command: &'a str,
+        result: EventResult,
+        duration_ms: u64,
+    },
+    Commit {
+        message: &'a str,
+        committed: bool,
+    },
+    FileDone {
+        file: &'a str,
+        outcome: FileOutcome,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        attempts: Option<usize>,
+    },
+    TranslationEnd {
+        result: EventResult,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// 事件写入目标：unix 上是复制出的原始标准输出描述符，其他平台直接写标准输出
+#[cfg(unix)]
+type EventWriter = std::fs::File;
+#[cfg(not(unix))]
+type EventWriter = std::io::Stdout;
+
+/// JSON 模式下事件的写入目标（进程原始的标准输出）；human 模式下为 `None`
+static EVENT_SINK: Mutex<Option<EventWriter>> = Mutex::new(None);
+
+/// 设置本次运行的输出格式
+///
+/// 切换到 JSON 模式时保留原始标准输出用于事件，并把进程的标准输出转到标准错误；
+/// 切回 human 模式时恢复原始标准输出。
+/// 重复调用是安全的：已处于 JSON 模式时不会再次重定向。
+pub fn set_output_format(format: OutputFormat) -> Result<()> {
+    let mut sink = EVENT_SINK.lock().unwrap_or_else(|e| e.into_inner());
+    match format {
+        OutputFormat::Human => {
+            if let Some(writer) = sink.take() {
+                restore_stdout(writer)?;
+            }
+        }
+        OutputFormat::Json => {
+            if sink.is_none() {
+                *sink = Some(redirect_stdout_to_stderr()?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 当前是否为 JSON 输出模式
+pub fn is_json_output() -> bool {
+    EVENT_SINK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+}
+
+/// 输出一个事件（human 模式下为空操作），并转发给注册的观察者（[`crate::observer`]）
+///
+/// 写入失败（如管道已关闭）只会丢弃该事件，不会中断工作流。
+pub fn emit(event: &Event) {
+    {
+        let mut sink = EVENT_SINK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(writer) = sink.as_mut() {
+            if let Ok(line) = serde_json::to_string(event) {
+                let _ = writeln!(writer, "{}", line);
+                let _ = writer.flush();
+            }
+        }
+    }
+    crate::observer::notify(event);
+}
+
+#[cfg(unix)]
+fn redirect_stdout_to_stderr() -> Result<EventWriter> {
+    use std::os::unix::io::FromRawFd;
+
+    std::io::stdout().flush()?;
+    // SAFETY: 只复制/替换进程自身的标准文件描述符；dup 返回的新描述符归 File 所有。
+    let original_stdout = unsafe { libc::dup(libc::STDOUT_FILENO) };
+    if original_stdout < 0 {
+        anyhow::bail!(
+            "Failed to duplicate stdout for JSON output: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        let err = std::io::Error::last_os_error();
+        unsafe { libc::close(original_stdout) };
+        anyhow::bail!(
+            "Failed to redirect stdout to stderr for JSON output: {}",
+            err
+        );
+    }
+    Ok(unsafe { std::fs::File::from_raw_fd(original_stdout) })
+}
+
+/// 把 JSON 模式保存的原始标准输出放回 fd 1
+#[cfg(unix)]
+fn restore_stdout(original_stdout: EventWriter) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    std::io::stdout().flush()?;
+    // SAFETY: 只替换进程自身的标准输出；`original_stdout` 在 dup2 之后才关闭。
+    if unsafe { libc::dup2(original_stdout.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+        anyhow::bail!(
+            "Failed to restore stdout after JSON output: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn redirect_stdout_to_stderr() -> Result<EventWriter> {
+    Ok(std::io::stdout())
+}
+
+#[cfg(not(unix))]
+fn restore_stdout(_original_stdout: EventWriter) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_parsing() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "HUMAN".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Human
+        );
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Human);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::default(), OutputFormat::Human);
+        assert_eq!(OutputFormat::Json.to_string(), "json");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn test_human_output_restores_stdout() {
+        let stdout_identity = || {
+            let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+            assert_eq!(unsafe { libc::fstat(libc::STDOUT_FILENO, &mut stat) }, 0);
+            (stat.st_dev, stat.st_ino)
+        };
+        let before = stdout_identity();
+        set_output_format(OutputFormat::Json).unwrap();
+        let _reset = scopeguard::guard((), |_| {
+            let _ = set_output_format(OutputFormat::Human);
+        });
+        assert!(is_json_output());
+        set_output_format(OutputFormat::Human).unwrap();
+        assert!(!is_json_output());
+        assert_eq!(stdout_identity(), before);
+    }
+
+    #[test]
+    fn test_event_serialization_shape() {
+        let file_start = Event::FileStart {
+            file: "var_x.rs",
+            index: 3,
+            total: 10,
+        };
+        assert_eq!(
+            serde_json::to_string(&file_start).unwrap(),
+            r#"{"event":"file_start","file":"var_x.rs","index":3,"total":10}"#
+        );
+
+        let build = Event::Build {
+            command: "cargo check",
+            result: EventResult::Ok,
+            duration_ms: 1234,
+        };
+        assert_eq!(
+            serde_json::to_string(&build).unwrap(),
+            r#"{"event":"build","command":"cargo check","result":"ok","duration_ms":1234}"#
+        );
+
+        let done = Event::FileDone {
+            file: "fun_y.rs",
+            outcome: FileOutcome::Skipped,
+            attempts: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&done).unwrap(),
+            r#"{"event":"file_done","file":"fun_y.rs","outcome":"skipped"}"#
+        );
+
+        let end = Event::TranslationEnd {
+            result: EventResult::from_ok(false),
+            error: Some("boom".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_string(&end).unwrap(),
+            r#"{"event":"translation_end","result":"error","error":"boom"}"#
+        );
+    }
+
+    #[test]
+    fn test_emit_is_noop_in_human_mode() {
+        assert!(!is_json_output());
+        emit(&Event::TranslationStart { feature: "default" });
+    }
+}
@@ -1,20 +1,203 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use inquire::Text;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use walkdir::WalkDir;
 
 /// 判断路径是否为需要翻译的文件（文件名以 var_ 或 fun_ 开头的 .rs 文件）
 fn is_translatable_rs_file(path: &Path) -> bool {
-    path.file_stem()
-        .and_then(|s| s.to_str())
-        .map(|name| name.starts_with("var_") || name.starts_with("fun_"))
-        .unwrap_or(false)
+    !crate::backup::is_backup_path(path)
+        && path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|name| name.starts_with("var_") || name.starts_with("fun_"))
+            .unwrap_or(false)
+}
+
+/// 一组 glob 忽略模式，匹配相对 `.c2rust/<feature>/rust/` 的路径（`/` 分隔，如 `src/net/fun_a.rs`）
+///
+/// - 匹配规则见 [`glob_matches`]
+/// - 以 `.c` 结尾的模式匹配与 `.rs` 文件同名的 C 文件路径（`src/gen/*.c` 忽略 `src/gen/fun_a.rs`）
+/// - 以 `!` 开头的模式将之前忽略的文件重新纳入范围；多个模式匹配时以最后一个为准
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnorePatterns {
+    patterns: Vec<String>,
+}
+
+impl IgnorePatterns {
+    /// 空白模式会被丢弃
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|pattern| pattern.trim().to_string())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// `relative_path` 是否被忽略（相对 rust 目录，使用 `/` 分隔）
+    pub fn is_ignored(&self, relative_path: &str) -> bool {
+        let c_path = relative_path
+            .strip_suffix(".rs")
+            .map(|stem| format!("{}.c", stem));
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            let (negated, pattern) = match pattern.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, pattern.as_str()),
+            };
+            // 只有可能改变结果的模式才需要匹配
+            if ignored != negated {
+                continue;
+            }
+            let path = match &c_path {
+                Some(c_path) if pattern.ends_with(".c") => c_path,
+                _ => relative_path,
+            };
+            if glob_matches(pattern, path) {
+                ignored = !negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// 忽略文件名，可放在项目根目录和 `.c2rust/<feature>/` 下
+pub const IGNORE_FILE_NAME: &str = ".c2rustignore";
+
+/// 读取项目级与 feature 级 `.c2rustignore` 中的模式，项目级在前（feature 级的模式可以覆盖它）
+///
+/// 两个文件中的模式都相对 `.c2rust/<feature>/rust/` 匹配，而不是相对忽略文件所在的目录。
+/// 文件不存在时视为空。
+pub fn c2rustignore_patterns(project_root: &Path, feature: &str) -> Result<Vec<String>> {
+    let mut patterns = Vec::new();
+    for path in [
+        project_root.join(IGNORE_FILE_NAME),
+        project_root
+            .join(".c2rust")
+            .join(feature)
+            .join(IGNORE_FILE_NAME),
+    ] {
+        match fs::read_to_string(&path) {
+            Ok(content) => patterns.extend(parse_ignore_file(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        }
+    }
+    Ok(patterns)
+}
+
+/// 解析忽略文件：每行一个模式，空行和 `#` 开头的行被忽略；
+/// 以 `/` 结尾的模式表示目录，忽略其下的所有文件
+fn parse_ignore_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (negation, pattern) = match line.strip_prefix('!') {
+                Some(rest) => ("!", rest),
+                None => ("", line),
+            };
+            match pattern.strip_suffix('/') {
+                // 不含其他 `/` 的目录名匹配任意层级的同名目录；以 `/` 开头的只匹配 rust 目录下的
+                Some(dir) if !dir.contains('/') => format!("{}**/{}/**", negation, dir),
+                Some(dir) => format!("{}{}/**", negation, dir),
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// 当前生效的忽略模式（`.c2rustignore`、配置的 `ignore.patterns` 与 `--skip`），由 `translate_feature` 设置
+static IGNORE_PATTERNS: Mutex<IgnorePatterns> = Mutex::new(IgnorePatterns {
+    patterns: Vec::new(),
+});
+
+/// 设置扫描待翻译文件时使用的忽略模式
+pub fn set_ignore_patterns(patterns: IgnorePatterns) {
+    *IGNORE_PATTERNS.lock().unwrap_or_else(|e| e.into_inner()) = patterns;
+}
+
+fn ignore_patterns() -> IgnorePatterns {
+    IGNORE_PATTERNS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// 用 glob 模式匹配相对 rust 目录、`/` 分隔的路径
+///
+/// - `*` 匹配路径段内的任意字符，`?` 匹配单个字符，`**` 匹配任意层目录
+/// - 不含 `/` 的模式只匹配文件名（如 `fun_legacy_*.rs`）
+/// - 含 `/` 的模式匹配完整路径，路径可以省略开头的 `src/`（`net/*` 匹配 `src/net/fun_a.rs`）
+/// - 以 `/` 开头的模式锚定在 rust 目录：匹配完整路径，不能省略 `src/`（`/src/fun_a.rs`）
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let path: Vec<&str> = path.split('/').collect();
+    if let Some(anchored) = pattern.strip_prefix('/') {
+        let pattern: Vec<&str> = anchored.split('/').collect();
+        return segments_match(&pattern, &path);
+    }
+    if !pattern.contains('/') {
+        return path
+            .last()
+            .is_some_and(|name| segment_matches(&chars(pattern), &chars(name)));
+    }
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    segments_match(&pattern, &path)
+        || (path.first() == Some(&"src") && segments_match(&pattern, &path[1..]))
+}
+
+fn chars(text: &str) -> Vec<char> {
+    text.chars().collect()
+}
+
+/// 逐段匹配路径，`**` 段匹配零个或多个路径段
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, path_rest)| {
+            segment_matches(&chars(first), &chars(segment)) && segments_match(rest, path_rest)
+        }),
+    }
+}
+
+/// 匹配单个路径段内的 `*` 和 `?`
+fn segment_matches(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|skip| segment_matches(rest, &text[skip..])),
+        Some(('?', rest)) => !text.is_empty() && segment_matches(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && segment_matches(rest, &text[1..]),
+    }
+}
+
+/// 相对 rust 目录、使用 `/` 分隔的路径
+fn relative_slash_path(rust_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(rust_dir)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 /// 统计给定目录中需要翻译的 .rs 文件（文件名以 var_ 或 fun_ 开头，包括空文件和非空文件）
+///
+/// 被忽略模式排除的文件不计入总数。
 pub fn count_all_rs_files(rust_dir: &Path) -> Result<usize> {
+    let ignore = ignore_patterns();
     let mut count = 0;
 
     for entry in WalkDir::new(rust_dir) {
@@ -24,6 +207,7 @@ pub fn count_all_rs_files(rust_dir: &Path) -> Result<usize> {
         if path.is_file()
             && path.extension().is_some_and(|ext| ext == "rs")
             && is_translatable_rs_file(path)
+            && !ignore.is_ignored(&relative_slash_path(rust_dir, path))
         {
             count += 1;
         }
@@ -35,6 +219,7 @@ pub fn count_all_rs_files(rust_dir: &Path) -> Result<usize> {
 /// 单次遍历统计给定目录中需要翻译的 .rs 文件总数和空文件数。
 /// 返回 `(total, empty_count)`。
 pub fn count_rs_files_with_empty(rust_dir: &Path) -> Result<(usize, usize)> {
+    let ignore = ignore_patterns();
     let mut total = 0;
     let mut empty = 0;
 
@@ -44,7 +229,10 @@ pub fn count_rs_files_with_empty(rust_dir: &Path) -> Result<(usize, usize)> {
             continue;
         }
         let path = entry.path();
-        if path.extension().is_some_and(|ext| ext == "rs") && is_translatable_rs_file(path) {
+        if path.extension().is_some_and(|ext| ext == "rs")
+            && is_translatable_rs_file(path)
+            && !ignore.is_ignored(&relative_slash_path(rust_dir, path))
+        {
             total += 1;
             if entry.metadata()?.len() == 0 {
                 empty += 1;
@@ -56,7 +244,10 @@ pub fn count_rs_files_with_empty(rust_dir: &Path) -> Result<(usize, usize)> {
 }
 
 /// 查找给定目录中需要翻译的空 .rs 文件（文件名以 var_ 或 fun_ 开头且内容为空）
+///
+/// 匹配当前忽略模式的文件不会返回。
 pub fn find_empty_rs_files(rust_dir: &Path) -> Result<Vec<PathBuf>> {
+    let ignore = ignore_patterns();
     let mut empty_files = Vec::new();
 
     for entry in WalkDir::new(rust_dir) {
@@ -66,6 +257,7 @@ pub fn find_empty_rs_files(rust_dir: &Path) -> Result<Vec<PathBuf>> {
         if path.is_file()
             && path.extension().is_some_and(|ext| ext == "rs")
             && is_translatable_rs_file(path)
+            && !ignore.is_ignored(&relative_slash_path(rust_dir, path))
         {
             let metadata = fs::metadata(path)?;
             if metadata.len() == 0 {
@@ -80,6 +272,324 @@ pub fn find_empty_rs_files(rust_dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(empty_files)
 }
 
+/// 待翻译空文件的描述信息（`list-empty` 子命令的输出条目）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmptyFileEntry {
+    /// 相对 rust 目录的路径（使用 `/` 分隔）
+    pub path: String,
+    /// 从文件名提取的类型（"var" 或 "fn"）
+    pub file_type: Option<String>,
+    /// 同目录下是否存在对应的 `.c` 文件
+    pub has_c_file: bool,
+}
+
+/// 列出需要翻译的空 .rs 文件及其类型和 C 文件配对情况（只读，不产生副作用）
+///
+/// 结果顺序与 `find_empty_rs_files` 一致（按路径排序）。
+pub fn list_empty_rs_files(rust_dir: &Path) -> Result<Vec<EmptyFileEntry>> {
+    let entries = find_empty_rs_files(rust_dir)?
+        .into_iter()
+        .map(|path| {
+            let relative = relative_slash_path(rust_dir, &path);
+            let file_type = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(extract_file_type)
+                .map(|(file_type, _)| file_type.to_string());
+            EmptyFileEntry {
+                path: relative,
+                file_type,
+                has_c_file: path.with_extension("c").is_file(),
+            }
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// 一组待翻译文件的完成情况（按 var/fun 类型分别统计）
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatusCounts {
+    /// `var_*.rs` 文件总数
+    pub var_total: usize,
+    /// 仍为空（未翻译）的 `var_*.rs` 文件数
+    pub var_empty: usize,
+    /// `fun_*.rs` 文件总数
+    pub fun_total: usize,
+    /// 仍为空（未翻译）的 `fun_*.rs` 文件数
+    pub fun_empty: usize,
+}
+
+impl StatusCounts {
+    pub fn total(&self) -> usize {
+        self.var_total + self.fun_total
+    }
+
+    pub fn empty(&self) -> usize {
+        self.var_empty + self.fun_empty
+    }
+
+    pub fn translated(&self) -> usize {
+        self.total() - self.empty()
+    }
+
+    /// 完成百分比（没有待翻译文件时视为 100%）
+    pub fn percent_complete(&self) -> f64 {
+        if self.total() == 0 {
+            100.0
+        } else {
+            self.translated() as f64 * 100.0 / self.total() as f64
+        }
+    }
+
+    fn add(&mut self, is_var: bool, is_empty: bool) {
+        if is_var {
+            self.var_total += 1;
+            self.var_empty += usize::from(is_empty);
+        } else {
+            self.fun_total += 1;
+            self.fun_empty += usize::from(is_empty);
+        }
+    }
+}
+
+/// 单个顶层模块目录（`rust/src` 下的第一级目录）的完成情况
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleStatus {
+    /// 模块目录名；直接位于 `src` 下的文件归入 `.`
+    pub module: String,
+    #[serde(flatten)]
+    pub counts: StatusCounts,
+    pub percent_complete: f64,
+}
+
+/// `status` 子命令的统计结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranslationStatus {
+    #[serde(flatten)]
+    pub counts: StatusCounts,
+    pub percent_complete: f64,
+    /// 按模块名排序
+    pub modules: Vec<ModuleStatus>,
+}
+
+/// 统计 rust 目录的翻译完成情况（只读），并按 `src` 下的顶层模块目录分组
+///
+/// 空文件集合来自 [`find_empty_rs_files`]，总数与 [`count_all_rs_files`] 一致。
+pub fn collect_translation_status(rust_dir: &Path) -> Result<TranslationStatus> {
+    let ignore = ignore_patterns();
+    let empty_files: std::collections::HashSet<PathBuf> =
+        find_empty_rs_files(rust_dir)?.into_iter().collect();
+    let src_dir = rust_dir.join("src");
+
+    let mut counts = StatusCounts::default();
+    let mut modules: std::collections::BTreeMap<String, StatusCounts> =
+        std::collections::BTreeMap::new();
+    for entry in WalkDir::new(rust_dir) {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file()
+            || path.extension().is_none_or(|ext| ext != "rs")
+            || !is_translatable_rs_file(path)
+            || ignore.is_ignored(&relative_slash_path(rust_dir, path))
+        {
+            continue;
+        }
+        let is_var = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|stem| stem.starts_with("var_"));
+        let is_empty = empty_files.contains(path);
+
+        let relative = path
+            .strip_prefix(&src_dir)
+            .or_else(|_| path.strip_prefix(rust_dir))
+            .unwrap_or(path);
+        let mut components = relative.components();
+        let module = match (components.next(), components.next()) {
+            (Some(first), Some(_)) => first.as_os_str().to_string_lossy().into_owned(),
+            _ => ".".to_string(),
+        };
+
+        counts.add(is_var, is_empty);
+        modules.entry(module).or_default().add(is_var, is_empty);
+    }
+
+    Ok(TranslationStatus {
+        percent_complete: counts.percent_complete(),
+        counts,
+        modules: modules
+            .into_iter()
+            .map(|(module, counts)| ModuleStatus {
+                module,
+                percent_complete: counts.percent_complete(),
+                counts,
+            })
+            .collect(),
+    })
+}
+
+/// rust 目录结构检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructuralViolation {
+    /// 空的待翻译 .rs 文件缺少同名 .c 文件
+    MissingCSibling(PathBuf),
+    /// 空 .rs 文件的文件名无法识别类型（不是 var_/fun_ 前缀或缺少名称）
+    UnknownPrefix(PathBuf),
+}
+
+impl std::fmt::Display for StructuralViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StructuralViolation::MissingCSibling(path) => {
+                write!(f, "missing C sibling: {}", path.display())
+            }
+            StructuralViolation::UnknownPrefix(path) => {
+                write!(
+                    f,
+                    "unrecognized file type (expected var_/fun_ prefix): {}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+/// 允许为空、不参与翻译的模块文件
+const STRUCTURAL_FILES: &[&str] = &["lib.rs", "main.rs", "mod.rs", "build.rs"];
+
+/// 检查 rust 目录是否符合 code-analyse 生成的布局
+///
+/// 每个空 .rs 文件都应当能识别出类型（var_/fun_ 前缀加名称）并且有同名 .c 文件。
+/// 收集所有问题一并返回（按路径排序），`target/` 目录会被跳过。
+pub fn check_rust_dir_structure(rust_dir: &Path) -> Result<Vec<StructuralViolation>> {
+    let mut violations = Vec::new();
+
+    let walker = WalkDir::new(rust_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !(e.file_type().is_dir() && e.file_name() == "target"));
+    for entry in walker {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        if fs::metadata(path)?.len() != 0 {
+            continue;
+        }
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if STRUCTURAL_FILES.contains(&file_name) {
+            continue;
+        }
+
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        match extract_file_type(stem) {
+            Some((_, name)) if !name.is_empty() => {
+                if !path.with_extension("c").is_file() {
+                    violations.push(StructuralViolation::MissingCSibling(path.to_path_buf()));
+                }
+            }
+            _ => violations.push(StructuralViolation::UnknownPrefix(path.to_path_buf())),
+        }
+    }
+
+    Ok(violations)
+}
+
+/// `verify-pairs` 子命令发现的 C/Rust 文件配对问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairingProblem {
+    /// var_/fun_ .rs 文件没有同名 .c 文件
+    MissingCFile(PathBuf),
+    /// .c 文件没有同名 .rs 文件
+    OrphanCFile(PathBuf),
+    /// .rs 文件的文件名无法识别类型（不是 var_/fun_ 前缀或缺少名称）
+    UnknownPrefix(PathBuf),
+}
+
+impl PairingProblem {
+    /// 建议的修复方式
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            PairingProblem::MissingCFile(_) => {
+                "re-run `code-analyse --init` to regenerate the C source, or remove the .rs file if the C item no longer exists"
+            }
+            PairingProblem::OrphanCFile(_) => {
+                "re-run `code-analyse --init` to generate the matching .rs placeholder"
+            }
+            PairingProblem::UnknownPrefix(_) => {
+                "rename it to var_<name>.rs / fun_<name>.rs, or re-run `code-analyse --init` to regenerate the layout"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PairingProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PairingProblem::MissingCFile(path) => {
+                write!(f, "missing .c file for: {}", path.display())
+            }
+            PairingProblem::OrphanCFile(path) => {
+                write!(f, "orphaned .c file (no .rs): {}", path.display())
+            }
+            PairingProblem::UnknownPrefix(path) => {
+                write!(
+                    f,
+                    "unrecognized file type (expected var_/fun_ prefix): {}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+/// 检查 `rust_dir/src` 下所有 .rs 与 .c 文件是否一一配对（包括已翻译的文件）
+///
+/// 与 [`check_rust_dir_structure`] 不同，这里不限于空文件，并且也报告没有 .rs 的 .c 文件。
+/// 结果按路径排序，`target/` 目录会被跳过。
+pub fn check_file_pairing(rust_dir: &Path) -> Result<Vec<PairingProblem>> {
+    let mut problems = Vec::new();
+
+    let walker = WalkDir::new(rust_dir.join("src"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir()
+                && (e.file_name() == "target" || e.file_name() == crate::backup::BACKUP_DIR_NAME))
+        });
+    for entry in walker {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("rs") => {
+                let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                if STRUCTURAL_FILES.contains(&file_name) {
+                    continue;
+                }
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                match extract_file_type(stem) {
+                    Some((_, name)) if !name.is_empty() => {
+                        if !path.with_extension("c").is_file() {
+                            problems.push(PairingProblem::MissingCFile(path.to_path_buf()));
+                        }
+                    }
+                    _ => problems.push(PairingProblem::UnknownPrefix(path.to_path_buf())),
+                }
+            }
+            Some("c") if !path.with_extension("rs").is_file() => {
+                problems.push(PairingProblem::OrphanCFile(path.to_path_buf()));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(problems)
+}
+
 /// 从文件名中提取文件类型（var_ 或 fun_ 前缀）
 pub fn extract_file_type(filename: &str) -> Option<(&'static str, &str)> {
     if let Some(stripped) = filename.strip_prefix("var_") {
@@ -177,36 +687,127 @@ pub fn parse_file_selection(input: &str, total_files: usize) -> Result<Vec<usize
     Ok(selected_indices)
 }
 
+/// 按子串匹配文件路径的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubstringMatch {
+    /// 仅有一个文件匹配（给出其在候选列表中的索引）
+    Unique(usize),
+    /// 多个文件匹配（给出所有匹配项的索引，按原顺序）
+    Ambiguous(Vec<usize>),
+    /// 没有文件匹配
+    NoMatch,
+}
+
+/// 判断输入是否应按数字/范围选择解析（而非子串匹配）
+fn is_index_selection(input: &str) -> bool {
+    let input = input.trim();
+    input.eq_ignore_ascii_case("all")
+        || input
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '-' || c.is_whitespace())
+}
+
+/// 在相对路径列表中按子串（不区分大小写）查找文件
+pub fn match_files_by_substring(query: &str, relative_paths: &[String]) -> SubstringMatch {
+    let query = query.trim().to_lowercase();
+    let matches: Vec<usize> = relative_paths
+        .iter()
+        .enumerate()
+        .filter(|(_, path)| path.to_lowercase().contains(&query))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    match matches.len() {
+        0 => SubstringMatch::NoMatch,
+        1 => SubstringMatch::Unique(matches[0]),
+        _ => SubstringMatch::Ambiguous(matches),
+    }
+}
+
 /// 提示用户从列表中选择文件
+///
+/// 除编号/范围/`all` 外，也可以输入文件路径的子串：唯一匹配时直接选中该文件，
+/// 匹配多个时缩小显示列表并重新提示。
 pub fn prompt_file_selection(files: &[&PathBuf], rust_dir: &Path) -> Result<Vec<usize>> {
-    println!("\n{}", "Available files to process:".bright_cyan().bold());
-
-    // 显示文件及其索引号和相对路径
-    for (idx, file) in files.iter().enumerate() {
-        let relative_path = file.strip_prefix(rust_dir).unwrap_or(file);
-        println!("  {}. {}", idx + 1, relative_path.display());
-    }
-
-    println!();
-    println!("{}", "Select files to process:".bright_yellow());
-    println!("  - Enter numbers separated by commas (e.g., 1,3,5)");
-    println!("  - Enter ranges (e.g., 1-3,5)");
-    println!("  - Enter 'all' to process all files");
-    println!();
-
-    // Use inquire::Text for better terminal handling (Delete key, arrow keys, etc.)
-    let input = match Text::new("Your selection:")
-        .with_help_message("Enter file numbers/ranges or 'all'")
-        .prompt()
-    {
-        Ok(s) => s,
-        Err(inquire::InquireError::OperationCanceled) => {
-            anyhow::bail!("File selection canceled by user");
+    let relative_paths: Vec<String> = files
+        .iter()
+        .map(|file| {
+            file.strip_prefix(rust_dir)
+                .unwrap_or(file)
+                .display()
+                .to_string()
+        })
+        .collect();
+    if let Some(picked) = crate::observer::choose(
+        crate::observer::ChoiceKind::FilesToProcess,
+        "Select files to process:",
+        &relative_paths,
+        true,
+    ) {
+        return Ok(picked);
+    }
+    // 当前显示的文件（在 files 中的索引），子串匹配多个文件时会被缩小
+    let mut visible: Vec<usize> = (0..files.len()).collect();
+
+    loop {
+        println!("\n{}", "Available files to process:".bright_cyan().bold());
+
+        // 显示文件及其索引号和相对路径
+        for (idx, &file_idx) in visible.iter().enumerate() {
+            println!("  {}. {}", idx + 1, relative_paths[file_idx]);
         }
-        Err(e) => return Err(anyhow::Error::new(e)).context("Failed to get file selection"),
-    };
 
-    parse_file_selection(&input, files.len())
+        println!();
+        println!("{}", "Select files to process:".bright_yellow());
+        println!("  - Enter numbers separated by commas (e.g., 1,3,5)");
+        println!("  - Enter ranges (e.g., 1-3,5)");
+        println!("  - Enter 'all' to process all listed files");
+        println!("  - Enter part of a file name to jump to it (e.g., parse_header)");
+        println!();
+
+        // Use inquire::Text for better terminal handling (Delete key, arrow keys, etc.)
+        let input = match Text::new("Your selection:")
+            .with_help_message("Enter file numbers/ranges, 'all', or a file name substring")
+            .prompt()
+        {
+            Ok(s) => s,
+            Err(inquire::InquireError::OperationCanceled) => {
+                anyhow::bail!("File selection canceled by user");
+            }
+            Err(e) => return Err(anyhow::Error::new(e)).context("Failed to get file selection"),
+        };
+
+        if is_index_selection(&input) {
+            let selected = parse_file_selection(&input, visible.len())?;
+            return Ok(selected.into_iter().map(|idx| visible[idx]).collect());
+        }
+
+        let visible_paths: Vec<String> = visible
+            .iter()
+            .map(|&idx| relative_paths[idx].clone())
+            .collect();
+        match match_files_by_substring(&input, &visible_paths) {
+            SubstringMatch::Unique(idx) => return Ok(vec![visible[idx]]),
+            SubstringMatch::Ambiguous(matches) => {
+                println!(
+                    "{}",
+                    format!(
+                        "{} files match '{}'; narrowing the list.",
+                        matches.len(),
+                        input.trim()
+                    )
+                    .yellow()
+                );
+                visible = matches.into_iter().map(|idx| visible[idx]).collect();
+            }
+            SubstringMatch::NoMatch => {
+                println!(
+                    "{}",
+                    format!("No file matches '{}'; please try again.", input.trim()).yellow()
+                );
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +816,290 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_check_rust_dir_structure_reports_all_violations() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path();
+        let src = base.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(base.join("target").join("debug")).unwrap();
+
+        // 合法：有 .c 配对
+        fs::File::create(src.join("fun_ok.rs")).unwrap();
+        fs::File::create(src.join("fun_ok.c")).unwrap();
+        // 缺少 .c 配对
+        fs::File::create(src.join("var_lonely.rs")).unwrap();
+        // 无法识别的前缀
+        fs::File::create(src.join("helper.rs")).unwrap();
+        // 允许为空的模块文件以及 target/ 下的文件都应被忽略
+        fs::File::create(src.join("lib.rs")).unwrap();
+        fs::File::create(base.join("target").join("debug").join("junk.rs")).unwrap();
+
+        let violations = check_rust_dir_structure(base).unwrap();
+        assert_eq!(
+            violations,
+            vec![
+                StructuralViolation::UnknownPrefix(src.join("helper.rs")),
+                StructuralViolation::MissingCSibling(src.join("var_lonely.rs")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_file_pairing_reports_orphans_in_both_directions() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path();
+        let src = base.join("src");
+        fs::create_dir_all(src.join("net")).unwrap();
+
+        // 已翻译（非空）和待翻译的文件都有 .c 配对
+        fs::write(src.join("fun_done.rs"), "pub fn done() {}").unwrap();
+        fs::File::create(src.join("fun_done.c")).unwrap();
+        fs::File::create(src.join("net").join("var_port.rs")).unwrap();
+        fs::File::create(src.join("net").join("var_port.c")).unwrap();
+        // 已翻译但缺少 .c
+        fs::write(src.join("fun_lost.rs"), "pub fn lost() {}").unwrap();
+        // 没有 .rs 的 .c
+        fs::File::create(src.join("net").join("fun_send.c")).unwrap();
+        // 无法识别的前缀（即使非空）
+        fs::write(src.join("helper.rs"), "pub fn helper() {}").unwrap();
+        fs::write(src.join("lib.rs"), "mod net;").unwrap();
+
+        let problems = check_file_pairing(base).unwrap();
+        assert_eq!(
+            problems,
+            vec![
+                PairingProblem::MissingCFile(src.join("fun_lost.rs")),
+                PairingProblem::UnknownPrefix(src.join("helper.rs")),
+                PairingProblem::OrphanCFile(src.join("net").join("fun_send.c")),
+            ]
+        );
+        assert!(problems[2].remediation().contains("code-analyse --init"));
+    }
+
+    #[test]
+    fn test_match_files_by_substring_unique() {
+        let paths = vec![
+            "src/fun_parse_header.rs".to_string(),
+            "src/fun_parse_body.rs".to_string(),
+            "src/var_config.rs".to_string(),
+        ];
+        assert_eq!(
+            match_files_by_substring("HEADER", &paths),
+            SubstringMatch::Unique(0)
+        );
+        assert_eq!(
+            match_files_by_substring("config", &paths),
+            SubstringMatch::Unique(2)
+        );
+        assert_eq!(
+            match_files_by_substring("missing", &paths),
+            SubstringMatch::NoMatch
+        );
+    }
+
+    #[test]
+    fn test_match_files_by_substring_ambiguous_narrows() {
+        let paths = vec![
+            "src/fun_parse_header.rs".to_string(),
+            "src/var_config.rs".to_string(),
+            "src/fun_parse_body.rs".to_string(),
+        ];
+        let SubstringMatch::Ambiguous(matches) = match_files_by_substring("parse", &paths) else {
+            panic!("expected an ambiguous match");
+        };
+        assert_eq!(matches, vec![0, 2]);
+
+        // 在缩小后的列表中再次输入子串可唯一定位
+        let narrowed: Vec<String> = matches.iter().map(|&i| paths[i].clone()).collect();
+        assert_eq!(
+            match_files_by_substring("body", &narrowed),
+            SubstringMatch::Unique(1)
+        );
+    }
+
+    #[test]
+    fn test_ignore_patterns_glob_matching() {
+        let ignore = IgnorePatterns::new(
+            [
+                "fun_hand_*.rs",
+                "src/legacy/**",
+                "src/**/var_tmp?.rs",
+                "!src/legacy/fun_keep.rs",
+                " ",
+            ]
+            .map(String::from),
+        );
+
+        // 不含 `/` 的模式只匹配文件名，位于任意目录
+        assert!(ignore.is_ignored("src/fun_hand_port.rs"));
+        assert!(ignore.is_ignored("src/net/fun_hand_x.rs"));
+        assert!(!ignore.is_ignored("src/fun_handle.rs"));
+        // `**` 匹配任意层目录（包括零层）
+        assert!(ignore.is_ignored("src/legacy/fun_a.rs"));
+        assert!(ignore.is_ignored("src/legacy/deep/nested/var_b.rs"));
+        assert!(ignore.is_ignored("src/var_tmp1.rs"));
+        assert!(ignore.is_ignored("src/a/b/var_tmp2.rs"));
+        assert!(!ignore.is_ignored("src/var_tmp10.rs"));
+        assert!(!ignore.is_ignored("other/legacy/fun_a.rs"));
+        // 取反模式将文件重新纳入范围，之后的模式仍可再次忽略
+        assert!(!ignore.is_ignored("src/legacy/fun_keep.rs"));
+        let reignored = IgnorePatterns::new(
+            ["src/legacy/**", "!fun_keep.rs", "src/legacy/*.rs"].map(String::from),
+        );
+        assert!(reignored.is_ignored("src/legacy/fun_keep.rs"));
+        assert!(!reignored.is_ignored("src/legacy/deep/fun_keep.rs"));
+
+        // 含 `/` 的模式可以省略开头的 `src/`
+        assert!(glob_matches("net/*", "src/net/fun_send.rs"));
+        assert!(!glob_matches("net/*", "src/net/tcp/fun_send.rs"));
+        assert!(glob_matches("net/**", "src/net/tcp/fun_send.rs"));
+
+        assert!(IgnorePatterns::new([" ".to_string()]).is_empty());
+        assert!(!IgnorePatterns::default().is_ignored("src/fun_a.rs"));
+    }
+
+    #[test]
+    fn test_c2rustignore_nested_and_negation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let feature_dir = tmp.path().join(".c2rust").join("net");
+        std::fs::create_dir_all(&feature_dir).unwrap();
+        assert!(c2rustignore_patterns(tmp.path(), "net").unwrap().is_empty());
+
+        std::fs::write(
+            tmp.path().join(IGNORE_FILE_NAME),
+            "# generated code\n\ngenerated/\nfun_big_*.rs\n",
+        )
+        .unwrap();
+        std::fs::write(
+            feature_dir.join(IGNORE_FILE_NAME),
+            "!src/generated/fun_keep.rs\nsrc/proto/wire/\n",
+        )
+        .unwrap();
+
+        let patterns = c2rustignore_patterns(tmp.path(), "net").unwrap();
+        assert_eq!(
+            patterns,
+            vec![
+                "**/generated/**",
+                "fun_big_*.rs",
+                "!src/generated/fun_keep.rs",
+                "src/proto/wire/**",
+            ]
+        );
+        let ignore = IgnorePatterns::new(patterns);
+        // 项目级的目录模式匹配任意层级，feature 级的取反模式重新纳入单个文件
+        assert!(ignore.is_ignored("src/generated/fun_a.rs"));
+        assert!(ignore.is_ignored("src/net/generated/deep/var_b.rs"));
+        assert!(!ignore.is_ignored("src/generated/fun_keep.rs"));
+        assert!(ignore.is_ignored("src/fun_big_table.rs"));
+        assert!(ignore.is_ignored("src/proto/wire/fun_c.rs"));
+        assert!(!ignore.is_ignored("src/proto/fun_c.rs"));
+        // 其他 feature 只读取项目级的忽略文件
+        assert_eq!(c2rustignore_patterns(tmp.path(), "other").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_ignore_patterns_leading_slash_and_c_files() {
+        let patterns =
+            parse_ignore_file("/fun_top.rs\n/src/gen/\nsrc/legacy/*.c\n!/src/legacy/fun_keep.c\n");
+        assert_eq!(
+            patterns,
+            vec![
+                "/fun_top.rs",
+                "/src/gen/**",
+                "src/legacy/*.c",
+                "!/src/legacy/fun_keep.c",
+            ]
+        );
+        let ignore = IgnorePatterns::new(patterns);
+        // 以 `/` 开头的模式只匹配 rust 目录下的完整路径
+        assert!(ignore.is_ignored("fun_top.rs"));
+        assert!(!ignore.is_ignored("src/fun_top.rs"));
+        assert!(ignore.is_ignored("src/gen/fun_a.rs"));
+        assert!(!ignore.is_ignored("src/net/gen/fun_a.rs"));
+        assert!(!glob_matches("/gen/*", "src/gen/fun_a.rs"));
+        // `.c` 模式按同名的 C 文件匹配
+        assert!(ignore.is_ignored("src/legacy/fun_old.rs"));
+        assert!(!ignore.is_ignored("src/legacy/fun_keep.rs"));
+        assert!(!ignore.is_ignored("src/fun_old.rs"));
+    }
+
+    #[test]
+    fn test_is_index_selection() {
+        assert!(is_index_selection("1,3-5"));
+        assert!(is_index_selection(" all "));
+        assert!(!is_index_selection("parse"));
+        assert!(!is_index_selection("fun_2"));
+    }
+
+    #[test]
+    fn test_list_empty_rs_files_sorted_with_metadata() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path();
+        let src = base.join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+
+        fs::File::create(src.join("var_zeta.rs")).unwrap();
+        fs::File::create(src.join("fun_alpha.rs")).unwrap();
+        fs::File::create(src.join("fun_alpha.c")).unwrap();
+        fs::File::create(src.join("nested").join("fun_beta.rs")).unwrap();
+        // 非空或不可翻译的文件不应出现在列表中
+        fs::write(src.join("fun_done.rs"), "fn done() {}").unwrap();
+        fs::File::create(src.join("lib.rs")).unwrap();
+
+        let entries = list_empty_rs_files(base).unwrap();
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "src/fun_alpha.rs",
+                "src/nested/fun_beta.rs",
+                "src/var_zeta.rs"
+            ]
+        );
+        assert_eq!(entries[0].file_type.as_deref(), Some("fn"));
+        assert!(entries[0].has_c_file);
+        assert!(!entries[1].has_c_file);
+        assert_eq!(entries[2].file_type.as_deref(), Some("var"));
+    }
+
+    #[test]
+    fn test_collect_translation_status_groups_by_module() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path();
+        let src = base.join("src");
+        fs::create_dir_all(src.join("net").join("tcp")).unwrap();
+        fs::create_dir_all(src.join("util")).unwrap();
+
+        fs::File::create(src.join("var_root.rs")).unwrap();
+        fs::write(src.join("net").join("fun_send.rs"), "fn send() {}").unwrap();
+        fs::File::create(src.join("net").join("tcp").join("fun_recv.rs")).unwrap();
+        fs::write(src.join("net").join("var_port.rs"), "static PORT: u16 = 0;").unwrap();
+        fs::write(src.join("util").join("fun_max.rs"), "fn max() {}").unwrap();
+        fs::File::create(src.join("lib.rs")).unwrap();
+
+        let status = collect_translation_status(base).unwrap();
+        assert_eq!(status.counts.total(), 5);
+        assert_eq!(status.counts.var_total, 2);
+        assert_eq!(status.counts.var_empty, 1);
+        assert_eq!(status.counts.fun_total, 3);
+        assert_eq!(status.counts.fun_empty, 1);
+        assert!((status.percent_complete - 60.0).abs() < f64::EPSILON);
+
+        let modules: Vec<(&str, usize, usize)> = status
+            .modules
+            .iter()
+            .map(|m| (m.module.as_str(), m.counts.total(), m.counts.empty()))
+            .collect();
+        assert_eq!(modules, vec![(".", 1, 1), ("net", 3, 1), ("util", 1, 0)]);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["var_total"], 2);
+        assert_eq!(json["modules"][1]["module"], "net");
+        assert_eq!(json["modules"][1]["fun_empty"], 1);
+    }
+
     #[test]
     fn test_count_rs_files_with_empty_mixed_files() {
         // Create a temp directory with a mix of translatable/non-translatable and
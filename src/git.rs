@@ -1,6 +1,124 @@
 use crate::util;
 use anyhow::{Context, Result};
+use colored::Colorize;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Set by `--no-commit`: [`git_commit`] only records that a commit point was reached.
+static NO_COMMIT: AtomicBool = AtomicBool::new(false);
+
+/// Commit points skipped because of `--no-commit` in the current run.
+static SKIPPED_COMMITS: AtomicUsize = AtomicUsize::new(0);
+
+/// Enable or disable `--no-commit` mode and reset the skipped-commit counter.
+pub fn set_no_commit(no_commit: bool) {
+    NO_COMMIT.store(no_commit, Ordering::Relaxed);
+    SKIPPED_COMMITS.store(0, Ordering::Relaxed);
+}
+
+/// Whether `--no-commit` mode is active.
+pub fn is_no_commit() -> bool {
+    NO_COMMIT.load(Ordering::Relaxed)
+}
+
+/// Number of commit points skipped under `--no-commit` since [`set_no_commit`].
+pub fn skipped_commit_count() -> usize {
+    SKIPPED_COMMITS.load(Ordering::Relaxed)
+}
+
+/// Translated files waiting for the next batched commit under `--commit-every N`.
+struct CommitBatch {
+    every: usize,
+    files: Vec<String>,
+    /// A per-file commit (fix or analysis update) was folded into the batch.
+    deferred: bool,
+}
+
+static COMMIT_BATCH: Mutex<CommitBatch> = Mutex::new(CommitBatch {
+    every: 1,
+    files: Vec::new(),
+    deferred: false,
+});
+
+fn with_commit_batch<T>(f: impl FnOnce(&mut CommitBatch) -> T) -> T {
+    f(&mut COMMIT_BATCH.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Set `--commit-every N` and clear the pending batch; `1` commits after every file.
+pub fn set_commit_every(every: usize) {
+    with_commit_batch(|batch| {
+        *batch = CommitBatch {
+            every: every.max(1),
+            files: Vec::new(),
+            deferred: false,
+        }
+    });
+}
+
+/// The `--commit-every N` batch size; `1` commits after every file.
+pub fn commit_every() -> usize {
+    with_commit_batch(|batch| batch.every)
+}
+
+/// Whether translated files are committed in batches (`--commit-every` > 1).
+pub fn is_batching_commits() -> bool {
+    with_commit_batch(|batch| batch.every > 1)
+}
+
+/// Add a translated file to the pending batch; returns `(files batched, N)`.
+pub fn add_to_commit_batch(file_name: &str) -> (usize, usize) {
+    with_commit_batch(|batch| {
+        batch.files.push(file_name.to_string());
+        (batch.files.len(), batch.every)
+    })
+}
+
+/// Fold a per-file commit point into the pending batch instead of committing it.
+///
+/// Returns `false` when not batching, in which case the caller commits as usual.
+pub fn defer_to_commit_batch() -> bool {
+    with_commit_batch(|batch| {
+        if batch.every > 1 {
+            batch.deferred = true;
+        }
+        batch.every > 1
+    })
+}
+
+/// Whether the pending batch has reached `--commit-every N` files.
+pub fn is_commit_batch_full() -> bool {
+    with_commit_batch(|batch| batch.every > 1 && batch.files.len() >= batch.every)
+}
+
+/// Take the pending batch and return its commit message; `None` when nothing is pending.
+pub fn take_commit_batch(feature: &str) -> Option<String> {
+    let (files, deferred) = with_commit_batch(|batch| {
+        let deferred = std::mem::take(&mut batch.deferred);
+        (std::mem::take(&mut batch.files), deferred)
+    });
+    (deferred || !files.is_empty()).then(|| batch_commit_message(feature, &files, deferred))
+}
+
+/// Commit message for a batch: the translated files, one per line, with the folded
+/// fix and analysis updates mentioned in the body when any were deferred.
+fn batch_commit_message(feature: &str, files: &[String], deferred: bool) -> String {
+    if files.is_empty() {
+        return format!("Update code analysis for {}", feature);
+    }
+    let mut message = format!(
+        "Translate {} file(s) from C to Rust (feature: {})\n\n",
+        files.len(),
+        feature
+    );
+    for file in files {
+        message.push_str(&format!("- {}\n", file));
+    }
+    if deferred {
+        message.push_str("\nIncludes the build fixes and code analysis updates for these files.");
+    }
+    message
+}
 
 /// Return whether the dedicated `.c2rust/` tracking repo currently has
 /// uncommitted changes.
@@ -8,11 +126,12 @@ pub fn git_has_uncommitted_changes() -> Result<bool> {
     let project_root = util::find_project_root()?;
     let c2rust_dir = project_root.join(".c2rust");
 
-    let output = Command::new("git")
-        .current_dir(&c2rust_dir)
-        .args(["status", "--porcelain"])
-        .output()
-        .context("Failed to inspect .c2rust git status")?;
+    let output = crate::interrupt::output(
+        Command::new("git")
+            .current_dir(&c2rust_dir)
+            .args(["status", "--porcelain"]),
+    )
+    .context("Failed to inspect .c2rust git status")?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -22,61 +141,234 @@ pub fn git_has_uncommitted_changes() -> Result<bool> {
     Ok(!output.stdout.is_empty())
 }
 
+/// Files under `dir` that differ between `rev` and the working tree
+/// (`git diff --name-only --relative <rev> -- .`, run from `dir`), plus the untracked
+/// files that are not ignored (`git ls-files --others --exclude-standard -- .`).
+///
+/// `rev` is resolved to a commit first (`git rev-parse --verify --end-of-options`), so it
+/// can never be taken as a git option. Paths are relative to `dir`. An invalid `rev` fails
+/// with git's own error message.
+pub fn changed_files_since(rev: &str, dir: &std::path::Path) -> Result<Vec<String>> {
+    let commit = git_stdout(
+        dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--end-of-options",
+            &format!("{}^{{commit}}", rev),
+        ],
+    )
+    .with_context(|| format!("Invalid revision: {}", rev))?;
+    let commit = commit.trim();
+
+    let mut files = git_stdout(
+        dir,
+        &["diff", "--name-only", "--relative", commit, "--", "."],
+    )?;
+    files.push_str(&git_stdout(
+        dir,
+        &["ls-files", "--others", "--exclude-standard", "--", "."],
+    )?);
+
+    let mut files: Vec<String> = files
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Run git in `dir` and return its stdout; a non-zero exit fails with git's stderr.
+fn git_stdout(dir: &std::path::Path, args: &[&str]) -> Result<String> {
+    let output = crate::interrupt::output(Command::new("git").current_dir(dir).args(args))
+        .with_context(|| format!("Failed to run git {}", args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 /// Commit changes with a message.
-/// Stages all pending changes in the `.c2rust/` directory (which is a dedicated
-/// translation-tracking git repo) and creates a commit.  Because `.c2rust/` is
-/// used exclusively by this tool, staging the whole tree does not risk picking up
-/// unrelated local modifications from the host project.
+/// Stages the pending changes under `.c2rust/<feature>/` and commits only that
+/// path, so unrelated edits elsewhere in the working tree (other features, or the
+/// host project when `.c2rust/` is not its own repo) are neither staged nor committed.
+///
+/// The commit identity comes from the optional `git.author` (`Name <email>`) and
+/// `git.signoff` config keys; see [`commit_args`].
+///
+/// Under `--no-commit` nothing is staged or committed; the skipped commit point is
+/// counted and a one-line note printed instead.
 ///
 /// Returns `Ok(true)` when a commit was actually created, `Ok(false)` when there
 /// was nothing to commit (no-op), and `Err` for any other failure.
-pub fn git_commit(message: &str, _feature: &str) -> Result<bool> {
+pub fn git_commit(message: &str, feature: &str) -> Result<bool> {
+    if is_no_commit() {
+        SKIPPED_COMMITS.fetch_add(1, Ordering::Relaxed);
+        println!(
+            "│ {}",
+            format!(
+                "Skipping commit (--no-commit): {}",
+                message.lines().next().unwrap_or("")
+            )
+            .dimmed()
+        );
+        return Ok(false);
+    }
+
     let project_root = util::find_project_root()?;
     let c2rust_dir = project_root.join(".c2rust");
+    util::validate_feature_name(feature)?;
+    let pathspec = format!("{}/", feature);
+    let identity = commit_identity(feature)?;
 
-    // Stage all changes in the dedicated .c2rust tracking repo.
-    let add_output = Command::new("git")
-        .current_dir(&c2rust_dir)
-        .args(["add", "."])
-        .output()
-        .context("Failed to git add")?;
+    // Stage only the feature directory (including deletions), leaving out the run lock
+    // and the translation backups.
+    let lock_pathspec = format!(":(exclude){}/{}", feature, crate::run_lock::LOCK_FILE_NAME);
+    let backup_pathspec = format!(
+        ":(exclude){}/rust/src/{}",
+        feature,
+        crate::backup::BACKUP_DIR_NAME
+    );
+    let add_output = crate::interrupt::output(Command::new("git").current_dir(&c2rust_dir).args([
+        "add",
+        "-A",
+        "--",
+        &pathspec,
+        &lock_pathspec,
+        &backup_pathspec,
+    ]))
+    .context("Failed to git add")?;
 
     if !add_output.status.success() {
         let stderr = String::from_utf8_lossy(&add_output.stderr);
         anyhow::bail!("git add failed: {}", stderr);
     }
 
+    // Decided from the exit status rather than git's (localized) messages.
+    if !has_staged_changes(&c2rust_dir, &pathspec)? {
+        emit_commit_event(message, false);
+        return Ok(false);
+    }
+
     // Commit from the .c2rust directory
-    let commit_output = Command::new("git")
-        .current_dir(&c2rust_dir)
-        .args(["commit", "-m", message])
-        .output()
-        .context("Failed to git commit")?;
+    let commit_output = crate::interrupt::output(
+        Command::new("git")
+            .current_dir(&c2rust_dir)
+            .args(commit_args(message, &identity, &pathspec)),
+    )
+    .context("Failed to git commit")?;
 
     if !commit_output.status.success() {
         let stdout = String::from_utf8_lossy(&commit_output.stdout);
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
-        let combined_output = format!("{}{}", stdout, stderr);
-        let exit_code = commit_output.status.code();
-
-        // Nothing to commit is not an error (git exits with code 1 in this case)
-        let is_nothing_to_commit =
-            exit_code == Some(1) && combined_output.contains("nothing to commit");
-
-        if !is_nothing_to_commit {
-            anyhow::bail!(
-                "git commit failed with exit code {:?}: {}",
-                exit_code,
-                combined_output
-            );
-        }
-        // No-op: nothing was staged/committed
-        return Ok(false);
+        anyhow::bail!(
+            "git commit failed with exit code {:?}: {}{}",
+            commit_output.status.code(),
+            stdout,
+            stderr
+        );
     }
 
+    emit_commit_event(message, true);
     Ok(true)
 }
 
+/// Whether the index has changes under `pathspec` (`git diff --cached --quiet` exits with 1).
+///
+/// Nothing to commit is not an error; checking the exit status works regardless of the
+/// language git prints its messages in.
+fn has_staged_changes(c2rust_dir: &std::path::Path, pathspec: &str) -> Result<bool> {
+    let output = crate::interrupt::output(
+        Command::new("git")
+            .current_dir(c2rust_dir)
+            .args(["diff", "--cached", "--quiet", "--", pathspec]),
+    )
+    .context("Failed to run git diff --cached")?;
+    match output.status.code() {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        code => anyhow::bail!(
+            "git diff --cached failed with exit code {:?}: {}",
+            code,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    }
+}
+
+/// Identity used for the tool's own commits.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct CommitIdentity {
+    /// `(name, email)` from `git.author`; `None` keeps git's configured identity.
+    author: Option<(String, String)>,
+    /// Add a `Signed-off-by` trailer (`git.signoff`).
+    signoff: bool,
+}
+
+/// Read `git.author` and `git.signoff` from the feature config (both optional).
+fn commit_identity(feature: &str) -> Result<CommitIdentity> {
+    let author = match crate::builder::get_config_value("git.author", feature) {
+        Ok(value) => Some(parse_author(&value)?),
+        Err(_) => None,
+    };
+    let signoff = crate::builder::get_config_value("git.signoff", feature)
+        .map(|value| parse_signoff(&value))
+        .unwrap_or(false);
+    Ok(CommitIdentity { author, signoff })
+}
+
+/// Parse a `Name <email>` author string.
+fn parse_author(value: &str) -> Result<(String, String)> {
+    let value = value.trim();
+    let parsed = value
+        .strip_suffix('>')
+        .and_then(|rest| rest.split_once('<'))
+        .map(|(name, email)| (name.trim(), email.trim()))
+        .filter(|(name, email)| !name.is_empty() && !email.is_empty());
+    match parsed {
+        Some((name, email)) => Ok((name.to_string(), email.to_string())),
+        None => anyhow::bail!(
+            "Invalid git.author value '{}': expected 'Name <email>'",
+            value
+        ),
+    }
+}
+
+fn parse_signoff(value: &str) -> bool {
+    let value = value.trim();
+    value == "1" || value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("yes")
+}
+
+/// Arguments for the `git commit` invocation, limited to `pathspec`.
+///
+/// The author is passed as `-c user.name=... -c user.email=...`, which only applies to
+/// this one command, so the user's own repo/global identity is never modified.
+fn commit_args(message: &str, identity: &CommitIdentity, pathspec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some((name, email)) = &identity.author {
+        args.push("-c".to_string());
+        args.push(format!("user.name={}", name));
+        args.push("-c".to_string());
+        args.push(format!("user.email={}", email));
+    }
+    args.extend(["commit".to_string(), "-m".to_string(), message.to_string()]);
+    if identity.signoff {
+        args.push("--signoff".to_string());
+    }
+    args.extend(["--".to_string(), pathspec.to_string()]);
+    args
+}
+
+fn emit_commit_event(message: &str, committed: bool) {
+    crate::events::emit(&crate::events::Event::Commit {
+        message: message.lines().next().unwrap_or(""),
+        committed,
+    });
+}
+
 /// Run garbage collection on the `.c2rust` repository to compact history objects
 /// and reduce `.git` size.
 ///
@@ -109,11 +401,7 @@ pub fn git_gc(aggressive: bool) {
         args.push("--prune=now");
     }
 
-    match Command::new("git")
-        .current_dir(&c2rust_dir)
-        .args(&args)
-        .output()
-    {
+    match crate::interrupt::output(Command::new("git").current_dir(&c2rust_dir).args(&args)) {
         Err(e) => {
             // Could not spawn git (e.g. not installed); warn and continue.
             eprintln!("Warning: failed to run git gc: {}", e);
@@ -151,11 +439,13 @@ pub fn git_expire_reflog() {
     };
     let c2rust_dir = project_root.join(".c2rust");
 
-    match Command::new("git")
-        .current_dir(&c2rust_dir)
-        .args(["reflog", "expire", "--expire=90.days.ago", "--expire-unreachable=90.days.ago", "--all"])
-        .output()
-    {
+    match crate::interrupt::output(Command::new("git").current_dir(&c2rust_dir).args([
+        "reflog",
+        "expire",
+        "--expire=90.days.ago",
+        "--expire-unreachable=90.days.ago",
+        "--all",
+    ])) {
         Err(e) => {
             eprintln!("Warning: failed to run git reflog expire: {}", e);
         }
@@ -166,3 +456,160 @@ pub fn git_expire_reflog() {
         Ok(_) => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestRepo;
+
+    #[test]
+    fn test_parse_author() {
+        assert_eq!(
+            parse_author(" c2rust bot <bot@example.com> ").unwrap(),
+            ("c2rust bot".to_string(), "bot@example.com".to_string())
+        );
+        assert!(parse_author("bot@example.com").is_err());
+        assert!(parse_author("<bot@example.com>").is_err());
+        assert!(parse_author("bot <>").is_err());
+    }
+
+    #[test]
+    fn test_commit_args_with_identity() {
+        assert_eq!(
+            commit_args("msg", &CommitIdentity::default(), "default/"),
+            ["commit", "-m", "msg", "--", "default/"]
+        );
+
+        let identity = CommitIdentity {
+            author: Some(("bot".to_string(), "bot@example.com".to_string())),
+            signoff: parse_signoff("true"),
+        };
+        assert_eq!(
+            commit_args("msg", &identity, "default/"),
+            [
+                "-c",
+                "user.name=bot",
+                "-c",
+                "user.email=bot@example.com",
+                "commit",
+                "-m",
+                "msg",
+                "--signoff",
+                "--",
+                "default/"
+            ]
+        );
+        assert!(!parse_signoff("no"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_git_commit_only_stages_feature_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let c2rust_dir = tmp.path().join(".c2rust");
+        std::fs::create_dir_all(c2rust_dir.join("default").join("rust")).unwrap();
+        let repo = TestRepo::init(&c2rust_dir);
+        std::fs::write(c2rust_dir.join("default/rust/lib.rs"), "").unwrap();
+        std::fs::write(c2rust_dir.join("notes.txt"), "in progress").unwrap();
+        std::fs::create_dir_all(c2rust_dir.join("default/rust/src/.bak")).unwrap();
+        std::fs::write(c2rust_dir.join("default/rust/src/.bak/fun_a.1.1.rs"), "").unwrap();
+
+        let orig = std::env::current_dir().unwrap();
+        std::env::set_current_dir(tmp.path()).unwrap();
+        let _restore = scopeguard::guard(orig, |dir| {
+            let _ = std::env::set_current_dir(dir);
+        });
+
+        assert!(git_commit("Translate default", "default").unwrap());
+        assert_eq!(
+            repo.git(&["ls-files"]).lines().collect::<Vec<_>>(),
+            ["default/rust/lib.rs"]
+        );
+        assert_eq!(
+            repo.git(&["status", "--porcelain"]),
+            "?? default/rust/src/\n?? notes.txt\n"
+        );
+
+        // Only the outside file changed: a no-op, not an error.
+        std::fs::write(c2rust_dir.join("notes.txt"), "more").unwrap();
+        assert!(!git_commit("Translate default", "default").unwrap());
+
+        // Nothing changed at all, with git printing its messages in another language.
+        std::env::set_var("LANGUAGE", "de");
+        let _language = scopeguard::guard((), |_| std::env::remove_var("LANGUAGE"));
+        assert!(!git_commit("Translate default", "default").unwrap());
+        assert_eq!(repo.git(&["rev-list", "--count", "HEAD"]), "1\n");
+    }
+
+    #[test]
+    fn test_changed_files_since_includes_untracked_and_rejects_options() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        let test_repo = TestRepo::init(repo);
+        let rust_dir = repo.join("rust");
+        std::fs::create_dir_all(rust_dir.join("src")).unwrap();
+        std::fs::write(rust_dir.join("src/fun_old.rs"), "").unwrap();
+        std::fs::write(rust_dir.join("src/fun_same.rs"), "").unwrap();
+        test_repo.git(&["add", "-A"]);
+        test_repo.git(&["commit", "-qm", "base"]);
+
+        std::fs::write(rust_dir.join("src/fun_old.rs"), "fn changed() {}").unwrap();
+        std::fs::write(rust_dir.join("src/fun_new.rs"), "").unwrap();
+        assert_eq!(
+            changed_files_since("HEAD", &rust_dir).unwrap(),
+            ["src/fun_new.rs", "src/fun_old.rs"]
+        );
+
+        let output_file = repo.join("leak.txt");
+        let option = format!("--output={}", output_file.display());
+        assert!(changed_files_since(&option, &rust_dir).is_err());
+        assert!(!output_file.exists());
+        assert!(changed_files_since("no-such-rev", &rust_dir).is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_no_commit_counts_skipped_commit_points() {
+        set_no_commit(true);
+        let _reset = scopeguard::guard((), |_| set_no_commit(false));
+
+        // Returns before touching the project root or git.
+        assert!(!git_commit("Translate default", "default").unwrap());
+        assert!(!git_commit("Update code analysis", "default").unwrap());
+        assert_eq!(skipped_commit_count(), 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_commit_batch_collects_files_until_full() {
+        set_commit_every(2);
+        let _reset = scopeguard::guard((), |_| set_commit_every(1));
+
+        assert!(defer_to_commit_batch());
+        assert_eq!(add_to_commit_batch("src/fun_a.rs"), (1, 2));
+        assert!(!is_commit_batch_full());
+        assert_eq!(add_to_commit_batch("src/fun_b.rs"), (2, 2));
+        assert!(is_commit_batch_full());
+        let message = take_commit_batch("default").unwrap();
+        assert!(message.starts_with("Translate 2 file(s) from C to Rust (feature: default)\n\n"));
+        assert!(message.contains("- src/fun_a.rs\n- src/fun_b.rs\n"));
+        assert!(message.contains("Includes the build fixes"));
+        assert_eq!(take_commit_batch("default"), None);
+
+        // Nothing folded into the batch: only the file list.
+        add_to_commit_batch("src/fun_c.rs");
+        let message = take_commit_batch("default").unwrap();
+        assert!(message.ends_with("- src/fun_c.rs\n"));
+
+        // A folded analysis commit with no files still yields a commit.
+        assert!(defer_to_commit_batch());
+        assert_eq!(
+            take_commit_batch("default").as_deref(),
+            Some("Update code analysis for default")
+        );
+
+        set_commit_every(1);
+        assert!(!defer_to_commit_batch());
+        assert_eq!(take_commit_batch("default"), None);
+    }
+}
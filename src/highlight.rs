@@ -0,0 +1,246 @@
+//! 代码预览的轻量语法高亮（`highlight` feature）
+//!
+//! 按文件扩展名识别 C（`.c`/`.h`）和 Rust（`.rs`），逐行切分出关键字、字符串、数字、
+//! 注释和预处理指令并着色。颜色通过 `colored` 输出，因此 `NO_COLOR`、`--no-color`
+//! 以及非 TTY 输出时自动退化为原始文本。
+
+use colored::Colorize;
+use std::path::Path;
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "_Bool",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+/// 支持高亮的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    C,
+    Rust,
+}
+
+/// 切分出的片段类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Plain,
+    Keyword,
+    Str,
+    Number,
+    Comment,
+    Preprocessor,
+}
+
+/// 逐行高亮器；跨行的块注释状态保存在其中，因此同一文件的各行需按顺序传入
+pub(crate) struct Highlighter {
+    language: Option<Language>,
+    in_block_comment: bool,
+}
+
+impl Highlighter {
+    /// 根据扩展名选择语言；无法识别的文件原样输出
+    pub(crate) fn for_path(path: &Path) -> Self {
+        let language = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("c" | "h") => Some(Language::C),
+            Some("rs") => Some(Language::Rust),
+            _ => None,
+        };
+        Self {
+            language,
+            in_block_comment: false,
+        }
+    }
+
+    /// 返回着色后的一行（不含行号等前缀）
+    pub(crate) fn highlight(&mut self, line: &str) -> String {
+        self.tokens(line)
+            .into_iter()
+            .map(|(kind, text)| match kind {
+                TokenKind::Plain => text.to_string(),
+                TokenKind::Keyword => text.magenta().bold().to_string(),
+                TokenKind::Str => text.green().to_string(),
+                TokenKind::Number => text.yellow().to_string(),
+                TokenKind::Comment => text.bright_black().to_string(),
+                TokenKind::Preprocessor => text.cyan().to_string(),
+            })
+            .collect()
+    }
+
+    /// 将一行切分为 (类型, 原文) 片段，各片段按顺序拼接即为原行
+    fn tokens<'a>(&mut self, line: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let Some(language) = self.language else {
+            return vec![(TokenKind::Plain, line)];
+        };
+        if language == Language::C && !self.in_block_comment && line.trim_start().starts_with('#') {
+            return vec![(TokenKind::Preprocessor, line)];
+        }
+
+        let keywords = match language {
+            Language::C => C_KEYWORDS,
+            Language::Rust => RUST_KEYWORDS,
+        };
+        let bytes = line.as_bytes();
+        let mut tokens = Vec::new();
+        let mut plain_start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let (kind, end) = if self.in_block_comment {
+                (TokenKind::Comment, self.block_comment_end(line, i))
+            } else if line[i..].starts_with("//") {
+                (TokenKind::Comment, bytes.len())
+            } else if line[i..].starts_with("/*") {
+                (TokenKind::Comment, self.block_comment_end(line, i + 2))
+            } else if bytes[i] == b'"' {
+                (TokenKind::Str, quoted_end(bytes, i, b'"'))
+            } else if bytes[i] == b'\'' && !is_lifetime(language, bytes, i) {
+                (TokenKind::Str, quoted_end(bytes, i, b'\''))
+            } else if bytes[i].is_ascii_digit() && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+                let end = i + bytes[i..]
+                    .iter()
+                    .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_' || b == b'.')
+                    .count();
+                (TokenKind::Number, end)
+            } else if is_ident_start(bytes[i]) && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+                let end = i + bytes[i..].iter().take_while(|&&b| is_ident_byte(b)).count();
+                if keywords.contains(&&line[i..end]) {
+                    (TokenKind::Keyword, end)
+                } else {
+                    i = end;
+                    continue;
+                }
+            } else {
+                i += line[i..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+
+            if plain_start < i {
+                tokens.push((TokenKind::Plain, &line[plain_start..i]));
+            }
+            tokens.push((kind, &line[i..end]));
+            i = end;
+            plain_start = end;
+        }
+        if plain_start < bytes.len() {
+            tokens.push((TokenKind::Plain, &line[plain_start..]));
+        }
+        tokens
+    }
+
+    /// 从 `start` 开始查找块注释结尾，返回注释片段的结束位置；未结束时注释延续到下一行
+    fn block_comment_end(&mut self, line: &str, start: usize) -> usize {
+        match line[start..].find("*/") {
+            Some(pos) => {
+                self.in_block_comment = false;
+                start + pos + 2
+            }
+            None => {
+                self.in_block_comment = true;
+                line.len()
+            }
+        }
+    }
+}
+
+/// 字符串/字符字面量的结束位置（含结尾引号，处理反斜杠转义；未闭合时到行尾）
+fn quoted_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Rust 中 `'a` 这类生命周期（后面不是紧跟结尾引号的单个字符）不按字符字面量处理
+fn is_lifetime(language: Language, bytes: &[u8], start: usize) -> bool {
+    language == Language::Rust
+        && bytes.get(start + 1).is_some_and(|&b| is_ident_start(b))
+        && bytes.get(start + 2) != Some(&b'\'')
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds<'a>(highlighter: &mut Highlighter, line: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let tokens = highlighter.tokens(line);
+        let joined: String = tokens.iter().map(|(_, text)| *text).collect();
+        assert_eq!(joined, line, "tokens must reassemble the original line");
+        tokens
+            .into_iter()
+            .filter(|(kind, _)| *kind != TokenKind::Plain)
+            .collect()
+    }
+
+    #[test]
+    fn test_c_tokens_and_block_comment_across_lines() {
+        let mut highlighter = Highlighter::for_path(Path::new("src/fun_add.c"));
+        assert_eq!(
+            kinds(&mut highlighter, "#include <stdio.h>"),
+            vec![(TokenKind::Preprocessor, "#include <stdio.h>")]
+        );
+        assert_eq!(
+            kinds(&mut highlighter, "static int x1 = 42; /* start"),
+            vec![
+                (TokenKind::Keyword, "static"),
+                (TokenKind::Keyword, "int"),
+                (TokenKind::Number, "42"),
+                (TokenKind::Comment, "/* start"),
+            ]
+        );
+        assert_eq!(
+            kinds(&mut highlighter, "  end */ return \"a\\\"b\";"),
+            vec![
+                (TokenKind::Comment, "  end */"),
+                (TokenKind::Keyword, "return"),
+                (TokenKind::Str, "\"a\\\"b\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_lifetimes_are_not_char_literals() {
+        let mut highlighter = Highlighter::for_path(Path::new("src/fun_add.rs"));
+        assert_eq!(
+            kinds(
+                &mut highlighter,
+                "pub fn f<'a>(c: &'a u8) -> char { 'x' } // done"
+            ),
+            vec![
+                (TokenKind::Keyword, "pub"),
+                (TokenKind::Keyword, "fn"),
+                (TokenKind::Str, "'x'"),
+                (TokenKind::Comment, "// done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_extension_is_left_plain() {
+        let mut highlighter = Highlighter::for_path(Path::new("notes.txt"));
+        assert_eq!(
+            highlighter.tokens("int x = 1;"),
+            vec![(TokenKind::Plain, "int x = 1;")]
+        );
+    }
+}
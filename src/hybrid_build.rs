@@ -79,8 +79,11 @@ pub fn execute_hybrid_build_command(feature: &str, command_type: HybridCommandTy
 
     // 首先更新代码分析
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     run_hybrid_command(feature, command_type)
 }
@@ -95,8 +98,11 @@ pub fn execute_hybrid_build_sequence(feature: &str, skip_test: bool) -> Result<(
     util::validate_feature_name(feature)?;
 
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     // Clean and Build always run regardless of skip_test: they validate the build itself
     // and must succeed even when the test phase is skipped due to missing test configuration.
@@ -122,7 +128,8 @@ fn run_hybrid_command(feature: &str, command_type: HybridCommandType) -> Result<
         feature,
         command_type.needs_ld_preload(),
         command_type.as_str(),
-    )
+    )?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,6 +1,24 @@
 use crate::{interaction, util};
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// 初始化验证失败后的处理方式
+#[derive(Debug, PartialEq, Eq)]
+enum InitialFailureRoute {
+    /// 错误信息中定位到了 rust 目录下的 .rs 文件，进入文件修复流程
+    RepairFiles(Vec<PathBuf>),
+    /// 未定位到文件，只提供 继续/手动修复/退出 选择
+    Prompt,
+}
+
+/// 与混合构建失败的处理一致：先从错误信息中定位出错的 .rs 文件
+fn route_initial_failure(error_text: &str, feature: &str) -> InitialFailureRoute {
+    match crate::error_handler::parse_error_for_files(error_text, feature) {
+        Ok(files) if !files.is_empty() => InitialFailureRoute::RepairFiles(files),
+        _ => InitialFailureRoute::Prompt,
+    }
+}
 
 /// 从错误信息中提取失败的 .rs 文件并打开编辑器
 ///
@@ -27,14 +45,182 @@ fn open_failing_files_from_error(error_text: &str, feature: &str) -> Result<bool
         println!("│");
 
         let selected_file = interaction::prompt_file_selection_for_edit(&failing_files)?;
-        interaction::open_in_vim(&selected_file)?;
+        interaction::open_in_editor(&selected_file)?;
     } else {
-        interaction::open_in_vim(&failing_files[0])?;
+        interaction::open_in_editor(&failing_files[0])?;
     }
 
     Ok(true)
 }
 
+/// 检查 feature 名称是否像是输错了
+///
+/// `.c2rust/<feature>/` 已存在，或没有名称相近的已初始化 feature 时直接通过，随后照常进入初始化流程；
+/// 否则提示确认是否新建该 feature，未确认（或无法提示）时报错并给出最相近的名称。
+/// 要新建与已有名称相近的 feature 而无法交互时，可先手动创建 `.c2rust/<feature>/` 目录。
+pub fn check_feature_name(feature: &str) -> Result<()> {
+    util::validate_feature_name(feature)?;
+
+    let c2rust_dir = util::find_project_root()?.join(".c2rust");
+    let feature_path = c2rust_dir.join(feature);
+    if feature_path.exists() {
+        return Ok(());
+    }
+    let Some(closest) = closest_feature_name(feature, &list_initialized_features(&c2rust_dir))
+    else {
+        return Ok(());
+    };
+    if interaction::prompt_confirm_new_feature(feature, &closest)? {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Feature '{}' does not exist; did you mean '{}'? (to start a new feature with this name, create {} first)",
+        feature,
+        closest,
+        feature_path.display()
+    );
+}
+
+/// 指定 feature 名称的环境变量（未传 `--feature` 时使用）
+pub const FEATURE_ENV_VAR: &str = "C2RUST_FEATURE";
+
+/// 确定 `translate` 使用的 feature 名称
+///
+/// 优先级：`--feature` > 环境变量 `C2RUST_FEATURE` > config.toml（`config_path`，否则为
+/// `.c2rust/config.toml`）顶层的 `default_feature`。都未设置时列出 `.c2rust/` 下已初始化的 feature
+/// 并提示选择（`allow_prompt` 为 false 或无 TTY 时不提示）；还没有任何 feature 或其中有 `default`
+/// 时沿用 `default`，否则报错并列出可用的名称。
+pub fn resolve_feature_name(
+    cli_feature: Option<&str>,
+    config_path: Option<&Path>,
+    allow_prompt: bool,
+) -> Result<String> {
+    if let Some(feature) = cli_feature {
+        return Ok(feature.to_string());
+    }
+    let env_feature = std::env::var(FEATURE_ENV_VAR).ok();
+    let c2rust_dir = util::find_project_root()?.join(".c2rust");
+    let config_path = config_path.map_or_else(|| c2rust_dir.join("config.toml"), Path::to_path_buf);
+    let config_feature = read_default_feature(&config_path)?;
+
+    if let Some((feature, source)) =
+        configured_feature_name(env_feature.as_deref(), config_feature.as_deref())
+    {
+        util::validate_feature_name(&feature)
+            .with_context(|| format!("Invalid feature name from {}", source))?;
+        println!(
+            "{}",
+            format!("Using feature '{}' (from {})", feature, source).dimmed()
+        );
+        return Ok(feature);
+    }
+
+    let features = list_initialized_features(&c2rust_dir);
+    if features.is_empty() {
+        return Ok("default".to_string());
+    }
+    println!(
+        "{}",
+        format!(
+            "No --feature given (set {} or `default_feature` in {} to skip this step). Existing features:",
+            FEATURE_ENV_VAR,
+            config_path.display()
+        )
+        .cyan()
+    );
+    for feature in &features {
+        println!("  - {}", feature);
+    }
+    if allow_prompt {
+        if let Some(feature) = interaction::prompt_feature_selection(&features)? {
+            return Ok(feature);
+        }
+    }
+    if features.iter().any(|f| f == "default") {
+        return Ok("default".to_string());
+    }
+    anyhow::bail!(
+        "No feature selected; pass --feature <NAME> (available: {})",
+        features.join(", ")
+    );
+}
+
+/// 环境变量与配置中的 feature 名称（按优先级），返回名称及其来源说明；空白值视为未设置
+fn configured_feature_name(
+    env_feature: Option<&str>,
+    config_feature: Option<&str>,
+) -> Option<(String, &'static str)> {
+    let non_empty = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    non_empty(env_feature)
+        .map(|f| (f, FEATURE_ENV_VAR))
+        .or_else(|| non_empty(config_feature).map(|f| (f, "default_feature in config.toml")))
+}
+
+/// 读取 config.toml 顶层的 `default_feature`；文件不存在或未设置时返回 `None`
+fn read_default_feature(config_path: &Path) -> Result<Option<String>> {
+    if !config_path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let table: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+    match table.get("default_feature") {
+        None => Ok(None),
+        Some(toml::Value::String(feature)) => Ok(Some(feature.clone())),
+        Some(_) => anyhow::bail!(
+            "`default_feature` in {} must be a string",
+            config_path.display()
+        ),
+    }
+}
+
+/// `.c2rust` 下已初始化（包含 rust 目录）的 feature 名称，按名称排序
+fn list_initialized_features(c2rust_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(c2rust_dir) else {
+        return Vec::new();
+    };
+    let mut features: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("rust").is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    features.sort();
+    features
+}
+
+/// 与 `feature` 编辑距离最小的候选名称；距离超过名称长度的三分之一（至少为 1）时不算相近
+fn closest_feature_name(feature: &str, candidates: &[String]) -> Option<String> {
+    let max_distance = (feature.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(feature, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// 两个字符串之间的 Levenshtein 编辑距离（按字符计算）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 /// 检查并初始化 feature 目录
 ///
 /// 如果 rust 目录不存在，则初始化并提交
@@ -126,6 +312,31 @@ pub fn execute_initial_verification(feature: &str, show_full_output: bool, skip_
             Ok(())
         }
         Err(mut last_error) => {
+            if let InitialFailureRoute::RepairFiles(files) =
+                route_initial_failure(&format!("{:#}", last_error), feature)
+            {
+                println!(
+                    "{}",
+                    "✗ 初始化验证失败，已从错误信息中定位到出错文件"
+                        .red()
+                        .bold()
+                );
+                // 修复流程在构建和测试通过后返回，再执行一次检查以提交修复结果
+                crate::error_handler::handle_startup_test_failure_with_files(
+                    feature, last_error, files,
+                )?;
+                match crate::common_tasks::execute_code_error_check(
+                    feature,
+                    show_full_output,
+                    skip_test,
+                ) {
+                    Ok(_) => {
+                        println!("{}", "✓ 初始化验证完成并已提交".bright_green().bold());
+                        return Ok(());
+                    }
+                    Err(e) => last_error = e,
+                }
+            }
             loop {
                 println!("{}", "✗ 初始化验证失败！".red().bold());
                 println!();
@@ -208,4 +419,92 @@ mod tests {
 
         assert_signature(execute_initial_verification);
     }
+
+    #[test]
+    fn configured_feature_name_prefers_env_over_config() {
+        assert_eq!(
+            configured_feature_name(Some("net"), Some("parser")),
+            Some(("net".to_string(), FEATURE_ENV_VAR))
+        );
+        assert_eq!(
+            configured_feature_name(Some("  "), Some("parser")).map(|(f, _)| f),
+            Some("parser".to_string())
+        );
+        assert_eq!(configured_feature_name(None, None), None);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let config = tmp.path().join("config.toml");
+        assert_eq!(read_default_feature(&config).unwrap(), None);
+        std::fs::write(
+            &config,
+            "default_feature = \"net\"\n[test]\ncmd = \"make\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_default_feature(&config).unwrap(),
+            Some("net".to_string())
+        );
+        std::fs::write(&config, "default_feature = 1\n").unwrap();
+        assert!(read_default_feature(&config).is_err());
+    }
+
+    #[test]
+    fn closest_feature_name_suggests_initialized_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        for feature in ["network", "parser"] {
+            std::fs::create_dir_all(tmp.path().join(feature).join("rust")).unwrap();
+        }
+        // 未初始化的目录和隐藏目录不算已有 feature
+        std::fs::create_dir_all(tmp.path().join("networks")).unwrap();
+        std::fs::create_dir_all(tmp.path().join(".git").join("rust")).unwrap();
+        let features = list_initialized_features(tmp.path());
+        assert_eq!(features, vec!["network".to_string(), "parser".to_string()]);
+
+        assert_eq!(edit_distance("netwrok", "network"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(
+            closest_feature_name("netwrok", &features),
+            Some("network".to_string())
+        );
+        assert_eq!(
+            closest_feature_name("parsr", &features),
+            Some("parser".to_string())
+        );
+        // 差别较大的名称视为全新的 feature
+        assert_eq!(closest_feature_name("storage", &features), None);
+        assert_eq!(closest_feature_name("net", &features), None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn route_initial_failure_repairs_files_named_in_build_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let orig = std::env::current_dir().unwrap();
+        std::env::set_current_dir(tmp.path()).unwrap();
+        let _restore = scopeguard::guard(orig, |dir| {
+            let _ = std::env::set_current_dir(dir);
+        });
+
+        let feature = "test_feature";
+        let src_dir = tmp
+            .path()
+            .join(".c2rust")
+            .join(feature)
+            .join("rust")
+            .join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        let rs_file = src_dir.join("fun_foo.rs");
+        std::fs::write(&rs_file, "fn foo() {}").unwrap();
+
+        let build_error =
+            "error[E0425]: cannot find value `x` in this scope\n  --> src/fun_foo.rs:1:12";
+        assert_eq!(
+            route_initial_failure(build_error, feature),
+            InitialFailureRoute::RepairFiles(vec![rs_file.canonicalize().unwrap()])
+        );
+        assert_eq!(
+            route_initial_failure("error: linker `cc` not found", feature),
+            InitialFailureRoute::Prompt
+        );
+    }
 }
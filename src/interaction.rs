@@ -1,12 +1,14 @@
 //! 用于提示和收集输入的用户交互工具
 
+use crate::observer::{self, ChoiceKind, Decision, PromptKind};
+use crate::FailurePolicy;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use inquire::{Select, Text};
 use std::io::IsTerminal;
 use std::path::Path;
 use std::process::Command;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
 /// 全局自动接受模式标志
 static AUTO_ACCEPT_MODE: AtomicBool = AtomicBool::new(false);
@@ -27,12 +29,100 @@ pub fn enable_auto_accept_mode() {
     );
 }
 
-/// 禁用自动接受模式（仅用于测试）
-#[cfg(test)]
+/// 禁用自动接受模式（每次运行开始时按 `--interactive` 重新设置）
 pub fn disable_auto_accept_mode() {
     AUTO_ACCEPT_MODE.store(false, Ordering::Relaxed);
 }
 
+/// 全局禁止交互提示标志（`--interactive auto` 时启用）
+static PROMPTS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否禁止交互提示
+///
+/// 禁止后所有提示函数都按无 TTY 的默认行为处理，不再读取 stdin。
+pub fn set_prompts_disabled(disabled: bool) {
+    PROMPTS_DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+/// 检查是否禁止了交互提示
+pub fn are_prompts_disabled() -> bool {
+    PROMPTS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// 是否总是在编辑器中输入修复建议（`--suggestion-editor`）
+static SUGGESTION_EDITOR: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否总是在编辑器中输入修复建议
+pub fn set_suggestion_editor(enabled: bool) {
+    SUGGESTION_EDITOR.store(enabled, Ordering::Relaxed);
+}
+
+/// 在建议提示处输入该命令时改为在编辑器中编写建议
+const EDIT_SUGGESTION_COMMAND: &str = ":edit";
+
+/// 无法提示时的失败处理策略（`--on-failure`），在 `translate_feature` 开始时设置
+static FAILURE_POLICY: AtomicU8 = AtomicU8::new(0);
+
+/// 当前文件是否已用掉 `retry-once-then-abort` 的那次重试
+static FAILURE_RETRY_USED: AtomicBool = AtomicBool::new(false);
+
+/// 设置无法提示时的失败处理策略
+pub fn set_failure_policy(policy: FailurePolicy) {
+    let value = match policy {
+        FailurePolicy::Continue => 0,
+        FailurePolicy::Abort => 1,
+        FailurePolicy::RetryOnceThenAbort => 2,
+    };
+    FAILURE_POLICY.store(value, Ordering::Relaxed);
+}
+
+/// 当前的失败处理策略
+pub fn failure_policy() -> FailurePolicy {
+    match FAILURE_POLICY.load(Ordering::Relaxed) {
+        1 => FailurePolicy::Abort,
+        2 => FailurePolicy::RetryOnceThenAbort,
+        _ => FailurePolicy::Continue,
+    }
+}
+
+/// 开始处理新文件时重置 `retry-once-then-abort` 的重试次数
+pub fn reset_failure_retry() {
+    FAILURE_RETRY_USED.store(false, Ordering::Relaxed);
+}
+
+/// 无法提示时按 [`failure_policy`] 决定失败后的操作，不读取 stdin
+///
+/// `can_skip` / `can_retry` 表示当前提示是否提供跳过和重新翻译；策略要求的操作不可用时
+/// 按中止处理。手动修复（编辑器）在这种情况下永远不会被选择。
+fn failure_choice_without_tty(
+    can_skip: bool,
+    can_retry: bool,
+    skip_message: &str,
+) -> FailureChoice {
+    let choice = match failure_policy() {
+        FailurePolicy::Continue if can_skip => FailureChoice::Skip,
+        FailurePolicy::RetryOnceThenAbort
+            if can_retry && !FAILURE_RETRY_USED.swap(true, Ordering::Relaxed) =>
+        {
+            FailureChoice::RetryDirectly
+        }
+        _ => FailureChoice::Exit,
+    };
+    let message = match choice {
+        FailureChoice::Skip => skip_message.to_string(),
+        FailureChoice::RetryDirectly => format!(
+            "No TTY detected; --on-failure {}: retrying translation from scratch once.",
+            failure_policy()
+        ),
+        _ => format!(
+            "No TTY detected; --on-failure {}: aborting.",
+            failure_policy()
+        ),
+    };
+    println!("│ {}", message.yellow());
+    choice
+}
+
 /// 编译成功且测试通过时的用户选择
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CompileSuccessChoice {
@@ -75,8 +165,8 @@ pub enum TestConfigChoice {
     Continue, // 继续但跳过测试阶段
 }
 
-fn has_interactive_terminal() -> bool {
-    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+pub(crate) fn has_interactive_terminal() -> bool {
+    !are_prompts_disabled() && std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
 }
 
 fn default_compile_success_choice_without_tty(context: &str) -> CompileSuccessChoice {
@@ -91,6 +181,55 @@ fn default_compile_success_choice_without_tty(context: &str) -> CompileSuccessCh
     CompileSuccessChoice::Accept
 }
 
+/// 注册了观察者（[`crate::observer::observe`]）时先询问它的决定；`offered` 为本次提示提供的选项
+fn observer_failure_choice(
+    kind: PromptKind,
+    context: &str,
+    offered: &[FailureChoice],
+) -> Option<FailureChoice> {
+    let allowed: Vec<Decision> = offered
+        .iter()
+        .map(|choice| match choice {
+            FailureChoice::RetryDirectly => Decision::RetryDirectly,
+            FailureChoice::AddSuggestion => Decision::AddSuggestion,
+            FailureChoice::ManualFix => Decision::ManualFix,
+            FailureChoice::Skip => Decision::Skip,
+            FailureChoice::FixOtherFile => Decision::FixOtherFile,
+            FailureChoice::Exit => Decision::Exit,
+        })
+        .collect();
+    let decision = observer::decide(kind, context, &allowed)?;
+    offered
+        .iter()
+        .zip(&allowed)
+        .find(|(_, allowed)| **allowed == decision)
+        .map(|(choice, _)| *choice)
+}
+
+/// 注册了观察者时先询问它的单项选择，返回选中项在 `options` 中的下标
+fn observer_choice_index(kind: ChoiceKind, message: &str, options: &[&str]) -> Option<usize> {
+    let options: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+    observer::choose(kind, message, &options, false).map(|picked| picked[0])
+}
+
+/// 注册了观察者时先询问它的 y/N 确认（选项为 "Yes" / "No"）
+fn observer_confirm(kind: ChoiceKind, message: &str) -> Option<bool> {
+    observer_choice_index(kind, message, &["Yes", "No"]).map(|index| index == 0)
+}
+
+/// 在终端用 `Select` 选择一项，返回选中项在 `options` 中的下标
+fn select_index(message: &str, options: &[&str]) -> Result<usize> {
+    let choice = Select::new(message, options.to_vec())
+        .with_vim_mode(true)
+        .prompt()
+        .context("Failed to get user selection")?;
+
+    options
+        .iter()
+        .position(|&o| o == choice)
+        .context("Unexpected selection value")
+}
+
 /// 统一的失败场景提示函数
 ///
 /// 在失败时提示并返回 ManualFix/Skip/Exit（上下文仅用于展示提示信息）
@@ -103,21 +242,29 @@ pub fn prompt_failure_choice(context: &str) -> Result<FailureChoice> {
     println!("│");
 
     let options = vec![
-        "手动修复（使用 VIM 编辑文件）",
+        "手动修复（使用编辑器编辑文件）",
         "跳过（忽略失败继续）",
         "退出（中止流程）",
     ];
 
+    if let Some(choice) = observer_failure_choice(
+        PromptKind::Failure,
+        context,
+        &[
+            FailureChoice::ManualFix,
+            FailureChoice::Skip,
+            FailureChoice::Exit,
+        ],
+    ) {
+        return Ok(choice);
+    }
+
     if !has_interactive_terminal() {
-        println!(
-            "│ {}",
-            format!(
-                "检测到当前会话无 TTY，无法弹出交互式选择；默认执行“跳过”并继续记录失败现场。上下文：{}",
-                context
-            )
-            .yellow()
+        let skip_message = format!(
+            "检测到当前会话无 TTY，无法弹出交互式选择；默认执行“跳过”并继续记录失败现场。上下文：{}",
+            context
         );
-        return Ok(FailureChoice::Skip);
+        return Ok(failure_choice_without_tty(true, false, &skip_message));
     }
 
     let choice = Select::new("请选择处理方式:", options.clone())
@@ -150,11 +297,32 @@ pub fn prompt_after_manual_fix_choice() -> Result<FailureChoice> {
     println!("│");
 
     let options = vec![
-        "重新手动修复（再次打开 VIM）",
+        "重新手动修复（再次打开编辑器）",
         "修复其他文件（跳过当前文件）",
         "退出（中止流程）",
     ];
 
+    if let Some(choice) = observer_failure_choice(
+        PromptKind::AfterManualFix,
+        "Still failing after manual fix",
+        &[
+            FailureChoice::ManualFix,
+            FailureChoice::FixOtherFile,
+            FailureChoice::Exit,
+        ],
+    ) {
+        return Ok(choice);
+    }
+
+    if !has_interactive_terminal() {
+        return Ok(
+            match failure_choice_without_tty(true, false, "No TTY detected; skipping this file.") {
+                FailureChoice::Skip => FailureChoice::FixOtherFile,
+                choice => choice,
+            },
+        );
+    }
+
     let choice = Select::new("请选择处理方式:", options.clone())
         .with_vim_mode(true)
         .prompt()
@@ -176,7 +344,33 @@ pub fn prompt_after_manual_fix_choice() -> Result<FailureChoice> {
 
 /// 如果 require_input 为 true，用户必须提供非空输入
 pub fn prompt_suggestion(require_input: bool) -> Result<Option<String>> {
+    if let Some(suggestion) = observer::suggestion(require_input) {
+        return Ok(suggestion);
+    }
+
+    if !has_interactive_terminal() {
+        println!(
+            "│ {}",
+            "No TTY detected; no suggestion can be entered.".yellow()
+        );
+        return Ok(None);
+    }
+
     loop {
+        if SUGGESTION_EDITOR.load(Ordering::Relaxed) {
+            match edit_suggestion_in_editor()? {
+                Some(suggestion) => return Ok(Some(suggestion)),
+                None if require_input => {
+                    println!("│ {}", "Error: A suggestion is required to continue.".red());
+                    continue;
+                }
+                None => {
+                    println!("│ {}", "No suggestion provided.".yellow());
+                    return Ok(None);
+                }
+            }
+        }
+
         println!("│");
         println!(
             "│ {}",
@@ -200,7 +394,10 @@ pub fn prompt_suggestion(require_input: bool) -> Result<Option<String>> {
         // including Delete key (\x1b[3~), Backspace, arrow keys, etc.
         let prompt_text = "│ Suggestion: ";
         let text_input = Text::new(prompt_text)
-            .with_help_message("Use Delete/Backspace to edit, Enter to submit")
+            .with_help_message(&format!(
+                "Use Delete/Backspace to edit, Enter to submit, {} for a multi-line suggestion",
+                EDIT_SUGGESTION_COMMAND
+            ))
             .prompt();
 
         let suggestion = match text_input {
@@ -213,7 +410,14 @@ pub fn prompt_suggestion(require_input: bool) -> Result<Option<String>> {
             Err(e) => return Err(e.into()),
         };
 
-        let trimmed = suggestion.trim().to_string();
+        let mut trimmed = suggestion.trim().to_string();
+
+        if trimmed == EDIT_SUGGESTION_COMMAND {
+            match edit_suggestion_in_editor()? {
+                Some(edited) => return Ok(Some(edited)),
+                None => trimmed.clear(),
+            }
+        }
 
         if trimmed.is_empty() {
             if require_input {
@@ -234,36 +438,150 @@ pub fn prompt_suggestion(require_input: bool) -> Result<Option<String>> {
     }
 }
 
-/// 在 vim 中打开文件进行手动编辑
-pub fn open_in_vim(file_path: &Path) -> Result<()> {
+/// 编辑器中建议模板的说明（以 `#` 开头的行读回时会被去掉）
+const SUGGESTION_TEMPLATE: &str = "\n\
+# Enter your fix suggestion above. Lines starting with '#' are ignored.\n\
+# Save and close the editor to submit; leave it empty to skip.\n";
+
+/// 在编辑器中打开临时文件编写建议，返回去掉注释行后的内容（为空时返回 `None`）
+fn edit_suggestion_in_editor() -> Result<Option<String>> {
+    let file = tempfile::Builder::new()
+        .prefix("c2rust-suggestion-")
+        .suffix(".txt")
+        .tempfile()
+        .context("Failed to create temporary suggestion file")?;
+    std::fs::write(file.path(), SUGGESTION_TEMPLATE)
+        .with_context(|| format!("Failed to write {}", file.path().display()))?;
+
+    launch_editor(file.path())?;
+
+    let content = std::fs::read_to_string(file.path())
+        .with_context(|| format!("Failed to read {}", file.path().display()))?;
+    let suggestion = strip_comment_lines(&content);
+    if suggestion.is_empty() {
+        return Ok(None);
+    }
+
+    println!("│ {}", "✓ Suggestion recorded:".bright_green());
+    for line in suggestion.lines() {
+        println!("│   {}", line.bright_green());
+    }
+    Ok(Some(suggestion))
+}
+
+/// 去掉以 `#` 开头的注释行及首尾空白
+fn strip_comment_lines(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// 未设置 `$VISUAL` / `$EDITOR` 时使用的编辑器
+const DEFAULT_EDITOR: &str = "vim";
+
+/// 根据 `$VISUAL`、`$EDITOR` 的取值解析编辑器命令（程序及其参数）
+///
+/// 优先使用 `$VISUAL`，其次 `$EDITOR`，都未设置（或为空）时回退到 vim。
+/// 对 VS Code 自动追加 `--wait`，否则编辑器会立即返回，构建流程会在用户保存前继续。
+pub(crate) fn resolve_editor_command(
+    visual: Option<&str>,
+    editor: Option<&str>,
+) -> Result<Vec<String>> {
+    let configured = [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_EDITOR);
+
+    let mut parts = shell_words::split(configured)
+        .with_context(|| format!("Failed to parse editor command '{}'", configured))?;
+    if parts.is_empty() {
+        parts.push(DEFAULT_EDITOR.to_string());
+    }
+
+    if is_vscode(&parts[0])
+        && !parts
+            .iter()
+            .skip(1)
+            .any(|arg| arg == "--wait" || arg == "-w")
+    {
+        parts.push("--wait".to_string());
+    }
+
+    Ok(parts)
+}
+
+/// 判断编辑器程序是否为 VS Code（含 Insiders 与 VSCodium）
+fn is_vscode(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(program);
+    matches!(name, "code" | "code-insiders" | "codium")
+}
+
+/// 在用户配置的编辑器（`$VISUAL` / `$EDITOR`，默认 vim）中打开文件进行手动编辑
+pub fn open_in_editor(file_path: &Path) -> Result<()> {
+    launch_editor(file_path)?;
+    println!("│ {}", "✓ File editing complete".bright_green());
+    crate::report::note_manual_edit();
+    Ok(())
+}
+
+/// 启动编辑器打开文件并等待其退出
+fn launch_editor(file_path: &Path) -> Result<()> {
+    let visual = std::env::var("VISUAL").ok();
+    let editor = std::env::var("EDITOR").ok();
+    let parts = resolve_editor_command(visual.as_deref(), editor.as_deref())?;
+    let editor_display = shell_words::join(&parts);
+
     println!("│");
     println!(
         "│ {}",
-        format!("Opening {} in vim...", file_path.display()).bright_cyan()
+        format!("Opening {} in {}...", file_path.display(), editor_display).bright_cyan()
     );
 
-    let status = Command::new("vim")
+    let status = match Command::new(&parts[0])
+        .args(&parts[1..])
         .arg(file_path)
         .status()
-        .context("Failed to open vim")?;
-
-    if status.success() {
-        println!("│ {}", "✓ File editing complete".bright_green());
-    } else {
-        println!("│ {}", "⚠ vim exited with non-zero status".yellow());
+    {
+        Ok(status) => status,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!(
+                "Editor '{}' not found. Set $VISUAL or $EDITOR to an installed editor",
+                parts[0]
+            );
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to launch editor '{}'", editor_display));
+        }
+    };
+
+    if !status.success() {
+        anyhow::bail!(
+            "Editor '{}' exited with non-zero status ({}) while editing {}",
+            editor_display,
+            status,
+            file_path.display()
+        );
     }
-
     Ok(())
 }
 
 /// 为手动修复打开一个或多个文件
 ///
-/// 如果只有一个文件，直接在 vim 中打开（保持原有行为）。
+/// 如果只有一个文件，直接在编辑器中打开（保持原有行为）。
 /// 如果有多个文件，展示文件选择列表供用户选择，然后打开所选文件。
 pub fn open_files_for_manual_fix(files: &[std::path::PathBuf]) -> Result<()> {
     match files.len() {
         0 => anyhow::bail!("No files provided for manual fix"),
-        1 => open_in_vim(&files[0]),
+        1 => open_in_editor(&files[0]),
         _ => {
             println!("│");
             println!(
@@ -276,7 +594,7 @@ pub fn open_files_for_manual_fix(files: &[std::path::PathBuf]) -> Result<()> {
                 .bold()
             );
             let selected = prompt_file_selection_for_edit(files)?;
-            open_in_vim(&selected)
+            open_in_editor(&selected)
         }
     }
 }
@@ -328,6 +646,10 @@ pub fn prompt_file_selection_for_edit(
         .map(|(i, f)| format!("{}: {}", i + 1, f.display()))
         .collect();
 
+    if let Some(picked) = observer::choose(ChoiceKind::FileToEdit, "文件:", &options, false) {
+        return Ok(files[picked[0]].clone());
+    }
+
     let selection = inquire::Select::new("文件:", options)
         .with_vim_mode(true)
         .prompt()
@@ -355,6 +677,17 @@ pub fn display_file_paths(c_file: Option<&Path>, rust_file: &Path) {
     println!("│");
 }
 
+/// 接受提示中第 `index` 个选项对应的选择（三个接受提示的选项顺序相同）
+fn compile_success_choice_at(index: usize) -> CompileSuccessChoice {
+    match index {
+        0 => CompileSuccessChoice::Accept,
+        1 => CompileSuccessChoice::AutoAccept,
+        2 => CompileSuccessChoice::ManualFix,
+        3 => CompileSuccessChoice::Exit,
+        _ => unreachable!("Invalid selection index"),
+    }
+}
+
 /// 编译成功且测试通过时提示用户
 pub fn prompt_compile_success_choice() -> Result<CompileSuccessChoice> {
     println!("│");
@@ -367,33 +700,28 @@ pub fn prompt_compile_success_choice() -> Result<CompileSuccessChoice> {
     let options = vec![
         "Accept this code (will be committed)",
         "Auto-accept all subsequent translations",
-        "Manual fix (edit the file with VIM)",
+        "Manual fix (edit the file in your editor)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(index) = observer_choice_index(
+        ChoiceKind::CompileSuccess,
+        "What would you like to do?",
+        &options,
+    ) {
+        return Ok(compile_success_choice_at(index));
+    }
+
     if !has_interactive_terminal() {
         return Ok(default_compile_success_choice_without_tty(
             "accepting this translation",
         ));
     }
 
-    let choice = Select::new("What would you like to do?", options.clone())
-        .with_vim_mode(true)
-        .prompt()
-        .context("Failed to get user selection")?;
-
-    let choice_index = options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?;
-
-    match choice_index {
-        0 => Ok(CompileSuccessChoice::Accept),
-        1 => Ok(CompileSuccessChoice::AutoAccept),
-        2 => Ok(CompileSuccessChoice::ManualFix),
-        3 => Ok(CompileSuccessChoice::Exit),
-        _ => unreachable!("Invalid selection index"),
-    }
+    Ok(compile_success_choice_at(select_index(
+        "What would you like to do?",
+        &options,
+    )?))
 }
 
 /// 构建成功但测试阶段被跳过时提示用户
@@ -417,33 +745,28 @@ pub fn prompt_build_success_tests_skipped_choice() -> Result<CompileSuccessChoic
     let options = vec![
         "Accept this code (will be committed)",
         "Auto-accept all subsequent translations",
-        "Manual fix (edit the file with VIM)",
+        "Manual fix (edit the file in your editor)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(index) = observer_choice_index(
+        ChoiceKind::BuildSuccessTestsSkipped,
+        "What would you like to do?",
+        &options,
+    ) {
+        return Ok(compile_success_choice_at(index));
+    }
+
     if !has_interactive_terminal() {
         return Ok(default_compile_success_choice_without_tty(
             "accepting this translation with skipped tests",
         ));
     }
 
-    let choice = Select::new("What would you like to do?", options.clone())
-        .with_vim_mode(true)
-        .prompt()
-        .context("Failed to get user selection")?;
-
-    let choice_index = options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?;
-
-    match choice_index {
-        0 => Ok(CompileSuccessChoice::Accept),
-        1 => Ok(CompileSuccessChoice::AutoAccept),
-        2 => Ok(CompileSuccessChoice::ManualFix),
-        3 => Ok(CompileSuccessChoice::Exit),
-        _ => unreachable!("Invalid selection index"),
-    }
+    Ok(compile_success_choice_at(select_index(
+        "What would you like to do?",
+        &options,
+    )?))
 }
 
 /// 构建成功但测试被 `C2RUST_TEST_INTERVAL` 推迟时提示用户
@@ -467,33 +790,28 @@ pub fn prompt_build_success_tests_deferred_choice() -> Result<CompileSuccessChoi
     let options = vec![
         "Accept this code (will be committed)",
         "Auto-accept all subsequent translations",
-        "Manual fix (edit the file with VIM, then run full build & tests)",
+        "Manual fix (edit the file in your editor, then run full build & tests)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(index) = observer_choice_index(
+        ChoiceKind::BuildSuccessTestsDeferred,
+        "What would you like to do?",
+        &options,
+    ) {
+        return Ok(compile_success_choice_at(index));
+    }
+
     if !has_interactive_terminal() {
         return Ok(default_compile_success_choice_without_tty(
             "accepting this translation with deferred tests",
         ));
     }
 
-    let choice = Select::new("What would you like to do?", options.clone())
-        .with_vim_mode(true)
-        .prompt()
-        .context("Failed to get user selection")?;
-
-    let choice_index = options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?;
-
-    match choice_index {
-        0 => Ok(CompileSuccessChoice::Accept),
-        1 => Ok(CompileSuccessChoice::AutoAccept),
-        2 => Ok(CompileSuccessChoice::ManualFix),
-        3 => Ok(CompileSuccessChoice::Exit),
-        _ => unreachable!("Invalid selection index"),
-    }
+    Ok(compile_success_choice_at(select_index(
+        "What would you like to do?",
+        &options,
+    )?))
 }
 
 /// 测试失败时提示用户选择下一步操作
@@ -510,10 +828,28 @@ pub fn prompt_test_failure_choice() -> Result<FailureChoice> {
     let options = vec![
         "Retry directly (⚠ Will clear .rs file, re-translate from C, and clear suggestions)",
         "Add fix suggestion for AI to modify",
-        "Manual fix (edit the file with VIM)",
+        "Manual fix (edit the file in your editor)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(choice) = observer_failure_choice(
+        PromptKind::TestFailure,
+        "Tests failed",
+        &[
+            FailureChoice::RetryDirectly,
+            FailureChoice::AddSuggestion,
+            FailureChoice::ManualFix,
+            FailureChoice::Exit,
+        ],
+    ) {
+        return Ok(choice);
+    }
+
+    if !has_interactive_terminal() {
+        // Test failures cannot be skipped, so `continue` aborts here as well.
+        return Ok(failure_choice_without_tty(false, true, ""));
+    }
+
     let choice = Select::new("Select an option:", options.clone())
         .with_vim_mode(true)
         .prompt()
@@ -547,17 +883,31 @@ pub fn prompt_compile_failure_choice() -> Result<FailureChoice> {
     let options = vec![
         "Retry directly (⚠ Will clear .rs file, re-translate from C, and clear suggestions)",
         "Add fix suggestion for AI to modify",
-        "Manual fix (edit the file with VIM)",
+        "Manual fix (edit the file in your editor)",
         "Skip this file (process later)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(choice) = observer_failure_choice(
+        PromptKind::CompileFailure,
+        "Compilation failed",
+        &[
+            FailureChoice::RetryDirectly,
+            FailureChoice::AddSuggestion,
+            FailureChoice::ManualFix,
+            FailureChoice::Skip,
+            FailureChoice::Exit,
+        ],
+    ) {
+        return Ok(choice);
+    }
+
     if !has_interactive_terminal() {
-        println!(
-            "│ {}",
-            "No TTY detected; defaulting to Skip and continuing.".yellow()
-        );
-        return Ok(FailureChoice::Skip);
+        return Ok(failure_choice_without_tty(
+            true,
+            true,
+            "No TTY detected; defaulting to Skip and continuing.",
+        ));
     }
 
     let choice = Select::new("Select an option:", options.clone())
@@ -592,16 +942,29 @@ pub fn prompt_build_failure_choice() -> Result<FailureChoice> {
     let options = vec![
         "Retry directly (⚠ Will clear .rs file, re-translate from C, and clear suggestions)",
         "Add fix suggestion for AI to modify",
-        "Manual fix (edit the file with VIM)",
+        "Manual fix (edit the file in your editor)",
         "Exit (abort the translation process)",
     ];
 
+    if let Some(choice) = observer_failure_choice(
+        PromptKind::BuildFailure,
+        "Build failed",
+        &[
+            FailureChoice::RetryDirectly,
+            FailureChoice::AddSuggestion,
+            FailureChoice::ManualFix,
+            FailureChoice::Exit,
+        ],
+    ) {
+        return Ok(choice);
+    }
+
     if !has_interactive_terminal() {
-        println!(
-            "│ {}",
-            "No TTY detected; defaulting to Skip and continuing.".yellow()
-        );
-        return Ok(FailureChoice::Skip);
+        return Ok(failure_choice_without_tty(
+            true,
+            true,
+            "No TTY detected; defaulting to Skip and continuing.",
+        ));
     }
 
     let choice = Select::new("Select an option:", options.clone())
@@ -647,15 +1010,18 @@ pub fn prompt_skipped_files_choice(skipped_files: &[String]) -> Result<SkippedFi
 
     let options = vec!["Process skipped files now", "Exit and process them later"];
 
-    let choice = Select::new("Select an option:", options.clone())
-        .with_vim_mode(true)
-        .prompt()
-        .context("Failed to get user selection")?;
-
-    let choice_index = options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?;
+    let choice_index =
+        match observer_choice_index(ChoiceKind::SkippedFiles, "Select an option:", &options) {
+            Some(index) => index,
+            None if !has_interactive_terminal() => {
+                println!(
+                    "{}",
+                    "No TTY detected; leaving skipped files for a later run by default.".yellow()
+                );
+                return Ok(SkippedFilesChoice::ExitForLater);
+            }
+            None => select_index("Select an option:", &options)?,
+        };
 
     match choice_index {
         0 => Ok(SkippedFilesChoice::ProcessNow),
@@ -679,28 +1045,130 @@ fn build_resume_options(has_skipped_files: bool) -> Vec<&'static str> {
 pub fn prompt_continue_or_restart(has_skipped_files: bool) -> Result<ContinueChoice> {
     let options = build_resume_options(has_skipped_files);
 
-    if !has_interactive_terminal() {
+    let choice_index = match observer_choice_index(
+        ChoiceKind::ContinueOrRestart,
+        "What would you like to do?",
+        &options,
+    ) {
+        Some(index) => index,
+        None if !has_interactive_terminal() => {
+            println!(
+                "{}",
+                "No TTY detected; continuing previous progress by default.".yellow()
+            );
+            return Ok(ContinueChoice::Continue);
+        }
+        None => select_index("What would you like to do?", &options)?,
+    };
+
+    match choice_index {
+        0 => Ok(ContinueChoice::Continue),
+        1 => Ok(ContinueChoice::Restart),
+        2 if has_skipped_files => Ok(ContinueChoice::FixSkippedFiles),
+        _ => unreachable!(),
+    }
+}
+
+/// 确认 `--confirm` 打印的运行计划（y/N，默认 N）
+///
+/// 无 TTY 或自动接受模式下不提示，直接视为确认。
+pub fn prompt_confirm_run_plan() -> Result<bool> {
+    if !is_auto_accept_mode() {
+        if let Some(confirmed) =
+            observer_confirm(ChoiceKind::ConfirmRunPlan, "Proceed with this plan?")
+        {
+            return Ok(confirmed);
+        }
+    }
+    if is_auto_accept_mode() || !has_interactive_terminal() {
         println!(
             "{}",
-            "No TTY detected; continuing previous progress by default.".yellow()
+            "No TTY or auto-accept mode; proceeding with the plan.".yellow()
         );
-        return Ok(ContinueChoice::Continue);
+        return Ok(true);
     }
 
-    let choice = Select::new("What would you like to do?", options.clone())
-        .with_vim_mode(true)
+    match inquire::Confirm::new("Proceed with this plan?")
+        .with_default(false)
         .prompt()
-        .context("Failed to get user selection")?;
+    {
+        Ok(confirmed) => Ok(confirmed),
+        Err(inquire::InquireError::OperationCanceled) => Ok(false),
+        Err(e) => Err(e).context("Failed to read plan confirmation"),
+    }
+}
 
-    match options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?
+/// feature 不存在但有名称相近的已有 feature 时，确认是否作为新 feature 初始化（y/N，默认 N）
+///
+/// 无 TTY 或自动接受模式下不提示，视为未确认。
+pub fn prompt_confirm_new_feature(feature: &str, closest: &str) -> Result<bool> {
+    if is_auto_accept_mode() {
+        return Ok(false);
+    }
+    let message = format!(
+        "Feature '{}' does not exist (did you mean '{}'?). Initialize it as a new feature?",
+        feature, closest
+    );
+    if let Some(confirmed) = observer_confirm(ChoiceKind::ConfirmNewFeature, &message) {
+        return Ok(confirmed);
+    }
+    if !has_interactive_terminal() {
+        return Ok(false);
+    }
+
+    match inquire::Confirm::new(&message).with_default(false).prompt() {
+        Ok(confirmed) => Ok(confirmed),
+        Err(inquire::InquireError::OperationCanceled) => Ok(false),
+        Err(e) => Err(e).context("Failed to read new feature confirmation"),
+    }
+}
+
+/// C 文件超过 `--max-c-file-bytes` 时确认是否仍然翻译（y/N，默认 N）
+///
+/// 无 TTY 或自动接受模式下不提示，视为跳过。
+pub fn prompt_translate_oversized_file(size: u64, limit: u64) -> Result<bool> {
+    if is_auto_accept_mode() {
+        return Ok(false);
+    }
+    let message = format!(
+        "The C file is {} bytes (limit {} bytes). Translate it anyway?",
+        size, limit
+    );
+    if let Some(confirmed) = observer_confirm(ChoiceKind::TranslateOversizedFile, &message) {
+        return Ok(confirmed);
+    }
+    if !has_interactive_terminal() {
+        return Ok(false);
+    }
+
+    match inquire::Confirm::new(&message).with_default(false).prompt() {
+        Ok(confirmed) => Ok(confirmed),
+        Err(inquire::InquireError::OperationCanceled) => Ok(false),
+        Err(e) => Err(e).context("Failed to read oversized file confirmation"),
+    }
+}
+
+/// 未指定 feature 时提示从已有的 feature 中选择
+///
+/// 无 TTY 或自动接受模式下不提示，返回 `None`；取消选择时同样返回 `None`。
+pub fn prompt_feature_selection(features: &[String]) -> Result<Option<String>> {
+    if is_auto_accept_mode() {
+        return Ok(None);
+    }
+    if let Some(picked) = observer::choose(ChoiceKind::Feature, "Feature:", features, false) {
+        return Ok(Some(features[picked[0]].clone()));
+    }
+    if !has_interactive_terminal() {
+        return Ok(None);
+    }
+
+    match inquire::Select::new("Feature:", features.to_vec())
+        .with_vim_mode(true)
+        .prompt()
     {
-        0 => Ok(ContinueChoice::Continue),
-        1 => Ok(ContinueChoice::Restart),
-        2 if has_skipped_files => Ok(ContinueChoice::FixSkippedFiles),
-        _ => unreachable!(),
+        Ok(feature) => Ok(Some(feature)),
+        Err(inquire::InquireError::OperationCanceled) => Ok(None),
+        Err(e) => Err(e).context("Failed to read feature selection"),
     }
 }
 
@@ -731,23 +1199,18 @@ pub fn prompt_test_config_missing_choice() -> Result<TestConfigChoice> {
         "Continue without test phase (tests will be skipped)",
     ];
 
-    if !has_interactive_terminal() {
-        println!(
-            "{}",
-            "No TTY detected; continuing without test phase by default.".yellow()
-        );
-        return Ok(TestConfigChoice::Continue);
-    }
-
-    let choice = Select::new("Select an option:", options.clone())
-        .with_vim_mode(true)
-        .prompt()
-        .context("Failed to get user selection")?;
-
-    let choice_index = options
-        .iter()
-        .position(|&o| o == choice)
-        .context("Unexpected selection value")?;
+    let choice_index =
+        match observer_choice_index(ChoiceKind::TestConfigMissing, "Select an option:", &options) {
+            Some(index) => index,
+            None if !has_interactive_terminal() => {
+                println!(
+                    "{}",
+                    "No TTY detected; continuing without test phase by default.".yellow()
+                );
+                return Ok(TestConfigChoice::Continue);
+            }
+            None => select_index("Select an option:", &options)?,
+        };
 
     match choice_index {
         0 => Ok(TestConfigChoice::Exit),
@@ -761,6 +1224,65 @@ mod tests {
     use super::*;
     use serial_test::serial;
 
+    #[test]
+    fn test_strip_comment_lines_keeps_multi_line_suggestion() {
+        let content = format!(
+            "Use wrapping_add for the counter.\n  # not this\n\nKeep the signature.\n{}",
+            SUGGESTION_TEMPLATE
+        );
+        assert_eq!(
+            strip_comment_lines(&content),
+            "Use wrapping_add for the counter.\n\nKeep the signature."
+        );
+        assert_eq!(strip_comment_lines(SUGGESTION_TEMPLATE), "");
+    }
+
+    #[test]
+    fn test_resolve_editor_command_prefers_visual_then_editor() {
+        assert_eq!(
+            resolve_editor_command(Some("nvim"), Some("hx")).unwrap(),
+            vec!["nvim"]
+        );
+        assert_eq!(
+            resolve_editor_command(None, Some("hx")).unwrap(),
+            vec!["hx"]
+        );
+        assert_eq!(
+            resolve_editor_command(Some("  "), Some("hx")).unwrap(),
+            vec!["hx"]
+        );
+        assert_eq!(resolve_editor_command(None, None).unwrap(), vec!["vim"]);
+    }
+
+    #[test]
+    fn test_resolve_editor_command_splits_arguments() {
+        assert_eq!(
+            resolve_editor_command(Some("emacs -nw"), None).unwrap(),
+            vec!["emacs", "-nw"]
+        );
+        assert!(resolve_editor_command(Some("vim 'unterminated"), None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_editor_command_adds_wait_for_vscode() {
+        assert_eq!(
+            resolve_editor_command(None, Some("code")).unwrap(),
+            vec!["code", "--wait"]
+        );
+        assert_eq!(
+            resolve_editor_command(None, Some("/usr/local/bin/code-insiders")).unwrap(),
+            vec!["/usr/local/bin/code-insiders", "--wait"]
+        );
+        assert_eq!(
+            resolve_editor_command(Some("code -w"), None).unwrap(),
+            vec!["code", "-w"]
+        );
+        assert_eq!(
+            resolve_editor_command(Some("code --wait --new-window"), None).unwrap(),
+            vec!["code", "--wait", "--new-window"]
+        );
+    }
+
     #[test]
     fn test_compile_success_choice_variants() {
         assert_eq!(CompileSuccessChoice::Accept, CompileSuccessChoice::Accept);
@@ -940,4 +1462,47 @@ mod tests {
     fn test_parse_file_option_out_of_bounds() {
         assert!(parse_file_option("5: /a/foo.rs", 3).is_err());
     }
+
+    #[test]
+    #[serial]
+    fn test_failure_policy_choices_without_tty() {
+        let _reset = scopeguard::guard((), |_| set_failure_policy(FailurePolicy::Continue));
+
+        set_failure_policy(FailurePolicy::Continue);
+        assert_eq!(
+            failure_choice_without_tty(true, true, "skip"),
+            FailureChoice::Skip
+        );
+        assert_eq!(
+            failure_choice_without_tty(false, true, ""),
+            FailureChoice::Exit
+        );
+
+        set_failure_policy(FailurePolicy::Abort);
+        assert_eq!(
+            failure_choice_without_tty(true, true, "skip"),
+            FailureChoice::Exit
+        );
+
+        set_failure_policy(FailurePolicy::RetryOnceThenAbort);
+        reset_failure_retry();
+        assert_eq!(
+            failure_choice_without_tty(true, true, "skip"),
+            FailureChoice::RetryDirectly
+        );
+        assert_eq!(
+            failure_choice_without_tty(true, true, "skip"),
+            FailureChoice::Exit
+        );
+        // Each file gets its own retry; prompts without a retry option abort.
+        reset_failure_retry();
+        assert_eq!(
+            failure_choice_without_tty(true, false, "skip"),
+            FailureChoice::Exit
+        );
+        assert_eq!(
+            failure_choice_without_tty(false, true, ""),
+            FailureChoice::RetryDirectly
+        );
+    }
 }
@@ -0,0 +1,174 @@
+//! Ctrl-C 处理：第一次请求在当前文件完成后停止，第二次立即中止
+//!
+//! [`install_handler`] 安装 SIGINT 处理函数后，第一次 Ctrl-C 只设置“请求停止”标志，
+//! 翻译循环在文件之间检查 [`stop_requested`]，当前文件完成（并提交、保存进度）后正常退出。
+//! 第二次 Ctrl-C 终止所有登记的子进程组（cargo、翻译脚本等）并以退出码 130 立即退出。
+//! 立即退出时不执行任何清理：当前文件的进度不保存（下次运行重新处理该文件），
+//! `--commit-every` 尚未提交的批次也不提交，这些文件的改动留在 `.c2rust/` 工作区中，
+//! 随下一次提交一并提交；运行锁由操作系统在进程退出时释放。
+//!
+//! 构建命令、翻译/修复脚本以及 git、rustfmt、code_analyse、c2rust-config（见 [`output`]）
+//! 运行在独立的进程组中，因此终端的 Ctrl-C 不会直接打断它们。
+
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 是否已请求停止（收到过一次 Ctrl-C）
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// 是否已安装处理函数；未安装时（作为库嵌入）子进程的进程组行为保持不变
+static HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// 是否已请求在当前文件完成后停止
+pub fn stop_requested() -> bool {
+    STOP_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// 清除上一次运行留下的停止请求（每次运行开始时调用）
+pub(crate) fn clear_stop_request() {
+    STOP_REQUESTED.store(false, Ordering::SeqCst);
+}
+
+/// 模拟第一次 Ctrl-C（仅用于测试）
+#[cfg(test)]
+pub(crate) fn request_stop() {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// 是否已通过 [`install_handler`] 接管 Ctrl-C
+pub fn handler_installed() -> bool {
+    HANDLER_INSTALLED.load(Ordering::SeqCst)
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{HANDLER_INSTALLED, STOP_REQUESTED};
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    /// 正在运行的子进程组（0 表示空槽位）；信号处理函数中只能访问原子变量
+    pub(super) static CHILD_PGIDS: [AtomicI32; 16] = [const { AtomicI32::new(0) }; 16];
+
+    const FIRST_MESSAGE: &[u8] =
+        b"\nStop requested: finishing the current file, then exiting (press Ctrl-C again to abort now)\n";
+    const ABORT_MESSAGE: &[u8] = b"\nAborted.\n";
+
+    extern "C" fn handle_sigint(_signal: libc::c_int) {
+        // 只调用异步信号安全的函数（write、kill、_exit）
+        if STOP_REQUESTED.swap(true, Ordering::SeqCst) {
+            for slot in CHILD_PGIDS.iter() {
+                let pgid = slot.load(Ordering::SeqCst);
+                if pgid > 0 {
+                    // SAFETY: 只向我们自己创建的进程组发送信号
+                    unsafe { libc::kill(-pgid, libc::SIGKILL) };
+                }
+            }
+            // SAFETY: write 与 _exit 都是异步信号安全的
+            unsafe {
+                libc::write(2, ABORT_MESSAGE.as_ptr().cast(), ABORT_MESSAGE.len());
+                libc::_exit(130);
+            }
+        }
+        // SAFETY: write 是异步信号安全的
+        unsafe { libc::write(2, FIRST_MESSAGE.as_ptr().cast(), FIRST_MESSAGE.len()) };
+    }
+
+    pub(super) fn install() {
+        let handler = handle_sigint as extern "C" fn(libc::c_int);
+        // SAFETY: 处理函数只访问原子变量并调用异步信号安全的函数
+        if unsafe { libc::signal(libc::SIGINT, handler as libc::sighandler_t) } != libc::SIG_ERR {
+            HANDLER_INSTALLED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(super) fn register(pgid: u32) -> Option<usize> {
+        let pgid = i32::try_from(pgid).ok()?;
+        CHILD_PGIDS.iter().position(|slot| {
+            slot.compare_exchange(0, pgid, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        })
+    }
+
+    pub(super) fn unregister(slot: usize) {
+        CHILD_PGIDS[slot].store(0, Ordering::SeqCst);
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    pub(super) fn install() {}
+
+    pub(super) fn register(_pgid: u32) -> Option<usize> {
+        None
+    }
+
+    pub(super) fn unregister(_slot: usize) {}
+}
+
+/// 接管 Ctrl-C（命令行 `translate` 使用；嵌入本库时由调用方决定是否安装）
+pub fn install_handler() {
+    imp::install();
+}
+
+/// 登记一个正在运行的子进程组，第二次 Ctrl-C 时将其终止；守卫 drop 时注销
+pub(crate) struct ChildGuard(Option<usize>);
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        if let Some(slot) = self.0 {
+            imp::unregister(slot);
+        }
+    }
+}
+
+/// 登记以 `pgid` 为组长的子进程组（子进程需通过 `process_group(0)` 启动）
+pub(crate) fn track_child(pgid: u32) -> ChildGuard {
+    ChildGuard(imp::register(pgid))
+}
+
+/// 执行命令并收集输出（同 [`Command::output`]）
+///
+/// 接管了 Ctrl-C 时子进程放在独立的进程组中并登记：第一次 Ctrl-C 不会打断它（如 git、
+/// rustfmt、code_analyse、c2rust-config），第二次连同其子进程一起终止。
+pub(crate) fn output(command: &mut Command) -> std::io::Result<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let own_group = cfg!(unix) && handler_installed();
+    #[cfg(unix)]
+    if own_group {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let child = command.spawn()?;
+    let _tracked = own_group.then(|| track_child(child.id()));
+    child.wait_with_output()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_track_child_releases_slot_on_drop() {
+        use std::sync::atomic::Ordering;
+
+        let guard = track_child(4_000_001);
+        let slot = guard.0.expect("a free slot");
+        assert_eq!(imp::CHILD_PGIDS[slot].load(Ordering::SeqCst), 4_000_001);
+        drop(guard);
+        assert_ne!(imp::CHILD_PGIDS[slot].load(Ordering::SeqCst), 4_000_001);
+        assert!(!stop_requested());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_collects_output() {
+        let out =
+            output(Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"])).unwrap();
+        assert_eq!(out.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "err\n");
+    }
+}
@@ -3,28 +3,75 @@
 //! This module provides the main translation workflow that coordinates initialization,
 //! gate verification, file selection, and translation execution across multiple modules.
 
+// Human-readable output goes through the registered observer first (see `observer`).
+// Defined before the modules below, these shadow the std macros of the same name in all of them.
+macro_rules! println {
+    () => {
+        $crate::observer::write_output($crate::observer::OutputStream::Stdout, format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::observer::write_output(
+            $crate::observer::OutputStream::Stdout,
+            format_args!("{}\n", format_args!($($arg)*)),
+        )
+    };
+}
+
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::observer::write_output($crate::observer::OutputStream::Stdout, format_args!($($arg)*))
+    };
+}
+
+macro_rules! eprintln {
+    () => {
+        $crate::observer::write_output($crate::observer::OutputStream::Stderr, format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::observer::write_output(
+            $crate::observer::OutputStream::Stderr,
+            format_args!("{}\n", format_args!($($arg)*)),
+        )
+    };
+}
+
 // Public modules - external API
 pub mod analyzer;
+pub mod backup;
 pub mod builder;
 pub mod common_tasks;
+pub mod diagnostics;
+pub mod diff_display;
+pub mod events;
 pub mod file_scanner;
 pub mod git;
 pub mod hybrid_build;
 pub mod initialization;
+pub mod interrupt;
+pub mod observer;
+pub mod patch_export;
+pub mod report;
+pub mod run_lock;
+pub mod summary;
 pub mod translator;
 pub mod util;
 pub mod verification;
 
 // Internal modules - implementation details
-pub(crate) mod diff_display;
 pub(crate) mod error_handler;
+#[cfg(feature = "highlight")]
+pub(crate) mod highlight;
 pub(crate) mod interaction;
 pub(crate) mod suggestion;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub(crate) mod ui;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use quote::ToTokens;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
 
@@ -39,7 +86,9 @@ const GIT_GC_INTERVAL: usize = 10;
 /// produced it, so that long runs with many skipped-file retries also get periodic
 /// compaction.  Both reflog expiry and GC failures are non-fatal (warnings only).
 fn maybe_run_periodic_git_gc(progress_state: &util::ProgressState) {
-    if progress_state.processed_count % GIT_GC_INTERVAL == 0
+    if progress_state
+        .processed_count
+        .is_multiple_of(GIT_GC_INTERVAL)
         && progress_state.processed_count > 0
     {
         git::git_expire_reflog();
@@ -47,6 +96,319 @@ fn maybe_run_periodic_git_gc(progress_state: &util::ProgressState) {
     }
 }
 
+/// How much the workflow is allowed to ask the user.
+///
+/// This is the single switch behind the older, overlapping interactivity flags:
+///
+/// | Mode     | File selection                     | Prompts                       | Auto-accept |
+/// |----------|------------------------------------|-------------------------------|-------------|
+/// | `auto`   | all files                          | none (non-TTY defaults apply) | on          |
+/// | `prompt` | prompt, or all with `--allow-all`  | interactive (default)         | off         |
+/// | `manual` | always prompt                      | interactive                   | off         |
+///
+/// `--allow-all` is kept as a deprecated alias: it only takes effect in `prompt`
+/// mode. `--interactive=false` is accepted as an alias for `auto`, and
+/// `--interactive=true` for `prompt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InteractiveMode {
+    Auto,
+    #[default]
+    Prompt,
+    Manual,
+}
+
+/// Internal interactivity flags derived from an [`InteractiveMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionFlags {
+    /// Process every empty file without asking which ones to translate
+    pub allow_all: bool,
+    /// Accept successful translations without asking
+    pub auto_accept: bool,
+    /// Whether any interactive prompt may be shown
+    pub prompts_enabled: bool,
+}
+
+impl InteractiveMode {
+    /// Resolve the internal flags for this mode, honouring the deprecated
+    /// `--allow-all` alias where it still applies.
+    pub fn flags(self, allow_all_alias: bool) -> InteractionFlags {
+        match self {
+            InteractiveMode::Auto => InteractionFlags {
+                allow_all: true,
+                auto_accept: true,
+                prompts_enabled: false,
+            },
+            InteractiveMode::Prompt => InteractionFlags {
+                allow_all: allow_all_alias,
+                auto_accept: false,
+                prompts_enabled: true,
+            },
+            InteractiveMode::Manual => InteractionFlags {
+                allow_all: false,
+                auto_accept: false,
+                prompts_enabled: true,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for InteractiveMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" | "false" => Ok(InteractiveMode::Auto),
+            "prompt" | "true" => Ok(InteractiveMode::Prompt),
+            "manual" => Ok(InteractiveMode::Manual),
+            other => Err(format!(
+                "`{other}` is not a valid interactive mode (expected auto, prompt or manual)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for InteractiveMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            InteractiveMode::Auto => "auto",
+            InteractiveMode::Prompt => "prompt",
+            InteractiveMode::Manual => "manual",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What to do when a build/test failure needs a decision but no prompt can be shown
+/// (`--on-failure`; applies under `--non-interactive`, `CI=true` or without a TTY).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// Skip the failed step or file and carry on (the historical no-TTY default);
+    /// failures that cannot be skipped abort
+    #[default]
+    Continue,
+    /// Abort the run
+    Abort,
+    /// Re-translate the file from scratch once, then abort if it fails again
+    RetryOnceThenAbort,
+}
+
+impl std::str::FromStr for FailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "continue" => Ok(FailurePolicy::Continue),
+            "abort" => Ok(FailurePolicy::Abort),
+            "retry-once-then-abort" => Ok(FailurePolicy::RetryOnceThenAbort),
+            other => Err(format!(
+                "`{other}` is not a valid failure policy (expected continue, abort or retry-once-then-abort)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for FailurePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FailurePolicy::Continue => "continue",
+            FailurePolicy::Abort => "abort",
+            FailurePolicy::RetryOnceThenAbort => "retry-once-then-abort",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Whether the `CI` environment variable marks a CI run (`true` or `1`); the CLI then
+/// implies `--non-interactive`.
+pub fn is_ci_environment(value: Option<&str>) -> bool {
+    value.is_some_and(|v| v.trim().eq_ignore_ascii_case("true") || v.trim() == "1")
+}
+
+/// Apply the derived interactivity flags to the global interaction state.
+///
+/// Every run starts from these flags: an auto-accept answer or a Ctrl-C from an
+/// earlier run in the same process does not carry over.
+fn apply_interaction_flags(flags: InteractionFlags) {
+    interaction::set_prompts_disabled(!flags.prompts_enabled);
+    interaction::disable_auto_accept_mode();
+    interaction::reset_failure_retry();
+    interrupt::clear_stop_request();
+    if flags.auto_accept {
+        interaction::enable_auto_accept_mode();
+    }
+}
+
+/// Options for [`translate_feature`]
+///
+/// `Default` matches the `translate` subcommand's defaults.
+#[derive(Debug, Clone)]
+pub struct TranslateOptions {
+    /// Deprecated alias: auto-process all files without prompting (`prompt` mode only)
+    pub allow_all: bool,
+    /// How much the workflow may prompt; see [`InteractiveMode`]
+    pub interactive_mode: InteractiveMode,
+    /// Only process this Rust file (path relative to `.c2rust/<feature>/rust`)
+    pub target_file: Option<String>,
+    /// Maximum number of build-error fix attempts per file
+    pub max_error_fix_attempts: usize,
+    /// Maximum number of warning-fix attempts per file
+    pub max_warning_fix_attempts: usize,
+    /// Maximum number of from-scratch translations per file (must be > 0)
+    pub max_translation_attempts: usize,
+    /// If true, show complete code/error output without truncation
+    pub show_full_output: bool,
+    /// Build profiles a file must build/test under before it is accepted;
+    /// the first one is the primary profile, empty means `debug` only
+    pub verify_profiles: Vec<builder::BuildProfile>,
+    /// How warnings in translated code are handled; see [`verification::WarningMode`]
+    pub warning_mode: verification::WarningMode,
+    /// Write errors sent to the fixer to `.c2rust/<feature>/logs/<file>.error.txt`
+    /// instead of an ephemeral temp file
+    pub keep_error_files: bool,
+    /// Skip the pre-flight check of the rust dir layout
+    pub skip_structural_check: bool,
+    /// Extra flags appended to `RUSTFLAGS` for every cargo build/check
+    pub extra_rustflags: Option<String>,
+    /// Only print the files that would be processed and the commands that would run;
+    /// the translator, cargo and git commits are never invoked
+    pub dry_run: bool,
+    /// Delete the saved `.c2rust/<feature>/progress-<branch>-<hash>.json` and
+    /// start the session counters from scratch
+    pub reset_progress: bool,
+    /// `Json` emits NDJSON workflow events on stdout and moves the
+    /// human-readable output to stderr
+    pub output_format: events::OutputFormat,
+    /// Per-command timeout in seconds for cargo and the configured
+    /// clean/build/test commands; `None` uses `build.timeout` or the 600s default
+    pub build_timeout: Option<u64>,
+    /// Number of files translated concurrently before the sequential build/fix
+    /// pass (must be > 0); values above 1 require `allow_all` and disable prompts
+    pub jobs: usize,
+    /// Directory receiving one bundle (argv, C input, error, suggestion,
+    /// produced Rust) per translator call, for auditing
+    pub record_io: Option<PathBuf>,
+    /// Warn (and offer a "reduce unsafe" suggestion) when more than this
+    /// share of a built file's statements are in an unsafe context; `None` disables the check
+    pub max_unsafe_ratio: Option<f64>,
+    /// Skip every git commit (analysis updates and translation still run) and
+    /// remind the user at the end how many commit points were skipped
+    pub no_commit: bool,
+    /// Keep `.c2rust/<feature>/logs/` as is instead of applying the
+    /// `logs.max_size_mb` / `logs.max_age_days` retention limits at run start
+    pub no_prune: bool,
+    /// After initialization, print the run plan and require a y/N confirmation
+    /// before the translation loop starts (auto-yes without a TTY)
+    pub confirm: bool,
+    /// How C and Rust code are compared after a failure; see
+    /// [`diff_display::DiffLayout`]
+    pub diff_layout: diff_display::DiffLayout,
+    /// Never read stdin: behaves like `interactive_mode = Auto`
+    pub non_interactive: bool,
+    /// Decision taken when a failure prompt cannot be shown; see [`FailurePolicy`]
+    pub on_failure: FailurePolicy,
+    /// Capture the translate script's stdout/stderr into
+    /// `.c2rust/<feature>/logs/<file>.translate.log` (fix calls: `<file>.fix.log`) instead of
+    /// inheriting the terminal (always on with `jobs > 1`); the output is echoed when
+    /// `show_full_output` is set
+    pub capture_translator_output: bool,
+    /// Kill a translate or fix script call after this many seconds; `None`
+    /// uses `translate.timeout` or no limit
+    pub translate_timeout: Option<u64>,
+    /// Keep a file's suggestions across its retries instead of
+    /// clearing them before each new suggestion or fresh translation
+    pub remember_suggestions: bool,
+    /// Always write fix suggestions in `$VISUAL`/`$EDITOR` instead of the
+    /// single-line prompt (`:edit` at the prompt does the same for one suggestion)
+    pub suggestion_editor: bool,
+    /// Build with `-D warnings` so warnings fail the build and go through the
+    /// error-fix loop, instead of suppressing them with `-A warnings`
+    pub deny_warnings: bool,
+    /// Extra glob patterns (after the configured `ignore.patterns`) for files
+    /// left out of the run and of the progress total; see [`file_scanner::IgnorePatterns`]
+    pub skip_patterns: Vec<String>,
+    /// Only offer the empty files matching this glob (see
+    /// [`file_scanner::glob_matches`]); all of them with `allow_all`, otherwise through the usual
+    /// file selection prompt. Fails if it matches no empty file
+    pub only_pattern: Option<String>,
+    /// Only offer the empty files that changed since this git revision
+    /// (`git diff --name-only <since>` in the feature's rust directory, plus untracked files);
+    /// an invalid revision fails with git's error
+    pub since: Option<String>,
+    /// Do not run `rustfmt --edition 2021` on the files the translator writes;
+    /// otherwise the optional `format.enabled` config key decides (on by default)
+    pub no_format: bool,
+    /// Config.toml passed to the translate script and checked before hybrid
+    /// builds, instead of `.c2rust/config.toml`; must exist and be readable
+    pub config_path: Option<PathBuf>,
+    /// Wall-clock budget for the run, counted from this call; once reached, no
+    /// new file is started (the current one is finished and committed) and the run ends with
+    /// `Ok`, so the next run resumes from the remaining files
+    pub max_duration: Option<std::time::Duration>,
+    /// Patch file (truncated at the start of the run) that collects a diff for
+    /// every file completed in this run, applicable with `git apply` from the project root; see
+    /// [`patch_export`]
+    pub export_patch: Option<PathBuf>,
+    /// Skip files whose C source is larger than this many bytes instead of
+    /// sending them to the translator (recorded as skipped with the reason in the run report);
+    /// an interactive run asks whether to translate the file anyway. `None` means no limit
+    pub max_c_file_bytes: Option<u64>,
+    /// Take over the feature's run lock (`.c2rust/<feature>/.lock`) even when
+    /// another process holds it; without it a second run on the same feature fails with the
+    /// holder's PID and start time (see [`run_lock`]). Dry runs take no lock
+    pub force_unlock: bool,
+    /// Commit translated files in batches of this many (must be > 0): one
+    /// commit lists the files and includes their fix and analysis updates; the remainder is
+    /// committed when the run ends, also on error. `1` commits after every file
+    pub commit_every: usize,
+}
+
+impl Default for TranslateOptions {
+    fn default() -> Self {
+        Self {
+            allow_all: Default::default(),
+            interactive_mode: Default::default(),
+            target_file: Default::default(),
+            max_error_fix_attempts: 5,
+            max_warning_fix_attempts: 10,
+            max_translation_attempts: util::MAX_TRANSLATION_ATTEMPTS,
+            show_full_output: Default::default(),
+            verify_profiles: Default::default(),
+            warning_mode: Default::default(),
+            keep_error_files: Default::default(),
+            skip_structural_check: Default::default(),
+            extra_rustflags: Default::default(),
+            dry_run: Default::default(),
+            reset_progress: Default::default(),
+            output_format: Default::default(),
+            build_timeout: Default::default(),
+            jobs: 1,
+            record_io: Default::default(),
+            max_unsafe_ratio: Default::default(),
+            no_commit: Default::default(),
+            no_prune: Default::default(),
+            confirm: Default::default(),
+            diff_layout: Default::default(),
+            non_interactive: Default::default(),
+            on_failure: Default::default(),
+            capture_translator_output: Default::default(),
+            translate_timeout: Default::default(),
+            remember_suggestions: Default::default(),
+            suggestion_editor: Default::default(),
+            deny_warnings: Default::default(),
+            skip_patterns: Default::default(),
+            only_pattern: Default::default(),
+            since: Default::default(),
+            no_format: Default::default(),
+            config_path: Default::default(),
+            max_duration: Default::default(),
+            export_patch: Default::default(),
+            max_c_file_bytes: Default::default(),
+            force_unlock: Default::default(),
+            commit_every: 1,
+        }
+    }
+}
+
 /// Main translation workflow for a feature
 ///
 /// Executes the complete C to Rust translation workflow in 5 steps:
@@ -56,28 +418,312 @@ fn maybe_run_periodic_git_gc(progress_state: &util::ProgressState) {
 /// 4. Display current progress status
 /// 5. Execute translation loop (select and process files interactively or auto-all)
 ///
-/// # Arguments
-/// * `feature` - Feature name (must not contain path separators)
-/// * `allow_all` - If true, auto-process all files without prompting
-/// * `max_error_fix_attempts` - Maximum number of build-error fix attempts per file
-/// * `max_warning_fix_attempts` - Maximum number of warning-fix attempts per file
-/// * `show_full_output` - If true, show complete code/error output without truncation
+/// `feature` is the feature name (must not contain path separators); see
+/// [`TranslateOptions`] for the run options.
 ///
 /// # Returns
 /// * `Ok(())` - All translations completed successfully
 /// * `Err` - Translation failed or user aborted
-pub fn translate_feature(
+pub fn translate_feature(feature: &str, options: &TranslateOptions) -> Result<()> {
+    let TranslateOptions {
+        allow_all,
+        interactive_mode,
+        max_translation_attempts,
+        ref verify_profiles,
+        warning_mode,
+        keep_error_files,
+        ref extra_rustflags,
+        dry_run,
+        output_format,
+        build_timeout,
+        jobs,
+        ref record_io,
+        max_unsafe_ratio,
+        no_commit,
+        no_prune,
+        diff_layout,
+        non_interactive,
+        on_failure,
+        capture_translator_output,
+        translate_timeout,
+        remember_suggestions,
+        suggestion_editor,
+        deny_warnings,
+        ref skip_patterns,
+        no_format,
+        ref config_path,
+        max_duration,
+        ref export_patch,
+        max_c_file_bytes,
+        force_unlock,
+        commit_every,
+        ..
+    } = *options;
+    let extra_rustflags = extra_rustflags.as_deref();
+    let record_io = record_io.as_deref();
+    let config_path = config_path.as_deref();
+    let export_patch = export_patch.as_deref();
+    if max_translation_attempts == 0 {
+        anyhow::bail!("max_translation_attempts must be at least 1 (got 0)");
+    }
+    if jobs == 0 {
+        anyhow::bail!("jobs must be at least 1 (got 0)");
+    }
+    if commit_every == 0 {
+        anyhow::bail!("commit_every must be at least 1 (got 0)");
+    }
+    if let Some(ratio) = max_unsafe_ratio {
+        if !(0.0..=1.0).contains(&ratio) {
+            anyhow::bail!(
+                "max_unsafe_ratio must be between 0.0 and 1.0 (got {})",
+                ratio
+            );
+        }
+    }
+    let interactive_mode = if non_interactive {
+        InteractiveMode::Auto
+    } else {
+        interactive_mode
+    };
+    let interaction_flags = interactive_mode.flags(allow_all);
+    let allow_all = interaction_flags.allow_all;
+    if jobs > 1 && !allow_all {
+        anyhow::bail!(
+            "--jobs {} requires --allow-all (or --interactive auto): files cannot be selected interactively in parallel mode",
+            jobs
+        );
+    }
+    apply_interaction_flags(interaction_flags);
+    if jobs > 1 {
+        // Parallel translations would interleave their stdin prompts.
+        interaction::set_prompts_disabled(true);
+    }
+    builder::set_verify_profiles(verify_profiles);
+    verification::set_warning_mode(warning_mode);
+    translator::set_keep_error_files(keep_error_files);
+    translator::set_record_io_dir(record_io);
+    // Parallel translations would interleave their output on the terminal.
+    translator::set_capture_translator_output(capture_translator_output || jobs > 1);
+    translator::set_translate_timeout(translate_timeout);
+    translator::set_no_format(no_format);
+    util::set_config_path(config_path)?;
+    suggestion::set_remember_suggestions(remember_suggestions);
+    interaction::set_suggestion_editor(suggestion_editor);
+    builder::set_extra_rustflags(extra_rustflags)?;
+    builder::set_deny_warnings(deny_warnings);
+    file_scanner::set_ignore_patterns(load_ignore_patterns(feature, skip_patterns)?);
+    builder::set_build_timeout(build_timeout);
+    set_max_unsafe_ratio(max_unsafe_ratio);
+    set_max_c_file_bytes(max_c_file_bytes);
+    set_run_deadline(max_duration.map(|budget| std::time::Instant::now() + budget));
+    git::set_no_commit(no_commit);
+    git::set_commit_every(commit_every);
+    diff_display::set_diff_layout(diff_layout);
+    interaction::set_failure_policy(on_failure);
+    events::set_output_format(output_format)?;
+    patch_export::set_export_patch(export_patch.filter(|_| !dry_run))?;
+    summary::reset();
+
+    // Catch a mistyped feature name before the run lock, log pruning or run report touch
+    // `.c2rust/<feature>/`; otherwise a second run with the same typo would find it existing.
+    initialization::check_feature_name(feature)?;
+
+    // Held until this function returns; a run on a feature that does not exist yet takes
+    // the lock once step 1 has created the directory, so that feature name checks still
+    // see the directory as missing.
+    let feature_dir = util::find_project_root()?.join(".c2rust").join(feature);
+    let mut lock = if dry_run || !feature_dir.is_dir() {
+        None
+    } else {
+        Some(run_lock::acquire(&feature_dir, force_unlock)?)
+    };
+
+    events::emit(&events::Event::TranslationStart { feature });
+    if !dry_run && !no_prune {
+        prune_feature_logs(feature);
+    }
+    // Written on drop, so the report also covers early errors.
+    let report_guard = report::RunReportGuard::start(feature, !dry_run);
+    let commit_batch_guard = CommitBatchGuard { feature };
+    let result = run_translation_workflow(feature, options, allow_all, &mut lock);
+    drop(commit_batch_guard);
+    report_guard.set_result(&result);
+    report::print_usage_summary();
+    print_skipped_commits_reminder(feature);
+    summary::print_run_summary();
+    patch_export::print_summary();
+    events::emit(&events::Event::TranslationEnd {
+        result: events::EventResult::from_ok(result.is_ok()),
+        error: result.as_ref().err().map(|e| format!("{:#}", e)),
+    });
+    result
+}
+
+/// [`translate_feature`] with every event, prompt and line of output going to `observer`
+///
+/// The observer is registered for the duration of the call; one registered with
+/// [`observer::observe`] is set aside meanwhile. See the [`observer`] module for what it
+/// receives. Passing
+/// [`observer::ConsoleObserver`] behaves exactly like [`translate_feature`].
+pub fn translate_feature_with_observer(
+    feature: &str,
+    options: &TranslateOptions,
+    observer: &mut dyn observer::WorkflowObserver,
+) -> Result<()> {
+    observer::observe_borrowed(observer, || translate_feature(feature, options))
+}
+
+/// Combine the `.c2rustignore` files (project root, then `.c2rust/<feature>/`), the configured
+/// `ignore.patterns` and the `--skip` patterns, in that order; later patterns win, so e.g.
+/// `--skip '!pattern'` can bring an ignored file back into scope.
+fn load_ignore_patterns(
+    feature: &str,
+    skip_patterns: &[String],
+) -> Result<file_scanner::IgnorePatterns> {
+    let mut patterns = file_scanner::c2rustignore_patterns(&util::find_project_root()?, feature)?;
+    if let Ok(configured) = builder::get_config_value("ignore.patterns", feature) {
+        patterns.extend(
+            shell_words::split(&configured)
+                .with_context(|| format!("Invalid ignore.patterns value: {}", configured))?,
+        );
+    }
+    patterns.extend(skip_patterns.iter().cloned());
+    Ok(file_scanner::IgnorePatterns::new(patterns))
+}
+
+/// Read a non-negative integer retention limit from the config, falling back to `default`.
+fn log_retention_limit(key: &str, feature: &str, default: u64) -> u64 {
+    match builder::get_config_value(key, feature) {
+        Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+            eprintln!(
+                "{}",
+                format!(
+                    "⚠ Warning: invalid {} value '{}'; using {}",
+                    key, value, default
+                )
+                .yellow()
+            );
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Apply the log retention limits to `.c2rust/<feature>/logs/` (oldest files first).
+///
+/// Failures are non-fatal: pruning is housekeeping and must not block a run.
+fn prune_feature_logs(feature: &str) {
+    let Ok(project_root) = util::find_project_root() else {
+        return;
+    };
+    let logs_dir = util::feature_logs_dir(&project_root, feature);
+    if !logs_dir.is_dir() {
+        return;
+    }
+    let max_size_mb =
+        log_retention_limit("logs.max_size_mb", feature, util::DEFAULT_LOG_MAX_SIZE_MB);
+    let max_age_days =
+        log_retention_limit("logs.max_age_days", feature, util::DEFAULT_LOG_MAX_AGE_DAYS);
+    let max_age = (max_age_days > 0)
+        .then(|| std::time::Duration::from_secs(max_age_days.saturating_mul(24 * 60 * 60)));
+    match util::prune_log_dir(
+        &logs_dir,
+        max_size_mb.saturating_mul(1024 * 1024),
+        max_age,
+        std::time::SystemTime::now(),
+    ) {
+        Ok(0) => {}
+        Ok(removed) => println!(
+            "{}",
+            format!(
+                "Pruned {} old log file(s) from {} (limits: {} MB, {} days)",
+                removed,
+                logs_dir.display(),
+                max_size_mb,
+                max_age_days
+            )
+            .dimmed()
+        ),
+        Err(e) => eprintln!(
+            "{}",
+            format!("⚠ Warning: failed to prune {}: {:#}", logs_dir.display(), e).yellow()
+        ),
+    }
+}
+
+/// Under `--no-commit`, tell the user how many commit points were left to them.
+fn print_skipped_commits_reminder(feature: &str) {
+    let skipped = git::skipped_commit_count();
+    if !git::is_no_commit() || skipped == 0 {
+        return;
+    }
+    println!(
+        "\n{}",
+        format!(
+            "⚠ --no-commit: skipped {} commit point(s); review and commit the changes under .c2rust/{}/ manually.",
+            skipped, feature
+        )
+        .yellow()
+        .bold()
+    );
+}
+
+/// Steps 1–5 of [`translate_feature`], run after the per-run options are applied.
+///
+/// `allow_all` is the effective value after `--interactive` was applied.
+fn run_translation_workflow(
     feature: &str,
+    options: &TranslateOptions,
     allow_all: bool,
-    target_file: Option<&str>,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
+    lock: &mut Option<run_lock::RunLock>,
 ) -> Result<()> {
+    let TranslateOptions {
+        max_error_fix_attempts,
+        max_warning_fix_attempts,
+        max_translation_attempts,
+        show_full_output,
+        skip_structural_check,
+        dry_run,
+        reset_progress,
+        jobs,
+        confirm,
+        force_unlock,
+        ..
+    } = *options;
+    let target_file = options.target_file.as_deref();
+    let selection = FileSelection {
+        allow_all,
+        target_file,
+        only_pattern: options.only_pattern.as_deref(),
+        since: options.since.as_deref(),
+        jobs,
+    };
     print_workflow_header(feature);
+    if dry_run {
+        println!(
+            "{}",
+            dry_run_line("Simulating run: no translation, cargo or git commit will be executed")
+        );
+    }
+
+    // Report missing tools and misconfigured build/test/clean commands before any work starts.
+    if !dry_run {
+        util::check_required_tools()?;
+    }
+    builder::validate_config(feature)?;
 
     // Step 1: Initialize feature directory
-    step_1_initialize(feature)?;
+    if dry_run {
+        if !dry_run_step_1_check_feature_dir(feature)? {
+            return Ok(());
+        }
+    } else {
+        step_1_initialize(feature)?;
+        if lock.is_none() {
+            let feature_dir = util::find_project_root()?.join(".c2rust").join(feature);
+            *lock = Some(run_lock::acquire(&feature_dir, force_unlock)?);
+        }
+    }
 
     // Capture whether the dedicated `.c2rust` repo was already dirty before any
     // verification side effects run. Resume snapshotting should reflect unfinished
@@ -101,40 +747,95 @@ pub fn translate_feature(
     let skip_test = check_test_configuration(feature)?;
 
     // Step 2: Run initial verification
-    step_2_initial_verification(feature, show_full_output, skip_test)?;
+    if dry_run {
+        println!(
+            "{}",
+            dry_run_line("Would run initial verification (c2rust clean/build/test)")
+        );
+    } else {
+        step_2_initial_verification(feature, show_full_output, skip_test)?;
+    }
 
     // Step 2.5: Check and load previous translation stats
-    let mut stats = step_2_5_load_or_create_stats(
-        feature,
-        preexisting_resume_snapshot_needed,
-        max_error_fix_attempts,
-        max_warning_fix_attempts,
-        show_full_output,
-        skip_test,
-    )?;
+    let mut stats = if dry_run {
+        dry_run_load_stats(feature)?
+    } else {
+        step_2_5_load_or_create_stats(
+            feature,
+            preexisting_resume_snapshot_needed,
+            max_error_fix_attempts,
+            max_warning_fix_attempts,
+            max_translation_attempts,
+            show_full_output,
+            skip_test,
+        )?
+    };
 
     if let Some(target_file) = target_file {
-        prepare_target_file_rerun(feature, target_file, &mut stats)?;
+        prepare_target_file_rerun(feature, target_file, &mut stats, dry_run)?;
     }
 
     // Step 3 & 4: Select files and initialize progress
-    let (rust_dir, mut progress_state) =
-        step_3_4_select_files_and_init_progress(feature, &stats, target_file)?;
+    let (rust_dir, mut progress_state) = step_3_4_select_files_and_init_progress(
+        feature,
+        &stats,
+        target_file,
+        reset_progress,
+        dry_run,
+    )?;
+
+    if skip_structural_check {
+        println!(
+            "{}",
+            "⚠ Structural check skipped (--skip-structural-check)".yellow()
+        );
+    } else {
+        check_rust_dir_structure_or_bail(&rust_dir)?;
+    }
+
+    if confirm && !dry_run {
+        let plan = RunPlan::collect(
+            feature,
+            &rust_dir,
+            &stats,
+            &selection,
+            max_translation_attempts,
+        )?;
+        if !confirm_run_plan(&plan, interaction::prompt_confirm_run_plan)? {
+            println!(
+                "{}",
+                "Run plan not confirmed; stopping before any file is translated.".yellow()
+            );
+            return Ok(());
+        }
+    }
 
     // Step 5: Execute translation loop
     let step5_result = step_5_execute_translation_loop(
         feature,
         &rust_dir,
         &mut progress_state,
-        allow_all,
-        target_file,
-        max_error_fix_attempts,
-        max_warning_fix_attempts,
-        show_full_output,
         &mut stats,
-        skip_test,
+        &selection,
+        FileRunOptions {
+            max_error_fix_attempts,
+            max_warning_fix_attempts,
+            max_translation_attempts,
+            show_full_output,
+            skip_test,
+            dry_run,
+        },
     );
 
+    if dry_run {
+        step5_result?;
+        println!(
+            "\n{}",
+            dry_run_line("Plan complete; no files, stats or commits were changed")
+        );
+        return Ok(());
+    }
+
     // Print summary even if step 5 fails, so progress is not lost
     if let Err(e) = step5_result {
         // Compact history even when translation aborts early.
@@ -151,6 +852,39 @@ pub fn translate_feature(
     Ok(())
 }
 
+/// Pre-flight check that the rust dir matches the layout produced by code-analyse.
+///
+/// All violations are reported together so the user can fix them in one go.
+fn check_rust_dir_structure_or_bail(rust_dir: &Path) -> Result<()> {
+    let violations = file_scanner::check_rust_dir_structure(rust_dir)?;
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "✗ Found {} structural problem(s) in {}:",
+            violations.len(),
+            rust_dir.display()
+        )
+        .red()
+        .bold()
+    );
+    for violation in &violations {
+        eprintln!("  - {}", violation);
+    }
+    eprintln!(
+        "{}",
+        "Re-run `code-analyse --init` to regenerate the layout, or pass --skip-structural-check to bypass."
+            .yellow()
+    );
+    anyhow::bail!(
+        "Rust directory structure check failed ({} problem(s))",
+        violations.len()
+    )
+}
+
 /// Run feature initialization and project-level verification without entering
 /// the translation loop.
 pub fn verify_feature(feature: &str, show_full_output: bool) -> Result<()> {
@@ -163,42 +897,710 @@ pub fn verify_feature(feature: &str, show_full_output: bool) -> Result<()> {
     Ok(())
 }
 
-// ============================================================================
-// Workflow Step Functions
-// ============================================================================
-
-/// Print the workflow header
-fn print_workflow_header(feature: &str) {
-    let msg = format!("Starting translation for feature: {}", feature);
-    println!("{}", msg.bright_cyan().bold());
-}
+/// Re-verify that every translated file still builds together, without translating.
+///
+/// Runs one `cargo build` over the feature followed by the hybrid clean/build/test
+/// (tests are skipped when `test.cmd`/`test.dir` are not configured). Nothing is
+/// committed and no prompts are shown; on failure the files located in the error
+/// are listed.
+pub fn verify_all(feature: &str, show_full_output: bool) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    if !rust_dir.is_dir() {
+        anyhow::bail!(
+            "Rust directory not found for feature '{}': {}",
+            feature,
+            rust_dir.display()
+        );
+    }
 
-/// Step 1: Find project root and initialize feature directory
-fn step_1_initialize(feature: &str) -> Result<()> {
     println!(
-        "\n{}",
-        "Step 1: Find Project Root and Initialize"
+        "{}",
+        format!("Verifying the whole feature: {}", feature)
             .bright_cyan()
             .bold()
     );
-    initialization::check_and_initialize_feature(feature)
+    let skip_test = builder::get_config_value("test.cmd", feature).is_err()
+        || builder::get_config_value("test.dir", feature).is_err();
+    verify_all_with(feature, || {
+        builder::cargo_build(feature, true, show_full_output)?;
+        common_tasks::execute_hybrid_build_check(feature, skip_test)
+    })
 }
 
-/// Step 2: Run initial verification
-fn step_2_initial_verification(feature: &str, show_full_output: bool, skip_test: bool) -> Result<()> {
-    initialization::execute_initial_verification(feature, show_full_output, skip_test)
+/// Run the whole-feature `build_and_test` once and report the outcome.
+fn verify_all_with(feature: &str, build_and_test: impl FnOnce() -> Result<()>) -> Result<()> {
+    let error = match build_and_test() {
+        Ok(()) => {
+            println!(
+                "{}",
+                "✓ All translated files build and pass the hybrid build checks together."
+                    .bright_green()
+                    .bold()
+            );
+            return Ok(());
+        }
+        Err(e) => e,
+    };
+
+    println!("{}", "✗ Whole-feature verification failed".red().bold());
+    println!("{}", format!("{:#}", error).red());
+    let files =
+        error_handler::parse_error_for_files(&format!("{:#}", error), feature).unwrap_or_default();
+    if files.is_empty() {
+        return Err(error)
+            .context("Whole-feature verification failed; no files could be located in the error");
+    }
+    println!(
+        "{}",
+        format!("Files located in the error ({}):", files.len()).yellow()
+    );
+    for file in &files {
+        println!("  {}", file.display());
+    }
+    let names = files
+        .iter()
+        .map(|f| {
+            f.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(error).context(format!("Whole-feature verification failed in: {}", names))
 }
 
-/// Check test configuration in `.c2rust/config.toml`.
+/// Print the empty `.rs` files that still need translation, without any prompts
+/// or side effects.
 ///
-/// Returns `Ok(false)` if both `test.cmd` and `test.dir` are present and non-empty
-/// (tests will run normally). Returns `Ok(true)` if the configuration is incomplete
-/// and the user chose to continue without tests (skip_test=true). Returns `Err` if
-/// the user chose to exit.
-fn check_test_configuration(feature: &str) -> Result<bool> {
-    let test_cmd = builder::get_config_value("test.cmd", feature);
-    let test_dir = builder::get_config_value("test.dir", feature);
-
+/// Plain output lists one path (relative to `.c2rust/<feature>/rust`) per line;
+/// `json` prints an array with the extracted file type and whether the `.c`
+/// sibling exists. Files excluded by `.c2rustignore` / `ignore.patterns` are left out.
+pub fn list_empty_files(feature: &str, json: bool) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    if !rust_dir.is_dir() {
+        anyhow::bail!(
+            "Rust directory not found for feature '{}': {}",
+            feature,
+            rust_dir.display()
+        );
+    }
+
+    file_scanner::set_ignore_patterns(load_ignore_patterns(feature, &[])?);
+    let entries = file_scanner::list_empty_rs_files(&rust_dir)?;
+    if json {
+        let output = serde_json::to_string_pretty(&entries)
+            .context("Failed to serialize empty file list")?;
+        println!("{}", output);
+    } else {
+        for entry in &entries {
+            println!("{}", entry.path);
+        }
+    }
+    Ok(())
+}
+
+/// Check that every `var_`/`fun_` `.rs` file under `.c2rust/<feature>/rust/src` has a `.c`
+/// sibling and vice versa, and that no `.rs` file has an unrecognized name.
+///
+/// Read-only. Fails when any problem is found, so CI can gate on it.
+pub fn verify_file_pairing(feature: &str) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    if !rust_dir.is_dir() {
+        anyhow::bail!(
+            "Rust directory not found for feature '{}': {}",
+            feature,
+            rust_dir.display()
+        );
+    }
+
+    let problems = file_scanner::check_file_pairing(&rust_dir)?;
+    if problems.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "✓ All C/Rust file pairs are consistent in {}",
+                rust_dir.display()
+            )
+            .bright_green()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "✗ Found {} file pairing problem(s) in {}:",
+            problems.len(),
+            rust_dir.display()
+        )
+        .red()
+        .bold()
+    );
+    for problem in &problems {
+        println!("  - {}", problem);
+        println!("    {}", format!("fix: {}", problem.remediation()).dimmed());
+    }
+    anyhow::bail!("File pairing check failed ({} problem(s))", problems.len())
+}
+
+/// Bring back the translation of `attempt` for `target_file` (relative to the feature's rust
+/// directory) from the backup taken before the next retry overwrote it, and commit it.
+///
+/// The file is only restored; run `fix` or the hybrid build afterwards to verify it.
+pub fn restore_translation_attempt(feature: &str, target_file: &str, attempt: usize) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    let rs_file = rust_dir.join(target_file);
+    if !rs_file.is_file() {
+        anyhow::bail!(
+            "Target Rust file not found under feature workspace: {}",
+            target_file
+        );
+    }
+
+    let backup = backup::restore_backup(&rust_dir, &rs_file, attempt)?;
+    println!(
+        "{}",
+        format!("✓ Restored {} from {}", target_file, backup.display()).bright_green()
+    );
+    git_commit_or_warn(
+        &format!(
+            "Restore translation attempt {} of {} (feature: {})",
+            attempt, target_file, feature
+        ),
+        feature,
+    );
+    println!(
+        "{}",
+        format!(
+            "Run `c2rust-translate fix --feature {} {}` to re-check and fix it.",
+            feature, target_file
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+/// Run the build-and-fix loop on already translated files, without translating them again.
+///
+/// Each file (relative to the feature's rust directory, e.g. `src/fun_a.rs`; a bare file name
+/// is looked up under `src/`) must be non-empty. For each one, `cargo build` runs up to
+/// `max_fix_attempts` times; after a failure the errors reported for that file are passed to
+/// the fix command. `translate_c_to_rust` is never called, so a good translation is never
+/// replaced by a from-scratch retry. Fixed files are committed. Fails if any file still does
+/// not build.
+pub fn fix_files(
+    feature: &str,
+    files: &[String],
+    max_fix_attempts: usize,
+    show_full_output: bool,
+) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    if max_fix_attempts == 0 {
+        anyhow::bail!("--max-fix-attempts must be greater than 0");
+    }
+    let project_root = util::find_project_root()?;
+    let feature_dir = project_root.join(".c2rust").join(feature);
+    if !feature_dir.is_dir() {
+        anyhow::bail!(
+            "Feature directory not found for feature '{}': {}",
+            feature,
+            feature_dir.display()
+        );
+    }
+    // Fixes edit, build and commit the same files a translation run does.
+    let _run_lock = run_lock::acquire(&feature_dir, false)?;
+    let rust_dir = feature_dir.join("rust");
+
+    let mut targets = Vec::with_capacity(files.len());
+    for file in files {
+        targets.push(resolve_fix_target(&rust_dir, file)?);
+    }
+
+    let mut still_failing = Vec::new();
+    for (index, rs_file) in targets.iter().enumerate() {
+        let file_name = relative_rs_file_name(rs_file, &rust_dir).to_string();
+        print_file_processing_header(index + 1, targets.len(), &file_name);
+        if !fix_file_until_build_passes(
+            feature,
+            rs_file,
+            &file_name,
+            max_fix_attempts,
+            show_full_output,
+        )? {
+            still_failing.push(file_name);
+        }
+        println!("{}", "└─ File processing complete".bright_white().bold());
+    }
+
+    if !still_failing.is_empty() {
+        anyhow::bail!(
+            "Build still fails after {} fix attempt(s) for: {}",
+            max_fix_attempts,
+            still_failing.join(", ")
+        );
+    }
+    println!("\n{}", "✓ All files build".bright_green().bold());
+    Ok(())
+}
+
+/// Resolve a `fix` argument to a non-empty `.rs` file under `rust_dir`.
+///
+/// Absolute paths and `..` components are rejected so a fix never leaves the rust dir.
+fn resolve_fix_target(rust_dir: &Path, file: &str) -> Result<std::path::PathBuf> {
+    if Path::new(file).components().any(|component| {
+        !matches!(
+            component,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    }) {
+        anyhow::bail!(
+            "{} must be a path relative to {} without '..'",
+            file,
+            rust_dir.display()
+        );
+    }
+    let candidates = [rust_dir.join(file), rust_dir.join("src").join(file)];
+    let Some(rs_file) = candidates.into_iter().find(|path| path.is_file()) else {
+        anyhow::bail!("Rust file not found under {}: {}", rust_dir.display(), file);
+    };
+    if !rust_file_has_content(&rs_file)? {
+        anyhow::bail!(
+            "{} is empty; use `translate --file {}` to translate it first",
+            file,
+            relative_rs_file_name(&rs_file, rust_dir)
+        );
+    }
+    Ok(rs_file)
+}
+
+/// Returns whether the build passes; fixes are committed when any were applied.
+fn fix_file_until_build_passes(
+    feature: &str,
+    rs_file: &Path,
+    file_name: &str,
+    max_fix_attempts: usize,
+    show_full_output: bool,
+) -> Result<bool> {
+    let file_stem = rs_file
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let (file_type, _) = file_scanner::extract_file_type(file_stem)
+        .with_context(|| format!("Cannot determine the file type of {}", file_name))?;
+    let format_progress = |op: &str| format!("Fixing {} - {}", file_name, op);
+
+    ui::info("Updating code analysis...");
+    let updated = analyzer::update_code_analysis(feature)?;
+    ui::success(analyzer::update_status_message(updated));
+
+    let (build_passes, fixes_applied) = verification::build_and_fix_loop(
+        max_fix_attempts,
+        || builder::cargo_build(feature, true, show_full_output).map(|_| ()),
+        |build_error| {
+            let Some(file_error) = build_errors_for_file(feature, build_error, rs_file) else {
+                println!(
+                    "│ {}",
+                    format!(
+                        "⚠ The build errors are not in {}; fix the other files first",
+                        file_name
+                    )
+                    .yellow()
+                );
+                return Ok(verification::FixStep::Stop);
+            };
+            let step = match apply_error_fix(
+                feature,
+                file_type,
+                rs_file,
+                &file_error,
+                &format_progress,
+                show_full_output,
+            ) {
+                Ok(()) => verification::FixStep::Applied,
+                Err(e) => verification::FixStep::Failed(e),
+            };
+            analyzer::update_code_analysis(feature)?;
+            Ok(step)
+        },
+    )?;
+
+    if fixes_applied > 0 {
+        let verdict = if build_passes {
+            "builds"
+        } else {
+            "still fails"
+        };
+        git_commit_or_batch(
+            &format!(
+                "Fix build errors in {} ({} fix(es), {}) (feature: {})",
+                file_name, fixes_applied, verdict, feature
+            ),
+            feature,
+        );
+    }
+    Ok(build_passes)
+}
+
+/// The part of a build error that belongs to `rs_file`; `None` when it is reported only for
+/// other files.
+fn build_errors_for_file(
+    feature: &str,
+    build_error: &anyhow::Error,
+    rs_file: &Path,
+) -> Option<anyhow::Error> {
+    let message = build_error.to_string();
+    let grouped = match diagnostics::diagnostics_of(build_error) {
+        Some(diagnostics) => error_handler::group_diagnostics_by_file(diagnostics, feature),
+        None => error_handler::group_errors_by_file(&message, feature),
+    };
+    let grouped = match grouped {
+        Ok(grouped) if !grouped.is_empty() => grouped,
+        // Nothing could be attributed to a file: let the fixer see the whole error.
+        _ => return Some(anyhow::anyhow!("{}", message)),
+    };
+    let target = rs_file
+        .canonicalize()
+        .unwrap_or_else(|_| rs_file.to_path_buf());
+    grouped
+        .into_iter()
+        .find(|(file, _)| file.canonicalize().unwrap_or_else(|_| file.clone()) == target)
+        .map(|(_, file_message)| anyhow::anyhow!("{}", file_message))
+}
+
+/// Print how much of a feature has been translated, broken down by top-level module.
+///
+/// Read-only: no builds, prompts or stats updates. Files excluded by `.c2rustignore` /
+/// `ignore.patterns` are not counted.
+pub fn show_status(feature: &str, format: events::OutputFormat) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    if !rust_dir.is_dir() {
+        anyhow::bail!(
+            "Rust directory not found for feature '{}': {}",
+            feature,
+            rust_dir.display()
+        );
+    }
+
+    file_scanner::set_ignore_patterns(load_ignore_patterns(feature, &[])?);
+    let status = file_scanner::collect_translation_status(&rust_dir)?;
+    if format == events::OutputFormat::Json {
+        let output = serde_json::to_string_pretty(&status)
+            .context("Failed to serialize translation status")?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Translation status for feature: {}", feature)
+            .bright_cyan()
+            .bold()
+    );
+    let counts = &status.counts;
+    println!(
+        "  var_*: {}/{} translated, fun_*: {}/{} translated",
+        counts.var_total - counts.var_empty,
+        counts.var_total,
+        counts.fun_total - counts.fun_empty,
+        counts.fun_total
+    );
+    println!(
+        "  {} of {} file(s) translated, {} empty ({:.1}% complete)",
+        counts.translated(),
+        counts.total(),
+        counts.empty(),
+        status.percent_complete
+    );
+    if status.modules.is_empty() {
+        return Ok(());
+    }
+
+    let width = status
+        .modules
+        .iter()
+        .map(|m| m.module.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("module".len());
+    println!();
+    println!(
+        "  {:<width$}  {:>9}  {:>9}  {:>7}",
+        "module",
+        "var",
+        "fun",
+        "done",
+        width = width
+    );
+    for module in &status.modules {
+        let line = format!(
+            "  {:<width$}  {:>9}  {:>9}  {:>6.1}%",
+            module.module,
+            format!(
+                "{}/{}",
+                module.counts.var_total - module.counts.var_empty,
+                module.counts.var_total
+            ),
+            format!(
+                "{}/{}",
+                module.counts.fun_total - module.counts.fun_empty,
+                module.counts.fun_total
+            ),
+            module.percent_complete,
+            width = width
+        );
+        if module.counts.empty() == 0 {
+            println!("{}", line.bright_green());
+        } else {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// Which generated state `clean` removes from `.c2rust/<feature>/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanScope {
+    /// The cargo build output (`rust/target`)
+    Build,
+    /// Saved session progress for every branch (`progress*.json`)
+    Progress,
+    /// Per-file fix suggestions (`suggestions/`)
+    Suggestions,
+    /// All of the above plus translation stats, logs and run reports
+    All,
+}
+
+impl std::str::FromStr for CleanScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "build" => Ok(CleanScope::Build),
+            "progress" => Ok(CleanScope::Progress),
+            "suggestions" => Ok(CleanScope::Suggestions),
+            "all" => Ok(CleanScope::All),
+            // `build.target` is not stored under `.c2rust/<feature>/`, so `clean` cannot drop it.
+            "targets" => Err(
+                "`build.target` is managed by c2rust-config and is not removed by clean; \
+                 change it with c2rust-config (it lives in .c2rust/config.toml). \
+                 To remove the cargo build output (rust/target), use `clean build`"
+                    .to_string(),
+            ),
+            other => Err(format!(
+                "`{other}` is not a valid clean scope (expected build, progress, suggestions or all)"
+            )),
+        }
+    }
+}
+
+/// List the existing paths under `feature_dir` that `scope` covers, in a stable order.
+fn clean_paths(feature_dir: &Path, scope: CleanScope) -> Result<Vec<std::path::PathBuf>> {
+    let mut paths = Vec::new();
+    if matches!(scope, CleanScope::Build | CleanScope::All) {
+        paths.push(feature_dir.join("rust").join("target"));
+    }
+    if matches!(scope, CleanScope::Progress | CleanScope::All) {
+        let mut progress_files = Vec::new();
+        for entry in std::fs::read_dir(feature_dir)
+            .with_context(|| format!("Failed to read directory: {}", feature_dir.display()))?
+        {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with("progress") && name.ends_with(".json") {
+                progress_files.push(path);
+            }
+        }
+        progress_files.sort();
+        paths.extend(progress_files);
+    }
+    if matches!(scope, CleanScope::Suggestions | CleanScope::All) {
+        paths.push(feature_dir.join("suggestions"));
+    }
+    if scope == CleanScope::All {
+        for name in [
+            "translation_stats.json",
+            "logs",
+            "report.json",
+            "report.txt",
+        ] {
+            paths.push(feature_dir.join(name));
+        }
+    }
+    Ok(paths
+        .into_iter()
+        .filter(|path| path.symlink_metadata().is_ok())
+        .collect())
+}
+
+/// Remove (or with `dry_run`, only list) what `scope` covers under `feature_dir`.
+///
+/// Symlinks are removed themselves, never followed. Returns the affected paths.
+fn clean_feature_dir(
+    feature_dir: &Path,
+    scope: CleanScope,
+    dry_run: bool,
+) -> Result<Vec<std::path::PathBuf>> {
+    let paths = clean_paths(feature_dir, scope)?;
+    for path in &paths {
+        let display = path.strip_prefix(feature_dir).unwrap_or(path).display();
+        if dry_run {
+            println!("{} Would remove {}", "[dry-run]".bright_yellow(), display);
+            continue;
+        }
+        let is_dir = path.symlink_metadata().is_ok_and(|m| m.is_dir());
+        if is_dir {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        }
+        .with_context(|| format!("Failed to remove {}", path.display()))?;
+        println!("{} {}", "Removed".bright_green(), display);
+    }
+    Ok(paths)
+}
+
+/// Reset generated state of a feature without touching its sources.
+///
+/// Only paths under `.c2rust/<feature>/` are removed; `dry_run` lists them instead.
+/// `build.target` and other settings live in c2rust-config and are left alone.
+pub fn clean_feature(feature: &str, scope: CleanScope, dry_run: bool) -> Result<()> {
+    util::validate_feature_name(feature)?;
+    let project_root = util::find_project_root()?;
+    let feature_dir = project_root.join(".c2rust").join(feature);
+    if !feature_dir.is_dir() {
+        anyhow::bail!(
+            "Feature directory not found for feature '{}': {}",
+            feature,
+            feature_dir.display()
+        );
+    }
+
+    // Removing progress and stats under a running translation would corrupt it.
+    let _run_lock = if dry_run {
+        None
+    } else {
+        Some(run_lock::acquire(&feature_dir, false)?)
+    };
+
+    println!(
+        "{}",
+        format!(
+            "Cleaning {} in {}",
+            format!("{:?}", scope).to_lowercase(),
+            feature_dir.display()
+        )
+        .bright_cyan()
+        .bold()
+    );
+    if clean_feature_dir(&feature_dir, scope, dry_run)?.is_empty() {
+        println!("Nothing to clean.");
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Workflow Step Functions
+// ============================================================================
+
+/// Print the workflow header
+fn print_workflow_header(feature: &str) {
+    let msg = format!("Starting translation for feature: {}", feature);
+    println!("{}", msg.bright_cyan().bold());
+}
+
+/// Format a line of simulated `--dry-run` output
+fn dry_run_line(message: &str) -> String {
+    format!("{} {}", "[dry-run]".bright_magenta().bold(), message)
+}
+
+/// Step 1 under `--dry-run`: report whether the feature directory would be initialized.
+///
+/// Returns `Ok(false)` when it does not exist yet, since there is nothing to scan.
+fn dry_run_step_1_check_feature_dir(feature: &str) -> Result<bool> {
+    println!(
+        "\n{}",
+        "Step 1: Find Project Root and Initialize"
+            .bright_cyan()
+            .bold()
+    );
+    util::validate_feature_name(feature)?;
+    let rust_dir = util::find_project_root()?
+        .join(".c2rust")
+        .join(feature)
+        .join("rust");
+    if rust_dir.is_dir() {
+        println!(
+            "{}",
+            "Feature directory exists, continuing...".bright_cyan()
+        );
+        return Ok(true);
+    }
+    println!(
+        "{}",
+        dry_run_line(&format!(
+            "Would initialize feature directory {} and commit it; nothing to scan yet",
+            rust_dir.display()
+        ))
+    );
+    Ok(false)
+}
+
+/// Step 2.5 under `--dry-run`: load previous stats read-only and plan as "continue".
+fn dry_run_load_stats(feature: &str) -> Result<util::TranslationStats> {
+    match util::TranslationStats::load_from_file(feature)? {
+        Some(mut existing_stats) => {
+            existing_stats.normalize_file_keys();
+            print_previous_progress_summary(&existing_stats);
+            println!(
+                "{}",
+                dry_run_line("Would ask whether to continue or restart; planning as continue")
+            );
+            Ok(existing_stats)
+        }
+        None => {
+            println!("{}", "Starting new translation session...".bright_cyan());
+            Ok(util::TranslationStats::new())
+        }
+    }
+}
+
+/// Step 1: Find project root and initialize feature directory
+fn step_1_initialize(feature: &str) -> Result<()> {
+    println!(
+        "\n{}",
+        "Step 1: Find Project Root and Initialize"
+            .bright_cyan()
+            .bold()
+    );
+    initialization::check_and_initialize_feature(feature)
+}
+
+/// Step 2: Run initial verification
+fn step_2_initial_verification(feature: &str, show_full_output: bool, skip_test: bool) -> Result<()> {
+    initialization::execute_initial_verification(feature, show_full_output, skip_test)
+}
+
+/// Check test configuration in `.c2rust/config.toml`.
+///
+/// Returns `Ok(false)` if both `test.cmd` and `test.dir` are present and non-empty
+/// (tests will run normally). Returns `Ok(true)` if the configuration is incomplete
+/// and the user chose to continue without tests (skip_test=true). Returns `Err` if
+/// the user chose to exit.
+fn check_test_configuration(feature: &str) -> Result<bool> {
+    let test_cmd = builder::get_config_value("test.cmd", feature);
+    let test_dir = builder::get_config_value("test.dir", feature);
+
     if test_cmd.is_ok() && test_dir.is_ok() {
         // `get_config_value` returns Err for missing or empty values, so Ok(_) here
         // guarantees both keys are present and non-empty in the config file.
@@ -232,6 +1634,7 @@ fn step_2_5_load_or_create_stats(
     preexisting_resume_snapshot_needed: bool,
     max_error_fix_attempts: usize,
     max_warning_fix_attempts: usize,
+    max_translation_attempts: usize,
     show_full_output: bool,
     skip_test: bool,
 ) -> Result<util::TranslationStats> {
@@ -285,12 +1688,16 @@ fn step_2_5_load_or_create_stats(
                             feature,
                             &rust_dir,
                             &mut progress_state,
-                            max_error_fix_attempts,
-                            max_warning_fix_attempts,
-                            show_full_output,
                             &mut existing_stats,
-                            skip_test,
                             &mut translations_since_last_test,
+                            FileRunOptions {
+                                max_error_fix_attempts,
+                                max_warning_fix_attempts,
+                                max_translation_attempts,
+                                show_full_output,
+                                skip_test,
+                                dry_run: false,
+                            },
                         )?;
                         run_final_interval_test_if_needed(
                             feature,
@@ -354,6 +1761,9 @@ fn print_previous_progress_summary(stats: &util::TranslationStats) {
             stats.translation_failed_files.len()
         );
     }
+    if !stats.oversized_files.is_empty() {
+        println!("  - Oversized C files: {}", stats.oversized_files.len());
+    }
 }
 
 /// Steps 3 & 4: Scan for files to translate and initialize progress tracking
@@ -361,6 +1771,8 @@ fn step_3_4_select_files_and_init_progress(
     feature: &str,
     _stats: &util::TranslationStats,
     target_file: Option<&str>,
+    reset_progress: bool,
+    dry_run: bool,
 ) -> Result<(std::path::PathBuf, util::ProgressState)> {
     println!(
         "\n{}",
@@ -370,16 +1782,89 @@ fn step_3_4_select_files_and_init_progress(
     // Get rust directory path
     let project_root = util::find_project_root()?;
     let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
-    let progress_state = build_progress_state(&rust_dir, target_file)?;
+    let mut progress_state = build_progress_state(&rust_dir, target_file)?;
     let already_processed = progress_state.processed_count;
     let total_rs_files = progress_state.total_count;
 
     // Display progress
     print_progress_status(already_processed, total_rs_files);
 
+    // A targeted rerun only tracks a single file, so it neither restores nor
+    // overwrites the saved progress of the full session.
+    if target_file.is_none() {
+        restore_saved_progress(feature, &mut progress_state, reset_progress, dry_run)?;
+    }
+
     Ok((rust_dir, progress_state))
 }
 
+/// Restore the session counters saved by a previous, interrupted run.
+///
+/// The saved state is only trusted while its total still matches the freshly
+/// scanned one; otherwise files were added or removed and it is discarded.
+fn restore_saved_progress(
+    feature: &str,
+    progress_state: &mut util::ProgressState,
+    reset_progress: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if reset_progress {
+        if dry_run {
+            println!(
+                "{}",
+                dry_run_line("Would delete saved progress state (--reset-progress)")
+            );
+            return Ok(());
+        }
+        util::ProgressState::clear_from_disk(feature)?;
+        println!(
+            "{}",
+            "✓ Saved progress state reset (--reset-progress)".bright_green()
+        );
+    } else {
+        match util::ProgressState::load_from_disk(feature) {
+            Ok(Some(saved)) if saved.total_count == progress_state.total_count => {
+                progress_state.session_processed_count = saved.session_processed_count;
+                println!(
+                    "{}",
+                    format!(
+                        "Resumed saved progress: {} file(s) processed this session so far",
+                        saved.session_processed_count
+                    )
+                    .cyan()
+                );
+            }
+            Ok(Some(saved)) => {
+                println!(
+                    "{}",
+                    format!(
+                        "⚠ Saved progress state is stale (saved total: {} files, now: {}); discarding it",
+                        saved.total_count, progress_state.total_count
+                    )
+                    .yellow()
+                );
+                if !dry_run {
+                    util::ProgressState::clear_from_disk(feature)?;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "⚠ Warning: failed to load saved progress state, starting fresh: {}",
+                        e
+                    )
+                    .yellow()
+                );
+            }
+        }
+    }
+
+    progress_state.persist = !dry_run;
+    Ok(())
+}
+
 fn build_progress_state(rust_dir: &Path, target_file: Option<&str>) -> Result<util::ProgressState> {
     let (already_processed, total_rs_files) = if let Some(target_file) = target_file {
         let target_path = rust_dir.join(target_file);
@@ -422,19 +1907,56 @@ fn print_progress_status(already_processed: usize, total_rs_files: usize) {
     );
 }
 
+/// Which pending files step 5 works on.
+#[derive(Debug, Clone, Copy)]
+struct FileSelection<'a> {
+    /// Process every pending file instead of asking which ones
+    allow_all: bool,
+    /// Only this file (`--file`), relative to the rust dir
+    target_file: Option<&'a str>,
+    /// Only files matching this glob (`--only`)
+    only_pattern: Option<&'a str>,
+    /// Only files changed since this git revision (`--since`)
+    since: Option<&'a str>,
+    /// Number of files translated concurrently (`--jobs`)
+    jobs: usize,
+}
+
+/// Per-file limits and switches shared by every file of a run.
+#[derive(Debug, Clone, Copy)]
+struct FileRunOptions {
+    max_error_fix_attempts: usize,
+    max_warning_fix_attempts: usize,
+    max_translation_attempts: usize,
+    show_full_output: bool,
+    /// Test configuration is unavailable; hybrid builds run without tests
+    skip_test: bool,
+    /// Only print the translate/build/commit steps instead of running them
+    dry_run: bool,
+}
+
 /// Step 5: Execute translation loop for all files
 fn step_5_execute_translation_loop(
     feature: &str,
     rust_dir: &Path,
     progress_state: &mut util::ProgressState,
-    allow_all: bool,
-    target_file: Option<&str>,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
+    selection: &FileSelection,
+    run: FileRunOptions,
 ) -> Result<()> {
+    let FileSelection {
+        allow_all,
+        target_file,
+        only_pattern,
+        since,
+        jobs,
+    } = *selection;
+    let FileRunOptions {
+        show_full_output,
+        skip_test,
+        dry_run,
+        ..
+    } = run;
     println!(
         "\n{}",
         "Step 5: Execute Translation for All Files"
@@ -442,10 +1964,57 @@ fn step_5_execute_translation_loop(
             .bold()
     );
 
+    // Checked once up front: after the matches are translated, later rescans are expected
+    // to find none.
+    if let Some(pattern) = only_pattern {
+        let matches = filter_only_pattern(
+            file_scanner::find_empty_rs_files(rust_dir)?,
+            rust_dir,
+            Some(pattern),
+        );
+        if matches.is_empty() {
+            anyhow::bail!(
+                "--only {} matches no empty .rs file under {}",
+                pattern,
+                rust_dir.display()
+            );
+        }
+    }
+    if let Some(rev) = since {
+        let changed = filter_changed_since(
+            file_scanner::find_empty_rs_files(rust_dir)?,
+            rust_dir,
+            Some(rev),
+        )?;
+        if changed.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "No empty .rs file changed since {}; nothing to translate.",
+                    rev
+                )
+                .yellow()
+            );
+            return Ok(());
+        }
+    }
+
     // Tracks how many translations have completed since the last test run.
     // Shared across all iterations of the main loop and the skipped-files loop so
     // that the interval is counted consistently across the entire session.
     let mut translations_since_last_test: usize = 0;
+    let mut early_stop = None;
+
+    if !dry_run && target_file.is_none() {
+        resume_interrupted_files(
+            feature,
+            rust_dir,
+            progress_state,
+            stats,
+            &mut translations_since_last_test,
+            run,
+        )?;
+    }
 
     loop {
         // Scan for empty .rs files, then exclude any that have already been skipped
@@ -454,26 +2023,15 @@ fn step_5_execute_translation_loop(
         // reported at the end as unrecoverable and not re-offered.
         // Completed files are already excluded because successfully translated files are
         // non-empty on disk (the existing file-content-based resume mechanism).
-        let all_empty_rs_files = file_scanner::find_empty_rs_files(rust_dir)?;
-        let excluded_set: std::collections::HashSet<&str> = stats
-            .skipped_files
-            .iter()
-            .chain(stats.translation_failed_files.iter())
-            .map(|s| s.as_str())
-            .collect();
-        let empty_rs_files: Vec<_> = all_empty_rs_files
-            .into_iter()
-            .filter(|p| {
-                let rel = p
-                    .strip_prefix(rust_dir)
-                    .ok()
-                    .and_then(|r| r.to_str())
-                    .unwrap_or("");
-                !excluded_set.contains(rel)
-            })
-            .collect();
+        let mut all_empty_rs_files = file_scanner::find_empty_rs_files(rust_dir)?;
+        if dry_run {
+            include_dry_run_target(&mut all_empty_rs_files, rust_dir, target_file);
+        }
+        let empty_rs_files = exclude_skipped_and_failed(all_empty_rs_files, rust_dir, stats);
 
         let empty_rs_files = filter_target_files(empty_rs_files, rust_dir, target_file)?;
+        let empty_rs_files = filter_only_pattern(empty_rs_files, rust_dir, only_pattern);
+        let empty_rs_files = filter_changed_since(empty_rs_files, rust_dir, since)?;
 
         if empty_rs_files.is_empty() {
             if stats.skipped_files.is_empty() {
@@ -487,38 +2045,86 @@ fn step_5_execute_translation_loop(
         // Select files to process
         let selected_indices = select_files_to_process(&empty_rs_files, rust_dir, allow_all)?;
 
+        if jobs > 1 && !dry_run {
+            pretranslate_files_in_parallel(
+                feature,
+                &empty_rs_files,
+                &selected_indices,
+                rust_dir,
+                jobs,
+                show_full_output,
+                stats,
+            );
+        }
+
         // Process each selected file
         process_selected_files(
             feature,
-            &empty_rs_files,
-            &selected_indices,
+            (&empty_rs_files, &selected_indices),
             rust_dir,
             progress_state,
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
-            show_full_output,
             stats,
-            skip_test,
             &mut translations_since_last_test,
+            run,
         )?;
 
-        if target_file.is_some() {
-            break;
+        if !dry_run {
+            early_stop = early_stop_reason();
+            if early_stop.is_some() {
+                break;
+            }
+        }
+        // Under --dry-run the files stay empty, so a rescan would find the same files again.
+        if target_file.is_some() || dry_run {
+            break;
+        }
+    }
+
+    if let Some(reason) = early_stop {
+        save_progress_or_warn(progress_state, feature);
+        let remaining = exclude_skipped_and_failed(
+            file_scanner::find_empty_rs_files(rust_dir)?,
+            rust_dir,
+            stats,
+        )
+        .len();
+        let headline = match reason {
+            EarlyStop::Interrupted => "✋ Stopped after Ctrl-C",
+            EarlyStop::TimeBudget => "⏱ Time budget (--max-duration) reached",
+        };
+        println!(
+            "\n{}",
+            format!(
+                "{}; {} file(s) remain. Re-run to resume.",
+                headline, remaining
+            )
+            .yellow()
+            .bold()
+        );
+    }
+
+    if dry_run {
+        if target_file.is_none() && !stats.skipped_files.is_empty() {
+            println!(
+                "\n{}",
+                dry_run_line(&format!(
+                    "Would offer {} previously skipped file(s) for reprocessing",
+                    stats.skipped_files.len()
+                ))
+            );
         }
+        return Ok(());
     }
 
     // Handle skipped files after the main translation loop
-    if target_file.is_none() {
+    if target_file.is_none() && early_stop.is_none() {
         handle_skipped_files_loop(
             feature,
             rust_dir,
             progress_state,
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
-            show_full_output,
             stats,
-            skip_test,
             &mut translations_since_last_test,
+            run,
         )?;
     }
 
@@ -531,6 +2137,70 @@ fn step_5_execute_translation_loop(
     Ok(())
 }
 
+/// Finish files a previous run was interrupted on (e.g. a crash during `c2rust test`).
+///
+/// Their Rust output is already on disk, so the empty-file scan would not find them;
+/// `process_rs_file` picks up their phase marker and skips the completed phases.
+fn resume_interrupted_files(
+    feature: &str,
+    rust_dir: &Path,
+    progress_state: &mut util::ProgressState,
+    stats: &mut util::TranslationStats,
+    translations_since_last_test: &mut usize,
+    run: FileRunOptions,
+) -> Result<()> {
+    let mut interrupted_files = Vec::new();
+    for (file_name, _) in stats.interrupted_files() {
+        let rs_file = rust_dir.join(&file_name);
+        if rust_file_has_content(&rs_file)? {
+            interrupted_files.push(rs_file);
+        } else {
+            stats.clear_file_phase(&file_name);
+        }
+    }
+    if interrupted_files.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Resuming {} file(s) interrupted during the previous run",
+            interrupted_files.len()
+        )
+        .bright_cyan()
+    );
+    let indices: Vec<usize> = (0..interrupted_files.len()).collect();
+    process_selected_files(
+        feature,
+        (&interrupted_files, &indices),
+        rust_dir,
+        progress_state,
+        stats,
+        translations_since_last_test,
+        FileRunOptions {
+            dry_run: false,
+            ..run
+        },
+    )
+}
+
+/// Under `--dry-run` a non-empty target file is not cleared by `prepare_target_file_rerun`,
+/// so add it to the scanned files to plan the rerun a real run would do.
+fn include_dry_run_target(
+    empty_rs_files: &mut Vec<std::path::PathBuf>,
+    rust_dir: &Path,
+    target_file: Option<&str>,
+) {
+    let Some(target_file) = target_file else {
+        return;
+    };
+    let target_path = rust_dir.join(target_file);
+    if target_path.is_file() && !empty_rs_files.contains(&target_path) {
+        empty_rs_files.push(target_path);
+    }
+}
+
 /// After the main translation loop, repeatedly offer to process any skipped files.
 ///
 /// If the user selects "Process skipped files now", all currently-skipped files are
@@ -541,12 +2211,9 @@ fn handle_skipped_files_loop(
     feature: &str,
     rust_dir: &Path,
     progress_state: &mut util::ProgressState,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
     translations_since_last_test: &mut usize,
+    run: FileRunOptions,
 ) -> Result<()> {
     loop {
         if stats.skipped_files.is_empty() {
@@ -556,17 +2223,19 @@ fn handle_skipped_files_loop(
         let choice = interaction::prompt_skipped_files_choice(&stats.skipped_files)?;
 
         match choice {
-            interaction::SkippedFilesChoice::ProcessNow => process_skipped_files_once(
-                feature,
-                rust_dir,
-                progress_state,
-                max_error_fix_attempts,
-                max_warning_fix_attempts,
-                show_full_output,
-                stats,
-                skip_test,
-                translations_since_last_test,
-            )?,
+            interaction::SkippedFilesChoice::ProcessNow => {
+                process_skipped_files_once(
+                    feature,
+                    rust_dir,
+                    progress_state,
+                    stats,
+                    translations_since_last_test,
+                    run,
+                )?;
+                if early_stop_reason().is_some() {
+                    break;
+                }
+            }
             interaction::SkippedFilesChoice::ExitForLater => break,
         }
     }
@@ -578,12 +2247,9 @@ fn process_skipped_files_once(
     feature: &str,
     rust_dir: &Path,
     progress_state: &mut util::ProgressState,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
     translations_since_last_test: &mut usize,
+    run: FileRunOptions,
 ) -> Result<()> {
     let failed_isolation = BackgroundFailedFileIsolation::activate(
         feature,
@@ -595,12 +2261,9 @@ fn process_skipped_files_once(
         feature,
         rust_dir,
         progress_state,
-        max_error_fix_attempts,
-        max_warning_fix_attempts,
-        show_full_output,
         stats,
-        skip_test,
         translations_since_last_test,
+        run,
     );
     failed_isolation.restore()?;
     result
@@ -610,16 +2273,21 @@ fn process_skipped_files_once_inner(
     feature: &str,
     rust_dir: &Path,
     progress_state: &mut util::ProgressState,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
     translations_since_last_test: &mut usize,
+    run: FileRunOptions,
 ) -> Result<()> {
     let files_to_process = std::mem::take(&mut stats.skipped_files);
     let total = files_to_process.len();
     for idx in 0..files_to_process.len() {
+        if early_stop_reason().is_some() {
+            // Put the files not reached back, so the next run offers them again.
+            for remaining_file in &files_to_process[idx..] {
+                stats.record_file_skipped(remaining_file.clone());
+            }
+            save_stats_or_warn(stats, feature);
+            break;
+        }
         let file_name = files_to_process[idx].clone();
         let rs_file = rust_dir.join(&file_name);
         let pos = idx + 1;
@@ -633,20 +2301,24 @@ fn process_skipped_files_once_inner(
             &files_to_process[idx..],
         )?;
 
-        match process_rs_file(
+        let result = process_rs_file(
             feature,
-            &rs_file,
-            &file_name,
-            pos,
-            total,
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
-            show_full_output,
+            FileJob {
+                rs_file: &rs_file,
+                file_name: &file_name,
+                current_position: pos,
+                total_count: total,
+                skip_interval_test,
+                translation_mode,
+            },
+            FileRunOptions {
+                dry_run: false,
+                ..run
+            },
             stats,
-            skip_test,
-            skip_interval_test,
-            translation_mode,
-        ) {
+        );
+        record_file_done(&file_name, &result, stats);
+        match result {
             Err(e) => {
                 if e.downcast_ref::<verification::SkipFileSignal>().is_some()
                     || e.downcast_ref::<verification::TranslationFailedSignal>().is_some()
@@ -979,8 +2651,11 @@ fn run_final_interval_test_if_needed(
 
     // 在整个序列开始前统一更新一次代码分析，避免 clean/build/test 各自重复更新
     println!("{}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("{}", "✓ Code analysis updated".bright_green());
+    let updated = analyzer::update_code_analysis(feature)?;
+    println!(
+        "{}",
+        format!("✓ {}", analyzer::update_status_message(updated)).bright_green()
+    );
 
     builder::c2rust_clean_no_analysis(feature)?;
 
@@ -1017,7 +2692,7 @@ fn run_final_interval_test_if_needed(
     // Commit any analysis changes produced by clean/build/test above. The commit
     // is non-fatal: if it fails a warning is printed, the working tree may remain
     // dirty, and subsequent analysis commits may include extra unintended changes.
-    git_commit_or_warn(
+    git_commit_or_batch(
         &format!("Update code analysis after final interval test (feature: {})", feature),
         feature,
     );
@@ -1049,6 +2724,44 @@ fn git_commit_or_warn(message: &str, feature: &str) -> bool {
     }
 }
 
+/// Commit now, or fold the commit into the pending `--commit-every` batch
+fn git_commit_or_batch(message: &str, feature: &str) -> bool {
+    !git::defer_to_commit_batch() && git_commit_or_warn(message, feature)
+}
+
+/// Make the batched `--commit-every` commit, if anything is pending
+fn flush_commit_batch(feature: &str) -> bool {
+    match git::take_commit_batch(feature) {
+        Some(message) => git_commit_or_warn(&message, feature),
+        None => false,
+    }
+}
+
+/// Commits the pending `--commit-every` batch when dropped, so a run that ends with an
+/// error (or panics) still commits the files translated so far.
+struct CommitBatchGuard<'a> {
+    feature: &'a str,
+}
+
+impl Drop for CommitBatchGuard<'_> {
+    fn drop(&mut self) {
+        flush_commit_batch(self.feature);
+    }
+}
+
+/// Save the progress state (when persisted) and print a warning if saving fails
+fn save_progress_or_warn(progress_state: &mut util::ProgressState, feature: &str) {
+    if !progress_state.persist {
+        return;
+    }
+    if let Err(e) = progress_state.save_to_disk(feature) {
+        eprintln!(
+            "{}",
+            format!("⚠ Warning: Failed to save progress state: {}", e).yellow()
+        );
+    }
+}
+
 /// Save translation stats and print a warning if saving fails
 fn save_stats_or_warn(stats: &util::TranslationStats, feature: &str) {
     if let Err(e) = stats.save_to_file(feature) {
@@ -1075,6 +2788,142 @@ fn select_files_to_process(
     }
 }
 
+/// Drop files the user skipped, that previously failed to translate or whose C file is too large.
+fn exclude_skipped_and_failed(
+    files: Vec<std::path::PathBuf>,
+    rust_dir: &Path,
+    stats: &util::TranslationStats,
+) -> Vec<std::path::PathBuf> {
+    let excluded_set: std::collections::HashSet<&str> = stats
+        .skipped_files
+        .iter()
+        .chain(stats.translation_failed_files.iter())
+        .chain(stats.oversized_files.iter())
+        .map(|s| s.as_str())
+        .collect();
+    files
+        .into_iter()
+        .filter(|p| {
+            let rel = p
+                .strip_prefix(rust_dir)
+                .ok()
+                .and_then(|r| r.to_str())
+                .unwrap_or("");
+            !excluded_set.contains(rel)
+        })
+        .collect()
+}
+
+/// What a `--confirm` run is about to do, shown before the translation loop.
+#[derive(Debug, Clone, PartialEq)]
+struct RunPlan {
+    feature: String,
+    /// Pending files in processing order, relative to the rust dir.
+    files: Vec<String>,
+    selection: String,
+    jobs: usize,
+    max_translation_attempts: usize,
+    commit_strategy: String,
+    translator: String,
+    build_profiles: Vec<String>,
+}
+
+impl RunPlan {
+    fn collect(
+        feature: &str,
+        rust_dir: &Path,
+        stats: &util::TranslationStats,
+        selection: &FileSelection,
+        max_translation_attempts: usize,
+    ) -> Result<Self> {
+        let FileSelection {
+            allow_all,
+            target_file,
+            only_pattern,
+            since,
+            jobs,
+        } = *selection;
+        let pending = exclude_skipped_and_failed(
+            file_scanner::find_empty_rs_files(rust_dir)?,
+            rust_dir,
+            stats,
+        );
+        let pending = filter_only_pattern(pending, rust_dir, only_pattern);
+        let pending = filter_changed_since(pending, rust_dir, since)?;
+        let files = filter_target_files(pending, rust_dir, target_file)?
+            .iter()
+            .map(|file| relative_rs_file_name(file, rust_dir).to_string())
+            .collect();
+        let mut pending_scope = match only_pattern {
+            Some(pattern) => format!("pending files matching --only {}", pattern),
+            None => "pending files".to_string(),
+        };
+        if let Some(rev) = since {
+            pending_scope.push_str(&format!(" changed since {}", rev));
+        }
+        let selection = match target_file {
+            Some(target_file) => format!("only --file {}", target_file),
+            None if allow_all => format!("all {}", pending_scope),
+            None => format!("chosen interactively from the {}", pending_scope),
+        };
+        let commit_strategy = if git::is_no_commit() {
+            "none (--no-commit)".to_string()
+        } else if git::is_batching_commits() {
+            format!(
+                "one commit per {} files (--commit-every) in .c2rust/{}/",
+                git::commit_every(),
+                feature
+            )
+        } else {
+            format!("one commit per file in .c2rust/{}/", feature)
+        };
+        let build_profiles = std::iter::once(builder::active_build_profile())
+            .chain(builder::extra_verify_profiles())
+            .map(|profile| profile.to_string())
+            .collect();
+        Ok(Self {
+            feature: feature.to_string(),
+            files,
+            selection,
+            jobs,
+            max_translation_attempts,
+            commit_strategy,
+            translator: translator::describe_translator_backend(feature),
+            build_profiles,
+        })
+    }
+
+    fn render(&self) -> String {
+        let mut text = format!("Run plan for feature: {}\n", self.feature);
+        text.push_str(&format!(
+            "  Files:       {} pending, {}\n",
+            self.files.len(),
+            self.selection
+        ));
+        for (index, file) in self.files.iter().enumerate() {
+            text.push_str(&format!("    {:>3}. {}\n", index + 1, file));
+        }
+        text.push_str(&format!(
+            "  Parallelism: {} job(s), up to {} translation attempt(s) per file\n",
+            self.jobs, self.max_translation_attempts
+        ));
+        text.push_str(&format!(
+            "  Profiles:    {}\n",
+            self.build_profiles.join(", ")
+        ));
+        text.push_str(&format!("  Commits:     {}\n", self.commit_strategy));
+        text.push_str(&format!("  Translator:  {}\n", self.translator));
+        text
+    }
+}
+
+/// Show `plan` and ask `confirm` whether the translation loop may start.
+fn confirm_run_plan(plan: &RunPlan, confirm: impl FnOnce() -> Result<bool>) -> Result<bool> {
+    println!("\n{}", "Run Plan".bright_cyan().bold());
+    print!("{}", plan.render());
+    confirm()
+}
+
 fn filter_target_files(
     empty_rs_files: Vec<std::path::PathBuf>,
     rust_dir: &Path,
@@ -1113,17 +2962,64 @@ fn filter_target_files(
     );
 }
 
+/// Keep the files whose path relative to `rust_dir` matches the `--only` glob.
+fn filter_only_pattern(
+    files: Vec<std::path::PathBuf>,
+    rust_dir: &Path,
+    only_pattern: Option<&str>,
+) -> Vec<std::path::PathBuf> {
+    let Some(pattern) = only_pattern else {
+        return files;
+    };
+    files
+        .into_iter()
+        .filter(|path| {
+            let relative = relative_rs_file_name(path, rust_dir).replace('\\', "/");
+            file_scanner::glob_matches(pattern, &relative)
+        })
+        .collect()
+}
+
+/// Keep only the files that `git diff` reports as changed since `since`
+fn filter_changed_since(
+    files: Vec<std::path::PathBuf>,
+    rust_dir: &Path,
+    since: Option<&str>,
+) -> Result<Vec<std::path::PathBuf>> {
+    let Some(rev) = since else {
+        return Ok(files);
+    };
+    let changed: std::collections::HashSet<String> = git::changed_files_since(rev, rust_dir)?
+        .into_iter()
+        .collect();
+    Ok(keep_changed_files(files, rust_dir, &changed))
+}
+
+fn keep_changed_files(
+    files: Vec<std::path::PathBuf>,
+    rust_dir: &Path,
+    changed: &std::collections::HashSet<String>,
+) -> Vec<std::path::PathBuf> {
+    files
+        .into_iter()
+        .filter(|path| changed.contains(&relative_rs_file_name(path, rust_dir).replace('\\', "/")))
+        .collect()
+}
+
 fn prepare_target_file_rerun(
     feature: &str,
     target_file: &str,
     stats: &mut util::TranslationStats,
+    dry_run: bool,
 ) -> Result<()> {
     let project_root = util::find_project_root()?;
     let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
     let target_path = rust_dir.join(target_file);
 
     stats.clear_target_history(target_file);
-    save_stats_or_warn(stats, feature);
+    if !dry_run {
+        save_stats_or_warn(stats, feature);
+    }
 
     if !target_path.exists() {
         return Ok(());
@@ -1134,6 +3030,17 @@ fn prepare_target_file_rerun(
         return Ok(());
     }
 
+    if dry_run {
+        println!(
+            "{}",
+            dry_run_line(&format!(
+                "Would clear previous Rust output of the target file for rerun: {}",
+                target_file
+            ))
+        );
+        return Ok(());
+    }
+
     println!(
         "{}",
         format!(
@@ -1149,76 +3056,229 @@ fn prepare_target_file_rerun(
 }
 
 /// Process all selected files
+///
+/// `(files, selected_indices)` are the scanned files and the indices chosen from them.
 fn process_selected_files(
     feature: &str,
-    empty_rs_files: &[std::path::PathBuf],
-    selected_indices: &[usize],
+    (empty_rs_files, selected_indices): (&[std::path::PathBuf], &[usize]),
     rust_dir: &Path,
     progress_state: &mut util::ProgressState,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
     translations_since_last_test: &mut usize,
+    run: FileRunOptions,
 ) -> Result<()> {
+    let dry_run = run.dry_run;
     for &idx in selected_indices.iter() {
+        if !dry_run && early_stop_reason().is_some() {
+            break;
+        }
         let rs_file = &empty_rs_files[idx];
         let current_position = progress_state.get_current_position();
         let total_count = progress_state.get_total_count();
 
-        let file_name = rs_file
-            .strip_prefix(rust_dir)
-            .ok()
-            .and_then(|p| p.to_str())
-            .unwrap_or_else(|| {
-                rs_file
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("<unknown>")
-            });
+        let file_name = relative_rs_file_name(rs_file, rust_dir);
+
+        // The list was scanned before earlier files were processed; a file that has since
+        // been removed or filled (e.g. by the user) is skipped instead of aborting the run.
+        if !dry_run {
+            if let Some(reason) = stale_scan_reason(rs_file, file_name, stats) {
+                println!(
+                    "{}",
+                    format!("│ ⚠ Skipping {}: it {} since the scan", file_name, reason).yellow()
+                );
+                continue;
+            }
+        }
 
         print_file_processing_header(current_position, total_count, file_name);
 
         let (_, skip_interval_test) =
             compute_interval_test_decision(*translations_since_last_test);
 
-        match process_rs_file(
+        let result = process_rs_file(
             feature,
-            rs_file,
-            file_name,
-            current_position,
-            total_count,
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
-            show_full_output,
+            FileJob {
+                rs_file,
+                file_name,
+                current_position,
+                total_count,
+                skip_interval_test,
+                translation_mode: TranslationInputMode::TranslateFromC,
+            },
+            run,
             stats,
-            skip_test,
-            skip_interval_test,
-            TranslationInputMode::TranslateFromC,
-        ) {
+        );
+        if !dry_run {
+            record_file_done(file_name, &result, stats);
+        }
+        match result {
             Err(e) => {
                 if e.downcast_ref::<verification::SkipFileSignal>().is_none()
                     && e.downcast_ref::<verification::TranslationFailedSignal>().is_none()
                 {
                     return Err(e);
                 }
-                // File was skipped (deliberate) or translation failed (non-fatal).
-                // Already recorded in process_rs_file. Don't mark as processed.
-                // Save stats immediately so the outcome is persisted.
-                save_stats_or_warn(stats, feature);
-            }
-            Ok(tests_ran) => {
-                // Mark file as processed. mark_processed() is capped at total_count.
-                progress_state.mark_processed();
-                update_interval_counter(translations_since_last_test, tests_ran);
-                // Save stats immediately after successful completion.
-                save_stats_or_warn(stats, feature);
-                maybe_run_periodic_git_gc(progress_state);
+                // File was skipped (deliberate) or translation failed (non-fatal).
+                // Already recorded in process_rs_file. Don't mark as processed.
+                // Save stats immediately so the outcome is persisted.
+                save_stats_or_warn(stats, feature);
+            }
+            Ok(tests_ran) => {
+                // Mark file as processed. mark_processed() is capped at total_count.
+                progress_state.mark_processed();
+                if dry_run {
+                    continue;
+                }
+                update_interval_counter(translations_since_last_test, tests_ran);
+                // Save stats immediately after successful completion.
+                save_stats_or_warn(stats, feature);
+                save_progress_or_warn(progress_state, feature);
+                maybe_run_periodic_git_gc(progress_state);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Why a scanned empty file should no longer be processed, or `None` if it still needs translation.
+///
+/// Files pre-translated by `--jobs` are non-empty on purpose; their `Translated` phase marker
+/// keeps them from being reported as filled.
+fn stale_scan_reason(
+    rs_file: &Path,
+    file_name: &str,
+    stats: &util::TranslationStats,
+) -> Option<&'static str> {
+    match std::fs::metadata(rs_file) {
+        Err(_) => Some("no longer exists"),
+        Ok(metadata) if metadata.len() > 0 && stats.file_phase(file_name).is_none() => {
+            Some("is no longer empty")
+        }
+        Ok(_) => None,
+    }
+}
+
+/// Path of `rs_file` relative to the rust dir, as used for stats keys and progress output.
+fn relative_rs_file_name<'a>(rs_file: &'a Path, rust_dir: &Path) -> &'a str {
+    rs_file
+        .strip_prefix(rust_dir)
+        .ok()
+        .and_then(|p| p.to_str())
+        .unwrap_or_else(|| {
+            rs_file
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("<unknown>")
+        })
+}
+
+/// Translate the selected files concurrently (`--jobs N`) ahead of the sequential pass.
+///
+/// Only the translator runs in parallel; builds share the rust dir and stay sequential.
+/// Each successful translation gets the `Translated` phase marker, so `process_rs_file`
+/// resumes it at the build/fix phase instead of translating it again. Files whose
+/// translation fails here are left as they are and get translated again sequentially,
+/// where the usual failure handling applies.
+fn pretranslate_files_in_parallel(
+    feature: &str,
+    empty_rs_files: &[std::path::PathBuf],
+    selected_indices: &[usize],
+    rust_dir: &Path,
+    jobs: usize,
+    show_full_output: bool,
+    stats: &mut util::TranslationStats,
+) {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let total = selected_indices.len();
+    let workers = jobs.min(total);
+    if workers == 0 {
+        return;
+    }
+    println!(
+        "\n{}",
+        format!(
+            "Translating {} file(s) with {} parallel job(s)...",
+            total, workers
+        )
+        .bright_cyan()
+        .bold()
+    );
+
+    let next_slot = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let results = std::sync::Mutex::new(Vec::with_capacity(total));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                // After Ctrl-C or once --max-duration is up no new file is sent to the
+                // translator; running ones finish.
+                if early_stop_reason().is_some() {
+                    break;
+                }
+                let slot = next_slot.fetch_add(1, Ordering::SeqCst);
+                let Some(&idx) = selected_indices.get(slot) else {
+                    break;
+                };
+                let rs_file = &empty_rs_files[idx];
+                let file_name = relative_rs_file_name(rs_file, rust_dir);
+                let format_progress =
+                    |operation: &str| format!("[parallel] {} - {}", file_name, operation);
+                let result = extract_and_validate_file_info(rs_file).and_then(|(file_type, _)| {
+                    check_c_file_exists(rs_file)?;
+                    translate_file(
+                        feature,
+                        file_type,
+                        rs_file,
+                        &format_progress,
+                        show_full_output,
+                        false,
+                    )
+                });
+                let done = finished.fetch_add(1, Ordering::SeqCst) + 1;
+                let status = if result.is_ok() {
+                    "✓ translated".bright_green()
+                } else {
+                    "⚠ translation failed".yellow()
+                };
+                println!("[{}/{}] {} {}", done, total, file_name, status);
+                results
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push((idx, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|(idx, _)| *idx);
+    let mut translated = 0usize;
+    for (idx, result) in results {
+        let file_name = relative_rs_file_name(&empty_rs_files[idx], rust_dir);
+        match result {
+            Ok(()) => {
+                stats.record_file_phase(file_name, util::FilePhase::Translated);
+                translated += 1;
             }
+            Err(e) => println!(
+                "{}",
+                format!(
+                    "⚠ Parallel translation of {} failed, it will be retried sequentially: {:#}",
+                    file_name, e
+                )
+                .yellow()
+            ),
         }
     }
-    Ok(())
+    save_stats_or_warn(stats, feature);
+    println!(
+        "{}",
+        format!(
+            "✓ Parallel translation finished: {}/{} file(s) translated; building and fixing sequentially...",
+            translated, total
+        )
+        .bright_green()
+    );
 }
 
 // ============================================================================
@@ -1246,26 +3306,76 @@ fn print_file_processing_header(current_position: usize, total_count: usize, fil
         current_position, total_count, file_name
     );
     println!("\n{}", progress_msg.bright_magenta().bold());
+    events::emit(&events::Event::FileStart {
+        file: file_name,
+        index: current_position,
+        total: total_count,
+    });
+    report::begin_file(file_name);
+    summary::begin_file();
+    interaction::reset_failure_retry();
+}
+
+/// Record the outcome of [`process_rs_file`] in the run report and emit `file_done`.
+fn record_file_done(file_name: &str, result: &Result<bool>, stats: &util::TranslationStats) {
+    let completed = stats
+        .file_attempts
+        .get(file_name)
+        .map(|stat| (stat.translation_attempts, stat.fix_attempts));
+    let (outcome, completed) = match result {
+        Ok(_) => (events::FileOutcome::Completed, completed),
+        Err(e) if e.downcast_ref::<verification::SkipFileSignal>().is_some() => {
+            (events::FileOutcome::Skipped, None)
+        }
+        Err(_) => (events::FileOutcome::Failed, None),
+    };
+    let report_outcome = report::record_file(
+        file_name,
+        completed,
+        outcome == events::FileOutcome::Skipped,
+    );
+    summary::finish_file(
+        file_name,
+        completed.map(|(attempts, _)| attempts),
+        report_outcome,
+    );
+    events::emit(&events::Event::FileDone {
+        file: file_name,
+        outcome,
+        attempts: completed.map(|(attempts, _)| attempts),
+    });
 }
 
 // ============================================================================
 // Single File Translation Workflow
 // ============================================================================
 
+/// A file handed to [`process_rs_file`].
+#[derive(Debug, Clone, Copy)]
+struct FileJob<'a> {
+    /// Path to the .rs file to process
+    rs_file: &'a Path,
+    /// Display name of the file, relative to the rust dir
+    file_name: &'a str,
+    /// Current file position in the overall workflow
+    current_position: usize,
+    /// Total number of files to process
+    total_count: usize,
+    /// Tests are deferred by `C2RUST_TEST_INTERVAL` for this file
+    skip_interval_test: bool,
+    translation_mode: TranslationInputMode,
+}
+
 /// Process a single .rs file through the translation workflow
 ///
-/// Attempts translation up to MAX_TRANSLATION_ATTEMPTS times, with each attempt
+/// Attempts translation up to `max_translation_attempts` times, with each attempt
 /// including: translation → build → fix (if needed) → hybrid tests → commit
 ///
 /// # Arguments
 /// * `feature` - Feature name
-/// * `rs_file` - Path to the .rs file to process
-/// * `file_name` - Display name of the file
-/// * `current_position` - Current file position in the overall workflow
-/// * `total_count` - Total number of files to process
-/// * `max_error_fix_attempts` - Maximum number of build-error fix attempts per translation
-/// * `max_warning_fix_attempts` - Maximum number of warning-fix attempts per translation
-/// * `show_full_output` - Whether to show full output
+/// * `job` - The file to process and its position in the overall workflow
+/// * `run` - Fix/translation attempt limits and run-wide switches
+/// * `stats` - Translation stats updated with the file's outcome
 ///
 /// # Returns
 /// * `Ok(tests_ran)` - File processed successfully; `true` when the test suite executed
@@ -1273,27 +3383,39 @@ fn print_file_processing_header(current_position: usize, total_count: usize, fil
 /// * `Err` - Processing failed after all retry attempts
 fn process_rs_file(
     feature: &str,
-    rs_file: &Path,
-    file_name: &str,
-    current_position: usize,
-    total_count: usize,
-    max_error_fix_attempts: usize,
-    max_warning_fix_attempts: usize,
-    show_full_output: bool,
+    job: FileJob,
+    run: FileRunOptions,
     stats: &mut util::TranslationStats,
-    skip_test: bool,
-    skip_interval_test: bool,
-    translation_mode: TranslationInputMode,
 ) -> Result<bool> {
-    use util::MAX_TRANSLATION_ATTEMPTS;
-
+    let FileJob {
+        rs_file,
+        file_name,
+        current_position,
+        total_count,
+        skip_interval_test,
+        translation_mode,
+    } = job;
+    let FileRunOptions {
+        max_error_fix_attempts,
+        max_warning_fix_attempts,
+        max_translation_attempts,
+        show_full_output,
+        skip_test,
+        dry_run,
+    } = run;
     let mut total_fix_attempts = 0usize;
     let mut had_restart = false;
+    // Suggestions entered while this file is processed are kept per file.
+    let _suggestion_scope = if dry_run {
+        None
+    } else {
+        Some(suggestion::FileScope::enter(feature, file_name)?)
+    };
 
-    for attempt_number in 1..=MAX_TRANSLATION_ATTEMPTS {
-        let is_last_attempt = attempt_number == MAX_TRANSLATION_ATTEMPTS;
+    for attempt_number in 1..=max_translation_attempts {
+        let is_last_attempt = attempt_number == max_translation_attempts;
 
-        print_attempt_header(attempt_number, rs_file);
+        print_attempt_header(attempt_number, max_translation_attempts, rs_file);
 
         if attempt_number > 1 {
             println!(
@@ -1305,7 +3427,32 @@ fn process_rs_file(
 
         // Extract file information and validate
         let (file_type, _name) = extract_and_validate_file_info(rs_file)?;
-        check_c_file_exists(rs_file)?;
+        match check_c_file_exists(rs_file) {
+            // Asked (and allowed) once on the first attempt; retries go ahead.
+            Err(e) if attempt_number > 1 && e.is::<CFileTooLargeError>() => {}
+            Err(e) => {
+                let Some(too_large) = e.downcast_ref::<CFileTooLargeError>() else {
+                    return Err(e);
+                };
+                if !interaction::prompt_translate_oversized_file(too_large.size, too_large.limit)? {
+                    println!(
+                        "│ {}",
+                        format!("Skipping file: {}", too_large).bright_yellow()
+                    );
+                    if !dry_run {
+                        report::note_skip_reason(&format!(
+                            "C file too large ({} bytes > {} bytes)",
+                            too_large.size, too_large.limit
+                        ));
+                        // Not a skip the user can retry in this run: the size won't change,
+                        // so keep it out of the "Process skipped files now" menu.
+                        stats.record_file_oversized(file_name.to_string());
+                    }
+                    return Err(verification::SkipFileSignal.into());
+                }
+            }
+            Ok(()) => {}
+        }
 
         // Create progress formatter
         let format_progress = |operation: &str| {
@@ -1315,6 +3462,42 @@ fn process_rs_file(
             )
         };
 
+        // A file interrupted by a previous run re-enters at its earliest unfinished
+        // phase, reusing the Rust output already on disk.
+        let resume_entry = if attempt_number == 1 && !dry_run {
+            pending_resume_entry(stats, file_name, rs_file)?
+        } else {
+            None
+        };
+        let skip_build_phases = matches!(
+            resume_entry,
+            Some(ResumeEntry::HybridTest | ResumeEntry::Commit)
+        );
+        let attempt_mode = if resume_entry.is_some() {
+            TranslationInputMode::ReuseExistingRust
+        } else {
+            translation_mode
+        };
+
+        if attempt_number > 1 && !dry_run {
+            let rust_dir = util::find_project_root()?
+                .join(".c2rust")
+                .join(feature)
+                .join("rust");
+            if let Some(path) = backup::backup_before_retry(&rust_dir, rs_file, attempt_number - 1)?
+            {
+                println!(
+                    "│ {}",
+                    format!(
+                        "Previous translation saved to {} (restore with --restore-attempt {})",
+                        path.display(),
+                        attempt_number - 1
+                    )
+                    .dimmed()
+                );
+            }
+        }
+
         // Translate C to Rust.
         // Only `TranslationScriptFailedError` (translate script non-zero exit) is treated
         // as a non-fatal translation failure.  All other errors (missing project root,
@@ -1326,13 +3509,11 @@ fn process_rs_file(
             rs_file,
             &format_progress,
             show_full_output,
-            translation_mode,
+            attempt_mode,
+            dry_run,
         ) {
             if e.downcast_ref::<translator::TranslationScriptFailedError>().is_some() {
-                println!(
-                    "│ {}",
-                    format!("⚠ Translation failed: {:#}", e).yellow()
-                );
+                ui::warning(format!("Translation failed: {:#}", e));
                 stash_skipped_file_for_later(feature, file_name, rs_file)?;
                 println!(
                     "│ {}",
@@ -1346,26 +3527,54 @@ fn process_rs_file(
             }
         }
 
+        if dry_run {
+            print_dry_run_build_plan(
+                feature,
+                file_name,
+                max_error_fix_attempts,
+                max_warning_fix_attempts,
+                skip_test || skip_interval_test,
+            );
+            return Ok(false);
+        }
+
+        if resume_entry.is_none() {
+            stats.record_file_phase(file_name, util::FilePhase::Translated);
+            save_stats_or_warn(stats, feature);
+        }
+
         // Phase 1: Build and fix errors (warnings suppressed via RUSTFLAGS="-A warnings")
-        println!("│");
-        println!(
-            "│ {}",
-            "Phase 1: Building and fixing errors..."
-                .bright_blue()
-                .bold()
-        );
-        let build_loop_result = verification::execute_code_error_check_with_fix_loop(
-            feature,
-            file_type,
-            rs_file,
-            file_name,
-            &format_progress,
-            is_last_attempt,
-            attempt_number,
-            max_error_fix_attempts,
-            show_full_output,
-            skip_test,
-        );
+        ui::blank();
+        let build_loop_result = if skip_build_phases {
+            println!(
+                "│ {}",
+                "Phase 1: Skipped (build already succeeded before the interruption)."
+                    .bright_yellow()
+            );
+            Ok((true, 0, false))
+        } else {
+            println!(
+                "│ {}",
+                "Phase 1: Building and fixing errors..."
+                    .bright_blue()
+                    .bold()
+            );
+            verification::execute_code_error_check_with_fix_loop(
+                feature,
+                file_type,
+                rs_file,
+                file_name,
+                &format_progress,
+                verification::FixLoopLimits {
+                    attempt_number,
+                    is_last_attempt,
+                    max_error_fix_attempts,
+                    max_translation_attempts,
+                    show_full_output,
+                    skip_test,
+                },
+            )
+        };
 
         // Check if the user chose to skip this file
         if let Err(ref e) = build_loop_result {
@@ -1390,9 +3599,15 @@ fn process_rs_file(
 
         if build_successful {
             // Phase 2: Fix warnings after all errors are resolved
-            // (skipped when C2RUST_PROCESS_WARNINGS=0/false or max_warning_fix_attempts=0)
-            if should_process_warnings() && max_warning_fix_attempts > 0 {
-                println!("│");
+            // (skipped when C2RUST_PROCESS_WARNINGS=0/false, max_warning_fix_attempts=0,
+            // or `--warnings ignore|fail`)
+            let warning_mode = verification::warning_mode();
+            if !skip_build_phases
+                && should_process_warnings()
+                && max_warning_fix_attempts > 0
+                && warning_mode.runs_warning_phase()
+            {
+                ui::blank();
                 println!(
                     "│ {}",
                     "Phase 2: Checking and fixing warnings..."
@@ -1409,16 +3624,22 @@ fn process_rs_file(
                     show_full_output,
                 )
                 .unwrap_or_else(|e| {
-                    println!(
-                        "│ {}",
-                        format!("⚠ Warning phase encountered an error: {}", e).yellow()
-                    );
+                    ui::warning(format!("Warning phase encountered an error: {}", e));
                     0
                 });
                 total_fix_attempts += warning_fix_attempts;
             } else {
-                println!("│");
-                let reason = if !should_process_warnings() {
+                ui::blank();
+                let reason = if skip_build_phases {
+                    "already completed before the interruption"
+                } else if !warning_mode.runs_warning_phase() {
+                    match warning_mode {
+                        verification::WarningMode::Fail => {
+                            "--warnings fail: warnings were fixed as errors"
+                        }
+                        _ => "--warnings ignore",
+                    }
+                } else if !should_process_warnings() {
                     "C2RUST_PROCESS_WARNINGS=0/false"
                 } else {
                     "max_warning_fix_attempts=0"
@@ -1430,15 +3651,60 @@ fn process_rs_file(
                 );
             }
 
-            let (processing_complete, tests_ran) = match complete_file_processing(
-                feature,
-                file_name,
-                file_type,
-                rs_file,
-                &format_progress,
-                skip_test,
-                skip_interval_test,
-            ) {
+            if !skip_build_phases {
+                check_unsafe_ratio(
+                    feature,
+                    file_type,
+                    rs_file,
+                    &format_progress,
+                    show_full_output,
+                )?;
+                stats.record_file_phase(file_name, util::FilePhase::Built);
+                save_stats_or_warn(stats, feature);
+            }
+
+            let commit_info = TranslationCommitInfo {
+                attempts: attempt_number,
+                outcome: if total_fix_attempts > 0 {
+                    TranslationOutcome::Fixed
+                } else {
+                    TranslationOutcome::Translated
+                },
+            };
+            let processing_result = if resume_entry == Some(ResumeEntry::Commit) {
+                ui::blank();
+                println!(
+                    "│ {}",
+                    "Hybrid build tests already passed before the interruption; skipping to acceptance."
+                        .bright_yellow()
+                );
+                handle_successful_tests(
+                    feature,
+                    file_name,
+                    file_type,
+                    rs_file,
+                    &format_progress,
+                    TestStatus::Passed,
+                    commit_info,
+                )
+                .map(|tests_ran| (true, tests_ran))
+            } else {
+                complete_file_processing(
+                    feature,
+                    file_name,
+                    file_type,
+                    rs_file,
+                    &format_progress,
+                    HybridTestAttempt {
+                        skip_test,
+                        skip_interval_test,
+                        commit_info,
+                        is_last_attempt,
+                    },
+                    stats,
+                )
+            };
+            let (processing_complete, tests_ran) = match processing_result {
                 Ok(result) => result,
                 Err(e) => {
                     if e.downcast_ref::<verification::SkipFileSignal>().is_some() {
@@ -1465,6 +3731,126 @@ fn process_rs_file(
     anyhow::bail!("Unexpected: all retry attempts completed without resolution")
 }
 
+/// Where a file interrupted by a previous run re-enters `process_rs_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResumeEntry {
+    /// Reuse the Rust output and run the build/fix phases.
+    BuildAndFix,
+    /// Skip the build/fix phases and run the hybrid build tests.
+    HybridTest,
+    /// Tests already passed; go straight to acceptance and commit.
+    Commit,
+}
+
+/// Map the last completed phase to the earliest unfinished one.
+fn resume_entry_for_phase(phase: util::FilePhase) -> ResumeEntry {
+    match phase {
+        util::FilePhase::Translated => ResumeEntry::BuildAndFix,
+        util::FilePhase::Built => ResumeEntry::HybridTest,
+        util::FilePhase::Tested => ResumeEntry::Commit,
+    }
+}
+
+/// Resume entry for a file with a persisted phase marker, if its Rust output is still on disk.
+///
+/// A stale marker for an empty file is dropped so the file is translated from scratch.
+fn pending_resume_entry(
+    stats: &mut util::TranslationStats,
+    file_name: &str,
+    rs_file: &Path,
+) -> Result<Option<ResumeEntry>> {
+    let Some(phase) = stats.file_phase(file_name) else {
+        return Ok(None);
+    };
+    if !rust_file_has_content(rs_file)? {
+        stats.clear_file_phase(file_name);
+        return Ok(None);
+    }
+    println!(
+        "│ {}",
+        format!(
+            "Resuming interrupted file (last completed phase: {})",
+            phase.as_str()
+        )
+        .bright_cyan()
+    );
+    Ok(Some(resume_entry_for_phase(phase)))
+}
+
+/// Print the build, fix and commit steps `process_rs_file` would run under `--dry-run`.
+fn print_dry_run_build_plan(
+    feature: &str,
+    file_name: &str,
+    max_error_fix_attempts: usize,
+    max_warning_fix_attempts: usize,
+    tests_skipped: bool,
+) {
+    let warning_mode = verification::warning_mode();
+    println!(
+        "│ {}",
+        dry_run_line(&format!(
+            "Phase 1: would run `{}` and fix errors (up to {} attempt(s))",
+            builder::describe_cargo_command(
+                feature,
+                "check",
+                warning_mode.suppresses_warnings_in_error_phase()
+            ),
+            max_error_fix_attempts
+        ))
+    );
+    if should_process_warnings()
+        && max_warning_fix_attempts > 0
+        && warning_mode.runs_warning_phase()
+    {
+        println!(
+            "│ {}",
+            dry_run_line(&format!(
+                "Phase 2: would run `{}` and fix warnings (up to {} attempt(s))",
+                builder::describe_cargo_command(feature, "check", false),
+                max_warning_fix_attempts
+            ))
+        );
+    } else {
+        println!(
+            "│ {}",
+            dry_run_line("Phase 2: warning processing would be skipped")
+        );
+    }
+
+    let mut profiles = vec![builder::active_build_profile()];
+    profiles.extend(builder::extra_verify_profiles());
+    let profiles: Vec<&str> = profiles.iter().map(|profile| profile.as_str()).collect();
+    println!(
+        "│ {}",
+        dry_run_line(&format!(
+            "Hybrid build: would run `{}` then c2rust {} (profiles: {})",
+            builder::describe_cargo_command(feature, "build", true),
+            if tests_skipped {
+                "clean/build"
+            } else {
+                "clean/build/test"
+            },
+            profiles.join(", ")
+        ))
+    );
+
+    let message = build_translation_commit_message(
+        feature,
+        file_name,
+        TranslationCommitInfo {
+            attempts: 1,
+            outcome: TranslationOutcome::Translated,
+        },
+    );
+    println!(
+        "│ {}",
+        dry_run_line(&format!(
+            "Would commit: {}",
+            message.lines().next().unwrap_or_default()
+        ))
+    );
+}
+
 /// Revert a skipped/failed translation back to an empty placeholder file.
 ///
 /// The workflow uses empty `fun_*.rs` / `var_*.rs` files to represent pending
@@ -1559,7 +3945,7 @@ pub(crate) fn get_test_interval() -> usize {
 fn compute_interval_test_decision(translations_since_last_test: usize) -> (bool, bool) {
     let interval = get_test_interval();
     let proposed_count = translations_since_last_test.saturating_add(1);
-    let should_run_test = proposed_count % interval == 0;
+    let should_run_test = proposed_count.is_multiple_of(interval);
     (should_run_test, !should_run_test)
 }
 
@@ -1580,10 +3966,10 @@ fn update_interval_counter(translations_since_last_test: &mut usize, tests_ran:
 // ============================================================================
 
 /// Print header for translation attempt
-fn print_attempt_header(attempt_number: usize, rs_file: &Path) {
+fn print_attempt_header(attempt_number: usize, max_translation_attempts: usize, rs_file: &Path) {
     if attempt_number > 1 {
         let retry_number = attempt_number - 1;
-        let max_retries = util::MAX_TRANSLATION_ATTEMPTS - 1;
+        let max_retries = max_translation_attempts.saturating_sub(1);
         println!(
             "\n{}",
             format!(
@@ -1624,17 +4010,27 @@ fn extract_and_validate_file_info(rs_file: &Path) -> Result<(&'static str, &str)
 }
 
 /// Check if corresponding C source file exists
+///
+/// Fails with [`CFileTooLargeError`] when the C file is larger than `--max-c-file-bytes`.
 fn check_c_file_exists(rs_file: &Path) -> Result<()> {
     let c_file = rs_file.with_extension("c");
 
     match std::fs::metadata(&c_file) {
-        Ok(_) => {
+        Ok(metadata) => {
             println!(
                 "│ {} {}",
                 "C source:".cyan(),
                 c_file.display().to_string().bright_yellow()
             );
-            Ok(())
+            match max_c_file_bytes() {
+                Some(limit) if metadata.len() > limit => Err(CFileTooLargeError {
+                    c_file,
+                    size: metadata.len(),
+                    limit,
+                }
+                .into()),
+                _ => Ok(()),
+            }
         }
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
             anyhow::bail!(
@@ -1661,20 +4057,23 @@ fn run_translation_phase<F>(
     format_progress: &F,
     show_full_output: bool,
     translation_mode: TranslationInputMode,
+    dry_run: bool,
 ) -> Result<()>
 where
     F: Fn(&str) -> String,
 {
     match translation_mode {
-        TranslationInputMode::TranslateFromC => {
-            translate_file(feature, file_type, rs_file, format_progress, show_full_output)
-        }
+        TranslationInputMode::TranslateFromC => translate_file(
+            feature,
+            file_type,
+            rs_file,
+            format_progress,
+            show_full_output,
+            dry_run,
+        ),
         TranslationInputMode::ReuseExistingRust => {
-            println!("│");
-            println!(
-                "│ {}",
-                format_progress("Reuse Existing Rust").bright_magenta().bold()
-            );
+            ui::blank();
+            ui::header(format_progress("Reuse Existing Rust"));
             println!(
                 "│ {}",
                 "Reusing previously stashed Rust output for skipped-file recovery."
@@ -1718,17 +4117,15 @@ fn translate_file<F>(
     rs_file: &Path,
     format_progress: &F,
     show_full_output: bool,
+    dry_run: bool,
 ) -> Result<()>
 where
     F: Fn(&str) -> String,
 {
     let c_file = rs_file.with_extension("c");
 
-    println!("│");
-    println!(
-        "│ {}",
-        format_progress("Translation").bright_magenta().bold()
-    );
+    ui::blank();
+    ui::header(format_progress("Translation"));
     println!(
         "│ {}",
         format!("Translating {} to Rust...", file_type)
@@ -1736,7 +4133,29 @@ where
             .bold()
     );
 
-    translator::translate_c_to_rust(feature, file_type, &c_file, rs_file, show_full_output)?;
+    if dry_run {
+        println!(
+            "│ {}",
+            dry_run_line(&format!(
+                "Would run: {}",
+                translator::describe_translate_command(feature, file_type, &c_file, rs_file)
+            ))
+        );
+        return Ok(());
+    }
+
+    let start_time = Instant::now();
+    let translate_result =
+        translator::translate_c_to_rust(feature, file_type, &c_file, rs_file, show_full_output);
+    events::emit(&events::Event::Translate {
+        file: rs_file
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("<unknown>"),
+        result: events::EventResult::from_ok(translate_result.is_ok()),
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    });
+    translate_result?;
 
     // Verify translation produced output
     let metadata = std::fs::metadata(rs_file)?;
@@ -1745,12 +4164,9 @@ where
     }
 
     try_collapse_exported_function_unsafe_regions(rs_file)?;
-    try_normalize_c_char_literal_ptrs(rs_file)?;
-
-    println!(
-        "│ {}",
-        format!("✓ Translation complete ({} bytes)", metadata.len()).bright_green()
-    );
+    try_normalize_c_char_literal_ptrs(rs_file)?;
+
+    ui::success(format!("Translation complete ({} bytes)", metadata.len()));
 
     Ok(())
 }
@@ -1773,14 +4189,11 @@ where
             .yellow()
             .bold()
     );
-    println!("│");
-    println!("│ {}", format_progress("Fix").bright_magenta().bold());
+    ui::blank();
+    ui::header(format_progress("Fix"));
 
     if try_apply_local_build_error_fix(rs_file, &build_error.to_string())? {
-        println!(
-            "│ {}",
-            "✓ Applied local compiler-error fix".bright_green()
-        );
+        ui::success("Applied local compiler-error fix");
         return Ok(());
     }
 
@@ -1801,7 +4214,7 @@ where
         anyhow::bail!("Fix failed: output file is empty");
     }
 
-    println!("│ {}", "✓ Fix applied".bright_green());
+    ui::success("Fix applied");
 
     Ok(())
 }
@@ -1822,6 +4235,237 @@ fn try_apply_local_build_error_fix(rs_file: &Path, build_error: &str) -> Result<
     Ok(false)
 }
 
+/// When the `--max-duration` budget runs out; `None` means no budget.
+static RUN_DEADLINE: std::sync::Mutex<Option<std::time::Instant>> = std::sync::Mutex::new(None);
+
+fn set_run_deadline(deadline: Option<std::time::Instant>) {
+    *RUN_DEADLINE.lock().unwrap_or_else(|e| e.into_inner()) = deadline;
+}
+
+/// Whether the `--max-duration` budget is used up; checked only between files.
+fn time_budget_exhausted() -> bool {
+    RUN_DEADLINE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+}
+
+/// Why the translation loop stops before all files are done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EarlyStop {
+    /// Ctrl-C was pressed once
+    Interrupted,
+    /// The `--max-duration` budget ran out
+    TimeBudget,
+}
+
+/// Checked only between files, so a file is never left half-built.
+fn early_stop_reason() -> Option<EarlyStop> {
+    if interrupt::stop_requested() {
+        Some(EarlyStop::Interrupted)
+    } else if time_budget_exhausted() {
+        Some(EarlyStop::TimeBudget)
+    } else {
+        None
+    }
+}
+
+/// Limit set by `--max-c-file-bytes`; `None` means no limit.
+static MAX_C_FILE_BYTES: std::sync::Mutex<Option<u64>> = std::sync::Mutex::new(None);
+
+fn set_max_c_file_bytes(limit: Option<u64>) {
+    *MAX_C_FILE_BYTES.lock().unwrap_or_else(|e| e.into_inner()) = limit;
+}
+
+fn max_c_file_bytes() -> Option<u64> {
+    *MAX_C_FILE_BYTES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The C source of a file is larger than `--max-c-file-bytes`.
+#[derive(Debug)]
+struct CFileTooLargeError {
+    c_file: std::path::PathBuf,
+    size: u64,
+    limit: u64,
+}
+
+impl std::fmt::Display for CFileTooLargeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "C file {} is too large ({} bytes, limit {} bytes)",
+            self.c_file.display(),
+            self.size,
+            self.limit
+        )
+    }
+}
+
+impl std::error::Error for CFileTooLargeError {}
+
+/// Threshold set by `--max-unsafe-ratio`; `None` disables the check.
+static MAX_UNSAFE_RATIO: std::sync::Mutex<Option<f64>> = std::sync::Mutex::new(None);
+
+fn set_max_unsafe_ratio(ratio: Option<f64>) {
+    *MAX_UNSAFE_RATIO.lock().unwrap_or_else(|e| e.into_inner()) = ratio;
+}
+
+fn max_unsafe_ratio() -> Option<f64> {
+    *MAX_UNSAFE_RATIO.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// How much of a translated file runs in an unsafe context.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct UnsafeUsage {
+    unsafe_blocks: usize,
+    unsafe_fns: usize,
+    total_stmts: usize,
+    /// Statements inside an `unsafe` block or an `unsafe fn`.
+    unsafe_stmts: usize,
+}
+
+impl UnsafeUsage {
+    fn ratio(&self) -> f64 {
+        if self.total_stmts == 0 {
+            0.0
+        } else {
+            self.unsafe_stmts as f64 / self.total_stmts as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct UnsafeUsageCounter {
+    usage: UnsafeUsage,
+    unsafe_depth: usize,
+}
+
+impl UnsafeUsageCounter {
+    fn visit_unsafe_fn_body(&mut self, is_unsafe: bool, block: &syn::Block) {
+        if is_unsafe {
+            self.usage.unsafe_fns += 1;
+            self.unsafe_depth += 1;
+        }
+        self.visit_block(block);
+        if is_unsafe {
+            self.unsafe_depth -= 1;
+        }
+    }
+}
+
+impl Visit<'_> for UnsafeUsageCounter {
+    fn visit_item_fn(&mut self, node: &syn::ItemFn) {
+        self.visit_unsafe_fn_body(node.sig.unsafety.is_some(), &node.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &syn::ImplItemFn) {
+        self.visit_unsafe_fn_body(node.sig.unsafety.is_some(), &node.block);
+    }
+
+    fn visit_expr_unsafe(&mut self, node: &syn::ExprUnsafe) {
+        self.usage.unsafe_blocks += 1;
+        self.unsafe_depth += 1;
+        syn::visit::visit_expr_unsafe(self, node);
+        self.unsafe_depth -= 1;
+    }
+
+    fn visit_stmt(&mut self, node: &syn::Stmt) {
+        self.usage.total_stmts += 1;
+        if self.unsafe_depth > 0 {
+            self.usage.unsafe_stmts += 1;
+        }
+        syn::visit::visit_stmt(self, node);
+    }
+}
+
+/// Count unsafe blocks/fns and the share of statements in an unsafe context.
+///
+/// Returns `None` when the source does not parse; the build phase reports that.
+fn measure_unsafe_usage(source: &str) -> Option<UnsafeUsage> {
+    let file = syn::parse_file(source).ok()?;
+    let mut counter = UnsafeUsageCounter::default();
+    counter.visit_file(&file);
+    Some(counter.usage)
+}
+
+/// Warn when the accepted translation is more unsafe than `--max-unsafe-ratio` allows.
+///
+/// With prompts enabled the user may enter a "reduce unsafe" suggestion: it is saved to
+/// the suggestion file and a fix round is run with it, then the file is rebuilt and
+/// measured again.  A fix that breaks the build is rolled back.  Pressing Enter (or
+/// running without prompts) accepts the translation as is.
+fn check_unsafe_ratio<F>(
+    feature: &str,
+    file_type: &str,
+    rs_file: &Path,
+    format_progress: &F,
+    show_full_output: bool,
+) -> Result<()>
+where
+    F: Fn(&str) -> String,
+{
+    let Some(threshold) = max_unsafe_ratio() else {
+        return Ok(());
+    };
+    loop {
+        let content = std::fs::read_to_string(rs_file)
+            .with_context(|| format!("Failed to read {}", rs_file.display()))?;
+        let Some(usage) = measure_unsafe_usage(&content) else {
+            return Ok(());
+        };
+        if usage.ratio() <= threshold {
+            return Ok(());
+        }
+
+        let message = format!(
+            "Translation is unsafe-heavy: {:.0}% of statements are unsafe ({} unsafe block(s), {} unsafe fn(s)); threshold is {:.0}%.",
+            usage.ratio() * 100.0,
+            usage.unsafe_blocks,
+            usage.unsafe_fns,
+            threshold * 100.0
+        );
+        ui::blank();
+        ui::warning(&message);
+        if interaction::is_auto_accept_mode() || !interaction::has_interactive_terminal() {
+            return Ok(());
+        }
+        println!(
+            "│ {}",
+            "Enter a suggestion on how to reduce unsafe usage, or press Enter to accept as is."
+                .bright_cyan()
+        );
+        let Some(suggestion_text) = interaction::prompt_suggestion(false)? else {
+            return Ok(());
+        };
+        suggestion::append_suggestion(&format!("Reduce unsafe usage: {}", suggestion_text))?;
+
+        if let Err(e) = apply_error_fix(
+            feature,
+            file_type,
+            rs_file,
+            &anyhow::anyhow!("{} Reduce unsafe usage.", message),
+            format_progress,
+            show_full_output,
+        ) {
+            ui::warning(format!(
+                "Unsafe reduction fix failed, keeping the current translation: {:#}",
+                e
+            ));
+            std::fs::write(rs_file, &content)
+                .with_context(|| format!("Failed to restore {}", rs_file.display()))?;
+            return Ok(());
+        }
+        if let Err(e) = builder::cargo_check(feature, true, show_full_output) {
+            ui::warning(format!(
+                "Unsafe reduction fix broke the build, restoring the previous translation: {:#}",
+                e
+            ));
+            std::fs::write(rs_file, &content)
+                .with_context(|| format!("Failed to restore {}", rs_file.display()))?;
+        }
+    }
+}
+
 #[derive(Default)]
 struct UnsafeExprCounter {
     count: usize,
@@ -1875,7 +4519,7 @@ fn collapse_fn_unsafe_regions(item_fn: &mut syn::ItemFn) -> bool {
     collapser.visit_block_mut(&mut item_fn.block);
 
     let old_block = item_fn.block.as_ref().clone();
-    item_fn.block = Box::new(syn::Block {
+    *item_fn.block = syn::Block {
         brace_token: old_block.brace_token,
         stmts: vec![syn::Stmt::Expr(
             syn::Expr::Unsafe(syn::ExprUnsafe {
@@ -1885,7 +4529,7 @@ fn collapse_fn_unsafe_regions(item_fn: &mut syn::ItemFn) -> bool {
             }),
             None,
         )],
-    });
+    };
     true
 }
 
@@ -2103,11 +4747,8 @@ where
         "│ {}",
         "⚠ Warnings detected, attempting to fix...".yellow().bold()
     );
-    println!("│");
-    println!(
-        "│ {}",
-        format_progress("Warning Fix").bright_magenta().bold()
-    );
+    ui::blank();
+    ui::header(format_progress("Warning Fix"));
 
     // Fix using the same translation tool, passing warnings as the "error" message
     translator::fix_translation_error(
@@ -2125,7 +4766,7 @@ where
         anyhow::bail!("Warning fix failed: output file is empty");
     }
 
-    println!("│ {}", "✓ Warning fix applied".bright_green());
+    ui::success("Warning fix applied");
 
     Ok(())
 }
@@ -2134,6 +4775,18 @@ where
 // File Completion and Finalization
 // ============================================================================
 
+/// Per-attempt state [`complete_file_processing`] needs besides the file itself.
+#[derive(Debug, Clone, Copy)]
+struct HybridTestAttempt {
+    /// Test configuration is unavailable; run clean/build only
+    skip_test: bool,
+    /// Tests are deferred by `C2RUST_TEST_INTERVAL` for this translation
+    skip_interval_test: bool,
+    commit_info: TranslationCommitInfo,
+    /// No further from-scratch translation is left for this file
+    is_last_attempt: bool,
+}
+
 /// Complete file processing by running hybrid build tests and committing changes
 ///
 /// This function runs the final verification steps:
@@ -2153,13 +4806,19 @@ fn complete_file_processing<F>(
     file_type: &str,
     rs_file: &Path,
     format_progress: &F,
-    skip_test: bool,
-    skip_interval_test: bool,
+    attempt: HybridTestAttempt,
+    stats: &mut util::TranslationStats,
 ) -> Result<(bool, bool)>
 where
     F: Fn(&str) -> String,
 {
-    println!("│");
+    let HybridTestAttempt {
+        skip_test,
+        skip_interval_test,
+        commit_info,
+        is_last_attempt,
+    } = attempt;
+    ui::blank();
     // Choose the progress header. `skip_test` (config unavailable) takes priority over
     // `skip_interval_test` so the user sees the correct reason when both flags are true.
     if skip_test {
@@ -2169,10 +4828,7 @@ where
                 .bright_magenta()
                 .bold()
         );
-        println!(
-            "│ {}",
-            "Running clean/build only (test configuration not available)...".bright_blue()
-        );
+        ui::info("Running clean/build only (test configuration not available)...");
     } else if skip_interval_test {
         let interval = get_test_interval();
         println!(
@@ -2199,16 +4855,50 @@ where
                 .bright_magenta()
                 .bold()
         );
-        println!("│ {}", "Running hybrid build tests...".bright_blue());
+        ui::info("Running hybrid build tests...");
     }
 
     // Pre-check: Verify config and tools are available
     verify_hybrid_build_prerequisites()?;
 
     // 在整个序列开始前统一更新一次代码分析，避免 clean/build/test 各自重复更新
-    println!("│ {}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("│ {}", "✓ Code analysis updated".bright_green());
+    ui::info("Updating code analysis...");
+    let updated = analyzer::update_code_analysis(feature)?;
+    ui::success(analyzer::update_status_message(updated));
+
+    // Build/test every extra `--verify-profiles` profile before the primary one, so the
+    // primary flow below (which may accept and commit) only runs once all of them passed.
+    let extra_profiles = builder::extra_verify_profiles();
+    if !extra_profiles.is_empty() {
+        let run_tests = !skip_test && !skip_interval_test;
+        let fixed = fix_until_profiles_pass(
+            &extra_profiles,
+            |profile| run_profile_verification(feature, profile, run_tests),
+            |profile, profile_error| {
+                println!(
+                    "│ {}",
+                    format!("✗ Verification failed for the '{}' profile", profile)
+                        .red()
+                        .bold()
+                );
+                let profile_error = profile_error
+                    .context(format!("Hybrid build failed for the '{}' profile", profile));
+                builder::handle_build_failure_interactive(
+                    feature,
+                    file_type,
+                    rs_file,
+                    profile_error,
+                    skip_test,
+                )
+            },
+        )?;
+        match fixed {
+            // The fix flow already built/tested the primary profile.
+            Some(true) => return Ok((true, !skip_test)),
+            Some(false) => return Ok((false, false)),
+            None => {}
+        }
+    }
 
     // Run hybrid build clean/build/test
     builder::c2rust_clean_no_analysis(feature)?;
@@ -2216,6 +4906,9 @@ where
     // Handle build
     if let Err(build_error) = builder::c2rust_build_no_analysis(feature) {
         println!("│ {}", "✗ Build failed".red().bold());
+        if should_retranslate_for_link_error(feature, rs_file, &build_error, is_last_attempt) {
+            return Ok((false, false)); // Re-translate this file; tests did not run
+        }
         let processing_complete =
             builder::handle_build_failure_interactive(feature, file_type, rs_file, build_error, skip_test)?;
         if !processing_complete {
@@ -2230,11 +4923,16 @@ where
     }
 
     if skip_test {
-        println!(
-            "│ {}",
-            "⚠ Skipping test phase (test configuration not available)".yellow()
-        );
-        let tests_ran = handle_successful_tests(feature, file_name, file_type, rs_file, format_progress, TestStatus::SkippedNoConfig)?;
+        ui::warning("Skipping test phase (test configuration not available)");
+        let tests_ran = handle_successful_tests(
+            feature,
+            file_name,
+            file_type,
+            rs_file,
+            format_progress,
+            TestStatus::SkippedNoConfig,
+            commit_info,
+        )?;
         return Ok((true, tests_ran));
     }
 
@@ -2248,7 +4946,15 @@ where
             )
             .yellow()
         );
-        let tests_ran = handle_successful_tests(feature, file_name, file_type, rs_file, format_progress, TestStatus::DeferredByInterval)?;
+        let tests_ran = handle_successful_tests(
+            feature,
+            file_name,
+            file_type,
+            rs_file,
+            format_progress,
+            TestStatus::DeferredByInterval,
+            commit_info,
+        )?;
         return Ok((true, tests_ran));
     }
 
@@ -2256,7 +4962,17 @@ where
     match builder::c2rust_test_no_analysis(feature) {
         Ok(_) => {
             println!("│ {}", "✓ Hybrid build tests passed".bright_green().bold());
-            let tests_ran = handle_successful_tests(feature, file_name, file_type, rs_file, format_progress, TestStatus::Passed)?;
+            stats.record_file_phase(file_name, util::FilePhase::Tested);
+            save_stats_or_warn(stats, feature);
+            let tests_ran = handle_successful_tests(
+                feature,
+                file_name,
+                file_type,
+                rs_file,
+                format_progress,
+                TestStatus::Passed,
+                commit_info,
+            )?;
             Ok((true, tests_ran)) // Processing complete; tests ran
         }
         Err(test_error) => {
@@ -2271,7 +4987,7 @@ where
                 );
                 // tests_passed=false: tests ran but failed; we're only accepting because
                 // C2RUST_TEST_CONTINUE_ON_ERROR is set — this must not emit --build-success.
-                finalize_file_processing(feature, file_name, format_progress, false)?;
+                finalize_file_processing(feature, file_name, format_progress, false, commit_info)?;
                 // C2RUST_TEST_CONTINUE_ON_ERROR was set: tests ran (and failed) but we're
                 // treating the failure as non-fatal and accepting the translation anyway.
                 Ok((true, true))
@@ -2290,10 +5006,126 @@ where
     }
 }
 
+/// Decide whether a hybrid build failure is a link error caused by the current file.
+///
+/// Link errors (undefined/duplicate symbols) are mapped back to their
+/// `fun_<symbol>.rs` / `var_<symbol>.rs` files. When one of them is `rs_file` and
+/// translation attempts remain, the file is re-translated automatically instead of
+/// going through the interactive failure menu. Files other than the current one are
+/// only reported, so the user can pick them via "fix other file".
+fn should_retranslate_for_link_error(
+    feature: &str,
+    rs_file: &Path,
+    build_error: &anyhow::Error,
+    is_last_attempt: bool,
+) -> bool {
+    let link_errors = error_handler::extract_link_errors(&format!("{:#}", build_error));
+    if link_errors.is_empty() {
+        return false;
+    }
+    let rust_dir = match util::find_project_root() {
+        Ok(root) => root.join(".c2rust").join(feature).join("rust"),
+        Err(_) => return false,
+    };
+    let files = error_handler::map_link_errors_to_files(&link_errors, &rust_dir);
+
+    let symbols: Vec<&str> = link_errors.iter().map(|e| e.symbol.as_str()).collect();
+    println!(
+        "│ {}",
+        format!("Link error on symbol(s): {}", symbols.join(", ")).yellow()
+    );
+    for file in &files {
+        println!("│   {}", file.display().to_string().bright_yellow());
+    }
+
+    let is_current_file = |file: &Path| match (file.canonicalize(), rs_file.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => file == rs_file,
+    };
+    if !files.iter().any(|f| is_current_file(f)) {
+        return false;
+    }
+    if is_last_attempt {
+        println!(
+            "│ {}",
+            "Link error maps to the current file, but no translation attempts remain.".yellow()
+        );
+        return false;
+    }
+    println!(
+        "│ {}",
+        "Link error maps to the current file; re-translating it...".bright_cyan()
+    );
+    true
+}
+
+/// Run `check` for each profile in order and return the first one that fails.
+///
+/// Stops at the first failure so later profiles are not built needlessly.
+/// Returns `None` when every profile passed, i.e. the translation may be accepted.
+fn find_failing_profile<F>(
+    profiles: &[builder::BuildProfile],
+    mut check: F,
+) -> Option<(builder::BuildProfile, anyhow::Error)>
+where
+    F: FnMut(builder::BuildProfile) -> Result<()>,
+{
+    for &profile in profiles {
+        if let Err(e) = check(profile) {
+            return Some((profile, e));
+        }
+    }
+    None
+}
+
+/// Re-run [`find_failing_profile`] after each fix until every profile passes.
+///
+/// `fix` handles one failing profile and returns `false` when the file should be
+/// re-translated instead. Returns `None` when every profile passed without a fix,
+/// `Some(true)` once they all pass after one or more fixes, and `Some(false)` when
+/// a fix asked for re-translation.
+fn fix_until_profiles_pass<C, H>(
+    profiles: &[builder::BuildProfile],
+    mut check: C,
+    mut fix: H,
+) -> Result<Option<bool>>
+where
+    C: FnMut(builder::BuildProfile) -> Result<()>,
+    H: FnMut(builder::BuildProfile, anyhow::Error) -> Result<bool>,
+{
+    let mut fixed = false;
+    while let Some((profile, error)) = find_failing_profile(profiles, &mut check) {
+        if !fix(profile, error)? {
+            return Ok(Some(false));
+        }
+        fixed = true;
+        ui::info("Re-verifying the --verify-profiles profiles after the fix...");
+    }
+    Ok(fixed.then_some(true))
+}
+
+/// Run cargo build plus hybrid clean/build (and test, if `run_tests`) under `profile`.
+fn run_profile_verification(
+    feature: &str,
+    profile: builder::BuildProfile,
+    run_tests: bool,
+) -> Result<()> {
+    ui::info(format!("Verifying the '{}' profile...", profile));
+    builder::with_build_profile(profile, || {
+        builder::cargo_build(feature, true, false)?;
+        builder::c2rust_clean_no_analysis(feature)?;
+        builder::c2rust_build_no_analysis(feature)?;
+        if run_tests {
+            builder::c2rust_test_no_analysis(feature)?;
+        }
+        ui::success(format!("'{}' profile verified", profile));
+        Ok(())
+    })
+}
+
 /// Verify prerequisites for hybrid build (config file and tools)
 fn verify_hybrid_build_prerequisites() -> Result<()> {
-    let project_root = util::find_project_root()?;
-    let config_path = project_root.join(".c2rust/config.toml");
+    let config_path = util::config_path()?;
 
     if !config_path.exists() {
         eprintln!(
@@ -2357,6 +5189,7 @@ fn handle_successful_tests<F>(
     rs_file: &Path,
     format_progress: &F,
     test_status: TestStatus,
+    commit_info: TranslationCommitInfo,
 ) -> Result<bool>
 where
     F: Fn(&str) -> String,
@@ -2372,6 +5205,7 @@ where
             file_name,
             format_progress,
             matches!(test_status, TestStatus::Passed),
+            commit_info,
         )?;
         // In auto-accept mode we skip user interaction. Tests are considered to have
         // run only when the status is `Passed` (c2rust_test executed before this call).
@@ -2409,7 +5243,13 @@ where
             match choice {
                 interaction::CompileSuccessChoice::Accept => {
                     println!("│ {}", "You chose: Accept this code".bright_cyan());
-                    finalize_file_processing(feature, file_name, format_progress, false)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        false,
+                        commit_info,
+                    )?;
                 }
                 interaction::CompileSuccessChoice::AutoAccept => {
                     println!(
@@ -2417,22 +5257,28 @@ where
                         "You chose: Auto-accept all subsequent translations".bright_cyan()
                     );
                     interaction::enable_auto_accept_mode();
-                    finalize_file_processing(feature, file_name, format_progress, false)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        false,
+                        commit_info,
+                    )?;
                 }
                 interaction::CompileSuccessChoice::ManualFix => {
                     println!("│ {}", "You chose: Manual fix".bright_cyan());
-                    interaction::open_in_vim(rs_file)?;
-                    println!(
-                        "│ {}",
-                        "Running full build after manual changes...".bright_blue()
-                    );
+                    interaction::open_in_editor(rs_file)?;
+                    ui::info("Running full build after manual changes...");
                     // Tests remain skipped: config is still unavailable.
                     builder::run_full_build_and_test_interactive(feature, file_type, rs_file, true)?;
-                    println!(
-                        "│ {}",
-                        "✓ Build passes after manual changes (tests skipped)".bright_green()
-                    );
-                    finalize_file_processing(feature, file_name, format_progress, false)?;
+                    ui::success("Build passes after manual changes (tests skipped)");
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        false,
+                        commit_info.with_outcome(TranslationOutcome::Manual),
+                    )?;
                 }
                 interaction::CompileSuccessChoice::Exit => {
                     println!("│ {}", "You chose: Exit".yellow());
@@ -2469,7 +5315,13 @@ where
             let tests_ran = match choice {
                 interaction::CompileSuccessChoice::Accept => {
                     println!("│ {}", "You chose: Accept this code".bright_cyan());
-                    finalize_file_processing(feature, file_name, format_progress, false)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        false,
+                        commit_info,
+                    )?;
                     false
                 }
                 interaction::CompileSuccessChoice::AutoAccept => {
@@ -2478,23 +5330,29 @@ where
                         "You chose: Auto-accept all subsequent translations".bright_cyan()
                     );
                     interaction::enable_auto_accept_mode();
-                    finalize_file_processing(feature, file_name, format_progress, false)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        false,
+                        commit_info,
+                    )?;
                     false
                 }
                 interaction::CompileSuccessChoice::ManualFix => {
                     println!("│ {}", "You chose: Manual fix".bright_cyan());
-                    interaction::open_in_vim(rs_file)?;
-                    println!(
-                        "│ {}",
-                        "Running full build and test after manual changes...".bright_blue()
-                    );
+                    interaction::open_in_editor(rs_file)?;
+                    ui::info("Running full build and test after manual changes...");
                     // Config is available: run real tests during the manual-fix validation.
                     builder::run_full_build_and_test_interactive(feature, file_type, rs_file, false)?;
-                    println!(
-                        "│ {}",
-                        "✓ All builds and tests pass after manual changes".bright_green()
-                    );
-                    finalize_file_processing(feature, file_name, format_progress, true)?;
+                    ui::success("All builds and tests pass after manual changes");
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        true,
+                        commit_info.with_outcome(TranslationOutcome::Manual),
+                    )?;
                     true // tests actually ran
                 }
                 interaction::CompileSuccessChoice::Exit => {
@@ -2526,7 +5384,13 @@ where
             match choice {
                 interaction::CompileSuccessChoice::Accept => {
                     println!("│ {}", "You chose: Accept this code".bright_cyan());
-                    finalize_file_processing(feature, file_name, format_progress, true)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        true,
+                        commit_info,
+                    )?;
                 }
                 interaction::CompileSuccessChoice::AutoAccept => {
                     println!(
@@ -2534,21 +5398,27 @@ where
                         "You chose: Auto-accept all subsequent translations".bright_cyan()
                     );
                     interaction::enable_auto_accept_mode();
-                    finalize_file_processing(feature, file_name, format_progress, true)?;
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        true,
+                        commit_info,
+                    )?;
                 }
                 interaction::CompileSuccessChoice::ManualFix => {
                     println!("│ {}", "You chose: Manual fix".bright_cyan());
-                    interaction::open_in_vim(rs_file)?;
-                    println!(
-                        "│ {}",
-                        "Running full build and test after manual changes...".bright_blue()
-                    );
+                    interaction::open_in_editor(rs_file)?;
+                    ui::info("Running full build and test after manual changes...");
                     builder::run_full_build_and_test_interactive(feature, file_type, rs_file, false)?;
-                    println!(
-                        "│ {}",
-                        "✓ All builds and tests pass after manual changes".bright_green()
-                    );
-                    finalize_file_processing(feature, file_name, format_progress, true)?;
+                    ui::success("All builds and tests pass after manual changes");
+                    finalize_file_processing(
+                        feature,
+                        file_name,
+                        format_progress,
+                        true,
+                        commit_info.with_outcome(TranslationOutcome::Manual),
+                    )?;
                 }
                 interaction::CompileSuccessChoice::Exit => {
                     println!("│ {}", "You chose: Exit".yellow());
@@ -2561,6 +5431,63 @@ where
     }
 }
 
+/// How the accepted translation of a file was produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TranslationOutcome {
+    /// Accepted as produced by the translator, without any fix rounds
+    Translated,
+    /// Needed one or more automatic fix rounds
+    Fixed,
+    /// The user edited the file manually before accepting it
+    Manual,
+}
+
+impl TranslationOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            TranslationOutcome::Translated => "translated",
+            TranslationOutcome::Fixed => "fixed",
+            TranslationOutcome::Manual => "manual",
+        }
+    }
+}
+
+/// Per-file tracking data recorded as trailers on the translation commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TranslationCommitInfo {
+    /// Translation attempt (1-based) that produced the accepted code
+    attempts: usize,
+    outcome: TranslationOutcome,
+}
+
+impl TranslationCommitInfo {
+    fn with_outcome(self, outcome: TranslationOutcome) -> Self {
+        Self { outcome, ..self }
+    }
+}
+
+/// Build the commit message for an accepted translation.
+///
+/// The body carries git-style trailers so files can later be found with e.g.
+/// `git log --grep 'C2rust-Outcome: manual'`.
+fn build_translation_commit_message(
+    feature: &str,
+    file_name: &str,
+    info: TranslationCommitInfo,
+) -> String {
+    format!(
+        "Translate {} from C to Rust (feature: {})\n\n\
+         C2rust-Attempts: {}\n\
+         C2rust-Outcome: {}\n\
+         C2rust-File: {}",
+        file_name,
+        feature,
+        info.attempts,
+        info.outcome.as_str(),
+        file_name
+    )
+}
+
 /// Finalize file processing: commit changes and update code analysis.
 ///
 /// `tests_passed` must be `true` only when tests actually ran **and** passed for
@@ -2571,50 +5498,70 @@ where
 /// - tests were skipped because the test configuration was unavailable (`SkippedNoConfig`)
 /// - tests were deferred by `C2RUST_TEST_INTERVAL` and no manual re-run was triggered (`DeferredByInterval`)
 /// - tests ran but failed and the caller is continuing due to `C2RUST_TEST_CONTINUE_ON_ERROR`
+///
+/// `commit_info` is recorded as machine-parseable trailers on the translation commit; a file
+/// the user edited in the editor from any prompt (e.g. the max-fix-attempts menu) is recorded
+/// as `manual`.
 fn finalize_file_processing<F>(
     feature: &str,
     file_name: &str,
     format_progress: &F,
     tests_passed: bool,
+    commit_info: TranslationCommitInfo,
 ) -> Result<()>
 where
     F: Fn(&str) -> String,
 {
+    let commit_info = if report::current_file_manually_edited() {
+        commit_info.with_outcome(TranslationOutcome::Manual)
+    } else {
+        commit_info
+    };
     // Commit changes
-    println!("│");
-    println!("│ {}", format_progress("Commit").bright_magenta().bold());
-    println!("│ {}", "Committing changes...".bright_blue());
-    if git_commit_or_warn(
-        &format!(
-            "Translate {} from C to Rust (feature: {})",
-            file_name, feature
-        ),
-        feature,
-    ) {
-        println!("│ {}", "✓ Changes committed".bright_green());
+    ui::blank();
+    ui::header(format_progress("Commit"));
+    let batching = git::is_batching_commits();
+    if batching {
+        let (batched, every) = git::add_to_commit_batch(file_name);
+        println!(
+            "│ {}",
+            format!(
+                "Commit batched (--commit-every {}): {}/{} file(s) pending",
+                every, batched, every
+            )
+            .bright_blue()
+        );
+    } else {
+        ui::info("Committing changes...");
+        if git_commit_or_warn(
+            &build_translation_commit_message(feature, file_name, commit_info),
+            feature,
+        ) {
+            ui::success("Changes committed");
+        }
     }
+    patch_export::append_file(feature, file_name);
 
     // Update code analysis
-    println!("│");
-    println!(
-        "│ {}",
-        format_progress("Update Analysis").bright_magenta().bold()
-    );
-    println!("│ {}", "Updating code analysis...".bright_blue());
-    if tests_passed {
+    ui::blank();
+    ui::header(format_progress("Update Analysis"));
+    ui::info("Updating code analysis...");
+    let updated = if tests_passed {
         analyzer::update_code_analysis_build_success(feature)?;
+        true
     } else {
-        analyzer::update_code_analysis(feature)?;
-    }
-    println!("│ {}", "✓ Code analysis updated".bright_green());
+        analyzer::update_code_analysis(feature)?
+    };
+    ui::success(analyzer::update_status_message(updated));
 
     // Commit analysis
-    println!("│");
-    println!(
-        "│ {}",
-        format_progress("Commit Analysis").bright_magenta().bold()
-    );
-    git_commit_or_warn(&format!("Update code analysis for {}", feature), feature);
+    ui::blank();
+    ui::header(format_progress("Commit Analysis"));
+    if !batching {
+        git_commit_or_warn(&format!("Update code analysis for {}", feature), feature);
+    } else if git::is_commit_batch_full() && flush_commit_batch(feature) {
+        ui::success("Batched changes committed");
+    }
 
     println!("{}", "└─ File processing complete".bright_white().bold());
 
@@ -2624,6 +5571,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::TestRepo;
     use serial_test::serial;
     use std::fs;
     use std::path::{Path, PathBuf};
@@ -2684,6 +5632,917 @@ mod tests {
         (temp_dir, guard, feature_root, rust_dir)
     }
 
+    #[test]
+    #[serial]
+    fn test_status_and_list_empty_apply_ignore_patterns() {
+        let (_temp_dir, _cwd, _feature_root, rust_dir) = create_temp_feature_workspace("default");
+        fs::create_dir_all(rust_dir.join("src/generated")).unwrap();
+        fs::write(rust_dir.join("src/fun_a.rs"), "").unwrap();
+        fs::write(rust_dir.join("src/generated/fun_b.rs"), "").unwrap();
+        fs::write(file_scanner::IGNORE_FILE_NAME, "generated/\n").unwrap();
+        let _reset = scopeguard::guard((), |_| {
+            file_scanner::set_ignore_patterns(file_scanner::IgnorePatterns::new(Vec::new()))
+        });
+
+        file_scanner::set_ignore_patterns(file_scanner::IgnorePatterns::new(Vec::new()));
+        list_empty_files("default", true).unwrap();
+        let listed: Vec<String> = file_scanner::list_empty_rs_files(&rust_dir)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(listed, ["src/fun_a.rs"]);
+
+        file_scanner::set_ignore_patterns(file_scanner::IgnorePatterns::new(Vec::new()));
+        show_status("default", events::OutputFormat::Json).unwrap();
+        assert_eq!(file_scanner::count_all_rs_files(&rust_dir).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_translation_commit_message_contains_trailers() {
+        let info = TranslationCommitInfo {
+            attempts: 2,
+            outcome: TranslationOutcome::Fixed,
+        };
+        let message = build_translation_commit_message("default", "src/fun_foo.rs", info);
+        let (subject, body) = message.split_once("\n\n").expect("subject and body");
+        assert_eq!(
+            subject,
+            "Translate src/fun_foo.rs from C to Rust (feature: default)"
+        );
+        let trailers: Vec<&str> = body.lines().collect();
+        assert_eq!(
+            trailers,
+            vec![
+                "C2rust-Attempts: 2",
+                "C2rust-Outcome: fixed",
+                "C2rust-File: src/fun_foo.rs",
+            ]
+        );
+
+        let manual = build_translation_commit_message(
+            "default",
+            "src/var_bar.rs",
+            info.with_outcome(TranslationOutcome::Manual),
+        );
+        assert!(manual.contains("\nC2rust-Outcome: manual\n"));
+        assert!(manual.contains("C2rust-Attempts: 2"));
+    }
+
+    #[test]
+    fn test_find_failing_profile_all_pass() {
+        use builder::BuildProfile;
+        let mut checked = Vec::new();
+        let result = find_failing_profile(&[BuildProfile::Debug, BuildProfile::Release], |p| {
+            checked.push(p);
+            Ok(())
+        });
+        assert!(result.is_none());
+        assert_eq!(checked, vec![BuildProfile::Debug, BuildProfile::Release]);
+    }
+
+    #[test]
+    fn test_find_failing_profile_release_only_failure_blocks_acceptance() {
+        use builder::BuildProfile;
+        let mut debug_passed = false;
+        let result =
+            find_failing_profile(&[BuildProfile::Debug, BuildProfile::Release], |p| match p {
+                BuildProfile::Debug => {
+                    debug_passed = true;
+                    Ok(())
+                }
+                BuildProfile::Release => anyhow::bail!("release link error"),
+            });
+        assert!(debug_passed);
+        let (profile, err) = result.expect("release failure must prevent acceptance");
+        assert_eq!(profile, BuildProfile::Release);
+        assert!(err.to_string().contains("release link error"));
+    }
+
+    #[test]
+    fn test_fix_until_profiles_pass_rechecks_after_each_fix() {
+        use builder::BuildProfile;
+        let profiles = [BuildProfile::Debug, BuildProfile::Release];
+        // Debug fails once, then the fix for Debug breaks Release, which needs a second fix.
+        let failures = [BuildProfile::Debug, BuildProfile::Release];
+        let fixes = std::cell::Cell::new(0);
+        let mut fixed = Vec::new();
+        let result = fix_until_profiles_pass(
+            &profiles,
+            |p| match failures.get(fixes.get()) {
+                Some(&failing) if failing == p => anyhow::bail!("{} error", p),
+                _ => Ok(()),
+            },
+            |p, _| {
+                fixed.push(p);
+                fixes.set(fixes.get() + 1);
+                Ok(true)
+            },
+        )
+        .unwrap();
+        assert_eq!(result, Some(true));
+        assert_eq!(fixed, vec![BuildProfile::Debug, BuildProfile::Release]);
+
+        assert_eq!(
+            fix_until_profiles_pass(&profiles, |_| Ok(()), |_, _| Ok(true)).unwrap(),
+            None
+        );
+        assert_eq!(
+            fix_until_profiles_pass(
+                &profiles,
+                |_| anyhow::bail!("always failing"),
+                |_, _| Ok(false)
+            )
+            .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_clean_feature_dir_only_removes_selected_state() {
+        let temp_dir = tempdir().unwrap();
+        let feature_dir = temp_dir.path();
+        fs::create_dir_all(feature_dir.join("rust/target/debug")).unwrap();
+        fs::create_dir_all(feature_dir.join("rust/src")).unwrap();
+        fs::write(feature_dir.join("rust/src/fun_a.rs"), "fn a() {}").unwrap();
+        fs::create_dir_all(feature_dir.join("suggestions")).unwrap();
+        fs::write(feature_dir.join("suggestions/fun_a.txt"), "hint").unwrap();
+        fs::write(feature_dir.join("progress-main.json"), "{}").unwrap();
+        fs::write(feature_dir.join("progress.json"), "{}").unwrap();
+        fs::write(feature_dir.join("translation_stats.json"), "{}").unwrap();
+
+        let removed = clean_feature_dir(feature_dir, CleanScope::Progress, true).unwrap();
+        assert_eq!(
+            removed,
+            vec![
+                feature_dir.join("progress-main.json"),
+                feature_dir.join("progress.json")
+            ]
+        );
+        assert!(
+            feature_dir.join("progress.json").exists(),
+            "dry run keeps files"
+        );
+
+        clean_feature_dir(feature_dir, CleanScope::Progress, false).unwrap();
+        assert!(!feature_dir.join("progress-main.json").exists());
+        assert!(feature_dir.join("suggestions/fun_a.txt").exists());
+
+        let removed = clean_feature_dir(feature_dir, CleanScope::All, false).unwrap();
+        assert_eq!(
+            removed,
+            vec![
+                feature_dir.join("rust/target"),
+                feature_dir.join("suggestions"),
+                feature_dir.join("translation_stats.json")
+            ]
+        );
+        assert!(feature_dir.join("rust/src/fun_a.rs").exists());
+        assert!("everything".parse::<CleanScope>().is_err());
+        assert_eq!("Build".parse::<CleanScope>(), Ok(CleanScope::Build));
+        let err = "targets".parse::<CleanScope>().unwrap_err();
+        assert!(err.contains("managed by c2rust-config"), "{err}");
+    }
+
+    #[test]
+    #[serial]
+    fn test_clean_feature_waits_for_the_run_lock() {
+        let (_temp_dir, _cwd, feature_root, _rust_dir) = create_temp_feature_workspace("default");
+        fs::write(feature_root.join("progress.json"), "{}").unwrap();
+
+        let held = run_lock::acquire(&feature_root, false).unwrap();
+        let err = clean_feature("default", CleanScope::Progress, false).unwrap_err();
+        assert!(err.to_string().contains("in progress"), "{:#}", err);
+        assert!(feature_root.join("progress.json").exists());
+        // A dry run only lists paths and takes no lock.
+        clean_feature("default", CleanScope::Progress, true).unwrap();
+
+        drop(held);
+        clean_feature("default", CleanScope::Progress, false).unwrap();
+        assert!(!feature_root.join("progress.json").exists());
+    }
+
+    #[test]
+    fn test_failure_policy_parsing_and_ci_detection() {
+        assert_eq!(
+            "retry-once-then-abort".parse::<FailurePolicy>().unwrap(),
+            FailurePolicy::RetryOnceThenAbort
+        );
+        assert_eq!(
+            "Abort".parse::<FailurePolicy>().unwrap(),
+            FailurePolicy::Abort
+        );
+        assert!("ignore".parse::<FailurePolicy>().is_err());
+        assert_eq!(FailurePolicy::default().to_string(), "continue");
+
+        assert!(is_ci_environment(Some("true")));
+        assert!(is_ci_environment(Some("1")));
+        assert!(!is_ci_environment(Some("false")));
+        assert!(!is_ci_environment(None));
+    }
+
+    #[test]
+    fn test_interactive_mode_auto_flags() {
+        let expected = InteractionFlags {
+            allow_all: true,
+            auto_accept: true,
+            prompts_enabled: false,
+        };
+        assert_eq!(InteractiveMode::Auto.flags(false), expected);
+        assert_eq!(InteractiveMode::Auto.flags(true), expected);
+    }
+
+    #[test]
+    fn test_interactive_mode_prompt_flags_honour_allow_all_alias() {
+        assert_eq!(
+            InteractiveMode::Prompt.flags(false),
+            InteractionFlags {
+                allow_all: false,
+                auto_accept: false,
+                prompts_enabled: true,
+            }
+        );
+        assert_eq!(
+            InteractiveMode::Prompt.flags(true),
+            InteractionFlags {
+                allow_all: true,
+                auto_accept: false,
+                prompts_enabled: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_interactive_mode_manual_flags_ignore_allow_all_alias() {
+        let expected = InteractionFlags {
+            allow_all: false,
+            auto_accept: false,
+            prompts_enabled: true,
+        };
+        assert_eq!(InteractiveMode::Manual.flags(false), expected);
+        assert_eq!(InteractiveMode::Manual.flags(true), expected);
+    }
+
+    #[test]
+    fn test_interactive_mode_from_str_and_aliases() {
+        let cases = [
+            ("auto", InteractiveMode::Auto),
+            ("false", InteractiveMode::Auto),
+            ("PROMPT", InteractiveMode::Prompt),
+            ("true", InteractiveMode::Prompt),
+            ("manual", InteractiveMode::Manual),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<InteractiveMode>(), Ok(expected), "{input}");
+        }
+        assert!("sometimes".parse::<InteractiveMode>().is_err());
+        assert_eq!(InteractiveMode::default(), InteractiveMode::Prompt);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_verify_all_runs_one_build_and_reports_located_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let orig = std::env::current_dir().unwrap();
+        std::env::set_current_dir(tmp.path()).unwrap();
+        let _restore = scopeguard::guard(orig, |dir| {
+            let _ = std::env::set_current_dir(dir);
+        });
+        let src_dir = tmp.path().join(".c2rust/default/rust/src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("fun_foo.rs"), "pub fn foo() {}").unwrap();
+
+        let mut builds = 0;
+        verify_all_with("default", || {
+            builds += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(builds, 1);
+
+        let mut builds = 0;
+        let err = verify_all_with("default", || {
+            builds += 1;
+            anyhow::bail!("error[E0308]: mismatched types\n  --> src/fun_foo.rs:1:1")
+        })
+        .unwrap_err();
+        assert_eq!(builds, 1);
+        assert!(err.to_string().contains("fun_foo.rs"), "{err}");
+
+        let err = verify_all_with("default", || anyhow::bail!("linker failed")).unwrap_err();
+        assert!(err.to_string().contains("no files could be located"));
+    }
+
+    #[test]
+    fn test_resolve_fix_target_requires_non_empty_file() {
+        let temp_dir = tempdir().unwrap();
+        let rust_dir = temp_dir.path();
+        fs::create_dir_all(rust_dir.join("src")).unwrap();
+        fs::write(rust_dir.join("src/fun_a.rs"), "fn a() {}\n").unwrap();
+        fs::write(rust_dir.join("src/fun_b.rs"), "").unwrap();
+
+        assert_eq!(
+            resolve_fix_target(rust_dir, "src/fun_a.rs").unwrap(),
+            rust_dir.join("src/fun_a.rs")
+        );
+        assert_eq!(
+            resolve_fix_target(rust_dir, "fun_a.rs").unwrap(),
+            rust_dir.join("src/fun_a.rs")
+        );
+        let err = resolve_fix_target(rust_dir, "fun_b.rs")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("translate --file src/fun_b.rs"), "{}", err);
+        assert!(resolve_fix_target(rust_dir, "fun_missing.rs").is_err());
+
+        fs::write(temp_dir.path().join("fun_outside.rs"), "fn o() {}\n").unwrap();
+        let err = resolve_fix_target(&rust_dir.join("src"), "../fun_outside.rs")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("without '..'"), "{}", err);
+        let absolute = rust_dir.join("src/fun_a.rs");
+        assert!(resolve_fix_target(rust_dir, absolute.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_keep_changed_files_intersects_with_git_diff() {
+        let rust_dir = Path::new("/p/.c2rust/f/rust");
+        let files = vec![
+            rust_dir.join("src/fun_a.rs"),
+            rust_dir.join("src/net/fun_b.rs"),
+            rust_dir.join("src/fun_c.rs"),
+        ];
+        let changed = ["src/net/fun_b.rs", "src/fun_c.c", "Cargo.toml"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            keep_changed_files(files, rust_dir, &changed),
+            vec![rust_dir.join("src/net/fun_b.rs")]
+        );
+    }
+
+    #[test]
+    fn test_filter_only_pattern_matches_relative_paths() {
+        let rust_dir = Path::new("/feature/rust");
+        let files: Vec<std::path::PathBuf> = [
+            "src/net/fun_send.rs",
+            "src/net/tcp/var_port.rs",
+            "src/fun_main.rs",
+        ]
+        .iter()
+        .map(|file| rust_dir.join(file))
+        .collect();
+
+        assert_eq!(
+            filter_only_pattern(files.clone(), rust_dir, Some("net/*")),
+            vec![rust_dir.join("src/net/fun_send.rs")]
+        );
+        assert_eq!(
+            filter_only_pattern(files.clone(), rust_dir, Some("src/net/**")).len(),
+            2
+        );
+        assert!(filter_only_pattern(files.clone(), rust_dir, Some("fun_missing*")).is_empty());
+        assert_eq!(filter_only_pattern(files.clone(), rust_dir, None), files);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_time_budget_exhausted_after_deadline() {
+        set_run_deadline(None);
+        assert!(!time_budget_exhausted());
+        set_run_deadline(Some(
+            std::time::Instant::now() + std::time::Duration::from_secs(3600),
+        ));
+        assert!(!time_budget_exhausted());
+        set_run_deadline(Some(std::time::Instant::now()));
+        assert!(time_budget_exhausted());
+        set_run_deadline(None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_early_stop_keeps_workers_and_skipped_files_from_starting() {
+        #[derive(Default)]
+        struct Capture(String);
+        impl observer::WorkflowObserver for Capture {
+            fn on_output(&mut self, _stream: observer::OutputStream, text: &str) -> bool {
+                self.0.push_str(text);
+                true
+            }
+        }
+
+        let (_temp_dir, _cwd, _feature_root, rust_dir) = create_temp_feature_workspace("default");
+        let files = vec![rust_dir.join("src/fun_a.rs"), rust_dir.join("src/fun_b.rs")];
+        for file in &files {
+            fs::write(file, "").unwrap();
+        }
+        let _reset = scopeguard::guard((), |_| {
+            interrupt::clear_stop_request();
+            set_run_deadline(None);
+        });
+        let stops: [fn(); 2] = [interrupt::request_stop, || {
+            interrupt::clear_stop_request();
+            set_run_deadline(Some(std::time::Instant::now()));
+        }];
+
+        for stop in stops {
+            stop();
+            let mut capture = Capture::default();
+            let mut stats = util::TranslationStats::new();
+            observer::observe_borrowed(&mut capture, || {
+                pretranslate_files_in_parallel(
+                    "default",
+                    &files,
+                    &[0, 1],
+                    &rust_dir,
+                    2,
+                    false,
+                    &mut stats,
+                )
+            });
+            assert!(
+                capture.0.contains("0/2 file(s) translated"),
+                "{}",
+                capture.0
+            );
+            assert!(!capture.0.contains("translation failed"), "{}", capture.0);
+
+            stats.skipped_files = vec!["src/fun_a.rs".to_string(), "src/fun_b.rs".to_string()];
+            let mut progress_state = util::ProgressState::new(2);
+            process_skipped_files_once_inner(
+                "default",
+                &rust_dir,
+                &mut progress_state,
+                &mut stats,
+                &mut 0,
+                FileRunOptions {
+                    max_error_fix_attempts: 1,
+                    max_warning_fix_attempts: 1,
+                    max_translation_attempts: 1,
+                    show_full_output: false,
+                    skip_test: true,
+                    dry_run: false,
+                },
+            )
+            .unwrap();
+            assert_eq!(stats.skipped_files, ["src/fun_a.rs", "src/fun_b.rs"]);
+            assert_eq!(progress_state.processed_count, 0);
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_commit_every_batches_commits_and_flushes_on_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let c2rust_dir = tmp.path().join(".c2rust");
+        let src_dir = c2rust_dir.join("default").join("rust").join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        let repo = TestRepo::init(&c2rust_dir);
+
+        let orig = std::env::current_dir().unwrap();
+        std::env::set_current_dir(tmp.path()).unwrap();
+        let _restore = scopeguard::guard(orig, |dir| {
+            let _ = std::env::set_current_dir(dir);
+        });
+        git::set_commit_every(3);
+        let _reset = scopeguard::guard((), |_| git::set_commit_every(1));
+
+        // Three files, each with its fix and analysis commit points: one commit.
+        for name in ["fun_a", "fun_b", "fun_c"] {
+            std::fs::write(src_dir.join(format!("{}.rs", name)), "").unwrap();
+            assert!(!git_commit_or_batch("Fix fun", "default"));
+            git::add_to_commit_batch(&format!("src/{}.rs", name));
+            assert!(!git_commit_or_batch("Update code analysis", "default"));
+        }
+        assert!(git::is_commit_batch_full());
+        assert!(flush_commit_batch("default"));
+        assert_eq!(repo.git(&["rev-list", "--count", "HEAD"]), "1\n");
+        assert!(repo
+            .git(&["log", "-1", "--format=%B"])
+            .contains("- src/fun_a.rs\n- src/fun_b.rs\n- src/fun_c.rs\n"));
+
+        // A run that ends with an error still commits the files batched so far.
+        let result: Result<()> = (|| {
+            let _guard = CommitBatchGuard { feature: "default" };
+            std::fs::write(src_dir.join("fun_d.rs"), "").unwrap();
+            git::add_to_commit_batch("src/fun_d.rs");
+            anyhow::bail!("build failed")
+        })();
+        assert!(result.is_err());
+        assert_eq!(repo.git(&["rev-list", "--count", "HEAD"]), "2\n");
+        assert!(repo
+            .git(&["log", "-1", "--format=%B"])
+            .starts_with("Translate 1 file(s) from C to Rust (feature: default)"));
+        assert_eq!(repo.git(&["status", "--porcelain"]), "");
+    }
+
+    #[test]
+    fn test_run_plan_render_and_confirmation() {
+        let plan = RunPlan {
+            feature: "default".to_string(),
+            files: vec!["src/fun_a.rs".to_string(), "src/var_b.rs".to_string()],
+            selection: "all pending files".to_string(),
+            jobs: 1,
+            max_translation_attempts: 3,
+            commit_strategy: "one commit per file in .c2rust/default/".to_string(),
+            translator: "python3 translate_and_fix.py".to_string(),
+            build_profiles: vec!["debug".to_string()],
+        };
+        let rendered = plan.render();
+        assert!(rendered.contains("2 pending, all pending files"));
+        assert!(rendered.contains("  1. src/fun_a.rs\n"));
+        assert!(rendered.contains("Commits:     one commit per file"));
+
+        assert!(!confirm_run_plan(&plan, || Ok(false)).unwrap());
+        assert!(confirm_run_plan(&plan, || Ok(true)).unwrap());
+        assert!(confirm_run_plan(&plan, || anyhow::bail!("no input")).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_declined_run_plan_runs_no_translator_build_or_commit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        struct DeclinePlan {
+            calls: PathBuf,
+            repo: TestRepo,
+            calls_at_plan: Option<String>,
+            log_at_plan: Option<String>,
+        }
+        impl observer::WorkflowObserver for DeclinePlan {
+            fn on_choice(&mut self, choice: &observer::Choice) -> observer::Selection {
+                if choice.kind != observer::ChoiceKind::ConfirmRunPlan {
+                    return observer::Selection::Console;
+                }
+                self.calls_at_plan = Some(fs::read_to_string(&self.calls).unwrap_or_default());
+                self.log_at_plan = Some(self.repo.git(&["log", "--format=%s"]));
+                observer::Selection::Choose(vec![1])
+            }
+
+            fn on_output(&mut self, _stream: observer::OutputStream, _text: &str) -> bool {
+                true
+            }
+        }
+
+        let (temp_dir, _cwd, _feature_root, rust_dir) = create_temp_feature_workspace("default");
+        let project_root = temp_dir.path();
+        fs::write(
+            rust_dir.join("Cargo.toml"),
+            "[package]\nname = \"default\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(rust_dir.join("src/lib.rs"), "mod fun_a;\n").unwrap();
+        fs::write(rust_dir.join("src/fun_a.rs"), "").unwrap();
+        fs::write(rust_dir.join("src/fun_a.c"), "int a(void) { return 1; }\n").unwrap();
+
+        // Fake build, analysis and translate tools (and the required clang/bindgen) that
+        // record every call; git is the real one.
+        let bin_dir = project_root.join("bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let calls = project_root.join("calls.txt");
+        let write_script = |name: &str, body: String| {
+            let script = bin_dir.join(name);
+            fs::write(&script, format!("#!/bin/sh\n{}", body)).unwrap();
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        for name in ["tool", "cargo", "code_analyse", "clang", "bindgen"] {
+            write_script(
+                name,
+                format!("echo \"{} $*\" >> '{}'\n", name, calls.display()),
+            );
+        }
+        let tool = bin_dir.join("tool").display().to_string();
+        write_script(
+            "c2rust-config",
+            format!(
+                "case \"$6\" in\n\
+                 build.cmd) echo '{tool} build' ;;\n\
+                 test.cmd) echo '{tool} test' ;;\n\
+                 clean.cmd) echo '{tool} clean' ;;\n\
+                 build.dir|test.dir|clean.dir) echo . ;;\n\
+                 translator.command) echo '{tool} translate {{type}} {{c_code}} {{output}}' ;;\n\
+                 *) exit 1 ;;\n\
+                 esac\n"
+            ),
+        );
+        let path = match std::env::var("PATH") {
+            Ok(prior) => format!("{}:{}", bin_dir.display(), prior),
+            Err(_) => bin_dir.display().to_string(),
+        };
+        let _path = EnvGuard::set("PATH", &path);
+
+        let repo = TestRepo::init(&project_root.join(".c2rust"));
+        repo.git(&["add", "-A"]);
+        repo.git(&["commit", "-q", "-m", "init"]);
+
+        let mut observer = DeclinePlan {
+            calls: calls.clone(),
+            repo: repo.clone(),
+            calls_at_plan: None,
+            log_at_plan: None,
+        };
+        translate_feature_with_observer(
+            "default",
+            &TranslateOptions {
+                allow_all: true,
+                confirm: true,
+                ..Default::default()
+            },
+            &mut observer,
+        )
+        .unwrap();
+
+        let calls_at_plan = observer.calls_at_plan.expect("run plan was not shown");
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap_or_default(),
+            calls_at_plan
+        );
+        // Initial verification runs before the plan; nothing is translated.
+        assert!(calls_at_plan.contains("tool build"), "{calls_at_plan}");
+        assert!(!calls_at_plan.contains("tool translate"), "{calls_at_plan}");
+        assert_eq!(
+            fs::read_to_string(rust_dir.join("src/fun_a.rs")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            Some(repo.git(&["log", "--format=%s"])),
+            observer.log_at_plan
+        );
+    }
+
+    #[test]
+    fn test_translate_feature_rejects_zero_translation_attempts() {
+        let err = translate_feature(
+            "default",
+            &TranslateOptions {
+                max_translation_attempts: 0,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("max_translation_attempts must be at least 1"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_interaction_state_is_reset_for_each_run() {
+        struct Quiet;
+        impl observer::WorkflowObserver for Quiet {
+            fn on_output(&mut self, _stream: observer::OutputStream, _text: &str) -> bool {
+                true
+            }
+        }
+
+        let (_temp_dir, _cwd, _feature_root, _rust_dir) = create_temp_feature_workspace("default");
+        let _reset = scopeguard::guard((), |_| {
+            interaction::disable_auto_accept_mode();
+            interaction::set_prompts_disabled(false);
+        });
+        // Both runs stop at the feature name check, after the flags were applied.
+        let run = |interactive_mode| {
+            let options = TranslateOptions {
+                interactive_mode,
+                ..Default::default()
+            };
+            assert!(translate_feature_with_observer("defautl", &options, &mut Quiet).is_err());
+        };
+
+        run(InteractiveMode::Auto);
+        assert!(interaction::is_auto_accept_mode());
+        run(InteractiveMode::Prompt);
+        assert!(!interaction::is_auto_accept_mode());
+        assert!(!interaction::are_prompts_disabled());
+    }
+
+    #[test]
+    #[serial]
+    fn test_translate_feature_rejects_same_feature_typo_twice() {
+        let (_temp_dir, _cwd, _feature_root, _rust_dir) = create_temp_feature_workspace("default");
+        let run = || {
+            translate_feature("defautl", &TranslateOptions::default())
+                .unwrap_err()
+                .to_string()
+        };
+
+        for _ in 0..2 {
+            let err = run();
+            assert!(err.contains("did you mean 'default'"), "{}", err);
+        }
+        assert!(!Path::new(".c2rust/defautl").exists());
+    }
+
+    #[test]
+    fn test_translate_feature_rejects_parallel_jobs_without_allow_all() {
+        let run = |jobs: usize| {
+            translate_feature(
+                "default",
+                &TranslateOptions {
+                    jobs,
+                    ..Default::default()
+                },
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        assert!(run(0).contains("jobs must be at least 1"));
+        assert!(run(4).contains("--jobs 4 requires --allow-all"));
+    }
+
+    #[test]
+    fn test_measure_unsafe_usage_counts_unsafe_context() {
+        let safe = measure_unsafe_usage("fn f() { let a = 1; let b = a + 1; }").unwrap();
+        assert_eq!(safe.total_stmts, 2);
+        assert_eq!(safe.unsafe_stmts, 0);
+        assert_eq!(safe.ratio(), 0.0);
+
+        let mixed = measure_unsafe_usage(
+            "fn f() { let a = 1; unsafe { g(a); h(); } }\n\
+             impl S { unsafe fn m(&self) { g(1); } }",
+        )
+        .unwrap();
+        assert_eq!(mixed.unsafe_blocks, 1);
+        assert_eq!(mixed.unsafe_fns, 1);
+        // `let a`, the unsafe block statement, `g(a)`, `h()` and `g(1)`.
+        assert_eq!(mixed.total_stmts, 5);
+        assert_eq!(mixed.unsafe_stmts, 3);
+        assert!((mixed.ratio() - 0.6).abs() < f64::EPSILON);
+
+        let all_unsafe = measure_unsafe_usage("unsafe fn f() { g(); h(); }").unwrap();
+        assert_eq!(all_unsafe.ratio(), 1.0);
+
+        assert_eq!(measure_unsafe_usage("fn broken( {"), None);
+        assert_eq!(measure_unsafe_usage("").unwrap().ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_resume_entry_for_phase_skips_completed_phases() {
+        assert_eq!(
+            resume_entry_for_phase(util::FilePhase::Translated),
+            ResumeEntry::BuildAndFix
+        );
+        assert_eq!(
+            resume_entry_for_phase(util::FilePhase::Built),
+            ResumeEntry::HybridTest
+        );
+        assert_eq!(
+            resume_entry_for_phase(util::FilePhase::Tested),
+            ResumeEntry::Commit
+        );
+    }
+
+    #[test]
+    fn test_pending_resume_entry_built_file_resumes_at_test_phase() {
+        let dir = tempdir().unwrap();
+        let rs_file = dir.path().join("fun_built.rs");
+        fs::write(&rs_file, "pub fn built() {}\n").unwrap();
+
+        let mut stats = util::TranslationStats::new();
+        stats.record_file_phase("src/fun_built.rs", util::FilePhase::Built);
+
+        let entry = pending_resume_entry(&mut stats, "src/fun_built.rs", &rs_file).unwrap();
+        assert_eq!(entry, Some(ResumeEntry::HybridTest));
+
+        // Without a marker the file is translated from scratch.
+        let entry = pending_resume_entry(&mut stats, "src/fun_other.rs", &rs_file).unwrap();
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn test_file_removed_after_scan_is_skipped() {
+        let dir = tempdir().unwrap();
+        let rust_dir = dir.path();
+        fs::create_dir_all(rust_dir.join("src")).unwrap();
+        let removed = rust_dir.join("src/fun_removed.rs");
+        let filled = rust_dir.join("src/fun_filled.rs");
+        fs::write(&removed, "").unwrap();
+        fs::write(&filled, "").unwrap();
+        let scanned = vec![removed.clone(), filled.clone()];
+
+        fs::remove_file(&removed).unwrap();
+        fs::write(&filled, "pub fn filled() {}\n").unwrap();
+
+        let mut stats = util::TranslationStats::new();
+        assert_eq!(
+            stale_scan_reason(&removed, "src/fun_removed.rs", &stats),
+            Some("no longer exists")
+        );
+        assert_eq!(
+            stale_scan_reason(&filled, "src/fun_filled.rs", &stats),
+            Some("is no longer empty")
+        );
+
+        let mut progress_state = util::ProgressState::new(2);
+        let mut translations_since_last_test = 0;
+        process_selected_files(
+            "default",
+            (&scanned, &[0, 1]),
+            rust_dir,
+            &mut progress_state,
+            &mut stats,
+            &mut translations_since_last_test,
+            FileRunOptions {
+                max_error_fix_attempts: 1,
+                max_warning_fix_attempts: 0,
+                max_translation_attempts: 1,
+                show_full_output: false,
+                skip_test: true,
+                dry_run: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(progress_state.processed_count, 0);
+
+        // A file pre-translated in parallel is expected to be non-empty.
+        stats.record_file_phase("src/fun_filled.rs", util::FilePhase::Translated);
+        assert_eq!(
+            stale_scan_reason(&filled, "src/fun_filled.rs", &stats),
+            None
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_oversized_file_is_not_offered_as_skipped() {
+        let (_temp_dir, _cwd, _feature_root, rust_dir) = create_temp_feature_workspace("default");
+        let rs_file = rust_dir.join("src/fun_big.rs");
+        fs::write(&rs_file, "").unwrap();
+        fs::write(rs_file.with_extension("c"), "int big(void) { return 0; }\n").unwrap();
+        set_max_c_file_bytes(Some(8));
+        interaction::enable_auto_accept_mode();
+        let _reset = scopeguard::guard((), |_| {
+            set_max_c_file_bytes(None);
+            interaction::disable_auto_accept_mode();
+        });
+
+        let scanned = vec![rs_file.clone()];
+        let mut stats = util::TranslationStats::new();
+        let mut progress_state = util::ProgressState::new(1);
+        let mut translations_since_last_test = 0;
+        process_selected_files(
+            "default",
+            (&scanned, &[0]),
+            &rust_dir,
+            &mut progress_state,
+            &mut stats,
+            &mut translations_since_last_test,
+            FileRunOptions {
+                max_error_fix_attempts: 1,
+                max_warning_fix_attempts: 0,
+                max_translation_attempts: 1,
+                show_full_output: false,
+                skip_test: true,
+                dry_run: false,
+            },
+        )
+        .unwrap();
+
+        assert!(stats.skipped_files.is_empty());
+        assert_eq!(stats.oversized_files, vec!["src/fun_big.rs"]);
+        assert!(exclude_skipped_and_failed(scanned, &rust_dir, &stats).is_empty());
+    }
+
+    #[test]
+    fn test_pending_resume_entry_drops_marker_for_empty_file() {
+        let dir = tempdir().unwrap();
+        let rs_file = dir.path().join("fun_empty.rs");
+        fs::write(&rs_file, "").unwrap();
+
+        let mut stats = util::TranslationStats::new();
+        stats.record_file_phase("src/fun_empty.rs", util::FilePhase::Tested);
+
+        let entry = pending_resume_entry(&mut stats, "src/fun_empty.rs", &rs_file).unwrap();
+        assert_eq!(entry, None);
+        assert_eq!(stats.file_phase("src/fun_empty.rs"), None);
+    }
+
+    #[test]
+    fn test_include_dry_run_target_adds_non_empty_target_once() {
+        let dir = tempdir().unwrap();
+        let rust_dir = dir.path();
+        fs::create_dir_all(rust_dir.join("src")).unwrap();
+        fs::write(rust_dir.join("src/fun_done.rs"), "fn done() {}").unwrap();
+        let pending = rust_dir.join("src/fun_pending.rs");
+        fs::write(&pending, "").unwrap();
+
+        let mut files = vec![pending.clone()];
+        include_dry_run_target(&mut files, rust_dir, Some("src/fun_pending.rs"));
+        assert_eq!(files, vec![pending.clone()]);
+
+        include_dry_run_target(&mut files, rust_dir, Some("src/fun_done.rs"));
+        assert_eq!(
+            files,
+            vec![pending.clone(), rust_dir.join("src/fun_done.rs")]
+        );
+
+        let mut files = vec![pending.clone()];
+        include_dry_run_target(&mut files, rust_dir, Some("src/fun_missing.rs"));
+        include_dry_run_target(&mut files, rust_dir, None);
+        assert_eq!(files, vec![pending]);
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_should_process_warnings_default() {
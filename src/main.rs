@@ -15,10 +15,45 @@ fn parse_non_negative_usize(s: &str) -> Result<usize, String> {
         .map_err(|_| format!("`{s}` is not a valid number"))
 }
 
+fn parse_max_duration(s: &str) -> Result<std::time::Duration, String> {
+    c2rust_translate::util::parse_duration(s).map_err(|e| e.to_string())
+}
+
+fn parse_max_c_file_bytes(s: &str) -> Result<u64, String> {
+    match c2rust_translate::util::parse_byte_size(s).map_err(|e| e.to_string())? {
+        0 => Err(String::from("value must be greater than 0")),
+        bytes => Ok(bytes),
+    }
+}
+
+fn parse_unit_ratio(s: &str) -> Result<f64, String> {
+    let value: f64 = s
+        .parse()
+        .map_err(|_| format!("`{s}` is not a valid number"))?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(String::from("value must be between 0.0 and 1.0"));
+    }
+    Ok(value)
+}
+
 #[derive(Parser)]
 #[command(name = "c2rust-translate")]
 #[command(about = "A tool for translating C code to Rust", long_about = None)]
 struct Cli {
+    /// 向上查找 `.c2rust` 项目根目录时最多到达的目录（含该目录），
+    /// 覆盖环境变量 `C2RUST_ROOT_BOUNDARY`；在边界内找不到时报错
+    #[arg(long, global = true, value_name = "DIR")]
+    root_boundary: Option<std::path::PathBuf>,
+
+    /// 直接指定项目根目录（必须包含 `.c2rust` 目录），覆盖环境变量 `C2RUST_PROJECT_ROOT`，
+    /// 不再从当前目录向上查找
+    #[arg(long, global = true, value_name = "DIR")]
+    project_root: Option<std::path::PathBuf>,
+
+    /// 关闭彩色输出和代码预览的语法高亮（也可设置环境变量 `NO_COLOR`；非 TTY 时自动关闭）
+    #[arg(long, global = true)]
+    no_color: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -26,30 +61,100 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// 为特定功能将 C 代码翻译为 Rust
-    Translate {
+    Translate(Box<TranslateArgs>),
+
+    /// 列出需要翻译的空 .rs 文件（只读，不提示）
+    ListEmpty {
         /// 功能名称（如未指定则默认为 "default"）
         #[arg(long, default_value = "default")]
         feature: String,
 
-        /// 允许处理所有未处理的文件，无需提示选择
+        /// 以 JSON 格式输出（包含文件类型和对应 .c 文件是否存在）
         #[arg(long)]
-        allow_all: bool,
+        json: bool,
 
-        /// 仅处理指定的 Rust 目标文件（相对 `.c2rust/<feature>/rust` 的路径）
-        #[arg(long)]
-        file: Option<String>,
+        /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`
+        #[arg(long, value_name = "PATH")]
+        config: Option<std::path::PathBuf>,
+    },
+
+    /// 统计翻译完成情况（按 var/fun 类型和顶层模块目录分组，只读，不执行构建）
+    Status {
+        /// 功能名称（如未指定则默认为 "default"）
+        #[arg(long, default_value = "default")]
+        feature: String,
 
-        /// 构建错误的最大修复尝试次数（必须 > 0，默认为 5）
+        /// 输出格式：human（默认）或 json
+        #[arg(long, default_value = "human", value_name = "human|json")]
+        format: c2rust_translate::events::OutputFormat,
+
+        /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`
+        #[arg(long, value_name = "PATH")]
+        config: Option<std::path::PathBuf>,
+    },
+
+    /// 只对已翻译（非空）的文件运行构建与修复循环，不重新翻译，避免从头重试覆盖已有的翻译
+    Fix {
+        /// 功能名称（如未指定则默认为 "default"）
+        #[arg(long, default_value = "default")]
+        feature: String,
+
+        /// 要修复的 .rs 文件（相对 rust 目录，如 `src/fun_a.rs`；只写文件名时在 `src/` 下查找）
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<String>,
+
+        /// 每个文件的最大修复尝试次数（必须 > 0，默认为 5）
         #[arg(long, default_value = "5", value_parser = parse_positive_usize)]
-        max_error_fix_attempts: usize,
+        max_fix_attempts: usize,
+
+        /// 显示完整输出
+        #[arg(long)]
+        show_full_output: bool,
+
+        /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`
+        #[arg(long, value_name = "PATH")]
+        config: Option<std::path::PathBuf>,
+    },
 
-        /// 构建告警的最大修复尝试次数（必须 >= 0，0 表示跳过告警修复，默认为 10）
-        #[arg(long, default_value = "10", value_parser = parse_non_negative_usize)]
-        max_warning_fix_attempts: usize,
+    /// 检查 `rust/src` 下 C/Rust 文件的配对完整性：每个 var_/fun_ .rs 都有同名 .c，每个 .c 都有 .rs，
+    /// 并报告无法识别类型的 .rs 文件（只读；发现问题时以非零状态退出）
+    VerifyPairs {
+        /// 功能名称（如未指定则默认为 "default"）
+        #[arg(long, default_value = "default")]
+        feature: String,
+    },
+
+    /// 重新验证已翻译的全部文件能否一起构建：对整个 feature 执行一次 cargo build 和混合构建/测试，
+    /// 不翻译、不提交；失败时列出错误中定位到的文件
+    VerifyAll {
+        /// 功能名称（如未指定则默认为 "default"）
+        #[arg(long, default_value = "default")]
+        feature: String,
 
-        /// 显示代码和错误的完整输出，不进行截断
+        /// 显示完整输出，不进行截断
         #[arg(long)]
         show_full_output: bool,
+
+        /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`
+        #[arg(long, value_name = "PATH")]
+        config: Option<std::path::PathBuf>,
+    },
+
+    /// 清除 feature 生成的状态（只删除 `.c2rust/<feature>/` 下的文件，不修改源码与翻译结果）
+    Clean {
+        /// 功能名称（如未指定则默认为 "default"）
+        #[arg(long, default_value = "default")]
+        feature: String,
+
+        /// 清除范围：build（cargo 构建产物 rust/target）、progress（各分支的会话进度）、
+        /// suggestions（按文件保存的修复建议）、all（以上全部以及统计、日志和运行报告）；
+        /// 配置项 build.target 由 c2rust-config 管理，不能用 clean 删除（指定 targets 时报错）
+        #[arg(value_name = "build|progress|suggestions|all")]
+        what: c2rust_translate::CleanScope,
+
+        /// 只列出将要删除的内容，不实际删除
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// 执行 feature 初始化校验，不进入翻译循环
@@ -61,32 +166,393 @@ enum Commands {
         /// 显示完整输出，不进行截断
         #[arg(long)]
         show_full_output: bool,
+
+        /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`
+        #[arg(long, value_name = "PATH")]
+        config: Option<std::path::PathBuf>,
     },
 }
 
+/// `translate` 子命令的参数
+#[derive(clap::Args)]
+struct TranslateArgs {
+    /// 功能名称；未指定时依次使用环境变量 `C2RUST_FEATURE`、config.toml 中的 `default_feature`，
+    /// 仍未设置时列出已有的 feature 供选择（无法提示时为 "default"）
+    #[arg(long)]
+    feature: Option<String>,
+
+    /// 允许处理所有未处理的文件，无需提示选择（已弃用：等价于 `--interactive prompt` 下跳过文件选择）
+    #[arg(long)]
+    allow_all: bool,
+
+    /// 交互模式：auto（不提示，自动处理全部文件并自动接受）、prompt（默认，当前行为）、
+    /// manual（始终提示选择文件，不自动接受）；`false`/`true` 分别是 auto/prompt 的别名
+    #[arg(long, default_value = "prompt", value_name = "auto|prompt|manual")]
+    interactive: c2rust_translate::InteractiveMode,
+
+    /// 仅处理指定的 Rust 目标文件（相对 `.c2rust/<feature>/rust` 的路径）
+    #[arg(long)]
+    file: Option<String>,
+
+    /// 构建错误的最大修复尝试次数（必须 > 0，默认为 5）
+    #[arg(long, default_value = "5", value_parser = parse_positive_usize)]
+    max_error_fix_attempts: usize,
+
+    /// 构建告警的最大修复尝试次数（必须 >= 0，0 表示跳过告警修复，默认为 10）
+    #[arg(long, default_value = "10", value_parser = parse_non_negative_usize)]
+    max_warning_fix_attempts: usize,
+
+    /// 每个文件从头重新翻译的最大次数（必须 > 0，默认为 3，即 1 次初始翻译 + 2 次重试）
+    #[arg(
+        long,
+        default_value_t = c2rust_translate::util::MAX_TRANSLATION_ATTEMPTS,
+        value_parser = parse_positive_usize
+    )]
+    max_translation_attempts: usize,
+
+    /// 显示代码和错误的完整输出，不进行截断
+    #[arg(long)]
+    show_full_output: bool,
+
+    /// 接受翻译前需要逐一通过混合构建/测试的 profile 列表（逗号分隔，如 `debug,release`；
+    /// 第一个为主 profile，默认仅 debug）
+    #[arg(long, value_delimiter = ',', value_name = "PROFILES")]
+    verify_profiles: Vec<c2rust_translate::builder::BuildProfile>,
+
+    /// 告警处理方式：fix（默认，构建通过后单独修复告警）、ignore（忽略告警）、
+    /// fail（将告警视为构建错误，进入错误修复循环）
+    #[arg(long, default_value = "fix", value_name = "fix|ignore|fail")]
+    warnings: c2rust_translate::verification::WarningMode,
+
+    /// 将发送给修复工具的错误保存到 `.c2rust/<feature>/logs/<file>.error.txt`（每次尝试覆盖），便于调试
+    #[arg(long)]
+    keep_error_files: bool,
+
+    /// 跳过翻译前的 rust 目录结构检查（空 .rs 文件的类型前缀和对应 .c 文件）
+    #[arg(long)]
+    skip_structural_check: bool,
+
+    /// 追加到 cargo 构建 RUSTFLAGS 的额外参数（如 `-Zmacro-backtrace`），与配置的 `build.rustflags` 合并；
+    /// 不允许修改 `warnings` lint 级别
+    #[arg(long, value_name = "FLAGS", allow_hyphen_values = true)]
+    extra_rustflags: Option<String>,
+
+    /// 以 `-D warnings` 构建，告警直接导致构建失败并进入错误修复循环（默认在错误阶段以
+    /// `-A warnings` 抑制告警）；环境变量中已有的 RUSTFLAGS 始终保留
+    #[arg(long, conflicts_with = "warnings")]
+    deny_warnings: bool,
+
+    /// 跳过匹配该 glob 模式的文件（相对 rust 目录，可重复指定；`**` 匹配任意层目录，
+    /// `!` 开头表示重新纳入），追加在配置的 `ignore.patterns` 之后；被跳过的文件不计入进度总数
+    #[arg(long = "skip", value_name = "PATTERN", allow_hyphen_values = true)]
+    skip: Vec<String>,
+
+    /// 只处理匹配该 glob 模式的空文件（相对 rust 目录，可省略开头的 `src/`，如 `net/*`）；
+    /// 配合 `--allow-all` 处理全部匹配文件，否则在匹配文件中交互选择；没有匹配的空文件时报错
+    #[arg(long, value_name = "PATTERN", conflicts_with = "file")]
+    only: Option<String>,
+
+    /// 只处理自该 git 版本以来有改动的空文件（在 rust 目录中执行 `git diff --name-only REV`，
+    /// 另含未跟踪的新文件），适合 C 源码更新、`code-analyse --update` 清空或新建部分文件后的增量翻译；
+    /// 版本无效时报告 git 的错误
+    #[arg(long, value_name = "REV", conflicts_with = "file")]
+    since: Option<String>,
+
+    /// 不对翻译/修复写出的文件执行 `rustfmt --edition 2021`（默认在构建前格式化；
+    /// 也可通过配置 `format.enabled = false` 关闭）
+    #[arg(long)]
+    no_fmt: bool,
+
+    /// 使用指定的 config.toml（必须存在且可读）代替 `.c2rust/config.toml`，
+    /// 传给翻译脚本，通过 c2rust-config 读取的配置项也从该文件读取
+    #[arg(long, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    /// 运行时间预算（如 `2h`、`30m`、`1h30m`）：超出后不再开始新文件，当前文件处理并提交完后
+    /// 打印剩余文件数并正常退出（退出码 0），下次运行从剩余文件继续
+    #[arg(long, value_name = "DURATION", value_parser = parse_max_duration)]
+    max_duration: Option<std::time::Duration>,
+
+    /// 将本次运行完成的每个文件的 diff 汇总写入该补丁文件（运行开始时清空），路径相对项目根目录，
+    /// 可用 `git apply` 应用；结束时打印新增行数
+    #[arg(long, value_name = "PATH")]
+    export_patch: Option<std::path::PathBuf>,
+
+    /// 不翻译，直接用 `--file` 指定文件第 N 次翻译的备份（重试前保存在 `rust/src/.bak/`）覆盖它并提交
+    #[arg(long, value_name = "N", requires = "file", value_parser = parse_positive_usize)]
+    restore_attempt: Option<usize>,
+
+    /// C 文件超过该大小（字节，可带 K/M/G 后缀）时不调用翻译器：交互模式下询问是否仍然翻译，
+    /// 否则跳过并在报告中记录原因（这些文件不会在"处理跳过的文件"中重新处理，
+    /// 之后可用 `--file` 单独翻译）；默认不限制
+    #[arg(long, value_name = "BYTES", value_parser = parse_max_c_file_bytes)]
+    max_c_file_bytes: Option<u64>,
+
+    /// 同一 feature 已有运行持有锁（`.c2rust/<feature>/.lock`）时仍强制接管；仅用于确认该运行已卡住或失效
+    #[arg(long)]
+    force_unlock: bool,
+
+    /// 每翻译完成 N 个文件才提交一次（提交信息列出这些文件，并包含它们的修复与代码分析更新），
+    /// 不足 N 个的剩余文件在运行结束（包括出错中止）时提交，但第二次 Ctrl-C 立即中止时不提交
+    /// （改动留在 `.c2rust/` 工作区，随下一次提交一并提交）；默认 1，即每个文件提交一次
+    #[arg(long, value_name = "N", default_value = "1", value_parser = parse_positive_usize)]
+    commit_every: usize,
+
+    /// 模拟运行：只打印将要处理的文件和将要执行的命令（以 `[dry-run]` 标注），
+    /// 不调用翻译脚本、不运行 cargo、不提交 git
+    #[arg(long)]
+    dry_run: bool,
+
+    /// 删除当前 git 分支保存的会话进度（`.c2rust/<feature>/progress-<branch>-<hash>.json`），从头开始统计
+    #[arg(long)]
+    reset_progress: bool,
+
+    /// 输出格式：human（默认，彩色可读输出）、json（在标准输出逐行输出 JSON 事件，
+    /// 可读输出转到标准错误），便于 CI 或编辑器插件解析
+    #[arg(long, default_value = "human", value_name = "human|json")]
+    format: c2rust_translate::events::OutputFormat,
+
+    /// 单个构建命令（cargo check/build 及 clean/build/test）的超时时间（秒，必须 > 0），
+    /// 覆盖配置的 `build.timeout`（默认 600 秒）；超时后终止该命令的整个进程组
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    build_timeout: Option<u64>,
+
+    /// 并行翻译的文件数（必须 > 0，默认为 1）：先并发调用翻译脚本，再逐个构建/修复；
+    /// 大于 1 时必须同时指定 `--allow-all`（或 `--interactive auto`），并关闭交互提示
+    #[arg(long, default_value = "1", value_parser = parse_positive_usize)]
+    jobs: usize,
+
+    /// 将每次翻译/修复调用的完整输入输出（argv、C 源码、错误信息、建议、生成的 Rust 代码）
+    /// 按时间戳分别保存到该目录下，用于审计和调试
+    #[arg(long, value_name = "DIR")]
+    record_io: Option<std::path::PathBuf>,
+
+    /// unsafe 上下文中语句占比的上限（0.0–1.0，默认不检查）：构建通过后超过该比例时给出告警，
+    /// 交互模式下可输入"减少 unsafe"的建议，随即按该建议执行一轮修复并重新构建、重新检查
+    #[arg(long, value_name = "RATIO", value_parser = parse_unit_ratio)]
+    max_unsafe_ratio: Option<f64>,
+
+    /// 不创建任何 git 提交（代码分析更新和翻译照常执行），结束时提示跳过的提交点数量，
+    /// 由用户自行提交
+    #[arg(long)]
+    no_commit: bool,
+
+    /// 启动时不清理 `.c2rust/<feature>/logs/`（默认按配置 `logs.max_size_mb`（200）和
+    /// `logs.max_age_days`（30，0 表示不限）从最旧的文件开始删除）
+    #[arg(long)]
+    no_prune: bool,
+
+    /// 初始化（及初始提交）之后、翻译循环开始前打印运行计划（文件及顺序、目标、提交方式、
+    /// 构建 profile、翻译后端），需确认 y/N 后才继续；无 TTY 时自动确认
+    #[arg(long)]
+    confirm: bool,
+
+    /// 失败时 C/Rust 代码对比的布局：auto（默认，终端宽度足够时并排，否则上下堆叠）、stacked、
+    /// side-by-side 或 side-by-side=<COLS>（指定总宽度）；非 TTY 时按 80 列计算
+    #[arg(
+        long,
+        default_value = "auto",
+        value_name = "auto|stacked|side-by-side[=COLS]"
+    )]
+    diff_layout: c2rust_translate::diff_display::DiffLayout,
+
+    /// 完全非交互（不读取标准输入），等价于 `--interactive auto`；环境变量 `CI=true` 时自动启用
+    #[arg(long)]
+    non_interactive: bool,
+
+    /// 无法提示时构建/测试失败的处理策略：continue（默认，跳过失败的步骤或文件；无法跳过时中止）、
+    /// abort（中止）、retry-once-then-abort（从头重新翻译一次，再失败则中止）；手动修复不可用
+    #[arg(
+        long,
+        default_value = "continue",
+        value_name = "continue|abort|retry-once-then-abort"
+    )]
+    on_failure: c2rust_translate::FailurePolicy,
+
+    /// 捕获翻译脚本的标准输出/错误，写入 `.c2rust/<feature>/logs/<file>.translate.log`
+    /// （修复调用写入 `<file>.fix.log`；默认直接输出到终端；`--jobs` > 1 时总是捕获）；
+    /// 配合 `--show-full-output` 时同时回显
+    #[arg(long)]
+    capture_translator_output: bool,
+
+    /// 单次翻译或修复脚本调用的超时时间（秒，必须 > 0），覆盖配置的 `translate.timeout`（默认不限时）；
+    /// 超时后终止脚本进程，按翻译（修复）失败处理
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    translate_timeout: Option<u64>,
+
+    /// 同一文件多次重试之间保留修复建议（默认在重新翻译或输入新建议前清除）；
+    /// 建议按文件保存在 `.c2rust/<feature>/suggestions/<mod>_<name>.txt`
+    #[arg(long)]
+    remember_suggestions: bool,
+
+    /// 总是在编辑器（$VISUAL / $EDITOR）中编写修复建议，以 `#` 开头的行会被忽略；
+    /// 默认使用单行输入，在输入处键入 `:edit` 可单次改用编辑器
+    #[arg(long)]
+    suggestion_editor: bool,
+}
+
 fn main() {
     let cli = Cli::parse();
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+    if cli.root_boundary.is_some() {
+        c2rust_translate::util::set_root_boundary(cli.root_boundary);
+    }
+    if cli.project_root.is_some() {
+        c2rust_translate::util::set_project_root(cli.project_root);
+    }
+
+    if matches!(cli.command, Commands::Translate(_)) {
+        c2rust_translate::interrupt::install_handler();
+    }
 
     let result = match cli.command {
-        Commands::Translate {
+        Commands::Translate(args) => {
+            let TranslateArgs {
+                feature,
+                allow_all,
+                interactive,
+                file,
+                max_error_fix_attempts,
+                max_warning_fix_attempts,
+                max_translation_attempts,
+                show_full_output,
+                verify_profiles,
+                warnings,
+                keep_error_files,
+                skip_structural_check,
+                extra_rustflags,
+                dry_run,
+                reset_progress,
+                format,
+                build_timeout,
+                jobs,
+                record_io,
+                max_unsafe_ratio,
+                no_commit,
+                no_prune,
+                confirm,
+                diff_layout,
+                non_interactive,
+                on_failure,
+                capture_translator_output,
+                translate_timeout,
+                remember_suggestions,
+                suggestion_editor,
+                deny_warnings,
+                skip,
+                only,
+                since,
+                no_fmt,
+                config,
+                max_duration,
+                export_patch,
+                restore_attempt,
+                max_c_file_bytes,
+                force_unlock,
+                commit_every,
+            } = *args;
+            c2rust_translate::initialization::resolve_feature_name(
+                feature.as_deref(),
+                config.as_deref(),
+                interactive != c2rust_translate::InteractiveMode::Auto
+                    && !non_interactive
+                    && !c2rust_translate::is_ci_environment(std::env::var("CI").ok().as_deref()),
+            )
+            .and_then(|feature| match (restore_attempt, file.as_deref()) {
+                (Some(attempt), Some(file)) => {
+                    c2rust_translate::restore_translation_attempt(&feature, file, attempt)
+                }
+                _ => c2rust_translate::translate_feature(
+                    &feature,
+                    &c2rust_translate::TranslateOptions {
+                        allow_all,
+                        interactive_mode: interactive,
+                        target_file: file,
+                        max_error_fix_attempts,
+                        max_warning_fix_attempts,
+                        max_translation_attempts,
+                        show_full_output,
+                        verify_profiles,
+                        warning_mode: warnings,
+                        keep_error_files,
+                        skip_structural_check,
+                        extra_rustflags,
+                        dry_run,
+                        reset_progress,
+                        output_format: format,
+                        build_timeout,
+                        jobs,
+                        record_io,
+                        max_unsafe_ratio,
+                        no_commit,
+                        no_prune,
+                        confirm,
+                        diff_layout,
+                        non_interactive: non_interactive
+                            || c2rust_translate::is_ci_environment(
+                                std::env::var("CI").ok().as_deref(),
+                            ),
+                        on_failure,
+                        capture_translator_output,
+                        translate_timeout,
+                        remember_suggestions,
+                        suggestion_editor,
+                        deny_warnings,
+                        skip_patterns: skip,
+                        only_pattern: only,
+                        since,
+                        no_format: no_fmt,
+                        config_path: config,
+                        max_duration,
+                        export_patch,
+                        max_c_file_bytes,
+                        force_unlock,
+                        commit_every,
+                    },
+                ),
+            })
+        }
+        Commands::ListEmpty {
+            feature,
+            json,
+            config,
+        } => c2rust_translate::util::set_config_path(config.as_deref())
+            .and_then(|()| c2rust_translate::list_empty_files(&feature, json)),
+        Commands::Status {
             feature,
-            allow_all,
-            file,
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
+            format,
+            config,
+        } => c2rust_translate::util::set_config_path(config.as_deref())
+            .and_then(|()| c2rust_translate::show_status(&feature, format)),
+        Commands::VerifyPairs { feature } => c2rust_translate::verify_file_pairing(&feature),
+        Commands::Fix {
+            feature,
+            files,
+            max_fix_attempts,
             show_full_output,
-        } => c2rust_translate::translate_feature(
-            &feature,
-            allow_all,
-            file.as_deref(),
-            max_error_fix_attempts,
-            max_warning_fix_attempts,
+            config,
+        } => c2rust_translate::util::set_config_path(config.as_deref()).and_then(|()| {
+            c2rust_translate::fix_files(&feature, &files, max_fix_attempts, show_full_output)
+        }),
+        Commands::VerifyAll {
+            feature,
             show_full_output,
-        ),
+            config,
+        } => c2rust_translate::util::set_config_path(config.as_deref())
+            .and_then(|()| c2rust_translate::verify_all(&feature, show_full_output)),
         Commands::Verify {
             feature,
             show_full_output,
-        } => c2rust_translate::verify_feature(&feature, show_full_output),
+            config,
+        } => c2rust_translate::util::set_config_path(config.as_deref())
+            .and_then(|()| c2rust_translate::verify_feature(&feature, show_full_output)),
+        Commands::Clean {
+            feature,
+            what,
+            dry_run,
+        } => c2rust_translate::clean_feature(&feature, what, dry_run),
     };
 
     if let Err(e) = result {
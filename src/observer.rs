@@ -0,0 +1,486 @@
+//! 嵌入本库时使用的工作流观察者（GUI 等其他前端、无终端的集成测试）
+//!
+//! 通过 [`observe`] 注册一个 [`WorkflowObserver`] 后（或直接调用
+//! [`crate::translate_feature_with_observer`]），工作流事件（与 `--format json` 输出的
+//! [`Event`] 相同）会回调给它，所有交互提示（失败处理、修复建议、接受结果、文件与 feature
+//! 选择、y/N 确认）也会先询问它的决定；返回 [`Decision::Console`] /
+//! [`SuggestionDecision::Console`] / [`Selection::Console`] 时按原有方式在终端提示。
+//! 面向人的进度输出逐段交给 [`WorkflowObserver::on_output`]，观察者不处理时照常写到终端
+//! （`--format json` 时标准输出转到标准错误）。输出带 ANSI 颜色，不需要时可用
+//! `colored::control::set_override(false)` 关闭。未注册观察者时行为与 [`ConsoleObserver`]
+//! 相同，即完全保持命令行的现有行为。
+//!
+//! 未捕获输出时（未使用 `--capture-translator-output`），翻译器与 cargo 等子进程的输出直接写到
+//! 终端，不经过观察者。回调期间不要再调用本库的函数，否则会等待观察者自身的锁。
+//!
+//! ```no_run
+//! use c2rust_translate::observer::{observe, Decision, Prompt, WorkflowObserver};
+//! use std::sync::{Arc, Mutex};
+//!
+//! #[derive(Default)]
+//! struct SkipEverything {
+//!     started: Vec<String>,
+//! }
+//!
+//! impl WorkflowObserver for SkipEverything {
+//!     fn on_file_start(&mut self, file: &str, _index: usize, _total: usize) {
+//!         self.started.push(file.to_string());
+//!     }
+//!     fn on_prompt(&mut self, prompt: &Prompt) -> Decision {
+//!         if prompt.allows(Decision::Skip) {
+//!             Decision::Skip
+//!         } else {
+//!             Decision::Exit
+//!         }
+//!     }
+//! }
+//!
+//! let observer = Arc::new(Mutex::new(SkipEverything::default()));
+//! let _guard = observe(observer.clone());
+//! // 在此调用 c2rust_translate::translate_feature(...)；结束后从 observer 读取记录
+//! ```
+
+use crate::events::{Event, EventResult};
+use std::sync::{Arc, Mutex};
+
+/// 需要用户决定的交互提示类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// 通用失败提示（如初始化验证失败）
+    Failure,
+    /// 手动修复后仍然失败
+    AfterManualFix,
+    /// 达到最大修复次数后编译仍失败
+    CompileFailure,
+    /// 混合构建失败
+    BuildFailure,
+    /// 测试失败
+    TestFailure,
+}
+
+/// 对失败提示的决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// 不替用户决定，按原有方式在终端提示（无 TTY 时按 `--on-failure` 处理）
+    Console,
+    /// 清空 .rs 文件，从 C 源码重新翻译
+    RetryDirectly,
+    /// 输入修复建议后重试（随后通过 [`WorkflowObserver::on_suggestion`] 询问建议内容）
+    AddSuggestion,
+    /// 在编辑器中手动修复
+    ManualFix,
+    /// 跳过当前步骤或文件并继续
+    Skip,
+    /// 跳过当前文件，修复其他文件
+    FixOtherFile,
+    /// 退出
+    Exit,
+}
+
+/// 一次交互提示
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prompt<'a> {
+    pub kind: PromptKind,
+    /// 提示的上下文说明（如 "Initial test failure"）
+    pub context: &'a str,
+    /// 本次提示可选的决定（不含 [`Decision::Console`]）
+    pub allowed: &'a [Decision],
+}
+
+impl Prompt<'_> {
+    /// 本次提示是否提供 `decision`
+    pub fn allows(&self, decision: Decision) -> bool {
+        self.allowed.contains(&decision)
+    }
+}
+
+/// 对修复建议输入的决定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuggestionDecision {
+    /// 按原有方式在终端输入
+    Console,
+    /// 使用给定的建议（首尾空白会被去掉，为空时视同 [`SuggestionDecision::Skip`]）
+    Provide(String),
+    /// 不提供建议（建议为必填时回退到终端输入）
+    Skip,
+}
+
+/// 除失败提示外的其他选择提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceKind {
+    /// 编译与测试通过，是否接受（接受 / 自动接受后续翻译 / 手动修复 / 退出）
+    CompileSuccess,
+    /// 构建通过但测试配置不可用，是否接受（选项同 [`Self::CompileSuccess`]）
+    BuildSuccessTestsSkipped,
+    /// 构建通过但测试被 `C2RUST_TEST_INTERVAL` 推迟，是否接受（选项同 [`Self::CompileSuccess`]）
+    BuildSuccessTestsDeferred,
+    /// 主循环结束后是否现在处理跳过的文件
+    SkippedFiles,
+    /// 发现已有进度时继续、重新开始或修复跳过的文件
+    ContinueOrRestart,
+    /// 确认 `--confirm` 打印的运行计划（是 / 否）
+    ConfirmRunPlan,
+    /// feature 不存在但有相近名称时是否作为新 feature 初始化（是 / 否）
+    ConfirmNewFeature,
+    /// C 文件超过 `--max-c-file-bytes` 时是否仍然翻译（是 / 否）
+    TranslateOversizedFile,
+    /// 未指定 feature 时从已有 feature 中选择
+    Feature,
+    /// 测试配置不完整时退出或跳过测试继续
+    TestConfigMissing,
+    /// 选择要手动编辑的文件
+    FileToEdit,
+    /// 选择要翻译的文件（可多选）
+    FilesToProcess,
+}
+
+/// 一次选择提示
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice<'a> {
+    pub kind: ChoiceKind,
+    /// 提示文字
+    pub message: &'a str,
+    /// 可选项的展示文本，[`Selection::Choose`] 中的下标即对应这里的位置
+    pub options: &'a [String],
+    /// 是否可以选择多项
+    pub multiple: bool,
+}
+
+/// 对选择提示的决定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    /// 按原有方式在终端提示（无 TTY 时使用各提示的默认值）
+    Console,
+    /// 选中的选项下标（从 0 开始）；单选时必须恰好一项，否则按 [`Self::Console`] 处理
+    Choose(Vec<usize>),
+}
+
+/// 面向人的输出写往的流
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// 工作流观察者；所有方法都有默认实现，只需覆盖关心的回调
+pub trait WorkflowObserver: Send {
+    /// 每个工作流事件；默认实现分发到 [`Self::on_file_start`] 与 [`Self::on_build_result`]
+    fn on_event(&mut self, event: &Event) {
+        match event {
+            Event::FileStart { file, index, total } => self.on_file_start(file, *index, *total),
+            Event::Build {
+                command,
+                result,
+                duration_ms,
+            } => self.on_build_result(command, *result == EventResult::Ok, *duration_ms),
+            _ => {}
+        }
+    }
+
+    /// 开始处理一个文件（`index` 从 1 开始）
+    fn on_file_start(&mut self, _file: &str, _index: usize, _total: usize) {}
+
+    /// 一次 cargo 构建/检查结束
+    fn on_build_result(&mut self, _command: &str, _ok: bool, _duration_ms: u64) {}
+
+    /// 失败提示；返回不在 `prompt.allowed` 中的决定时按 [`Decision::Console`] 处理
+    fn on_prompt(&mut self, _prompt: &Prompt) -> Decision {
+        Decision::Console
+    }
+
+    /// 修复建议输入
+    fn on_suggestion(&mut self, _required: bool) -> SuggestionDecision {
+        SuggestionDecision::Console
+    }
+
+    /// 其他选择提示；返回越界或重复的下标时按 [`Selection::Console`] 处理
+    fn on_choice(&mut self, _choice: &Choice) -> Selection {
+        Selection::Console
+    }
+
+    /// 一段面向人的输出（含换行与 ANSI 颜色）；返回 `true` 表示已处理，不再写到终端
+    fn on_output(&mut self, _stream: OutputStream, _text: &str) -> bool {
+        false
+    }
+}
+
+/// 保持命令行现有行为的观察者：不处理事件，所有提示都在终端进行
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleObserver;
+
+impl WorkflowObserver for ConsoleObserver {}
+
+/// 当前注册的观察者
+static OBSERVER: Mutex<Option<Arc<Mutex<dyn WorkflowObserver>>>> = Mutex::new(None);
+
+/// 注销观察者的守卫（drop 时恢复为注册前的观察者，之前未注册时即恢复为未注册）
+pub struct ObserverGuard(Option<Arc<Mutex<dyn WorkflowObserver>>>);
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        *OBSERVER.lock().unwrap_or_else(|e| e.into_inner()) = self.0.take();
+    }
+}
+
+/// 注册观察者，直到返回的守卫被 drop；调用方保留 `Arc` 以便结束后读取观察者的状态
+pub fn observe(observer: Arc<Mutex<dyn WorkflowObserver>>) -> ObserverGuard {
+    let previous = OBSERVER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(observer);
+    ObserverGuard(previous)
+}
+
+/// 注册一个借用的观察者执行 `f`，结束后恢复之前注册的观察者；供
+/// [`crate::translate_feature_with_observer`] 使用
+pub(crate) fn observe_borrowed<R>(observer: &mut dyn WorkflowObserver, f: impl FnOnce() -> R) -> R {
+    let observer = observer as *mut (dyn WorkflowObserver + '_);
+    // SAFETY: 只擦除借用的生命周期。守卫在本函数返回（或 `f` panic 展开）前注销观察者，
+    // 而工作流启动的线程都在 `f` 返回前结束，之后不会再有人通过注册表访问这一指针。
+    let observer: *mut (dyn WorkflowObserver + 'static) = unsafe { std::mem::transmute(observer) };
+    let _guard = observe(Arc::new(Mutex::new(Borrowed(observer))));
+    f()
+}
+
+/// 转发到借用观察者的包装
+struct Borrowed(*mut (dyn WorkflowObserver + 'static));
+
+// SAFETY: 被借用的观察者本身是 `Send`，且所有访问都经过外层的 `Mutex`。
+unsafe impl Send for Borrowed {}
+
+impl Borrowed {
+    fn get(&mut self) -> &mut dyn WorkflowObserver {
+        // SAFETY: 见 `observe_borrowed`：指针在注册期间一直有效，且外层 `Mutex` 保证独占访问。
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl WorkflowObserver for Borrowed {
+    fn on_event(&mut self, event: &Event) {
+        self.get().on_event(event)
+    }
+    fn on_file_start(&mut self, file: &str, index: usize, total: usize) {
+        self.get().on_file_start(file, index, total)
+    }
+    fn on_build_result(&mut self, command: &str, ok: bool, duration_ms: u64) {
+        self.get().on_build_result(command, ok, duration_ms)
+    }
+    fn on_prompt(&mut self, prompt: &Prompt) -> Decision {
+        self.get().on_prompt(prompt)
+    }
+    fn on_suggestion(&mut self, required: bool) -> SuggestionDecision {
+        self.get().on_suggestion(required)
+    }
+    fn on_choice(&mut self, choice: &Choice) -> Selection {
+        self.get().on_choice(choice)
+    }
+    fn on_output(&mut self, stream: OutputStream, text: &str) -> bool {
+        self.get().on_output(stream, text)
+    }
+}
+
+/// 对当前观察者执行回调；未注册时返回 `None`
+fn with_observer<T>(f: impl FnOnce(&mut dyn WorkflowObserver) -> T) -> Option<T> {
+    // 先取出 Arc 再调用，避免回调中再次访问注册表时死锁
+    let observer = OBSERVER.lock().unwrap_or_else(|e| e.into_inner()).clone()?;
+    let mut observer = observer.lock().unwrap_or_else(|e| e.into_inner());
+    Some(f(&mut *observer))
+}
+
+/// 将事件转发给观察者（由 [`crate::events::emit`] 调用）
+pub(crate) fn notify(event: &Event) {
+    with_observer(|observer| observer.on_event(event));
+}
+
+/// 询问观察者对失败提示的决定；未注册、返回 `Console` 或不在可选范围内时返回 `None`
+pub(crate) fn decide(kind: PromptKind, context: &str, allowed: &[Decision]) -> Option<Decision> {
+    let prompt = Prompt {
+        kind,
+        context,
+        allowed,
+    };
+    with_observer(|observer| observer.on_prompt(&prompt))
+        .filter(|decision| *decision != Decision::Console && allowed.contains(decision))
+}
+
+/// 询问观察者提供的修复建议；`None` 表示在终端输入
+pub(crate) fn suggestion(required: bool) -> Option<Option<String>> {
+    match with_observer(|observer| observer.on_suggestion(required))? {
+        SuggestionDecision::Console => None,
+        SuggestionDecision::Provide(text) if !text.trim().is_empty() => {
+            Some(Some(text.trim().to_string()))
+        }
+        SuggestionDecision::Provide(_) | SuggestionDecision::Skip if required => None,
+        SuggestionDecision::Provide(_) | SuggestionDecision::Skip => Some(None),
+    }
+}
+
+/// 询问观察者对选择提示的决定；未注册、返回 `Console` 或下标无效时返回 `None`
+pub(crate) fn choose(
+    kind: ChoiceKind,
+    message: &str,
+    options: &[String],
+    multiple: bool,
+) -> Option<Vec<usize>> {
+    let choice = Choice {
+        kind,
+        message,
+        options,
+        multiple,
+    };
+    let Selection::Choose(picked) = with_observer(|observer| observer.on_choice(&choice))? else {
+        return None;
+    };
+    let mut seen = std::collections::HashSet::new();
+    let valid = !picked.is_empty()
+        && (multiple || picked.len() == 1)
+        && picked
+            .iter()
+            .all(|&index| index < options.len() && seen.insert(index));
+    valid.then_some(picked)
+}
+
+/// 写出一段面向人的输出：先交给观察者，未注册或未处理时写到终端
+///
+/// 由 `lib.rs` 中遮蔽标准库 `println!` 等的宏调用。
+pub(crate) fn write_output(stream: OutputStream, args: std::fmt::Arguments) {
+    if with_observer(|observer| observer.on_output(stream, &args.to_string())).unwrap_or(false) {
+        return;
+    }
+    match stream {
+        OutputStream::Stdout => std::print!("{}", args),
+        OutputStream::Stderr => std::eprint!("{}", args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Scripted {
+        files: Vec<String>,
+        builds: Vec<bool>,
+        decision: Option<Decision>,
+    }
+
+    impl WorkflowObserver for Scripted {
+        fn on_file_start(&mut self, file: &str, _index: usize, _total: usize) {
+            self.files.push(file.to_string());
+        }
+        fn on_build_result(&mut self, _command: &str, ok: bool, _duration_ms: u64) {
+            self.builds.push(ok);
+        }
+        fn on_prompt(&mut self, _prompt: &Prompt) -> Decision {
+            self.decision.unwrap_or(Decision::Console)
+        }
+        fn on_suggestion(&mut self, _required: bool) -> SuggestionDecision {
+            SuggestionDecision::Provide("  use wrapping_add  ".to_string())
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_observer_receives_events_and_decides_prompts() {
+        assert_eq!(decide(PromptKind::Failure, "ctx", &[Decision::Skip]), None);
+
+        let observer = Arc::new(Mutex::new(Scripted {
+            decision: Some(Decision::FixOtherFile),
+            ..Default::default()
+        }));
+        {
+            let _guard = observe(observer.clone());
+            crate::events::emit(&Event::FileStart {
+                file: "src/fun_a.rs",
+                index: 1,
+                total: 2,
+            });
+            crate::events::emit(&Event::Build {
+                command: "cargo check",
+                result: EventResult::Error,
+                duration_ms: 5,
+            });
+
+            // 不在可选范围内的决定回退到终端提示
+            assert_eq!(
+                decide(PromptKind::CompileFailure, "ctx", &[Decision::Skip]),
+                None
+            );
+            observer.lock().unwrap().decision = Some(Decision::Skip);
+            assert_eq!(
+                decide(
+                    PromptKind::CompileFailure,
+                    "ctx",
+                    &[Decision::Skip, Decision::Exit]
+                ),
+                Some(Decision::Skip)
+            );
+            assert_eq!(suggestion(true), Some(Some("use wrapping_add".to_string())));
+        }
+        assert_eq!(suggestion(false), None, "guard unregisters the observer");
+
+        let observer = observer.lock().unwrap();
+        assert_eq!(observer.files, vec!["src/fun_a.rs".to_string()]);
+        assert_eq!(observer.builds, vec![false]);
+    }
+
+    #[derive(Default)]
+    struct Frontend {
+        output: String,
+        choices: Vec<ChoiceKind>,
+        pick: Vec<usize>,
+    }
+
+    impl WorkflowObserver for Frontend {
+        fn on_choice(&mut self, choice: &Choice) -> Selection {
+            self.choices.push(choice.kind);
+            Selection::Choose(self.pick.clone())
+        }
+        fn on_output(&mut self, _stream: OutputStream, text: &str) -> bool {
+            self.output.push_str(text);
+            true
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_borrowed_observer_receives_output_and_choices() {
+        let outer = Arc::new(Mutex::new(Frontend::default()));
+        let _outer_guard = observe(outer.clone());
+
+        let mut frontend = Frontend {
+            pick: vec![1],
+            ..Default::default()
+        };
+        let confirmed = observe_borrowed(&mut frontend, || {
+            println!("plan: {} file(s)", 2);
+            crate::interaction::prompt_confirm_run_plan().unwrap()
+        });
+        assert!(!confirmed, "index 1 is \"No\"");
+        // 并行运行的其他测试的输出也可能落到这里
+        assert!(frontend.output.contains("plan: 2 file(s)\n"));
+        assert!(frontend.choices.contains(&ChoiceKind::ConfirmRunPlan));
+
+        // 无效的下标回退到终端提示
+        frontend.pick = vec![0, 0];
+        let options = ["a".to_string(), "b".to_string()];
+        let picked = observe_borrowed(&mut frontend, || {
+            choose(ChoiceKind::FilesToProcess, "files", &options, true)
+        });
+        assert_eq!(picked, None);
+        frontend.pick = vec![1, 0];
+        let picked = observe_borrowed(&mut frontend, || {
+            (
+                choose(ChoiceKind::FilesToProcess, "files", &options, true),
+                choose(ChoiceKind::FileToEdit, "file", &options, false),
+            )
+        });
+        assert_eq!(picked, (Some(vec![1, 0]), None));
+
+        println!("after");
+        assert!(
+            outer.lock().unwrap().output.contains("after\n"),
+            "the previously registered observer is restored"
+        );
+    }
+}
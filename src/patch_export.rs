@@ -0,0 +1,154 @@
+//! `--export-patch`：把本次运行翻译完成的文件汇总为一个补丁文件，便于集中评审
+//!
+//! 每个文件完成处理（提交之后）时，追加一段从空文件到最终内容的统一 diff。
+//! 路径相对项目根目录（`.c2rust/<feature>/rust/...`），可在项目根目录用 `git apply` 应用。
+//! 补丁文件在每次运行开始时清空。
+
+use crate::util;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 当前运行的补丁文件及已写入的统计
+struct PatchExport {
+    path: PathBuf,
+    files: usize,
+    lines_added: usize,
+}
+
+static PATCH_EXPORT: Mutex<Option<PatchExport>> = Mutex::new(None);
+
+/// 设置 `--export-patch` 的目标文件并清空它；`None` 关闭导出
+pub fn set_export_patch(path: Option<&Path>) -> Result<()> {
+    let export = match path {
+        Some(path) => {
+            std::fs::write(path, "")
+                .with_context(|| format!("Failed to create patch file: {}", path.display()))?;
+            Some(PatchExport {
+                path: path.to_path_buf(),
+                files: 0,
+                lines_added: 0,
+            })
+        }
+        None => None,
+    };
+    *PATCH_EXPORT.lock().unwrap_or_else(|e| e.into_inner()) = export;
+    Ok(())
+}
+
+/// 追加 `file_name`（相对 rust 目录）的 diff；未启用导出时不做任何事，失败时只打印警告
+pub(crate) fn append_file(feature: &str, file_name: &str) {
+    let mut guard = PATCH_EXPORT.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(export) = guard.as_mut() else {
+        return;
+    };
+    if let Err(e) = append_file_to(export, feature, file_name) {
+        println!(
+            "│ {}",
+            format!("⚠ Failed to append {} to the patch: {:#}", file_name, e).yellow()
+        );
+    }
+}
+
+fn append_file_to(export: &mut PatchExport, feature: &str, file_name: &str) -> Result<()> {
+    let project_root = util::find_project_root()?;
+    let rs_file = project_root
+        .join(".c2rust")
+        .join(feature)
+        .join("rust")
+        .join(file_name);
+    let content = std::fs::read_to_string(&rs_file)
+        .with_context(|| format!("Failed to read {}", rs_file.display()))?;
+    let patch_path = format!(".c2rust/{}/rust/{}", feature, file_name.replace('\\', "/"));
+    let Some((diff, lines_added)) = diff_from_empty(&patch_path, &content) else {
+        return Ok(());
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&export.path)
+        .with_context(|| format!("Failed to open patch file: {}", export.path.display()))?;
+    file.write_all(diff.as_bytes())
+        .with_context(|| format!("Failed to write patch file: {}", export.path.display()))?;
+    export.files += 1;
+    export.lines_added += lines_added;
+    Ok(())
+}
+
+/// 从空文件到 `content` 的统一 diff 及新增行数；`content` 为空时返回 `None`
+fn diff_from_empty(path: &str, content: &str) -> Option<(String, usize)> {
+    if content.is_empty() {
+        return None;
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let mut diff = format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -0,0 +1,{} @@\n",
+        lines.len()
+    );
+    for line in &lines {
+        diff.push('+');
+        diff.push_str(line);
+        diff.push('\n');
+    }
+    if !content.ends_with('\n') {
+        diff.push_str("\\ No newline at end of file\n");
+    }
+    Some((diff, lines.len()))
+}
+
+/// 运行结束时打印补丁文件的位置与新增行数
+pub(crate) fn print_summary() {
+    let guard = PATCH_EXPORT.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(export) = guard.as_ref() else {
+        return;
+    };
+    println!(
+        "\n{}",
+        format!(
+            "Patch written to {}: {} file(s), {} line(s) added",
+            export.path.display(),
+            export.files,
+            export.lines_added
+        )
+        .bright_cyan()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_from_empty_is_valid_git_apply_input() {
+        let content = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        let (diff, added) = diff_from_empty(".c2rust/f/rust/src/fun_add.rs", content).unwrap();
+        assert_eq!(added, 3);
+        assert_eq!(
+            diff,
+            "diff --git a/.c2rust/f/rust/src/fun_add.rs b/.c2rust/f/rust/src/fun_add.rs\n\
+             --- a/.c2rust/f/rust/src/fun_add.rs\n\
+             +++ b/.c2rust/f/rust/src/fun_add.rs\n\
+             @@ -0,0 +1,3 @@\n\
+             +pub fn add(a: i32, b: i32) -> i32 {\n\
+             +    a + b\n\
+             +}\n"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join(".c2rust/f/rust/src");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("fun_add.rs"), "").unwrap();
+        std::fs::write(dir.path().join("out.patch"), &diff).unwrap();
+        crate::test_support::run_git(dir.path(), &["apply", "out.patch"]);
+        assert_eq!(
+            std::fs::read_to_string(target.join("fun_add.rs")).unwrap(),
+            content
+        );
+
+        let (diff, _) = diff_from_empty("a.rs", "x").unwrap();
+        assert!(diff.ends_with("+x\n\\ No newline at end of file\n"));
+        assert!(diff_from_empty("a.rs", "").is_none());
+    }
+}
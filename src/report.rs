@@ -0,0 +1,481 @@
+//! 运行结束时的翻译报告（`.c2rust/<feature>/report.json` 与 `report.txt`）
+//!
+//! 在 `translate_feature` 运行期间累计每个文件的处理结果（成功、修复次数、手动修复、
+//! 跳过、失败及耗时），结束时由 [`RunReportGuard`] 写出；提前出错或 panic 时同样会写出，
+//! 便于跨多次运行追踪经常让翻译工具出问题的文件。
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 单个文件的最终结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportOutcome {
+    /// 翻译后无需修复即通过
+    Translated,
+    /// 经过自动修复后通过
+    Fixed,
+    /// 用户在编辑器中手动修改后通过
+    ManuallyFixed,
+    /// 被跳过（用户选择或自动跳过）
+    Skipped,
+    /// 翻译失败或处理出错
+    Failed,
+}
+
+impl ReportOutcome {
+    /// 由文件的处理结果得出最终结果，规则见 [`record_file`]
+    fn classify(completed: Option<(usize, usize)>, skipped: bool, manual_edit: bool) -> Self {
+        match completed {
+            Some(_) if manual_edit => ReportOutcome::ManuallyFixed,
+            Some((_, fix_attempts)) if fix_attempts > 0 => ReportOutcome::Fixed,
+            Some(_) => ReportOutcome::Translated,
+            None if skipped => ReportOutcome::Skipped,
+            None => ReportOutcome::Failed,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ReportOutcome::Translated => "translated",
+            ReportOutcome::Fixed => "fixed",
+            ReportOutcome::ManuallyFixed => "manually_fixed",
+            ReportOutcome::Skipped => "skipped",
+            ReportOutcome::Failed => "failed",
+        }
+    }
+}
+
+/// 报告中的单个文件条目
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileReportEntry {
+    /// 相对 rust 目录的文件路径
+    pub file: String,
+    /// 文件类型（"var" 或 "fn"）
+    pub file_type: Option<String>,
+    pub outcome: ReportOutcome,
+    /// 翻译尝试次数（仅成功的文件）
+    pub translation_attempts: Option<usize>,
+    /// 修复尝试次数（仅成功的文件）
+    pub fix_attempts: Option<usize>,
+    /// 处理期间是否打开过编辑器手动修改
+    pub manual_edit: bool,
+    /// 处理该文件的耗时（毫秒）
+    pub duration_ms: u64,
+    /// 跳过的原因（如 C 文件过大），仅在已知时记录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 翻译后端报告的用量（调用后写出的 `<output>.meta.json`，字段均可省略）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    pub cost_usd: f64,
+}
+
+impl TokenUsage {
+    fn add(&mut self, other: TokenUsage) {
+        self.tokens_in += other.tokens_in;
+        self.tokens_out += other.tokens_out;
+        self.cost_usd += other.cost_usd;
+    }
+
+    /// 简短描述，如 `1200 tokens in, 300 tokens out, $0.0123`
+    pub fn describe(&self) -> String {
+        format!(
+            "{} tokens in, {} tokens out, ${:.4}",
+            self.tokens_in, self.tokens_out, self.cost_usd
+        )
+    }
+}
+
+/// 一次运行的完整报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunReport {
+    pub feature: String,
+    /// 开始时间（Unix 时间戳，秒）
+    pub started_at: u64,
+    /// 结束时间（Unix 时间戳，秒）
+    pub finished_at: u64,
+    /// `ok`、`error` 或 `aborted`（panic 等未正常返回的情况）
+    pub result: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub files: Vec<FileReportEntry>,
+    /// 翻译后端报告的累计用量；后端没有报告时为 `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// 正在处理的文件
+struct CurrentFile {
+    file: String,
+    started: Instant,
+    manual_edit: bool,
+    skip_reason: Option<String>,
+}
+
+struct ReportState {
+    report: RunReport,
+    current: Option<CurrentFile>,
+}
+
+/// 当前运行的报告累计状态；未开始运行（或 `--dry-run`）时为 `None`
+static REPORT: Mutex<Option<ReportState>> = Mutex::new(None);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn with_state(f: impl FnOnce(&mut ReportState)) {
+    let mut state = REPORT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(state) = state.as_mut() {
+        f(state);
+    }
+}
+
+/// 开始处理一个文件（计时起点）
+pub fn begin_file(file: &str) {
+    with_state(|state| {
+        state.current = Some(CurrentFile {
+            file: file.to_string(),
+            started: Instant::now(),
+            manual_edit: false,
+            skip_reason: None,
+        });
+    });
+}
+
+/// 记录当前文件被用户在编辑器中修改过
+pub fn note_manual_edit() {
+    with_state(|state| {
+        if let Some(current) = state.current.as_mut() {
+            current.manual_edit = true;
+        }
+    });
+}
+
+/// 当前文件是否被用户在编辑器中修改过（无论从哪个提示进入手动修复）
+pub fn current_file_manually_edited() -> bool {
+    let state = REPORT.lock().unwrap_or_else(|e| e.into_inner());
+    state
+        .as_ref()
+        .and_then(|state| state.current.as_ref())
+        .is_some_and(|current| current.manual_edit)
+}
+
+/// 记录当前文件被跳过的原因（文件最终记为跳过时写入报告）
+pub fn note_skip_reason(reason: &str) {
+    with_state(|state| {
+        if let Some(current) = state.current.as_mut() {
+            current.skip_reason = Some(reason.to_string());
+        }
+    });
+}
+
+/// 累计一次翻译或修复调用的用量
+pub fn add_usage(usage: TokenUsage) {
+    with_state(|state| {
+        state
+            .report
+            .usage
+            .get_or_insert_with(TokenUsage::default)
+            .add(usage);
+    });
+}
+
+/// 打印本次运行累计的翻译后端用量；后端没有报告用量时不打印
+pub(crate) fn print_usage_summary() {
+    let usage = REPORT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|state| state.report.usage);
+    if let Some(usage) = usage {
+        println!(
+            "\n{}",
+            format!("Translator usage: {} (estimated)", usage.describe()).bright_cyan()
+        );
+    }
+}
+
+/// 记录文件的最终结果
+///
+/// `completed` 为成功文件的 `(翻译尝试次数, 修复尝试次数)`，`None` 时按 `skipped` 记为跳过或失败。
+/// 修复次数大于 0 的成功结果记为 `fixed`，手动修改过的成功结果记为 `manually_fixed`。
+/// 返回记录的结果（未在记录报告时同样按这些规则得出）。
+pub fn record_file(file: &str, completed: Option<(usize, usize)>, skipped: bool) -> ReportOutcome {
+    let mut outcome = ReportOutcome::classify(completed, skipped, false);
+    with_state(|state| {
+        let current = state.current.take().filter(|c| c.file == file);
+        let manual_edit = current.as_ref().is_some_and(|c| c.manual_edit);
+        let duration = current
+            .as_ref()
+            .map(|c| c.started.elapsed())
+            .unwrap_or(Duration::ZERO);
+        outcome = ReportOutcome::classify(completed, skipped, manual_edit);
+        let reason = current
+            .and_then(|c| c.skip_reason)
+            .filter(|_| outcome == ReportOutcome::Skipped);
+        let file_type = Path::new(file)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(crate::file_scanner::extract_file_type)
+            .map(|(file_type, _)| file_type.to_string());
+        state.report.files.push(FileReportEntry {
+            file: file.to_string(),
+            file_type,
+            outcome,
+            translation_attempts: completed.map(|(attempts, _)| attempts),
+            fix_attempts: completed.map(|(_, fix_attempts)| fix_attempts),
+            manual_edit,
+            duration_ms: duration.as_millis() as u64,
+            reason,
+        });
+    });
+    outcome
+}
+
+impl RunReport {
+    fn new(feature: &str) -> Self {
+        Self {
+            feature: feature.to_string(),
+            started_at: unix_now(),
+            finished_at: 0,
+            result: "aborted".to_string(),
+            error: None,
+            files: Vec::new(),
+            usage: None,
+        }
+    }
+
+    fn count(&self, outcome: ReportOutcome) -> usize {
+        self.files.iter().filter(|f| f.outcome == outcome).count()
+    }
+
+    /// 生成简短的文本摘要（`report.txt`）
+    pub fn render_text(&self) -> String {
+        let mut text = format!("Translation report for feature: {}\n", self.feature);
+        text.push_str(&format!("Result: {}", self.result));
+        if let Some(error) = &self.error {
+            text.push_str(&format!(" ({})", error.lines().next().unwrap_or("")));
+        }
+        text.push('\n');
+        text.push_str(&format!(
+            "Duration: {}s\n",
+            self.finished_at.saturating_sub(self.started_at)
+        ));
+        text.push_str(&format!(
+            "Files: {} (translated {}, fixed {}, manually fixed {}, skipped {}, failed {})\n",
+            self.files.len(),
+            self.count(ReportOutcome::Translated),
+            self.count(ReportOutcome::Fixed),
+            self.count(ReportOutcome::ManuallyFixed),
+            self.count(ReportOutcome::Skipped),
+            self.count(ReportOutcome::Failed)
+        ));
+        if let Some(usage) = &self.usage {
+            text.push_str(&format!("Translator usage: {}\n", usage.describe()));
+        }
+        if !self.files.is_empty() {
+            text.push('\n');
+        }
+        for entry in &self.files {
+            let mut line = format!("  {:<14} {}", entry.outcome.as_str(), entry.file);
+            if let Some(fix_attempts) = entry.fix_attempts {
+                line.push_str(&format!(
+                    ", attempts {}, fixes {}",
+                    entry.translation_attempts.unwrap_or(0),
+                    fix_attempts
+                ));
+            }
+            if let Some(reason) = &entry.reason {
+                line.push_str(&format!(", {}", reason));
+            }
+            line.push_str(&format!(", {:.1}s\n", entry.duration_ms as f64 / 1000.0));
+            text.push_str(&line);
+        }
+        text
+    }
+
+    /// 写出 `report.json` 与 `report.txt` 到 `dir`
+    pub fn write_to_dir(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        let json_path = dir.join("report.json");
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize run report")?;
+        std::fs::write(&json_path, json)
+            .with_context(|| format!("Failed to write report: {}", json_path.display()))?;
+        let text_path = dir.join("report.txt");
+        std::fs::write(&text_path, self.render_text())
+            .with_context(|| format!("Failed to write report: {}", text_path.display()))
+    }
+}
+
+/// 运行报告的守卫：创建时开始累计，drop 时写出报告（包括提前返回和 panic）
+pub struct RunReportGuard {
+    feature_dir: Option<PathBuf>,
+}
+
+impl RunReportGuard {
+    /// 开始记录 `feature` 的运行报告；`enabled` 为 false（如 `--dry-run`）时不记录也不写出
+    pub fn start(feature: &str, enabled: bool) -> Self {
+        let feature_dir = if enabled {
+            crate::util::find_project_root()
+                .ok()
+                .map(|root| root.join(".c2rust").join(feature))
+        } else {
+            None
+        };
+        *REPORT.lock().unwrap_or_else(|e| e.into_inner()) =
+            feature_dir.as_ref().map(|_| ReportState {
+                report: RunReport::new(feature),
+                current: None,
+            });
+        Self { feature_dir }
+    }
+
+    /// 记录运行结果（未调用时报告结果为 `aborted`）
+    pub fn set_result<T>(&self, result: &Result<T>) {
+        with_state(|state| match result {
+            Ok(_) => state.report.result = "ok".to_string(),
+            Err(e) => {
+                state.report.result = "error".to_string();
+                state.report.error = Some(format!("{:#}", e));
+            }
+        });
+    }
+}
+
+impl Drop for RunReportGuard {
+    fn drop(&mut self) {
+        let state = REPORT.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let (Some(dir), Some(mut state)) = (self.feature_dir.as_ref(), state) else {
+            return;
+        };
+        state.report.finished_at = unix_now();
+        if let Err(e) = state.report.write_to_dir(dir) {
+            eprintln!("Warning: failed to write translation report: {:#}", e);
+        }
+    }
+}
+
+/// 测试用：开始累计报告但不写出文件（用 [`take_report`] 结束）
+#[cfg(test)]
+pub(crate) fn start_unwritten(feature: &str) {
+    *REPORT.lock().unwrap_or_else(|e| e.into_inner()) = Some(ReportState {
+        report: RunReport::new(feature),
+        current: None,
+    });
+}
+
+/// 测试用：结束累计并返回当前报告
+#[cfg(test)]
+pub(crate) fn take_report() -> Option<RunReport> {
+    REPORT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .map(|state| state.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file: &str, outcome: ReportOutcome, fixes: Option<usize>) -> FileReportEntry {
+        FileReportEntry {
+            file: file.to_string(),
+            file_type: Some("fn".to_string()),
+            outcome,
+            translation_attempts: fixes.map(|_| 1),
+            fix_attempts: fixes,
+            manual_edit: outcome == ReportOutcome::ManuallyFixed,
+            duration_ms: 1500,
+            reason: None,
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_manual_edit_is_tracked_per_file() {
+        *REPORT.lock().unwrap() = Some(ReportState {
+            report: RunReport::new("default"),
+            current: None,
+        });
+        begin_file("src/fun_a.rs");
+        assert!(!current_file_manually_edited());
+        note_manual_edit();
+        assert!(current_file_manually_edited());
+        begin_file("src/fun_b.rs");
+        assert!(!current_file_manually_edited());
+        *REPORT.lock().unwrap() = None;
+        assert!(!current_file_manually_edited());
+    }
+
+    #[test]
+    fn test_render_text_summarizes_outcomes() {
+        let mut report = RunReport::new("default");
+        report.started_at = 100;
+        report.finished_at = 160;
+        report.result = "error".to_string();
+        report.error = Some("build failed\nmore detail".to_string());
+        report.files = vec![
+            entry("src/fun_a.rs", ReportOutcome::Fixed, Some(3)),
+            FileReportEntry {
+                reason: Some("C file too large (9 bytes > 4 bytes)".to_string()),
+                ..entry("src/fun_b.rs", ReportOutcome::Skipped, None)
+            },
+        ];
+
+        let text = report.render_text();
+        assert!(text.contains("Result: error (build failed)\n"));
+        assert!(text.contains("Duration: 60s\n"));
+        assert!(!text.contains("Translator usage"));
+        assert!(text
+            .contains("Files: 2 (translated 0, fixed 1, manually fixed 0, skipped 1, failed 0)"));
+        assert!(text.contains("fixed          src/fun_a.rs, attempts 1, fixes 3, 1.5s"));
+        assert!(text
+            .contains("skipped        src/fun_b.rs, C file too large (9 bytes > 4 bytes), 1.5s"));
+    }
+
+    #[test]
+    fn test_write_to_dir_writes_json_and_text() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut report = RunReport::new("default");
+        report.result = "ok".to_string();
+        report.files = vec![entry("src/fun_a.rs", ReportOutcome::ManuallyFixed, Some(0))];
+
+        report.usage = Some(TokenUsage {
+            tokens_in: 1200,
+            tokens_out: 300,
+            cost_usd: 0.0123,
+        });
+        report.write_to_dir(temp_dir.path()).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join("report.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["result"], "ok");
+        assert!(json.get("error").is_none());
+        assert_eq!(json["files"][0]["outcome"], "manually_fixed");
+        assert_eq!(json["files"][0]["file_type"], "fn");
+        assert_eq!(json["files"][0]["duration_ms"], 1500);
+        assert_eq!(json["usage"]["tokens_in"], 1200);
+        assert!(report
+            .render_text()
+            .contains("Translator usage: 1200 tokens in, 300 tokens out, $0.0123\n"));
+        assert!(temp_dir.path().join("report.txt").is_file());
+    }
+}
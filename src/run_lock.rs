@@ -0,0 +1,136 @@
+//! 同一 feature 的运行锁（`.c2rust/<feature>/.lock`），防止两个 `translate` 同时处理同一 feature
+//!
+//! 锁是操作系统的咨询锁（`flock` / `LockFileEx`），进程退出（包括崩溃）时自动释放；
+//! 锁文件中记录持有者的 PID 与开始时间，无法获取时显示在错误信息中。
+//! 持有者挂起等情况下可用 `--force-unlock` 删除旧锁文件并重新加锁。
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 锁文件名（位于 `.c2rust/<feature>/` 下，不会被提交）
+pub const LOCK_FILE_NAME: &str = ".lock";
+
+/// 持有中的运行锁；drop 时释放（包括出错返回和 panic）
+///
+/// 锁文件本身保留：未加锁的锁文件不影响下一次运行，删除它反而可能删掉别的进程刚创建的锁。
+#[derive(Debug)]
+pub struct RunLock {
+    file: File,
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+fn open_lock_file(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("Failed to open lock file: {}", path.display()))
+}
+
+/// 锁文件中记录的持有者信息，如 `pid 1234, started 1700000000 (42s ago)`
+fn describe_holder(content: &str) -> String {
+    let field = |key: &str| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(str::trim)
+    };
+    let pid = field("pid").unwrap_or("unknown");
+    match field("started").and_then(|s| s.parse::<u64>().ok()) {
+        Some(started) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(started);
+            format!(
+                "pid {}, started {} ({}s ago)",
+                pid,
+                started,
+                now.saturating_sub(started)
+            )
+        }
+        None => format!("pid {}", pid),
+    }
+}
+
+/// 获取 `feature_dir` 的运行锁
+///
+/// 已被另一个进程持有时报错并给出持有者信息；`force_unlock` 时删除旧锁文件后重新加锁。
+pub fn acquire(feature_dir: &Path, force_unlock: bool) -> Result<RunLock> {
+    let path = feature_dir.join(LOCK_FILE_NAME);
+    let mut file = open_lock_file(&path)?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(std::fs::TryLockError::WouldBlock) => {
+            let mut content = String::new();
+            let _ = file.read_to_string(&mut content);
+            let holder = describe_holder(&content);
+            if !force_unlock {
+                anyhow::bail!(
+                    "Another translation of this feature is in progress ({}); lock file: {}. \
+                     If that run is stuck, stop it or pass --force-unlock",
+                    holder,
+                    path.display()
+                );
+            }
+            println!(
+                "{}",
+                format!("⚠ Removing the lock held by another run ({})", holder).yellow()
+            );
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove lock file: {}", path.display()))?;
+            file = open_lock_file(&path)?;
+            file.try_lock()
+                .with_context(|| format!("Failed to lock {}", path.display()))?;
+        }
+        Err(std::fs::TryLockError::Error(e)) => {
+            return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+        }
+    }
+
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    file.set_len(0)
+        .and_then(|()| file.rewind())
+        .and_then(|()| writeln!(file, "pid={}\nstarted={}", std::process::id(), started))
+        .with_context(|| format!("Failed to write lock file: {}", path.display()))?;
+    Ok(RunLock { file })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_acquire_fails_until_released_or_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = acquire(dir.path(), false).unwrap();
+        let content = std::fs::read_to_string(dir.path().join(LOCK_FILE_NAME)).unwrap();
+        assert!(content.starts_with(&format!("pid={}\n", std::process::id())));
+
+        let err = acquire(dir.path(), false).unwrap_err().to_string();
+        assert!(err.contains("Another translation of this feature is in progress"));
+        assert!(err.contains(&format!("pid {}, started ", std::process::id())));
+
+        let forced = acquire(dir.path(), true).unwrap();
+        drop(lock);
+        assert!(acquire(dir.path(), false).is_err());
+        drop(forced);
+        drop(acquire(dir.path(), false).unwrap());
+
+        assert_eq!(describe_holder("garbage"), "pid unknown");
+    }
+}
@@ -1,19 +1,116 @@
-//! suggestions.txt 的建议文件管理
+//! 修复建议文件管理
+//!
+//! 处理某个文件期间（[`FileScope`]）建议保存在 `.c2rust/<feature>/suggestions/<mod>_<name>.txt`，
+//! 按目标文件区分，再次处理同一文件时可继续使用；其他时候使用项目根目录的 `suggestions.txt`。
 
 use crate::util;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
-/// 获取 suggestions.txt 建议文件的路径
+/// 当前正在处理的文件对应的建议文件；不在处理文件时为 `None`
+static CURRENT_SUGGESTION_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// 是否在同一文件的多次重试之间保留建议（`--remember-suggestions`）
+static REMEMBER_SUGGESTIONS: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否保留建议；保留时 [`clear_suggestions`] 不再删除建议文件
+pub fn set_remember_suggestions(remember: bool) {
+    REMEMBER_SUGGESTIONS.store(remember, Ordering::Relaxed);
+}
+
+/// 目标文件的建议文件名：去掉 `src/` 前缀和 `.rs` 扩展名，路径分隔符替换为 `_`
+fn suggestion_file_name(file_name: &str) -> String {
+    let relative = file_name.strip_prefix("src/").unwrap_or(file_name);
+    let stem = relative.strip_suffix(".rs").unwrap_or(relative);
+    format!("{}.txt", stem.replace(['/', '\\'], "_"))
+}
+
+/// 目标文件的建议文件路径：`.c2rust/<feature>/suggestions/<mod>_<name>.txt`
+pub fn file_suggestion_path(project_root: &Path, feature: &str, file_name: &str) -> PathBuf {
+    project_root
+        .join(".c2rust")
+        .join(feature)
+        .join("suggestions")
+        .join(suggestion_file_name(file_name))
+}
+
+/// 处理单个文件期间的建议作用域：创建时切换到该文件的建议文件，drop 时恢复为全局文件
+pub struct FileScope(());
+
+impl FileScope {
+    /// 进入 `file_name`（相对 rust 目录的路径）的建议作用域
+    ///
+    /// 旧版本遗留的全局 `suggestions.txt` 会并入该文件的建议后删除。
+    pub fn enter(feature: &str, file_name: &str) -> Result<Self> {
+        let project_root = util::find_project_root()?;
+        let path = file_suggestion_path(&project_root, feature, file_name);
+        migrate_global_suggestions(&project_root.join("suggestions.txt"), &path)?;
+        *CURRENT_SUGGESTION_FILE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(path);
+        Ok(FileScope(()))
+    }
+}
+
+impl Drop for FileScope {
+    fn drop(&mut self) {
+        *CURRENT_SUGGESTION_FILE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// 将全局建议文件的内容追加到 `target` 后删除全局文件（不存在时不做任何事）
+fn migrate_global_suggestions(global: &Path, target: &Path) -> Result<()> {
+    if !global.is_file() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(global)
+        .with_context(|| format!("Failed to read suggestion file: {}", global.display()))?;
+    if !content.trim().is_empty() {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(target)
+            .with_context(|| format!("Failed to open suggestion file: {}", target.display()))?;
+        write!(file, "{}", content)?;
+        println!(
+            "│ {}",
+            format!(
+                "Moved existing suggestions from {} to {}",
+                global.display(),
+                target.display()
+            )
+            .bright_yellow()
+        );
+    }
+    fs::remove_file(global)
+        .with_context(|| format!("Failed to remove suggestion file: {}", global.display()))
+}
+
+/// 获取当前的建议文件路径：处理文件期间为该文件的建议文件，否则为项目根目录的 suggestions.txt
 pub fn get_suggestion_file_path() -> Result<PathBuf> {
+    if let Some(path) = CURRENT_SUGGESTION_FILE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+    {
+        return Ok(path);
+    }
     let project_root = util::find_project_root()?;
     Ok(project_root.join("suggestions.txt"))
 }
 
-/// 读取 suggestions.txt 的当前内容（如果存在）
+/// 读取当前建议文件的内容（如果存在）
 #[cfg(test)]
 pub fn read_suggestions() -> Result<Option<String>> {
     let suggestion_file = get_suggestion_file_path()?;
@@ -36,7 +133,7 @@ pub fn read_suggestions() -> Result<Option<String>> {
     }
 }
 
-/// 将建议追加到 suggestions.txt 文件
+/// 将建议追加到当前建议文件
 pub fn append_suggestion(suggestion: &str) -> Result<()> {
     let suggestion_file = get_suggestion_file_path()?;
 
@@ -68,11 +165,21 @@ pub fn append_suggestion(suggestion: &str) -> Result<()> {
     Ok(())
 }
 
-/// 清除 suggestions.txt 文件中的所有建议
-/// 这在开始全新重试时很有用，以避免建议积累
+/// 清除当前建议文件中的所有建议
+/// 这在开始全新重试时很有用，以避免建议积累；`--remember-suggestions` 时保留
 pub fn clear_suggestions() -> Result<()> {
     let suggestion_file = get_suggestion_file_path()?;
 
+    if REMEMBER_SUGGESTIONS.load(Ordering::Relaxed) {
+        if suggestion_file.exists() {
+            println!(
+                "│ {}",
+                "Keeping previous suggestions (--remember-suggestions)".bright_yellow()
+            );
+        }
+        return Ok(());
+    }
+
     if suggestion_file.exists() {
         fs::remove_file(&suggestion_file).with_context(|| {
             format!(
@@ -254,4 +361,48 @@ mod tests {
         // 在 temp_dir 被删除前恢复原始工作目录
         env::set_current_dir(&old_dir).unwrap();
     }
+
+    #[test]
+    #[serial]
+    fn test_file_scope_keys_suggestions_by_file_and_migrates_global() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_dir = env::current_dir().unwrap();
+        fs::create_dir(temp_dir.path().join(".c2rust")).unwrap();
+        env::set_current_dir(temp_dir.path()).unwrap();
+        let _restore = scopeguard::guard((), |_| {
+            set_remember_suggestions(false);
+            env::set_current_dir(&old_dir).unwrap();
+        });
+
+        // 旧版本留下的全局建议并入第一个处理的文件
+        append_suggestion("legacy hint").unwrap();
+        let root = util::find_project_root().unwrap();
+        let fun_a = file_suggestion_path(&root, "default", "src/net/fun_a.rs");
+        assert!(fun_a.ends_with(".c2rust/default/suggestions/net_fun_a.txt"));
+
+        {
+            let _scope = FileScope::enter("default", "src/net/fun_a.rs").unwrap();
+            assert_eq!(get_suggestion_file_path().unwrap(), fun_a);
+            append_suggestion("hint for a").unwrap();
+        }
+        assert!(!root.join("suggestions.txt").exists());
+        assert_eq!(
+            fs::read_to_string(&fun_a).unwrap(),
+            "legacy hint\nhint for a\n"
+        );
+
+        // 处理另一个文件不会影响 fun_a 的建议
+        {
+            let _scope = FileScope::enter("default", "src/fun_b.rs").unwrap();
+            assert_eq!(read_suggestions().unwrap(), None);
+            clear_suggestions().unwrap();
+        }
+        assert!(fun_a.exists());
+
+        // --remember-suggestions 时重试不会清除建议
+        set_remember_suggestions(true);
+        let _scope = FileScope::enter("default", "src/net/fun_a.rs").unwrap();
+        clear_suggestions().unwrap();
+        assert!(read_suggestions().unwrap().unwrap().contains("hint for a"));
+    }
 }
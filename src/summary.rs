@@ -0,0 +1,317 @@
+//! 每个文件处理结束时打印的摘要表格，以及运行结束时的汇总表格
+//!
+//! 表格列为文件、类型、翻译尝试次数、构建耗时、测试耗时与最终结果，按固定列宽对齐，
+//! 便于在长时间运行的日志中快速浏览。构建与测试耗时由 `builder` 在每条命令结束后累计到当前文件。
+//! 颜色关闭时（`NO_COLOR`、`--no-color`、非 TTY）使用 ASCII 边框且不着色。
+
+use crate::report::ReportOutcome;
+use colored::Colorize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 表格中的一行（一个文件）
+#[derive(Debug, Clone, PartialEq)]
+struct SummaryRow {
+    /// 相对 rust 目录的文件路径
+    file: String,
+    /// 文件类型（"var" 或 "fn"）
+    file_type: Option<String>,
+    /// 翻译尝试次数（仅成功的文件）
+    attempts: Option<usize>,
+    /// cargo 与混合构建命令（含 clean）的累计耗时
+    build_time: Duration,
+    /// 混合测试命令的累计耗时
+    test_time: Duration,
+    outcome: ReportOutcome,
+}
+
+#[derive(Default)]
+struct SummaryState {
+    build_time: Duration,
+    test_time: Duration,
+    rows: Vec<SummaryRow>,
+}
+
+static SUMMARY: Mutex<Option<SummaryState>> = Mutex::new(None);
+
+fn with_state(f: impl FnOnce(&mut SummaryState)) {
+    let mut state = SUMMARY.lock().unwrap_or_else(|e| e.into_inner());
+    f(state.get_or_insert_with(SummaryState::default));
+}
+
+/// 清空上一次运行累计的内容（`translate_feature` 开始时调用）
+pub(crate) fn reset() {
+    *SUMMARY.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// 开始处理一个文件：清零其构建与测试耗时
+pub(crate) fn begin_file() {
+    with_state(|state| {
+        state.build_time = Duration::ZERO;
+        state.test_time = Duration::ZERO;
+    });
+}
+
+/// 累计当前文件的构建耗时
+pub(crate) fn add_build_time(duration: Duration) {
+    with_state(|state| state.build_time += duration);
+}
+
+/// 累计当前文件的测试耗时
+pub(crate) fn add_test_time(duration: Duration) {
+    with_state(|state| state.test_time += duration);
+}
+
+/// 记录并打印文件的摘要表格
+pub(crate) fn finish_file(file: &str, attempts: Option<usize>, outcome: ReportOutcome) {
+    let mut row = None;
+    with_state(|state| {
+        let entry = SummaryRow {
+            file: file.to_string(),
+            file_type: Path::new(file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(crate::file_scanner::extract_file_type)
+                .map(|(file_type, _)| file_type.to_string()),
+            attempts,
+            build_time: state.build_time,
+            test_time: state.test_time,
+            outcome,
+        };
+        state.rows.push(entry.clone());
+        row = Some(entry);
+    });
+    if let Some(row) = row {
+        println!("{}", render_table(&[row], false, use_styling()));
+    }
+}
+
+/// 运行结束时打印本次运行所有文件的汇总表格；没有处理任何文件时不打印
+pub(crate) fn print_run_summary() {
+    let rows = SUMMARY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|state| state.rows.clone())
+        .unwrap_or_default();
+    if rows.is_empty() {
+        return;
+    }
+    println!("\n{}", "Run summary:".bright_cyan().bold());
+    println!("{}", render_table(&rows, true, use_styling()));
+}
+
+fn use_styling() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// 边框字符：左、中、右交点
+struct Border {
+    horizontal: char,
+    vertical: char,
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+const BOX_BORDER: Border = Border {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+const ASCII_BORDER: Border = Border {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+', '+', '+'],
+    middle: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+};
+
+const HEADERS: [&str; 6] = ["File", "Type", "Attempts", "Build", "Test", "Status"];
+
+/// 右对齐的列（数字与耗时）
+const RIGHT_ALIGNED: [bool; 6] = [false, false, true, true, true, false];
+
+/// 文件列的最大宽度，更长的路径保留末尾部分
+const MAX_FILE_WIDTH: usize = 48;
+
+fn format_duration(duration: Duration) -> String {
+    if duration.is_zero() {
+        return "-".to_string();
+    }
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        let secs = duration.as_secs();
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+fn truncate_file(file: &str) -> String {
+    let len = file.chars().count();
+    if len <= MAX_FILE_WIDTH {
+        return file.to_string();
+    }
+    let tail: String = file.chars().skip(len - (MAX_FILE_WIDTH - 1)).collect();
+    format!("…{}", tail)
+}
+
+fn style_outcome(text: &str, outcome: ReportOutcome) -> String {
+    match outcome {
+        ReportOutcome::Translated => text.bright_green().to_string(),
+        ReportOutcome::Fixed => text.green().to_string(),
+        ReportOutcome::ManuallyFixed => text.cyan().to_string(),
+        ReportOutcome::Skipped => text.yellow().to_string(),
+        ReportOutcome::Failed => text.bright_red().to_string(),
+    }
+}
+
+/// 渲染表格；`with_total` 时末尾追加合计行，`styled` 为 false 时使用 ASCII 边框且不着色
+fn render_table(rows: &[SummaryRow], with_total: bool, styled: bool) -> String {
+    let border = if styled { &BOX_BORDER } else { &ASCII_BORDER };
+
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            [
+                truncate_file(&row.file),
+                row.file_type.clone().unwrap_or_else(|| "-".to_string()),
+                row.attempts
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                format_duration(row.build_time),
+                format_duration(row.test_time),
+                row.outcome.as_str().to_string(),
+            ]
+        })
+        .collect();
+    let total = with_total.then(|| {
+        let succeeded = rows
+            .iter()
+            .filter(|r| !matches!(r.outcome, ReportOutcome::Skipped | ReportOutcome::Failed))
+            .count();
+        let skipped = rows
+            .iter()
+            .filter(|r| r.outcome == ReportOutcome::Skipped)
+            .count();
+        let failed = rows
+            .iter()
+            .filter(|r| r.outcome == ReportOutcome::Failed)
+            .count();
+        [
+            format!("Total ({} files)", rows.len()),
+            String::new(),
+            rows.iter()
+                .filter_map(|r| r.attempts)
+                .sum::<usize>()
+                .to_string(),
+            format_duration(rows.iter().map(|r| r.build_time).sum()),
+            format_duration(rows.iter().map(|r| r.test_time).sum()),
+            format!("{} ok, {} skipped, {} failed", succeeded, skipped, failed),
+        ]
+    });
+
+    let mut widths = HEADERS.map(|h| h.chars().count());
+    for row in cells.iter().chain(total.iter()) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let rule = |[left, middle, right]: [char; 3]| {
+        let segments: Vec<String> = widths
+            .iter()
+            .map(|w| border.horizontal.to_string().repeat(w + 2))
+            .collect();
+        format!("{}{}{}", left, segments.join(&middle.to_string()), right)
+    };
+    let line = |row: &[String; 6], outcome: Option<ReportOutcome>, bold: bool| {
+        let mut out = border.vertical.to_string();
+        for (i, cell) in row.iter().enumerate() {
+            let pad = widths[i] - cell.chars().count();
+            let padded = if RIGHT_ALIGNED[i] {
+                format!("{}{}", " ".repeat(pad), cell)
+            } else {
+                format!("{}{}", cell, " ".repeat(pad))
+            };
+            let padded = match (styled, outcome) {
+                (true, Some(outcome)) if i == HEADERS.len() - 1 => style_outcome(&padded, outcome),
+                (true, _) if bold => padded.bold().to_string(),
+                _ => padded,
+            };
+            out.push_str(&format!(" {} {}", padded, border.vertical));
+        }
+        out
+    };
+
+    let header = HEADERS.map(String::from);
+    let mut lines = vec![
+        rule(border.top),
+        line(&header, None, true),
+        rule(border.middle),
+    ];
+    for (cells, row) in cells.iter().zip(rows) {
+        lines.push(line(cells, Some(row.outcome), false));
+    }
+    if let Some(total) = &total {
+        lines.push(rule(border.middle));
+        lines.push(line(total, None, true));
+    }
+    lines.push(rule(border.bottom));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(file: &str, attempts: Option<usize>, outcome: ReportOutcome) -> SummaryRow {
+        SummaryRow {
+            file: file.to_string(),
+            file_type: Some("fn".to_string()),
+            attempts,
+            build_time: Duration::from_millis(12_340),
+            test_time: Duration::from_secs(75),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_render_table_plain_is_aligned() {
+        let rows = vec![
+            row("src/fun_a.rs", Some(2), ReportOutcome::Fixed),
+            row("src/net/fun_long_name.rs", None, ReportOutcome::Skipped),
+        ];
+        let table = render_table(&rows, true, false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 8);
+        let width = lines[0].chars().count();
+        assert!(
+            lines.iter().all(|l| l.chars().count() == width),
+            "{}",
+            table
+        );
+        assert!(lines[0].starts_with("+-") && lines[0].ends_with("-+"));
+        assert_eq!(
+            lines[3],
+            "| src/fun_a.rs             | fn   |        2 | 12.3s | 1m15s | fixed                     |"
+        );
+        assert!(lines[4].contains("|        - |") && lines[4].contains("| skipped "));
+        assert!(lines[6].starts_with("| Total (2 files) "));
+        assert!(lines[6].ends_with("| 24.7s | 2m30s | 1 ok, 1 skipped, 0 failed |"));
+    }
+
+    #[test]
+    fn test_truncate_file_keeps_tail() {
+        let long = format!("src/{}/fun_x.rs", "d".repeat(60));
+        let truncated = truncate_file(&long);
+        assert_eq!(truncated.chars().count(), MAX_FILE_WIDTH);
+        assert!(truncated.starts_with('…') && truncated.ends_with("/fun_x.rs"));
+        assert_eq!(format_duration(Duration::ZERO), "-");
+    }
+}
@@ -0,0 +1,47 @@
+//! 单元测试共用的辅助函数
+//!
+//! 依赖 git 的测试都通过这里创建仓库：git 不可用时直接失败，而不是让测试静默通过。
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 在 `dir` 中执行 git，返回 stdout；git 无法执行或命令失败时 panic
+pub(crate) fn run_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("git is required to run this test");
+    assert!(
+        output.status.success(),
+        "git {} failed in {}: {}",
+        args.join(" "),
+        dir.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// 配置好提交身份的临时 git 仓库
+#[derive(Debug, Clone)]
+pub(crate) struct TestRepo {
+    dir: PathBuf,
+}
+
+impl TestRepo {
+    /// 在 `dir`（须已存在）中执行 `git init` 并设置提交身份
+    pub(crate) fn init(dir: &Path) -> Self {
+        let repo = Self {
+            dir: dir.to_path_buf(),
+        };
+        repo.git(&["init", "-q"]);
+        repo.git(&["config", "user.name", "test"]);
+        repo.git(&["config", "user.email", "test@example.com"]);
+        repo
+    }
+
+    /// 见 [`run_git`]
+    pub(crate) fn git(&self, args: &[&str]) -> String {
+        run_git(&self.dir, args)
+    }
+}
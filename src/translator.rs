@@ -4,9 +4,246 @@ use colored::Colorize;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use toml::value::Table;
 
-/// Typed error returned when the translate script exits with a non-zero code.
+/// 是否将发送给翻译工具的错误信息保留到 `.c2rust/<feature>/logs/`（`--keep-error-files`）
+static KEEP_ERROR_FILES: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否保留错误文件
+pub fn set_keep_error_files(keep: bool) {
+    KEEP_ERROR_FILES.store(keep, Ordering::Relaxed);
+}
+
+/// 是否捕获翻译脚本的 stdout/stderr（`--capture-translator-output`，`--jobs` > 1 时自动开启）
+static CAPTURE_TRANSLATOR_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否捕获翻译脚本输出；捕获的输出写入 `.c2rust/<feature>/logs/<file>.translate.log`
+/// （修复调用写入 `<file>.fix.log`）
+pub fn set_capture_translator_output(capture: bool) {
+    CAPTURE_TRANSLATOR_OUTPUT.store(capture, Ordering::Relaxed);
+}
+
+/// 通过 `--translate-timeout` 指定的翻译超时时间（秒），优先于配置的 `translate.timeout`
+static TRANSLATE_TIMEOUT_OVERRIDE: Mutex<Option<u64>> = Mutex::new(None);
+
+/// 设置 `--translate-timeout`（`None` 表示使用配置的 `translate.timeout`，未配置时不限时）
+pub fn set_translate_timeout(secs: Option<u64>) {
+    *TRANSLATE_TIMEOUT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = secs;
+}
+
+/// 单次翻译（或修复）调用的超时时间：`--translate-timeout` > `translate.timeout` > 不限时
+fn translate_timeout(feature: &str) -> Option<Duration> {
+    let override_secs = *TRANSLATE_TIMEOUT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let secs = override_secs.or_else(|| {
+        let value = crate::builder::get_config_value("translate.timeout", feature).ok()?;
+        match value.trim().parse::<u64>() {
+            Ok(secs) if secs > 0 => Some(secs),
+            _ => {
+                eprintln!(
+                    "Warning: translate.timeout must be a positive number of seconds, got: {}; not limiting translation time",
+                    value
+                );
+                None
+            }
+        }
+    });
+    secs.map(Duration::from_secs)
+}
+
+/// 是否跳过翻译/修复后的 rustfmt 格式化（`--no-fmt`）
+static NO_FORMAT: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否跳过 rustfmt 格式化
+pub fn set_no_format(disabled: bool) {
+    NO_FORMAT.store(disabled, Ordering::Relaxed);
+}
+
+/// 是否格式化翻译工具写出的文件：`--no-fmt` 时关闭，否则由可选配置 `format.enabled` 决定（默认开启）
+fn format_enabled(feature: &str) -> bool {
+    if NO_FORMAT.load(Ordering::Relaxed) {
+        return false;
+    }
+    crate::builder::get_config_value("format.enabled", feature)
+        .map(|value| parse_format_enabled(&value))
+        .unwrap_or(true)
+}
+
+/// `format.enabled` 设为 `false`、`0`、`no` 或 `off`（大小写不敏感）时关闭格式化
+fn parse_format_enabled(value: &str) -> bool {
+    let value = value.trim();
+    !["false", "0", "no", "off"]
+        .iter()
+        .any(|disabled| value.eq_ignore_ascii_case(disabled))
+}
+
+/// 一次 rustfmt 格式化的结果
+#[derive(Debug, PartialEq, Eq)]
+enum FormatOutcome {
+    Formatted,
+    /// 未格式化，附带原因；文件内容保持不变
+    Skipped(String),
+}
+
+/// 用 `rustfmt --edition 2021` 就地格式化 `rs_file`
+///
+/// 空文件（包括只有空白的文件，rustfmt 会把它清空）不处理，以免影响按文件是否为空判断翻译进度；
+/// rustfmt 未安装或代码暂时无法解析时跳过。
+fn rustfmt_file(rs_file: &Path) -> FormatOutcome {
+    match std::fs::read_to_string(rs_file) {
+        Ok(content) if content.trim().is_empty() => {
+            return FormatOutcome::Skipped("file is empty".to_string())
+        }
+        Ok(_) => {}
+        Err(e) => return FormatOutcome::Skipped(format!("cannot read file: {}", e)),
+    }
+    let output = match crate::interrupt::output(
+        Command::new("rustfmt")
+            .args(["--edition", "2021"])
+            .arg(rs_file),
+    ) {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return FormatOutcome::Skipped("rustfmt is not installed".to_string())
+        }
+        Err(e) => return FormatOutcome::Skipped(format!("failed to run rustfmt: {}", e)),
+    };
+    if output.status.success() {
+        FormatOutcome::Formatted
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let first_line = stderr.lines().next().unwrap_or("").trim();
+        FormatOutcome::Skipped(format!(
+            "rustfmt exited with code {} (the code may not parse yet): {}",
+            output.status.code().unwrap_or(-1),
+            first_line
+        ))
+    }
+}
+
+/// 翻译工具写出文件后、构建之前的格式化步骤；失败只给出警告
+fn format_translated_file(feature: &str, rs_file: &Path) {
+    if !format_enabled(feature) {
+        return;
+    }
+    if let FormatOutcome::Skipped(reason) = rustfmt_file(rs_file) {
+        println!(
+            "│ {}",
+            format!("⚠ Skipping rustfmt for {}: {}", rs_file.display(), reason).yellow()
+        );
+    }
+}
+
+/// 记录每次翻译工具调用输入/输出的目录（`--record-io`），`None` 表示不记录
+static RECORD_IO_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// 记录包序号，保证同一毫秒内（如 `--jobs` 并行翻译）的记录包不重名
+static RECORD_IO_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// 设置 `--record-io` 目录
+pub fn set_record_io_dir(dir: Option<&Path>) {
+    *RECORD_IO_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = dir.map(Path::to_path_buf);
+}
+
+fn record_io_dir() -> Option<PathBuf> {
+    RECORD_IO_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// 一次翻译工具调用的输入/输出，用于 `--record-io` 审计记录
+struct TranslatorCall<'a> {
+    /// 调用类型：`translate` 或 `fix`
+    kind: &'a str,
+    /// 完整命令行（解释器 + 参数）
+    argv: Vec<&'a str>,
+    c_file: &'a Path,
+    rs_file: &'a Path,
+    /// 修复前的 Rust 代码（仅 fix）
+    input_rust: Option<String>,
+    /// 发送给修复工具的错误信息（仅 fix）
+    error: Option<&'a str>,
+    suggestion_file: Option<&'a Path>,
+    exit_code: Option<i32>,
+}
+
+/// 将一次调用写入 `<dir>/<毫秒时间戳>-<序号>-<类型>-<文件名>/` 记录包
+///
+/// 记录包包含 `call.json`（argv、退出码）、`input.c`、`output.rs`，
+/// 修复调用另有 `input.rs`、`error.txt` 和 `suggestion.md`（如存在）。
+fn write_io_record(dir: &Path, call: &TranslatorCall) -> Result<PathBuf> {
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let seq = RECORD_IO_SEQ.fetch_add(1, Ordering::SeqCst);
+    let stem = call
+        .rs_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown");
+    let bundle = dir.join(format!(
+        "{}-{:04}-{}-{}",
+        timestamp_ms, seq, call.kind, stem
+    ));
+    std::fs::create_dir_all(&bundle)
+        .with_context(|| format!("Failed to create record directory: {}", bundle.display()))?;
+
+    let metadata = serde_json::json!({
+        "kind": call.kind,
+        "argv": call.argv,
+        "exit_code": call.exit_code,
+        "timestamp_ms": timestamp_ms as u64,
+        "c_file": call.c_file.display().to_string(),
+        "rs_file": call.rs_file.display().to_string(),
+    });
+    let write = |name: &str, contents: &[u8]| {
+        let path = bundle.join(name);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write record file: {}", path.display()))
+    };
+    write(
+        "call.json",
+        serde_json::to_string_pretty(&metadata)
+            .context("Failed to serialize translator call")?
+            .as_bytes(),
+    )?;
+    if let Ok(c_source) = std::fs::read(call.c_file) {
+        write("input.c", &c_source)?;
+    }
+    if let Some(input_rust) = &call.input_rust {
+        write("input.rs", input_rust.as_bytes())?;
+    }
+    if let Some(error) = call.error {
+        write("error.txt", error.as_bytes())?;
+    }
+    if let Some(suggestion) = call.suggestion_file.and_then(|p| std::fs::read(p).ok()) {
+        write("suggestion.md", &suggestion)?;
+    }
+    if let Ok(output) = std::fs::read(call.rs_file) {
+        write("output.rs", &output)?;
+    }
+    Ok(bundle)
+}
+
+/// `--record-io` 开启时记录一次调用
+fn record_translator_call(call: &TranslatorCall) -> Result<()> {
+    if let Some(dir) = record_io_dir() {
+        write_io_record(&dir, call).context("Failed to record translator call (--record-io)")?;
+    }
+    Ok(())
+}
+
+/// Typed error returned when the translate script exits with a non-zero code or is
+/// killed after exceeding the translation timeout.
 ///
 /// This is the only "expected" translation failure (the script ran but could not
 /// produce valid Rust output). All other errors from `translate_c_to_rust` (missing
@@ -15,15 +252,24 @@ use toml::value::Table;
 #[derive(Debug)]
 pub struct TranslationScriptFailedError {
     pub exit_code: i32,
+    /// Set when the script was killed after this many seconds
+    pub timed_out_after: Option<u64>,
 }
 
 impl std::fmt::Display for TranslationScriptFailedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Translation script exited with code {} (check output above for details)",
-            self.exit_code
-        )
+        match self.timed_out_after {
+            Some(secs) => write!(
+                f,
+                "Translation timed out after {} seconds; the translator process was killed",
+                secs
+            ),
+            None => write!(
+                f,
+                "Translation script exited with code {} (check output above for details)",
+                self.exit_code
+            ),
+        }
     }
 }
 
@@ -90,10 +336,9 @@ fn find_python_interpreter() -> Result<String> {
     )
 }
 
-/// 通过搜索 .c2rust 目录获取 config.toml 路径
+/// 获取 config.toml 路径（`--config` 指定的文件，否则为 `.c2rust/config.toml`）
 fn get_project_config_path() -> Result<PathBuf> {
-    let project_root = util::find_project_root()?;
-    Ok(project_root.join(".c2rust/config.toml"))
+    util::config_path()
 }
 
 fn get_user_env_config_path() -> Result<PathBuf> {
@@ -228,6 +473,62 @@ fn get_decl_file_path(rs_file: &Path) -> Option<PathBuf> {
     Some(rs_file.parent()?.join(format!("decl_{}.rs", name)))
 }
 
+/// 自动作为上下文传给翻译脚本的 feature 类型头文件（`.c2rust/<feature>/types.h`，存在时）
+const FEATURE_TYPES_HEADER: &str = "types.h";
+
+/// 解析翻译时作为 `--context` 传给翻译脚本的上下文文件
+///
+/// `configured` 为配置 `translate.context_files` 的值：空白分隔（可用引号包含空格），
+/// 相对路径基于项目根目录，列出的文件必须存在。feature 目录下的 `types.h` 存在时自动追加。
+fn resolve_context_files(
+    project_root: &Path,
+    feature: &str,
+    configured: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if let Some(configured) = configured {
+        let entries = shell_words::split(configured)
+            .with_context(|| format!("Invalid translate.context_files value: {}", configured))?;
+        let mut missing = Vec::new();
+        for entry in entries {
+            let path = project_root.join(&entry);
+            if path.is_file() {
+                files.push(path);
+            } else {
+                missing.push(entry);
+            }
+        }
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "translate.context_files lists file(s) that do not exist: {}",
+                missing.join(", ")
+            );
+        }
+    }
+
+    let types_header = project_root
+        .join(".c2rust")
+        .join(feature)
+        .join(FEATURE_TYPES_HEADER);
+    if types_header.is_file() && !files.contains(&types_header) {
+        files.push(types_header);
+    }
+    Ok(files)
+}
+
+/// 上下文文件对应的翻译脚本参数：每个文件一个 `--context <path>`
+fn context_args(files: &[PathBuf]) -> Result<Vec<String>> {
+    let mut args = Vec::with_capacity(files.len() * 2);
+    for file in files {
+        let file_str = file
+            .to_str()
+            .with_context(|| format!("Non-UTF8 path: {}", file.display()))?;
+        args.push("--context".to_string());
+        args.push(file_str.to_string());
+    }
+    Ok(args)
+}
+
 /// 从对应的声明文件中读取 rusttype
 ///
 /// 对于 `var_<name>.rs` 或 `fun_<name>.rs` 文件，
@@ -261,6 +562,38 @@ fn read_rusttype_from_decl_file(rs_file: &Path) -> Option<String> {
     }
 }
 
+/// 构建翻译命令的参数列表
+///
+/// 每个路径都是独立的参数（不拼接成命令字符串），因此路径中的空格等字符无需转义。
+/// 参数遵循格式：script_path --config --type --c_code --output [--rusttype] [--context ...]
+fn build_translate_args<'a>(
+    script_path: &'a str,
+    config_path: &'a str,
+    file_type: &'a str,
+    c_code_file: &'a str,
+    output_file: &'a str,
+    rusttype: Option<&'a str>,
+    context_args: &'a [String],
+) -> Vec<&'a str> {
+    let mut args = vec![
+        script_path,
+        "--config",
+        config_path,
+        "--type",
+        file_type,
+        "--c_code",
+        c_code_file,
+        "--output",
+        output_file,
+    ];
+    if let Some(rt) = rusttype {
+        args.push("--rusttype");
+        args.push(rt);
+    }
+    args.extend(context_args.iter().map(String::as_str));
+    args
+}
+
 /// 构建修复命令的参数列表
 ///
 /// 返回一个参数向量，传递给 translate_and_fix.py 用于修复错误。
@@ -308,6 +641,153 @@ fn build_fix_args<'a>(
     args
 }
 
+/// 翻译命令模板（`translator.command`）必须包含的占位符
+const TRANSLATE_REQUIRED_PLACEHOLDERS: &[&str] = &["type", "c_code", "output"];
+/// 翻译命令模板可用的全部占位符
+const TRANSLATE_PLACEHOLDERS: &[&str] =
+    &["config", "type", "c_code", "output", "rusttype", "context"];
+/// 修复命令模板（`translator.fix_command`）必须包含的占位符
+const FIX_REQUIRED_PLACEHOLDERS: &[&str] = &["c_code", "output", "error"];
+/// 修复命令模板可用的全部占位符
+const FIX_PLACEHOLDERS: &[&str] = &[
+    "config",
+    "c_code",
+    "rust_code",
+    "output",
+    "error",
+    "suggestion",
+];
+
+/// 读取配置的命令模板（`translator.command` / `translator.fix_command`）；未配置时返回 `None`，
+/// 使用默认的 `python translate_and_fix.py ...` 调用
+fn configured_command_template(feature: &str, key: &str) -> Option<String> {
+    crate::builder::get_config_value(key, feature)
+        .ok()
+        .filter(|template| !template.trim().is_empty())
+}
+
+/// 检查配置的命令模板（`translator.command` / `translator.fix_command`），返回发现的问题
+///
+/// 供 [`crate::builder::validate_config`] 在翻译循环开始前调用，未配置的模板不检查。
+pub(crate) fn command_template_problems(lookup: impl Fn(&str) -> Result<String>) -> Vec<String> {
+    [
+        (
+            "translator.command",
+            TRANSLATE_REQUIRED_PLACEHOLDERS,
+            TRANSLATE_PLACEHOLDERS,
+        ),
+        (
+            "translator.fix_command",
+            FIX_REQUIRED_PLACEHOLDERS,
+            FIX_PLACEHOLDERS,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(key, required, known)| {
+        let template = lookup(key).ok().filter(|t| !t.trim().is_empty())?;
+        check_command_template(key, &template, required, known)
+            .err()
+            .map(|e| format!("{:#}", e))
+    })
+    .collect()
+}
+
+/// 按 shell 规则切分命令模板，检查程序名与占位符；返回切分后的参数
+///
+/// 模板缺少 `required` 中的占位符或含有未知占位符时报错。
+fn check_command_template(
+    key: &str,
+    template: &str,
+    required: &[&str],
+    known: &[&str],
+) -> Result<Vec<String>> {
+    let words = shell_words::split(template)
+        .with_context(|| format!("Failed to parse {}: {}", key, template))?;
+    if words.first().is_none_or(|program| program.is_empty()) {
+        anyhow::bail!("{} must start with a program: {}", key, template);
+    }
+
+    let placeholder = regex::Regex::new(r"\{([a-z_]+)\}").expect("valid regex");
+    let used: Vec<&str> = words
+        .iter()
+        .flat_map(|word| placeholder.captures_iter(word))
+        .map(|captures| captures.get(1).map_or("", |m| m.as_str()))
+        .collect();
+    if let Some(unknown) = used.iter().find(|name| !known.contains(name)) {
+        anyhow::bail!(
+            "{} uses unknown placeholder {{{}}} (available: {})",
+            key,
+            unknown,
+            known
+                .iter()
+                .map(|name| format!("{{{}}}", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let missing: Vec<String> = required
+        .iter()
+        .filter(|name| !used.contains(name))
+        .map(|name| format!("{{{}}}", name))
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "{} is missing required placeholder(s) {}: {}",
+            key,
+            missing.join(", "),
+            template
+        );
+    }
+    Ok(words)
+}
+
+/// 按 shell 规则切分命令模板并替换其中的 `{name}` 占位符
+///
+/// `values` 中值为 `None` 的占位符（如没有建议文件时的 `{suggestion}`）所在的整个参数会被省略，
+/// 因此可选参数应写成单个参数，如 `--suggestion={suggestion}`。`lists` 中的占位符
+/// （如 `{context}`）所在的参数按列表中的每个值各重复一次，列表为空时同样省略。
+/// 模板缺少 `required` 中的占位符或含有未知占位符时报错。
+fn expand_command_template(
+    key: &str,
+    template: &str,
+    required: &[&str],
+    known: &[&str],
+    values: &[(&str, Option<&str>)],
+    lists: &[(&str, &[String])],
+) -> Result<Vec<String>> {
+    let words = check_command_template(key, template, required, known)?;
+
+    let mut argv = Vec::with_capacity(words.len());
+    'words: for word in &words {
+        let mut expanded = vec![word.clone()];
+        for (name, value) in values {
+            let token = format!("{{{}}}", name);
+            if !word.contains(&token) {
+                continue;
+            }
+            match value {
+                Some(value) => {
+                    for arg in &mut expanded {
+                        *arg = arg.replace(&token, value);
+                    }
+                }
+                None => continue 'words,
+            }
+        }
+        for (name, items) in lists {
+            let token = format!("{{{}}}", name);
+            if word.contains(&token) {
+                expanded = expanded
+                    .iter()
+                    .flat_map(|arg| items.iter().map(|item| arg.replace(&token, item)))
+                    .collect();
+            }
+        }
+        argv.extend(expanded);
+    }
+    Ok(argv)
+}
+
 /// 使用格式化输出显示文件中的代码
 pub(crate) fn display_code(file_path: &Path, header: &str, max_lines: usize, show_full: bool) {
     match std::fs::read_to_string(file_path) {
@@ -320,8 +800,13 @@ pub(crate) fn display_code(file_path: &Path, header: &str, max_lines: usize, sho
                 std::cmp::min(total_lines, max_lines)
             };
 
+            #[cfg(feature = "highlight")]
+            let mut highlighter = crate::highlight::Highlighter::for_path(file_path);
+
             println!("│ {}", header.bright_cyan());
             for (i, line) in lines.iter().take(display_lines).enumerate() {
+                #[cfg(feature = "highlight")]
+                let line = highlighter.highlight(line);
                 println!("│ {} {}", format!("{:3}", i + 1).dimmed(), line);
             }
             if total_lines > display_lines {
@@ -341,6 +826,104 @@ pub(crate) fn display_code(file_path: &Path, header: &str, max_lines: usize, sho
     }
 }
 
+/// 生成翻译命令的展示字符串（`--dry-run` 时打印，不实际执行）
+///
+/// 运行时配置文件只在真正翻译时生成，这里用占位符代替。
+pub(crate) fn describe_translate_command(
+    feature: &str,
+    file_type: &str,
+    c_file: &Path,
+    rs_file: &Path,
+) -> String {
+    if let Some(template) = configured_command_template(feature, "translator.command") {
+        let c_file = c_file.display().to_string();
+        let rs_file = rs_file.display().to_string();
+        let context_files = util::find_project_root()
+            .ok()
+            .and_then(|project_root| {
+                let configured =
+                    crate::builder::get_config_value("translate.context_files", feature).ok();
+                resolve_context_files(&project_root, feature, configured.as_deref()).ok()
+            })
+            .unwrap_or_default()
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>();
+        return match expand_command_template(
+            "translator.command",
+            &template,
+            TRANSLATE_REQUIRED_PLACEHOLDERS,
+            TRANSLATE_PLACEHOLDERS,
+            &[
+                ("config", Some("<runtime-config>")),
+                ("type", Some(file_type)),
+                ("c_code", Some(&c_file)),
+                ("output", Some(&rs_file)),
+                ("rusttype", Some("<rusttype>")),
+            ],
+            &[("context", &context_files)],
+        ) {
+            Ok(argv) => shell_words::join(&argv),
+            Err(e) => format!("<invalid translator.command: {}>", e),
+        };
+    }
+    let python = find_python_interpreter().unwrap_or_else(|_| "python3".to_string());
+    let script = get_translate_script_full_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "translate_and_fix.py".to_string());
+    format!(
+        "{} {} --config <runtime-config> --type {} --c_code {} --output {}",
+        shell_words::quote(&python),
+        shell_words::quote(&script),
+        file_type,
+        shell_words::quote(&c_file.display().to_string()),
+        shell_words::quote(&rs_file.display().to_string())
+    )
+}
+
+/// 翻译后端的展示字符串（解释器与翻译脚本路径，或配置的命令模板），用于 `--confirm` 的运行计划
+pub(crate) fn describe_translator_backend(feature: &str) -> String {
+    if let Some(template) = configured_command_template(feature, "translator.command") {
+        return format!("{} (translator.command)", template);
+    }
+    let python = find_python_interpreter().unwrap_or_else(|_| "python3".to_string());
+    let script = get_translate_script_full_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "translate_and_fix.py".to_string());
+    format!(
+        "{} {}",
+        shell_words::quote(&python),
+        shell_words::quote(&script)
+    )
+}
+
+/// 打印默认的 `python translate_and_fix.py ...` 翻译命令
+///
+/// `trailing` 是已着色的 `--rusttype` 与 `--context` 部分，紧接在 `--output` 之后输出。
+fn print_default_translate_command(
+    python: &str,
+    script_str: &str,
+    config_str: &str,
+    file_type: &str,
+    c_file_str: &str,
+    rs_file_str: &str,
+    trailing: &str,
+) {
+    println!("│ {}", "Executing translation command:".bright_blue());
+    println!(
+        "│ {} {} {} --config {} --type {} --c_code {} --output {}{}",
+        "→".bright_blue(),
+        shell_words::quote(python).bright_blue(),
+        shell_words::quote(script_str).dimmed(),
+        shell_words::quote(config_str).dimmed(),
+        file_type.bright_yellow(),
+        shell_words::quote(c_file_str).bright_yellow(),
+        shell_words::quote(rs_file_str).bright_yellow(),
+        trailing
+    );
+    println!("│");
+}
+
 /// 使用翻译工具将 C 文件翻译为 Rust
 pub fn translate_c_to_rust(
     feature: &str,
@@ -371,11 +954,6 @@ pub fn translate_c_to_rust(
         show_full_output,
     );
 
-    let script_path = get_translate_script_full_path()?;
-    let script_str = script_path
-        .to_str()
-        .with_context(|| format!("Non-UTF8 path: {}", script_path.display()))?;
-
     let config_str = runtime_config
         .path()
         .to_str()
@@ -390,67 +968,128 @@ pub fn translate_c_to_rust(
     // 对于 var 和 fn 类型，从对应的声明文件中读取 rusttype
     let rusttype = read_rusttype_from_decl_file(rs_file);
 
-    let python = find_python_interpreter()?;
-
-    println!("│ {}", "Executing translation command:".bright_blue());
-    if let Some(ref rt) = rusttype {
-        // Display rusttype with escaped newlines to preserve box formatting
-        let rt_display = rt.replace('\n', "\\n");
-        println!(
-            "│ {} {} {} --config {} --type {} --c_code {} --output {} --rusttype {}",
-            "→".bright_blue(),
-            python.as_str().bright_blue(),
-            script_str.dimmed(),
-            config_str.dimmed(),
-            file_type.bright_yellow(),
-            c_file_str.bright_yellow(),
-            rs_file_str.bright_yellow(),
-            rt_display.bright_cyan()
-        );
-    } else {
-        println!(
-            "│ {} {} {} --config {} --type {} --c_code {} --output {}",
-            "→".bright_blue(),
-            python.as_str().bright_blue(),
-            script_str.dimmed(),
-            config_str.dimmed(),
-            file_type.bright_yellow(),
-            c_file_str.bright_yellow(),
-            rs_file_str.bright_yellow()
-        );
-    }
-    println!("│");
+    let configured_context =
+        crate::builder::get_config_value("translate.context_files", feature).ok();
+    let context_files =
+        resolve_context_files(&project_root, feature, configured_context.as_deref())?;
+    let context_args = context_args(&context_files)?;
+    let context_display = context_args
+        .chunks(2)
+        .map(|pair| format!(" --context {}", shell_words::quote(&pair[1])))
+        .collect::<String>();
+    let context_paths: Vec<String> = context_args.chunks(2).map(|pair| pair[1].clone()).collect();
+
+    let argv: Vec<String> =
+        if let Some(template) = configured_command_template(feature, "translator.command") {
+            let argv = expand_command_template(
+                "translator.command",
+                &template,
+                TRANSLATE_REQUIRED_PLACEHOLDERS,
+                TRANSLATE_PLACEHOLDERS,
+                &[
+                    ("config", Some(config_str)),
+                    ("type", Some(file_type)),
+                    ("c_code", Some(c_file_str)),
+                    ("output", Some(rs_file_str)),
+                    ("rusttype", rusttype.as_deref()),
+                ],
+                &[("context", &context_paths)],
+            )?;
+            println!("│ {}", "Executing translation command:".bright_blue());
+            println!(
+                "│ {} {}",
+                "→".bright_blue(),
+                shell_words::join(&argv)
+                    .replace('\n', "\\n")
+                    .bright_yellow()
+            );
+            println!("│");
+            argv
+        } else {
+            let script_path = get_translate_script_full_path()?;
+            let script_str = script_path
+                .to_str()
+                .with_context(|| format!("Non-UTF8 path: {}", script_path.display()))?;
+            let python = find_python_interpreter()?;
+            // Display rusttype with escaped newlines to preserve box formatting
+            let rusttype_display = rusttype
+                .as_deref()
+                .map(|rt| {
+                    let rt_display = rt.replace('\n', "\\n");
+                    format!(
+                        " --rusttype {}",
+                        shell_words::quote(&rt_display).bright_cyan()
+                    )
+                })
+                .unwrap_or_default();
+            print_default_translate_command(
+                &python,
+                script_str,
+                config_str,
+                file_type,
+                c_file_str,
+                rs_file_str,
+                &format!("{}{}", rusttype_display, context_display.dimmed()),
+            );
+            std::iter::once(python.as_str())
+                .chain(build_translate_args(
+                    script_str,
+                    config_str,
+                    file_type,
+                    c_file_str,
+                    rs_file_str,
+                    rusttype.as_deref(),
+                    &context_args,
+                ))
+                .map(str::to_string)
+                .collect()
+        };
 
-    let mut args = vec![
-        script_str,
-        "--config",
-        config_str,
-        "--type",
-        file_type,
-        "--c_code",
-        c_file_str,
-        "--output",
-        rs_file_str,
-    ];
-    if let Some(ref rt) = rusttype {
-        args.push("--rusttype");
-        args.push(rt.as_str());
+    let capture = CAPTURE_TRANSLATOR_OUTPUT.load(Ordering::Relaxed);
+    let timeout = translate_timeout(feature);
+    let run = run_translator_process(Command::new(&argv[0]).args(&argv[1..]), timeout, capture)
+        .with_context(|| format!("Failed to execute translation command: {}", argv[0]))?;
+    let exit_code = run.status.and_then(|status| status.code());
+    collect_usage_sidecar(rs_file);
+
+    let argv: Vec<&str> = argv.iter().map(String::as_str).collect();
+    if capture {
+        let log_path = translator_log_path(&project_root, feature, rs_file, "translate")?;
+        write_translate_log(&log_path, &argv, &run, timeout)?;
+        report_captured_output(&run, &log_path, show_full_output);
     }
 
-    let status = Command::new(&python)
-        .args(&args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .context("Failed to execute translate_and_fix.py")?;
-
-    if !status.success() {
-        return Err(TranslationScriptFailedError {
-            exit_code: status.code().unwrap_or(-1),
+    record_translator_call(&TranslatorCall {
+        kind: "translate",
+        argv,
+        c_file,
+        rs_file,
+        input_rust: None,
+        error: None,
+        suggestion_file: None,
+        exit_code,
+    })?;
+
+    match run.status {
+        None => {
+            return Err(TranslationScriptFailedError {
+                exit_code: -1,
+                timed_out_after: timeout.map(|t| t.as_secs()),
+            }
+            .into());
+        }
+        Some(status) if !status.success() => {
+            return Err(TranslationScriptFailedError {
+                exit_code: exit_code.unwrap_or(-1),
+                timed_out_after: None,
+            }
+            .into());
         }
-        .into());
+        Some(_) => {}
     }
 
+    format_translated_file(feature, rs_file);
+
     // 读取并显示翻译后的 Rust 代码
     display_code(
         rs_file,
@@ -462,6 +1101,200 @@ pub fn translate_c_to_rust(
     Ok(())
 }
 
+/// 翻译后端可选写出的用量文件：`<output>.meta.json`
+fn usage_sidecar_path(output: &Path) -> PathBuf {
+    let mut name = output.file_name().unwrap_or_default().to_os_string();
+    name.push(".meta.json");
+    output.with_file_name(name)
+}
+
+/// 读取翻译后端写出的用量文件并计入运行报告，随后删除它（以免被提交）
+///
+/// 文件不存在时不做任何事；内容无法解析时只打印警告。
+fn collect_usage_sidecar(output: &Path) {
+    let path = usage_sidecar_path(output);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let _ = std::fs::remove_file(&path);
+    match serde_json::from_str::<crate::report::TokenUsage>(&content) {
+        Ok(usage) => {
+            println!("│ {}", format!("Usage: {}", usage.describe()).dimmed());
+            crate::report::add_usage(usage);
+        }
+        Err(e) => println!(
+            "│ {}",
+            format!("⚠ Ignoring unreadable usage file {}: {}", path.display(), e).yellow()
+        ),
+    }
+}
+
+/// 一次翻译脚本运行的结果；`status` 为 `None` 表示超时被终止
+struct TranslatorRun {
+    status: Option<std::process::ExitStatus>,
+    /// 捕获的 stdout/stderr（未捕获时为空）
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// 运行翻译脚本：`capture` 时读取 stdout/stderr 到缓冲区，否则直接继承终端输出
+///
+/// 设置了 `timeout` 或接管了 Ctrl-C 时子进程放在独立的进程组中：超时后连同其子进程一起终止，
+/// 第一次 Ctrl-C 不会打断正在进行的翻译。
+fn run_translator_process(
+    command: &mut Command,
+    timeout: Option<Duration>,
+    capture: bool,
+) -> Result<TranslatorRun> {
+    if capture {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    } else {
+        command.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+    }
+    let own_group = cfg!(unix) && (timeout.is_some() || crate::interrupt::handler_installed());
+    #[cfg(unix)]
+    if own_group {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    let _tracked = own_group.then(|| crate::interrupt::track_child(child.id()));
+    let stdout_reader = read_pipe_in_background(child.stdout.take());
+    let stderr_reader = read_pipe_in_background(child.stderr.take());
+
+    let status = match timeout {
+        None => Some(child.wait()?),
+        Some(timeout) => {
+            let start = Instant::now();
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break Some(status);
+                }
+                if start.elapsed() >= timeout {
+                    crate::builder::kill_process_group(&mut child);
+                    let _ = child.wait();
+                    break None;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+    };
+
+    Ok(TranslatorRun {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+/// 在后台线程读完管道，避免管道写满导致子进程阻塞
+fn read_pipe_in_background<R: std::io::Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+fn rs_file_stem(rs_file: &Path) -> Result<&str> {
+    rs_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .with_context(|| format!("Invalid Rust file name: {}", rs_file.display()))
+}
+
+/// `logs/` 下按文件命名的日志文件名前缀：相对 `rust/src` 的路径去掉扩展名，目录之间用 `__` 连接
+///
+/// 例如 `src/a/fun_x.rs` -> `a__fun_x`，避免不同目录下的同名文件互相覆盖。
+fn log_file_stem(project_root: &Path, feature: &str, rs_file: &Path) -> Result<String> {
+    let file_stem = rs_file_stem(rs_file)?;
+    let rust_dir = project_root.join(".c2rust").join(feature).join("rust");
+    let relative_dir = rs_file
+        .strip_prefix(rust_dir.join("src"))
+        .or_else(|_| rs_file.strip_prefix(&rust_dir))
+        .ok()
+        .and_then(Path::parent);
+    let mut parts: Vec<String> = relative_dir
+        .into_iter()
+        .flat_map(|dir| dir.components())
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.push(file_stem.to_string());
+    Ok(parts.join("__"))
+}
+
+/// 捕获的翻译脚本输出的日志路径：`logs/<文件>.<kind>.log`（`kind` 为 `translate` 或 `fix`）
+fn translator_log_path(
+    project_root: &Path,
+    feature: &str,
+    rs_file: &Path,
+    kind: &str,
+) -> Result<PathBuf> {
+    Ok(util::feature_logs_dir(project_root, feature).join(format!(
+        "{}.{}.log",
+        log_file_stem(project_root, feature, rs_file)?,
+        kind
+    )))
+}
+
+/// 将捕获的翻译输出写入日志文件（每次翻译覆盖）
+fn write_translate_log(
+    log_path: &Path,
+    argv: &[&str],
+    run: &TranslatorRun,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let result = match (run.status, timeout) {
+        (Some(status), _) => match status.code() {
+            Some(code) => format!("exit code {}", code),
+            None => "terminated by signal".to_string(),
+        },
+        (None, Some(timeout)) => format!("timed out after {} seconds", timeout.as_secs()),
+        (None, None) => "timed out".to_string(),
+    };
+    let content = format!(
+        "$ {}\nresult: {}\n\n--- stdout ---\n{}\n--- stderr ---\n{}",
+        shell_words::join(argv),
+        result,
+        String::from_utf8_lossy(&run.stdout),
+        String::from_utf8_lossy(&run.stderr)
+    );
+    std::fs::write(log_path, content)
+        .with_context(|| format!("Failed to write translation log: {}", log_path.display()))
+}
+
+/// 捕获模式下展示翻译输出：`verbose` 时完整回显，否则只在失败时显示 stderr 末尾几行
+fn report_captured_output(run: &TranslatorRun, log_path: &Path, verbose: bool) {
+    let failed = !run.status.is_some_and(|status| status.success());
+    if verbose {
+        let stdout = String::from_utf8_lossy(&run.stdout);
+        let stderr = String::from_utf8_lossy(&run.stderr);
+        for line in stdout.lines().chain(stderr.lines()) {
+            println!("│ {}", line.dimmed());
+        }
+    } else if failed {
+        let stderr = String::from_utf8_lossy(&run.stderr);
+        let lines: Vec<&str> = stderr.lines().collect();
+        let start = lines.len().saturating_sub(util::ERROR_PREVIEW_LINES);
+        for line in &lines[start..] {
+            println!("│ {}", line.dimmed());
+        }
+    }
+    println!(
+        "│ {}",
+        format!("Translator output saved to {}", log_path.display()).dimmed()
+    );
+}
+
 /// 显示错误消息预览
 fn display_error_preview(error_msg: &str, show_full: bool) {
     let error_lines: Vec<&str> = error_msg.lines().collect();
@@ -499,6 +1332,86 @@ fn create_error_temp_file(error_msg: &str) -> Result<tempfile::NamedTempFile> {
     Ok(temp_file)
 }
 
+/// 传给翻译工具的错误文件：默认是用完即删的临时文件，`--keep-error-files` 时为固定路径
+enum ErrorFile {
+    Temp(tempfile::NamedTempFile),
+    Kept(PathBuf),
+}
+
+impl ErrorFile {
+    fn path(&self) -> &Path {
+        match self {
+            ErrorFile::Temp(temp_file) => temp_file.path(),
+            ErrorFile::Kept(path) => path,
+        }
+    }
+}
+
+/// 固定的错误文件路径：`.c2rust/<feature>/logs/<file>.error.txt`（每次尝试覆盖），
+/// `<file>` 见 [`log_file_stem`]
+fn kept_error_file_path(project_root: &Path, feature: &str, rs_file: &Path) -> Result<PathBuf> {
+    let file_stem = log_file_stem(project_root, feature, rs_file)?;
+    Ok(util::feature_logs_dir(project_root, feature).join(format!("{}.error.txt", file_stem)))
+}
+
+/// 写入错误文件，`keep` 为 true 时写到固定路径以便事后查看
+fn write_error_file(
+    project_root: &Path,
+    feature: &str,
+    rs_file: &Path,
+    error_msg: &str,
+    keep: bool,
+) -> Result<ErrorFile> {
+    if !keep {
+        return create_error_temp_file(error_msg).map(ErrorFile::Temp);
+    }
+
+    let path = kept_error_file_path(project_root, feature, rs_file)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create log directory: {}", parent.display()))?;
+    }
+    std::fs::write(&path, error_msg)
+        .with_context(|| format!("Failed to write error file: {}", path.display()))?;
+    Ok(ErrorFile::Kept(path))
+}
+
+/// 打印默认的 `python translate_and_fix.py --type syntax_fix ...` 修复命令
+fn print_default_fix_command(
+    python: &str,
+    script_str: &str,
+    config_str: &str,
+    c_file_str: &str,
+    rs_file_str: &str,
+    error_file_str: &str,
+    suggestion_str: Option<&str>,
+) {
+    println!("│ {}", "Executing error fix command:".yellow());
+    if let Some(suggestion) = suggestion_str {
+        println!("│ {} {} {} --config {} --type syntax_fix --c_code {} --rust_code {} --output {} --error {} --suggestion {}",
+            "→".yellow(),
+            shell_words::quote(python).yellow(),
+            shell_words::quote(script_str).dimmed(),
+            shell_words::quote(config_str).dimmed(),
+            shell_words::quote(c_file_str).bright_yellow(),
+            shell_words::quote(rs_file_str).bright_yellow(),
+            shell_words::quote(rs_file_str).bright_yellow(),
+            shell_words::quote(error_file_str).dimmed(),
+            shell_words::quote(suggestion).bright_cyan());
+    } else {
+        println!("│ {} {} {} --config {} --type syntax_fix --c_code {} --rust_code {} --output {} --error {}",
+            "→".yellow(),
+            shell_words::quote(python).yellow(),
+            shell_words::quote(script_str).dimmed(),
+            shell_words::quote(config_str).dimmed(),
+            shell_words::quote(c_file_str).bright_yellow(),
+            shell_words::quote(rs_file_str).bright_yellow(),
+            shell_words::quote(rs_file_str).bright_yellow(),
+            shell_words::quote(error_file_str).dimmed());
+    }
+    println!("│");
+}
+
 /// 使用翻译工具修复翻译错误
 pub fn fix_translation_error(
     feature: &str,
@@ -524,8 +1437,13 @@ pub fn fix_translation_error(
 
     display_error_preview(error_msg, show_full_error);
 
-    let temp_file = create_error_temp_file(error_msg)?;
-    let script_path = get_translate_script_full_path()?;
+    let error_file = write_error_file(
+        &project_root,
+        feature,
+        rs_file,
+        error_msg,
+        KEEP_ERROR_FILES.load(Ordering::Relaxed),
+    )?;
 
     // 从 Rust 文件路径派生 C 源文件路径
     // 示例：var_example.rs -> var_example.c
@@ -549,17 +1467,14 @@ pub fn fix_translation_error(
     let suggestion_path = crate::suggestion::get_suggestion_file_path()?;
     let suggestion_exists = suggestion_path.exists();
 
-    let script_str = script_path
-        .to_str()
-        .with_context(|| format!("Non-UTF8 path: {}", script_path.display()))?;
     let config_str = runtime_config
         .path()
         .to_str()
         .with_context(|| format!("Non-UTF8 path: {}", runtime_config.path().display()))?;
-    let error_file_str = temp_file
+    let error_file_str = error_file
         .path()
         .to_str()
-        .with_context(|| format!("Non-UTF8 path: {}", temp_file.path().display()))?;
+        .with_context(|| format!("Non-UTF8 path: {}", error_file.path().display()))?;
     let rs_file_str = rs_file
         .to_str()
         .with_context(|| format!("Non-UTF8 path: {}", rs_file.display()))?;
@@ -577,54 +1492,100 @@ pub fn fix_translation_error(
         None
     };
 
-    let python = find_python_interpreter()?;
+    let argv: Vec<String> =
+        if let Some(template) = configured_command_template(feature, "translator.fix_command") {
+            let argv = expand_command_template(
+                "translator.fix_command",
+                &template,
+                FIX_REQUIRED_PLACEHOLDERS,
+                FIX_PLACEHOLDERS,
+                &[
+                    ("config", Some(config_str)),
+                    ("c_code", Some(c_file_str)),
+                    ("rust_code", Some(rs_file_str)),
+                    ("output", Some(rs_file_str)),
+                    ("error", Some(error_file_str)),
+                    ("suggestion", suggestion_str),
+                ],
+                &[],
+            )?;
+            println!("│ {}", "Executing error fix command:".yellow());
+            println!(
+                "│ {} {}",
+                "→".yellow(),
+                shell_words::join(&argv).bright_yellow()
+            );
+            println!("│");
+            argv
+        } else {
+            let script_path = get_translate_script_full_path()?;
+            let script_str = script_path
+                .to_str()
+                .with_context(|| format!("Non-UTF8 path: {}", script_path.display()))?;
+            let python = find_python_interpreter()?;
+            print_default_fix_command(
+                &python,
+                script_str,
+                config_str,
+                c_file_str,
+                rs_file_str,
+                error_file_str,
+                suggestion_str,
+            );
+            std::iter::once(python.as_str())
+                .chain(build_fix_args(
+                    script_str,
+                    config_str,
+                    c_file_str,
+                    rs_file_str,
+                    rs_file_str,
+                    error_file_str,
+                    suggestion_str,
+                ))
+                .map(str::to_string)
+                .collect()
+        };
 
-    println!("│ {}", "Executing error fix command:".yellow());
-    if suggestion_exists {
-        println!("│ {} {} {} --config {} --type syntax_fix --c_code {} --rust_code {} --output {} --error {} --suggestion {}",
-            "→".yellow(),
-            python.as_str().yellow(),
-            script_str.dimmed(),
-            config_str.dimmed(),
-            c_file_str.bright_yellow(),
-            rs_file_str.bright_yellow(),
-            rs_file_str.bright_yellow(),
-            error_file_str.dimmed(),
-            suggestion_str.unwrap().bright_cyan());
-    } else {
-        println!("│ {} {} {} --config {} --type syntax_fix --c_code {} --rust_code {} --output {} --error {}",
-            "→".yellow(),
-            python.as_str().yellow(),
-            script_str.dimmed(),
-            config_str.dimmed(),
-            c_file_str.bright_yellow(),
-            rs_file_str.bright_yellow(),
-            rs_file_str.bright_yellow(),
-            error_file_str.dimmed());
+    let input_rust = record_io_dir().and_then(|_| std::fs::read_to_string(rs_file).ok());
+    // 与翻译相同：同样的超时与输出捕获，在独立的进程组中运行
+    let capture = CAPTURE_TRANSLATOR_OUTPUT.load(Ordering::Relaxed);
+    let timeout = translate_timeout(feature);
+    let run = run_translator_process(Command::new(&argv[0]).args(&argv[1..]), timeout, capture)
+        .with_context(|| format!("Failed to execute fix command: {}", argv[0]))?;
+    let exit_code = run.status.and_then(|status| status.code());
+    collect_usage_sidecar(rs_file);
+
+    let argv: Vec<&str> = argv.iter().map(String::as_str).collect();
+    if capture {
+        let log_path = translator_log_path(&project_root, feature, rs_file, "fix")?;
+        write_translate_log(&log_path, &argv, &run, timeout)?;
+        report_captured_output(&run, &log_path, show_full_error);
     }
-    println!("│");
-
-    let args = build_fix_args(
-        script_str,
-        config_str,
-        c_file_str,
-        rs_file_str,
-        rs_file_str,
-        error_file_str,
-        suggestion_str,
-    );
-
-    let status = Command::new(&python)
-        .args(&args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .context("Failed to execute translate_and_fix.py for fixing")?;
 
-    if !status.success() {
-        anyhow::bail!("Fix failed with exit code: {}", status.code().unwrap_or(-1));
+    record_translator_call(&TranslatorCall {
+        kind: "fix",
+        argv,
+        c_file: &c_file,
+        rs_file,
+        input_rust,
+        error: Some(error_msg),
+        suggestion_file: suggestion_exists.then_some(suggestion_path.as_path()),
+        exit_code,
+    })?;
+
+    match run.status {
+        None => anyhow::bail!(
+            "Fix timed out after {} seconds",
+            timeout.map_or(0, |t| t.as_secs())
+        ),
+        Some(status) if !status.success() => {
+            anyhow::bail!("Fix failed with exit code: {}", exit_code.unwrap_or(-1))
+        }
+        Some(_) => {}
     }
 
+    format_translated_file(feature, rs_file);
+
     display_code(
         rs_file,
         "─ Fixed Rust Code ─",
@@ -675,6 +1636,225 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_run_translator_process_captures_output_and_times_out() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo translated; echo warning >&2; exit 2"]);
+        let run = run_translator_process(&mut cmd, Some(Duration::from_secs(10)), true).unwrap();
+        assert_eq!(run.status.and_then(|s| s.code()), Some(2));
+        assert_eq!(String::from_utf8_lossy(&run.stdout), "translated\n");
+        assert_eq!(String::from_utf8_lossy(&run.stderr), "warning\n");
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo started; sleep 30 & sleep 30"]);
+        let start = Instant::now();
+        let run = run_translator_process(&mut cmd, Some(Duration::from_millis(200)), true).unwrap();
+        assert!(run.status.is_none());
+        assert_eq!(String::from_utf8_lossy(&run.stdout), "started\n");
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("logs/fun_slow.translate.log");
+        write_translate_log(
+            &log_path,
+            &["python3", "translate_and_fix.py"],
+            &run,
+            Some(Duration::from_secs(900)),
+        )
+        .unwrap();
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log
+            .starts_with("$ python3 translate_and_fix.py\nresult: timed out after 900 seconds\n"));
+        assert!(log.contains("--- stdout ---\nstarted\n"));
+
+        let err = TranslationScriptFailedError {
+            exit_code: -1,
+            timed_out_after: Some(900),
+        };
+        assert!(err
+            .to_string()
+            .starts_with("Translation timed out after 900 seconds"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_collect_usage_sidecar_removes_file() {
+        crate::report::start_unwritten("default");
+        let _reset = scopeguard::guard((), |_| {
+            crate::report::take_report();
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let rs_file = dir.path().join("fun_add.rs");
+        let sidecar = usage_sidecar_path(&rs_file);
+        assert_eq!(sidecar, dir.path().join("fun_add.rs.meta.json"));
+
+        // 不存在时静默跳过
+        collect_usage_sidecar(&rs_file);
+
+        std::fs::write(&sidecar, r#"{"tokens_in": 10, "cost_usd": 0.5}"#).unwrap();
+        collect_usage_sidecar(&rs_file);
+        assert!(!sidecar.exists());
+
+        // 多次调用的用量累加到本次运行的报告中
+        std::fs::write(
+            &sidecar,
+            r#"{"tokens_in": 5, "tokens_out": 3, "cost_usd": 0.25}"#,
+        )
+        .unwrap();
+        collect_usage_sidecar(&rs_file);
+        assert!(!sidecar.exists());
+        assert_eq!(
+            crate::report::take_report().unwrap().usage,
+            Some(crate::report::TokenUsage {
+                tokens_in: 15,
+                tokens_out: 3,
+                cost_usd: 0.75,
+            })
+        );
+
+        let usage: crate::report::TokenUsage =
+            serde_json::from_str(r#"{"tokens_out": 7, "extra": true}"#).unwrap();
+        assert_eq!(usage.tokens_in, 0);
+        assert_eq!(usage.tokens_out, 7);
+    }
+
+    #[test]
+    fn test_write_error_file_keeps_stable_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path();
+        let rs_file = project_root.join(".c2rust/default/rust/src/fun_parse.rs");
+
+        let error_file = write_error_file(
+            project_root,
+            "default",
+            &rs_file,
+            "error[E0308]: first",
+            true,
+        )
+        .unwrap();
+        let expected = project_root.join(".c2rust/default/logs/fun_parse.error.txt");
+        assert_eq!(error_file.path(), expected.as_path());
+        // 不同目录下的同名文件使用不同的错误文件
+        assert_eq!(
+            kept_error_file_path(
+                project_root,
+                "default",
+                &project_root.join(".c2rust/default/rust/src/net/fun_parse.rs")
+            )
+            .unwrap(),
+            project_root.join(".c2rust/default/logs/net__fun_parse.error.txt")
+        );
+        assert_eq!(
+            translator_log_path(project_root, "default", &rs_file, "fix").unwrap(),
+            project_root.join(".c2rust/default/logs/fun_parse.fix.log")
+        );
+        assert_eq!(
+            std::fs::read_to_string(&expected).unwrap(),
+            "error[E0308]: first"
+        );
+
+        // 再次写入时覆盖同一路径，并在调用结束后保留
+        drop(error_file);
+        write_error_file(project_root, "default", &rs_file, "error: second", true).unwrap();
+        assert_eq!(std::fs::read_to_string(&expected).unwrap(), "error: second");
+    }
+
+    #[test]
+    fn test_context_files_are_appended_to_translator_args() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("include dir")).unwrap();
+        std::fs::create_dir_all(project_root.join(".c2rust/default")).unwrap();
+        std::fs::write(project_root.join("include dir/defs.h"), "").unwrap();
+        std::fs::write(project_root.join(".c2rust/default/types.h"), "").unwrap();
+
+        let files =
+            resolve_context_files(project_root, "default", Some("'include dir/defs.h'")).unwrap();
+        let args = context_args(&files).unwrap();
+        assert_eq!(
+            args,
+            [
+                "--context".to_string(),
+                project_root
+                    .join("include dir/defs.h")
+                    .display()
+                    .to_string(),
+                "--context".to_string(),
+                project_root
+                    .join(".c2rust/default/types.h")
+                    .display()
+                    .to_string(),
+            ]
+        );
+
+        // 未配置时只使用自动发现的 types.h
+        assert_eq!(
+            resolve_context_files(project_root, "default", None).unwrap(),
+            [project_root.join(".c2rust/default/types.h")]
+        );
+
+        let err = resolve_context_files(project_root, "default", Some("missing.h")).unwrap_err();
+        assert!(err.to_string().contains("missing.h"));
+    }
+
+    #[test]
+    fn test_write_io_record_bundle_contents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let c_file = temp_dir.path().join("fun_add.c");
+        let rs_file = temp_dir.path().join("fun_add.rs");
+        std::fs::write(&c_file, "int add(int a, int b) { return a + b; }").unwrap();
+        std::fs::write(&rs_file, "pub fn add(a: i32, b: i32) -> i32 { a + b }").unwrap();
+        let record_dir = temp_dir.path().join("records");
+
+        let bundle = write_io_record(
+            &record_dir,
+            &TranslatorCall {
+                kind: "translate",
+                argv: vec!["python3", "translate_and_fix.py", "--type", "fn"],
+                c_file: &c_file,
+                rs_file: &rs_file,
+                input_rust: None,
+                error: None,
+                suggestion_file: None,
+                exit_code: Some(0),
+            },
+        )
+        .unwrap();
+
+        assert!(bundle.starts_with(&record_dir));
+        let name = bundle.file_name().unwrap().to_str().unwrap();
+        assert!(name.ends_with("-translate-fun_add"), "{}", name);
+
+        let call: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(bundle.join("call.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            call["argv"],
+            serde_json::json!(["python3", "translate_and_fix.py", "--type", "fn"])
+        );
+        assert_eq!(call["exit_code"], 0);
+        assert_eq!(
+            std::fs::read_to_string(bundle.join("output.rs")).unwrap(),
+            "pub fn add(a: i32, b: i32) -> i32 { a + b }"
+        );
+        assert!(bundle.join("input.c").is_file());
+        assert!(!bundle.join("error.txt").exists());
+    }
+
+    #[test]
+    fn test_write_error_file_default_is_ephemeral() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rs_file = temp_dir.path().join("fun_parse.rs");
+        let error_file =
+            write_error_file(temp_dir.path(), "default", &rs_file, "boom", false).unwrap();
+        let path = error_file.path().to_path_buf();
+        assert!(path.exists());
+        assert!(!temp_dir.path().join(".c2rust").exists());
+        drop(error_file);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_temp_error_file_creation() {
         let test_msg = "test error message";
@@ -839,6 +2019,171 @@ mod tests {
         assert_eq!(result.unwrap(), PathBuf::from("/path/to/scripts"));
     }
 
+    #[test]
+    fn test_paths_with_spaces_are_single_args_and_displayed_quoted() {
+        let c_code = "/my project/src/fun_a.c";
+        let output = "/my project/src/fun_a.rs";
+        let context = vec!["--context".to_string(), "/my project/types.h".to_string()];
+        let args = build_translate_args(
+            "/opt/tools/translate_and_fix.py",
+            "/tmp/config.toml",
+            "fn",
+            c_code,
+            output,
+            Some("fn a(x: i32)"),
+            &context,
+        );
+        assert_eq!(
+            args,
+            [
+                "/opt/tools/translate_and_fix.py",
+                "--config",
+                "/tmp/config.toml",
+                "--type",
+                "fn",
+                "--c_code",
+                c_code,
+                "--output",
+                output,
+                "--rusttype",
+                "fn a(x: i32)",
+                "--context",
+                "/my project/types.h",
+            ]
+        );
+
+        let described = describe_translate_command("f", "fn", Path::new(c_code), Path::new(output));
+        assert!(
+            described.contains("--c_code '/my project/src/fun_a.c'"),
+            "{described}"
+        );
+        assert!(
+            described.contains("--output '/my project/src/fun_a.rs'"),
+            "{described}"
+        );
+        // 复制粘贴后 shell 按原样还原为单个参数
+        let reparsed = shell_words::split(&described).unwrap();
+        assert!(reparsed.iter().any(|arg| arg == c_code));
+    }
+
+    #[test]
+    fn test_expand_command_template() {
+        let argv = expand_command_template(
+            "translator.fix_command",
+            "my-fixer --in '{rust_code}' --out={output} --error {error} --hint={suggestion}",
+            FIX_REQUIRED_PLACEHOLDERS,
+            FIX_PLACEHOLDERS,
+            &[
+                ("c_code", Some("/p/a.c")),
+                ("rust_code", Some("/my p/a.rs")),
+                ("output", Some("/my p/a.rs")),
+                ("error", Some("/tmp/err")),
+                ("suggestion", None),
+            ],
+            &[],
+        );
+        // {c_code} 缺失
+        let err = argv.unwrap_err().to_string();
+        assert!(
+            err.contains("missing required placeholder(s) {c_code}"),
+            "{err}"
+        );
+
+        let argv = expand_command_template(
+            "translator.fix_command",
+            "my-fixer {c_code} --in '{rust_code}' --out={output} --error {error} --hint={suggestion}",
+            FIX_REQUIRED_PLACEHOLDERS,
+            FIX_PLACEHOLDERS,
+            &[
+                ("c_code", Some("/p/a.c")),
+                ("rust_code", Some("/my p/a.rs")),
+                ("output", Some("/my p/a.rs")),
+                ("error", Some("/tmp/err")),
+                ("suggestion", None),
+            ],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            vec![
+                "my-fixer",
+                "/p/a.c",
+                "--in",
+                "/my p/a.rs",
+                "--out=/my p/a.rs",
+                "--error",
+                "/tmp/err",
+            ]
+        );
+
+        let err = expand_command_template(
+            "translator.command",
+            "tool {type} {c_code} {output} {outptu}",
+            TRANSLATE_REQUIRED_PLACEHOLDERS,
+            TRANSLATE_PLACEHOLDERS,
+            &[],
+            &[],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("unknown placeholder {outptu}"), "{err}");
+
+        // 每个上下文文件一个参数；没有上下文文件时整个参数省略
+        let contexts = ["/p/types.h".to_string(), "/p/common.h".to_string()];
+        let template = "tool {type} {c_code} {output} --ctx={context}";
+        let values = [
+            ("type", Some("fn")),
+            ("c_code", Some("/p/a.c")),
+            ("output", Some("/p/a.rs")),
+        ];
+        let argv = expand_command_template(
+            "translator.command",
+            template,
+            TRANSLATE_REQUIRED_PLACEHOLDERS,
+            TRANSLATE_PLACEHOLDERS,
+            &values,
+            &[("context", &contexts)],
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            vec![
+                "tool",
+                "fn",
+                "/p/a.c",
+                "/p/a.rs",
+                "--ctx=/p/types.h",
+                "--ctx=/p/common.h"
+            ]
+        );
+        let argv = expand_command_template(
+            "translator.command",
+            template,
+            TRANSLATE_REQUIRED_PLACEHOLDERS,
+            TRANSLATE_PLACEHOLDERS,
+            &values,
+            &[("context", &[])],
+        )
+        .unwrap();
+        assert_eq!(argv, vec!["tool", "fn", "/p/a.c", "/p/a.rs"]);
+    }
+
+    #[test]
+    fn test_command_template_problems() {
+        let lookup = |key: &str| match key {
+            "translator.command" => Ok("tool {type} {c_code} {output}".to_string()),
+            "translator.fix_command" => Ok("fixer {c_code} {output}".to_string()),
+            _ => anyhow::bail!("not set"),
+        };
+        let problems = command_template_problems(lookup);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0]
+            .starts_with("translator.fix_command is missing required placeholder(s) {error}"));
+
+        assert!(command_template_problems(|_| anyhow::bail!("not set")).is_empty());
+    }
+
     #[test]
     fn test_build_fix_args() {
         let script = "/path/to/translate_and_fix.py";
@@ -1324,4 +2669,40 @@ mod tests {
         let result = read_rusttype_from_decl_file(&rs_file);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_rustfmt_file_keeps_empty_and_unparsable_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let empty = temp_dir.path().join("fun_empty.rs");
+        std::fs::write(&empty, "  \n").unwrap();
+        assert_eq!(
+            rustfmt_file(&empty),
+            FormatOutcome::Skipped("file is empty".to_string())
+        );
+        assert_eq!(std::fs::read_to_string(&empty).unwrap(), "  \n");
+
+        assert!(parse_format_enabled("true"));
+        assert!(parse_format_enabled(" yes "));
+        assert!(!parse_format_enabled("False"));
+        assert!(!parse_format_enabled("0"));
+
+        if Command::new("rustfmt").arg("--version").output().is_err() {
+            return;
+        }
+        let messy = temp_dir.path().join("fun_messy.rs");
+        std::fs::write(&messy, "pub fn add(a:i32,b:i32)->i32{a+b}").unwrap();
+        assert_eq!(rustfmt_file(&messy), FormatOutcome::Formatted);
+        assert_eq!(
+            std::fs::read_to_string(&messy).unwrap(),
+            "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+        );
+
+        let broken = temp_dir.path().join("fun_broken.rs");
+        std::fs::write(&broken, "pub fn add(a:i32 {").unwrap();
+        assert!(matches!(rustfmt_file(&broken), FormatOutcome::Skipped(_)));
+        assert_eq!(
+            std::fs::read_to_string(&broken).unwrap(),
+            "pub fn add(a:i32 {"
+        );
+    }
 }
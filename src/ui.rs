@@ -0,0 +1,46 @@
+//! 文件处理过程中 `│` 边栏输出的公共格式
+//!
+//! 每个文件的处理输出以 `┌─`/`└─` 包围，中间各行带 `│ ` 前缀。这里统一边栏字符、
+//! 各类消息的颜色与前缀符号（`✓`、`⚠`、`✗`），避免各模块各自拼接导致格式不一致。
+//! 需要特殊样式（如加粗、其他颜色）的行仍可用 [`line`] 输出已着色的文本。
+
+use colored::Colorize;
+use std::fmt::Display;
+
+/// 边栏字符
+pub(crate) const GUTTER: &str = "│";
+
+/// 空的边栏行，用于分隔各步骤
+pub(crate) fn blank() {
+    println!("{}", GUTTER);
+}
+
+/// 原样输出一行（文本可已着色）
+pub(crate) fn line(msg: impl Display) {
+    println!("{} {}", GUTTER, msg);
+}
+
+/// 步骤标题，如 `format_progress("Commit")`
+pub(crate) fn header(title: impl Display) {
+    line(title.to_string().bright_magenta().bold());
+}
+
+/// 进行中的操作说明
+pub(crate) fn info(msg: impl Display) {
+    line(msg.to_string().bright_blue());
+}
+
+/// 操作成功（`✓` 前缀）
+pub(crate) fn success(msg: impl Display) {
+    line(format!("✓ {}", msg).bright_green());
+}
+
+/// 警告，处理继续（`⚠` 前缀）
+pub(crate) fn warning(msg: impl Display) {
+    line(format!("⚠ {}", msg).yellow());
+}
+
+/// 操作失败（`✗` 前缀）
+pub(crate) fn error(msg: impl Display) {
+    line(format!("✗ {}", msg).red());
+}
@@ -8,7 +8,7 @@ use walkdir::WalkDir;
 // Constants
 // ============================================================================
 
-/// 翻译文件的最大尝试次数（1 次初始 + 2 次重试）
+/// 翻译文件的默认最大尝试次数（1 次初始 + 2 次重试），可通过 `--max-translation-attempts` 覆盖
 pub const MAX_TRANSLATION_ATTEMPTS: usize = 3;
 
 /// 从代码文件预览的行数（C 源代码或 Rust 代码）
@@ -31,6 +31,31 @@ pub struct FileAttemptStat {
     pub had_restart: bool,
 }
 
+/// 单个文件在翻译流程中最近完成的阶段
+///
+/// 文件最终被接受（或跳过/失败）前持久化到统计文件中，进程中途崩溃后可据此
+/// 从最早未完成的阶段恢复，而不是重新翻译。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilePhase {
+    /// 已生成 Rust 代码，尚未通过构建/修复
+    Translated,
+    /// 已通过构建/修复，尚未通过混合构建测试
+    Built,
+    /// 已通过混合构建测试，尚未提交
+    Tested,
+}
+
+impl FilePhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FilePhase::Translated => "translated",
+            FilePhase::Built => "built",
+            FilePhase::Tested => "tested",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TranslationStats {
     /// 总文件数
@@ -53,6 +78,14 @@ pub struct TranslationStats {
     /// 与 `skipped_files`（用户主动/自动跳过）不同，此列表仅记录翻译命令非零退出的情况。
     #[serde(default)]
     pub translation_failed_files: Vec<String>,
+    /// C 文件超过 `--max-c-file-bytes` 而未翻译的文件列表（文件名）。
+    /// 与 `skipped_files` 不同，这些文件不会在"处理跳过的文件"中重新处理；
+    /// 用 `--file` 单独指定或重置进度后才会再次翻译。
+    #[serde(default)]
+    pub oversized_files: Vec<String>,
+    /// 尚未完成的文件已完成的阶段（文件名 -> 阶段），文件完成/跳过/失败后移除
+    #[serde(default)]
+    pub file_phases: HashMap<String, FilePhase>,
 }
 
 impl TranslationStats {
@@ -69,6 +102,7 @@ impl TranslationStats {
         fix_attempts: usize,
     ) {
         let file_name = canonicalize_stats_file_key(&file_name);
+        self.file_phases.remove(&file_name);
         self.total_files += 1;
 
         debug_assert!(
@@ -100,6 +134,7 @@ impl TranslationStats {
     /// 记录文件被跳过
     pub fn record_file_skipped(&mut self, file_name: String) {
         let file_name = canonicalize_stats_file_key(&file_name);
+        self.file_phases.remove(&file_name);
         if !self.skipped_files.contains(&file_name) {
             self.skipped_files.push(file_name);
         }
@@ -108,21 +143,63 @@ impl TranslationStats {
     /// 记录翻译命令失败（与用户主动跳过区分）
     pub fn record_file_translation_failed(&mut self, file_name: String) {
         let file_name = canonicalize_stats_file_key(&file_name);
+        self.file_phases.remove(&file_name);
         if !self.translation_failed_files.contains(&file_name) {
             self.translation_failed_files.push(file_name);
         }
     }
 
+    /// 记录文件因 C 文件过大而未翻译
+    pub fn record_file_oversized(&mut self, file_name: String) {
+        let file_name = canonicalize_stats_file_key(&file_name);
+        self.file_phases.remove(&file_name);
+        if !self.oversized_files.contains(&file_name) {
+            self.oversized_files.push(file_name);
+        }
+    }
+
+    /// 记录文件最近完成的阶段
+    pub fn record_file_phase(&mut self, file_name: &str, phase: FilePhase) {
+        self.file_phases
+            .insert(canonicalize_stats_file_key(file_name), phase);
+    }
+
+    /// 获取文件最近完成的阶段（无记录表示未开始或已结束）
+    pub fn file_phase(&self, file_name: &str) -> Option<FilePhase> {
+        self.file_phases
+            .get(&canonicalize_stats_file_key(file_name))
+            .copied()
+    }
+
+    /// 移除文件的阶段记录
+    pub fn clear_file_phase(&mut self, file_name: &str) {
+        self.file_phases
+            .remove(&canonicalize_stats_file_key(file_name));
+    }
+
+    /// 上次运行中断时仍未完成的文件（按文件名排序）
+    pub fn interrupted_files(&self) -> Vec<(String, FilePhase)> {
+        let mut files: Vec<_> = self
+            .file_phases
+            .iter()
+            .map(|(file_name, phase)| (file_name.clone(), *phase))
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        files
+    }
+
     /// 为定点重跑清理单个目标的历史状态。
     ///
     /// 该操作不会影响其它文件的统计，只移除当前目标在
-    /// `skipped_files` / `translation_failed_files` / `file_attempts`
+    /// `skipped_files` / `translation_failed_files` / `oversized_files` / `file_attempts`
     /// 中的历史痕迹，并同步调整聚合成功计数。
     pub fn clear_target_history(&mut self, file_name: &str) {
         let canonical_name = canonicalize_stats_file_key(file_name);
+        self.file_phases.remove(&canonical_name);
         self.skipped_files.retain(|item| item != &canonical_name);
         self.translation_failed_files
             .retain(|item| item != &canonical_name);
+        self.oversized_files.retain(|item| item != &canonical_name);
 
         if let Some(previous) = self.file_attempts.remove(&canonical_name) {
             self.total_files = self.total_files.saturating_sub(1);
@@ -151,7 +228,10 @@ impl TranslationStats {
 
         if self.total_files == 0 {
             println!("\n{}", "No files were successfully translated.".yellow());
-            if self.skipped_files.is_empty() && self.translation_failed_files.is_empty() {
+            if self.skipped_files.is_empty()
+                && self.translation_failed_files.is_empty()
+                && self.oversized_files.is_empty()
+            {
                 println!("\n{}", "═".repeat(80).bright_cyan());
                 return;
             }
@@ -265,6 +345,21 @@ impl TranslationStats {
             );
         }
 
+        // C 文件过大而未翻译的文件
+        if !self.oversized_files.is_empty() {
+            println!(
+                "\n{}",
+                "Oversized C Files (not translated):".bright_yellow().bold()
+            );
+            for (idx, file_name) in self.oversized_files.iter().enumerate() {
+                println!("  {}. {}", idx + 1, file_name.bright_yellow());
+            }
+            println!(
+                "  {}",
+                format!("Total oversized: {}", self.oversized_files.len()).bright_yellow()
+            );
+        }
+
         println!("\n{}", "═".repeat(80).bright_cyan());
         println!(
             "{}",
@@ -328,6 +423,7 @@ impl TranslationStats {
     /// This is intentionally conservative:
     /// - only non-empty translatable `fun_*.rs` / `var_*.rs` files are considered
     /// - existing per-file stats are preserved
+    /// - files with a pending phase marker were interrupted mid-workflow and are left to resume
     /// - newly discovered completed files are backfilled as first-try successes
     ///
     /// This keeps `translation_stats.json` usable after workspace migration,
@@ -341,7 +437,8 @@ impl TranslationStats {
                 continue;
             }
             let path = entry.path();
-            if path.extension().is_none_or(|ext| ext != "rs") {
+            if path.extension().is_none_or(|ext| ext != "rs") || crate::backup::is_backup_path(path)
+            {
                 continue;
             }
             let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
@@ -366,11 +463,13 @@ impl TranslationStats {
         let mut changed = false;
         for file_name in discovered {
             if self.file_attempts.contains_key(&file_name)
+                || self.file_phases.contains_key(&file_name)
                 || self.skipped_files.iter().any(|item| item == &file_name)
                 || self
                     .translation_failed_files
                     .iter()
                     .any(|item| item == &file_name)
+                || self.oversized_files.iter().any(|item| item == &file_name)
             {
                 continue;
             }
@@ -407,6 +506,8 @@ impl TranslationStats {
         let previous_attempts = std::mem::take(&mut self.file_attempts);
         let previous_skipped = std::mem::take(&mut self.skipped_files);
         let previous_failed = std::mem::take(&mut self.translation_failed_files);
+        let previous_oversized = std::mem::take(&mut self.oversized_files);
+        let previous_phases = std::mem::take(&mut self.file_phases);
 
         self.total_files = 0;
         self.success_first_try = 0;
@@ -454,6 +555,12 @@ impl TranslationStats {
         for file_name in previous_failed {
             self.record_file_translation_failed(file_name);
         }
+        for file_name in previous_oversized {
+            self.record_file_oversized(file_name);
+        }
+        for (file_name, phase) in previous_phases {
+            self.record_file_phase(&file_name, phase);
+        }
 
         let after = serde_json::to_string(self).ok();
         before != after
@@ -473,16 +580,98 @@ fn canonicalize_stats_file_key(file_name: &str) -> String {
     }
 }
 
+/// 解析 `2h`、`30m`、`1h30m`、`90s` 形式的时长（单位 d/h/m/s，可组合；纯数字按秒计算）
+pub fn parse_duration(input: &str) -> Result<std::time::Duration> {
+    let text = input.trim();
+    if text.is_empty() {
+        anyhow::bail!("duration must not be empty");
+    }
+    if let Ok(secs) = text.parse::<u64>() {
+        return Ok(std::time::Duration::from_secs(secs));
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => anyhow::bail!("invalid duration '{}': unknown unit '{}'", input, c),
+        };
+        let value: u64 = digits.parse().with_context(|| {
+            format!(
+                "invalid duration '{}': missing number before '{}'",
+                input, c
+            )
+        })?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .with_context(|| format!("duration '{}' is too large", input))?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        anyhow::bail!(
+            "invalid duration '{}': missing unit after '{}' (use d, h, m or s)",
+            input,
+            digits
+        );
+    }
+    Ok(std::time::Duration::from_secs(total))
+}
+
+/// 解析字节数：纯数字，或带 `K`、`M`、`G` 后缀（不区分大小写，按 1024 进制）
+pub fn parse_byte_size(input: &str) -> Result<u64> {
+    let text = input.trim();
+    let (digits, multiplier) = match text.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => {
+            let multiplier: u64 = match c.to_ascii_uppercase() {
+                'K' => 1 << 10,
+                'M' => 1 << 20,
+                'G' => 1 << 30,
+                _ => anyhow::bail!(
+                    "invalid size '{}': unknown unit '{}' (use K, M or G)",
+                    input,
+                    c
+                ),
+            };
+            (&text[..i], multiplier)
+        }
+        _ => (text, 1),
+    };
+    let value: u64 = digits
+        .parse()
+        .with_context(|| format!("invalid size '{}'", input))?;
+    value
+        .checked_mul(multiplier)
+        .with_context(|| format!("size '{}' is too large", input))
+}
+
 // ============================================================================
 // Progress Tracking
 // ============================================================================
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProgressState {
     /// 已处理文件的总数（包括之前运行和当前会话的文件）
     pub processed_count: usize,
     /// 要处理的文件总数（用于显示目的）
     pub total_count: usize,
+    /// 本会话已处理的文件数（中断后重新运行会从进度文件继续累计）
+    #[serde(default)]
+    pub session_processed_count: usize,
+    /// 最近一次保存到磁盘的时间（Unix 时间戳，秒）
+    #[serde(default)]
+    pub saved_at: u64,
+    /// 是否在每个文件处理完后保存到磁盘（定点重跑单个文件时不保存）
+    #[serde(skip)]
+    pub persist: bool,
 }
 
 impl ProgressState {
@@ -491,6 +680,7 @@ impl ProgressState {
         Self {
             processed_count: 0,
             total_count,
+            ..Self::default()
         }
     }
 
@@ -500,6 +690,7 @@ impl ProgressState {
         Self {
             processed_count: already_processed.min(total_count),
             total_count,
+            ..Self::default()
         }
     }
 
@@ -508,6 +699,7 @@ impl ProgressState {
         if self.processed_count < self.total_count {
             self.processed_count += 1;
         }
+        self.session_processed_count += 1;
     }
 
     /// Update progress state from caller-supplied actuals.
@@ -516,6 +708,7 @@ impl ProgressState {
     /// clamping `processed` at `total` to prevent overflow.
     /// Useful when resuming a session or when an external source supplies
     /// the true counts directly.
+    #[allow(dead_code)]
     pub(crate) fn refresh(&mut self, total: usize, processed: usize) {
         self.total_count = total;
         self.processed_count = processed.min(total);
@@ -530,14 +723,214 @@ impl ProgressState {
     pub fn get_total_count(&self) -> usize {
         self.total_count
     }
+
+    /// 获取进度文件路径
+    ///
+    /// 进度按宿主项目当前的 git 分支区分（`.c2rust/<feature>/progress-<branch>-<hash>.json`），
+    /// 切换分支后不会复用其他分支的进度；无法确定分支（不在 git 仓库中或 detached HEAD）时
+    /// 使用 `.c2rust/<feature>/progress.json`。
+    pub fn get_progress_file_path(feature: &str) -> Result<PathBuf> {
+        validate_feature_name(feature)?;
+        let project_root = find_project_root()?;
+        let branch = current_git_branch(&project_root);
+        Ok(progress_file_path_for_branch(
+            &project_root.join(".c2rust").join(feature),
+            branch.as_deref(),
+        ))
+    }
+
+    /// 保存进度到 [`Self::get_progress_file_path`]，同时更新 `saved_at`
+    pub fn save_to_disk(&mut self, feature: &str) -> Result<()> {
+        let path = Self::get_progress_file_path(feature)?;
+        self.save_to_path(&path)
+    }
+
+    fn save_to_path(&mut self, path: &Path) -> Result<()> {
+        self.saved_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize progress state")?;
+        std::fs::write(path, contents)
+            .with_context(|| format!("Failed to write progress file: {}", path.display()))
+    }
+
+    /// 从 [`Self::get_progress_file_path`] 加载当前分支的进度（文件不存在时返回 `None`）
+    pub fn load_from_disk(feature: &str) -> Result<Option<Self>> {
+        let path = Self::get_progress_file_path(feature)?;
+        Self::load_from_path(&path)
+    }
+
+    fn load_from_path(path: &Path) -> Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map(Some)
+                .with_context(|| format!("Failed to parse progress file: {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to read progress file: {}", path.display()))
+            }
+        }
+    }
+
+    /// 删除当前分支的进度文件（`--reset-progress`）
+    pub fn clear_from_disk(feature: &str) -> Result<()> {
+        let path = Self::get_progress_file_path(feature)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e)
+                .with_context(|| format!("Failed to remove progress file: {}", path.display())),
+        }
+    }
+}
+
+/// 拼出指定分支对应的进度文件路径；`branch` 为 `None` 时使用不区分分支的 `progress.json`
+fn progress_file_path_for_branch(feature_dir: &Path, branch: Option<&str>) -> PathBuf {
+    match branch {
+        Some(branch) => feature_dir.join(format!(
+            "progress-{}.json",
+            sanitize_branch_for_file_name(branch)
+        )),
+        None => feature_dir.join("progress.json"),
+    }
+}
+
+/// 将分支名转换为可用作文件名的形式（`feature/x` -> `feature_x-<哈希>`）
+///
+/// 替换字符后不同的分支名可能相同（`feat/x` 与 `feat_x`），因此追加原始分支名的短哈希。
+fn sanitize_branch_for_file_name(branch: &str) -> String {
+    let sanitized: String = branch
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{:08x}", sanitized, fnv1a_32(branch.as_bytes()))
+}
+
+/// 32 位 FNV-1a 哈希；与 `DefaultHasher` 不同，结果不随 Rust 版本变化，可用于文件名
+fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// 获取 `repo_dir` 所在 git 仓库的当前分支名
+///
+/// 不在 git 仓库中、git 不可用或处于 detached HEAD 时返回 `None`。
+fn current_git_branch(repo_dir: &Path) -> Option<String> {
+    let output = crate::interrupt::output(
+        std::process::Command::new("git")
+            .current_dir(repo_dir)
+            .args(["symbolic-ref", "--short", "-q", "HEAD"]),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if branch.is_empty() {
+        None
+    } else {
+        Some(branch)
+    }
 }
 
 // ============================================================================
 // Path and Project Utilities
 // ============================================================================
 
+/// 通过 `--root-boundary` 指定的搜索边界，优先于 `C2RUST_ROOT_BOUNDARY` 环境变量
+static ROOT_BOUNDARY: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+
+/// 设置项目根目录搜索边界（`--root-boundary`）
+pub fn set_root_boundary(boundary: Option<PathBuf>) {
+    *ROOT_BOUNDARY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = boundary;
+}
+
+/// 当前生效的搜索边界：`--root-boundary` > `C2RUST_ROOT_BOUNDARY` > 不限制
+fn root_boundary() -> Option<PathBuf> {
+    let configured = ROOT_BOUNDARY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    configured.or_else(|| {
+        std::env::var_os("C2RUST_ROOT_BOUNDARY")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// 通过 `--project-root` 指定的项目根目录，优先于 `C2RUST_PROJECT_ROOT` 环境变量
+static PROJECT_ROOT_OVERRIDE: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+
+/// 设置项目根目录（`--project-root`），不再自动查找
+pub fn set_project_root(root: Option<PathBuf>) {
+    *PROJECT_ROOT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = root;
+}
+
+/// 当前生效的项目根目录覆盖：`--project-root` > `C2RUST_PROJECT_ROOT` > 自动查找（`None`）
+fn project_root_override() -> Option<PathBuf> {
+    let configured = PROJECT_ROOT_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    select_project_root_override(configured, std::env::var_os("C2RUST_PROJECT_ROOT"))
+}
+
+fn select_project_root_override(
+    configured: Option<PathBuf>,
+    env_value: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    configured.or_else(|| {
+        env_value
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// 校验显式指定的项目根目录（相对路径相对 `current_dir`）必须包含 .c2rust 目录
+fn validate_project_root(root: &Path, current_dir: &Path) -> Result<PathBuf> {
+    let root = current_dir.join(root);
+    if !root.join(".c2rust").is_dir() {
+        anyhow::bail!(
+            "Project root {} (from --project-root or C2RUST_PROJECT_ROOT) does not contain a .c2rust directory",
+            root.display()
+        );
+    }
+    Ok(root)
+}
+
 /// 从起始路径向上搜索 .c2rust 目录以查找项目根目录
-fn find_project_root_from(start_path: &Path) -> Result<PathBuf> {
+///
+/// 使用离起始路径最近的、包含 .c2rust 的目录；只包含 `.git` 的目录（如嵌套的 git 子模块）不算项目根目录。
+/// 指定 `boundary` 时搜索最多到达该目录（含）为止，起始路径不在边界之内时直接报错。
+fn find_project_root_from(start_path: &Path, boundary: Option<&Path>) -> Result<PathBuf> {
+    // 边界比较使用规范化路径，避免符号链接或相对路径导致比较失效
+    let boundary = boundary.map(canonical_or_self);
+    if let Some(boundary) = &boundary {
+        if !canonical_or_self(start_path).starts_with(boundary) {
+            anyhow::bail!(
+                "Current directory {} is outside the root boundary {}",
+                start_path.display(),
+                boundary.display()
+            );
+        }
+    }
     let mut current = start_path.to_path_buf();
 
     loop {
@@ -565,6 +958,14 @@ fn find_project_root_from(start_path: &Path) -> Result<PathBuf> {
             }
         }
 
+        if let Some(boundary) = &boundary {
+            if canonical_or_self(&current) == *boundary {
+                anyhow::bail!(
+                    "Could not find .c2rust directory within the root boundary {}",
+                    boundary.display()
+                );
+            }
+        }
         match current.parent() {
             Some(parent) => current = parent.to_path_buf(),
             None => anyhow::bail!("Could not find .c2rust directory in any parent directory"),
@@ -572,10 +973,124 @@ fn find_project_root_from(start_path: &Path) -> Result<PathBuf> {
     }
 }
 
-/// 从当前目录向上搜索 .c2rust 目录以查找项目根目录
+fn canonical_or_self(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// 查找项目根目录
+///
+/// 优先级：
+/// 1. `--project-root`（见 [`set_project_root`]）
+/// 2. `C2RUST_PROJECT_ROOT` 环境变量
+/// 3. 从当前目录向上搜索最近的 .c2rust 目录，搜索范围受 `--root-boundary` / `C2RUST_ROOT_BOUNDARY`
+///    限制（见 [`set_root_boundary`]）
+///
+/// 显式指定的目录必须包含 .c2rust 目录，否则报错而不回退到自动查找。
 pub fn find_project_root() -> Result<PathBuf> {
     let current = std::env::current_dir().context("Failed to get current directory")?;
-    find_project_root_from(&current)
+    if let Some(root) = project_root_override() {
+        return validate_project_root(&root, &current);
+    }
+    find_project_root_from(&current, root_boundary().as_deref())
+}
+
+/// 通过 `--config` 指定的 config.toml 路径，`None` 表示使用 `<project_root>/.c2rust/config.toml`
+static CONFIG_PATH_OVERRIDE: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+
+/// 设置 config.toml 路径（`--config`）；文件必须存在且可读，相对路径相对当前目录
+pub fn set_config_path(path: Option<&Path>) -> Result<()> {
+    let resolved = match path {
+        Some(path) => {
+            let current = std::env::current_dir().context("Failed to get current directory")?;
+            Some(validate_config_path(path, &current)?)
+        }
+        None => None,
+    };
+    *CONFIG_PATH_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = resolved;
+    Ok(())
+}
+
+fn validate_config_path(path: &Path, current_dir: &Path) -> Result<PathBuf> {
+    let path = current_dir.join(path);
+    if !path.is_file() {
+        anyhow::bail!("Config file not found: {}", path.display());
+    }
+    std::fs::File::open(&path)
+        .with_context(|| format!("Config file is not readable: {}", path.display()))?;
+    Ok(path)
+}
+
+/// `--config` 指定的 config.toml，未指定时为 `None`
+pub(crate) fn config_path_override() -> Option<PathBuf> {
+    CONFIG_PATH_OVERRIDE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// 当前使用的 config.toml 路径：`--config` 指定的文件，否则为 `<project_root>/.c2rust/config.toml`
+pub fn config_path() -> Result<PathBuf> {
+    match config_path_override() {
+        Some(path) => Ok(path),
+        None => Ok(find_project_root()?.join(".c2rust/config.toml")),
+    }
+}
+
+/// 获取功能的日志目录：`<project_root>/.c2rust/<feature>/logs`
+pub fn feature_logs_dir(project_root: &Path, feature: &str) -> PathBuf {
+    project_root.join(".c2rust").join(feature).join("logs")
+}
+
+/// 日志目录的默认总大小上限（MB），可通过配置 `logs.max_size_mb` 修改
+pub const DEFAULT_LOG_MAX_SIZE_MB: u64 = 200;
+
+/// 日志文件的默认最长保留天数，可通过配置 `logs.max_age_days` 修改（0 表示不按时间清理）
+pub const DEFAULT_LOG_MAX_AGE_DAYS: u64 = 30;
+
+/// 按保留策略清理日志目录，返回删除的文件数
+///
+/// 先删除修改时间早于 `now - max_age` 的文件，再按修改时间从旧到新删除，
+/// 直到目录（含子目录）中文件总大小不超过 `max_total_bytes`。目录不存在时不做任何事。
+pub fn prune_log_dir(
+    dir: &Path,
+    max_total_bytes: u64,
+    max_age: Option<std::time::Duration>,
+    now: std::time::SystemTime,
+) -> Result<usize> {
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir) {
+        let entry = entry.with_context(|| format!("Failed to scan {}", dir.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("Failed to read metadata: {}", entry.path().display()))?;
+        let modified = metadata.modified().unwrap_or(now);
+        files.push((modified, metadata.len(), entry.into_path()));
+    }
+    files.sort();
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    let mut removed = 0;
+    for (modified, size, path) in files {
+        let expired = max_age
+            .is_some_and(|max_age| now.duration_since(modified).is_ok_and(|age| age > max_age));
+        if !expired && total <= max_total_bytes {
+            continue;
+        }
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove log file: {}", path.display()))?;
+        total -= size;
+        removed += 1;
+    }
+    Ok(removed)
 }
 
 /// 验证功能名称以防止路径遍历攻击
@@ -593,6 +1108,94 @@ pub fn validate_feature_name(feature: &str) -> Result<()> {
     Ok(())
 }
 
+/// 翻译流程依赖的外部工具：(程序名, 可覆盖程序路径的环境变量, 安装提示)
+///
+/// clang 与 bindgen 由 `code_analyse` 在初始化和更新代码分析时调用。
+const REQUIRED_TOOLS: &[(&str, Option<&str>, &str)] = &[
+    ("git", None, "install git from your package manager"),
+    ("cargo", None, "install Rust via https://rustup.rs"),
+    (
+        "code_analyse",
+        None,
+        "install the code-analyse tool and add it to PATH",
+    ),
+    (
+        "c2rust-config",
+        None,
+        "install c2rust-config and add it to PATH",
+    ),
+    (
+        "clang",
+        Some("C2RUST_CLANG"),
+        "install clang (e.g. `apt install clang`) or point C2RUST_CLANG at it",
+    ),
+    (
+        "bindgen",
+        None,
+        "install it with `cargo install bindgen-cli`",
+    ),
+];
+
+/// 在 `path_var`（PATH 格式）中查找可执行文件；含路径分隔符的程序名直接检查该路径
+pub fn find_executable(program: &str, path_var: Option<&std::ffi::OsStr>) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return is_executable(candidate).then(|| candidate.to_path_buf());
+    }
+    std::env::split_paths(path_var?)
+        .map(|dir| dir.join(program))
+        .find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() || path.with_extension("exe").is_file()
+}
+
+/// 返回缺失工具的描述（含安装提示）；`env` 用于读取环境变量覆盖
+fn missing_tools(
+    path_var: Option<&std::ffi::OsStr>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    REQUIRED_TOOLS
+        .iter()
+        .filter_map(|&(name, env_override, hint)| {
+            let program = env_override
+                .and_then(&env)
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| name.to_string());
+            if find_executable(&program, path_var).is_some() {
+                return None;
+            }
+            let source = match env_override {
+                Some(var) if program != name => format!("{} (from ${})", program, var),
+                _ => program,
+            };
+            Some(format!("{} not found: {}", source, hint))
+        })
+        .collect()
+}
+
+/// 在翻译开始前检查所需的外部工具是否都在 PATH 上，缺失时一次性列出并给出安装提示
+pub fn check_required_tools() -> Result<()> {
+    let path_var = std::env::var_os("PATH");
+    let missing = missing_tools(path_var.as_deref(), |var| std::env::var(var).ok());
+    if missing.is_empty() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Required tools are missing ({}):\n  - {}",
+        missing.len(),
+        missing.join("\n  - ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -615,7 +1218,7 @@ mod tests {
         fs::create_dir_all(&subdir2).unwrap();
 
         // 应该从嵌套子目录找到 .c2rust 目录
-        let result = find_project_root_from(&subdir2);
+        let result = find_project_root_from(&subdir2, None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), temp_dir.path());
@@ -629,13 +1232,149 @@ mod tests {
         fs::create_dir(&subdir).unwrap();
 
         // 应该无法找到 .c2rust 目录
-        let result = find_project_root_from(&subdir);
+        let result = find_project_root_from(&subdir, None);
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("Could not find .c2rust"));
     }
 
+    #[test]
+    fn test_find_project_root_stops_at_boundary() {
+        // temp/
+        //   .c2rust/
+        //   boundary/
+        //     subdir/
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join(".c2rust")).unwrap();
+        let boundary = temp_dir.path().join("boundary");
+        let subdir = boundary.join("subdir");
+        fs::create_dir_all(&subdir).unwrap();
+
+        // .c2rust 位于边界之上，不应被找到
+        let err = find_project_root_from(&subdir, Some(&boundary)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Could not find .c2rust directory within the root boundary"));
+
+        // 边界目录本身仍会被检查
+        fs::create_dir(boundary.join(".c2rust")).unwrap();
+        assert_eq!(
+            find_project_root_from(&subdir, Some(&boundary)).unwrap(),
+            boundary
+        );
+
+        // 起始目录不在边界内时报错
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir(&outside).unwrap();
+        let err = find_project_root_from(&outside, Some(&boundary)).unwrap_err();
+        assert!(err.to_string().contains("is outside the root boundary"));
+    }
+
+    #[test]
+    fn test_find_project_root_prefers_nearest_c2rust_over_git() {
+        // temp/
+        //   .c2rust/
+        //   submodule/
+        //     .git/
+        //     nested/
+        //       .c2rust/
+        //       src/
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join(".c2rust")).unwrap();
+        let submodule = temp_dir.path().join("submodule");
+        fs::create_dir_all(submodule.join(".git")).unwrap();
+        let nested = submodule.join("nested");
+        fs::create_dir_all(nested.join(".c2rust")).unwrap();
+        fs::create_dir_all(nested.join("src")).unwrap();
+
+        assert_eq!(
+            find_project_root_from(&nested.join("src"), None).unwrap(),
+            nested
+        );
+        // 只有 .git 的子模块目录被跳过
+        assert_eq!(
+            find_project_root_from(&submodule, None).unwrap(),
+            temp_dir.path()
+        );
+    }
+
+    #[test]
+    fn test_project_root_override_precedence_and_validation() {
+        let flag = PathBuf::from("/from/flag");
+        assert_eq!(
+            select_project_root_override(Some(flag.clone()), Some("/from/env".into())),
+            Some(flag)
+        );
+        assert_eq!(
+            select_project_root_override(None, Some("/from/env".into())),
+            Some(PathBuf::from("/from/env"))
+        );
+        assert_eq!(select_project_root_override(None, Some("".into())), None);
+        assert_eq!(select_project_root_override(None, None), None);
+
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join(".c2rust")).unwrap();
+        assert_eq!(
+            validate_project_root(Path::new("project"), temp_dir.path()).unwrap(),
+            project
+        );
+        assert_eq!(
+            validate_project_root(&project, Path::new("/elsewhere")).unwrap(),
+            project
+        );
+        let err = validate_project_root(temp_dir.path(), temp_dir.path()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not contain a .c2rust directory"));
+    }
+
+    #[test]
+    fn test_validate_config_path_resolves_relative_paths() {
+        let temp_dir = tempdir().unwrap();
+        let fixture = temp_dir.path().join("fixtures").join("config.toml");
+        fs::create_dir_all(fixture.parent().unwrap()).unwrap();
+        fs::write(&fixture, "[model]\n").unwrap();
+
+        assert_eq!(
+            validate_config_path(Path::new("fixtures/config.toml"), temp_dir.path()).unwrap(),
+            fixture
+        );
+        let err = validate_config_path(Path::new("missing.toml"), temp_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Config file not found"));
+        // 目录不是配置文件
+        assert!(validate_config_path(Path::new("fixtures"), temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("4096").unwrap(), 4096);
+        assert_eq!(parse_byte_size("512k").unwrap(), 512 * 1024);
+        assert_eq!(parse_byte_size(" 2M ").unwrap(), 2 * 1024 * 1024);
+        assert_eq!(parse_byte_size("1G").unwrap(), 1 << 30);
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("M").is_err());
+        assert!(parse_byte_size("3T").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(
+            parse_duration(" 1h30m ").unwrap(),
+            Duration::from_secs(5400)
+        );
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86_400));
+        assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("2x").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
     #[test]
     fn test_find_project_root_from_root_dir() {
         // 创建根目录带有 .c2rust 的临时目录
@@ -644,7 +1383,7 @@ mod tests {
         fs::create_dir(&c2rust_dir).unwrap();
 
         // 应该在起始目录中找到 .c2rust
-        let result = find_project_root_from(temp_dir.path());
+        let result = find_project_root_from(temp_dir.path(), None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), temp_dir.path());
@@ -794,6 +1533,86 @@ mod tests {
         assert_eq!(state.processed_count, 2);
     }
 
+    #[test]
+    fn test_progress_state_save_and_load_roundtrip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("default").join("progress.json");
+
+        assert_eq!(ProgressState::load_from_path(&path).unwrap(), None);
+
+        let mut state = ProgressState::with_initial_progress(10, 3);
+        state.persist = true;
+        state.mark_processed();
+        state.save_to_path(&path).unwrap();
+        assert!(state.saved_at > 0);
+
+        let loaded = ProgressState::load_from_path(&path).unwrap().unwrap();
+        assert_eq!(loaded.processed_count, 4);
+        assert_eq!(loaded.total_count, 10);
+        assert_eq!(loaded.session_processed_count, 1);
+        assert_eq!(loaded.saved_at, state.saved_at);
+        // `persist` is runtime-only and never read back from disk.
+        assert!(!loaded.persist);
+    }
+
+    #[test]
+    fn test_progress_file_path_is_namespaced_by_branch() {
+        let temp_dir = tempdir().unwrap();
+        let feature_dir = temp_dir.path().join("default");
+
+        let main_path = progress_file_path_for_branch(&feature_dir, Some("main"));
+        let topic_path = progress_file_path_for_branch(&feature_dir, Some("topic/x"));
+        assert_ne!(main_path, topic_path);
+        assert_eq!(main_path, feature_dir.join("progress-main-ea90e208.json"));
+        assert_eq!(
+            topic_path,
+            feature_dir.join(format!(
+                "progress-topic_x-{:08x}.json",
+                fnv1a_32(b"topic/x")
+            ))
+        );
+        // 替换字符后相同的分支名不共用进度文件
+        assert_ne!(
+            progress_file_path_for_branch(&feature_dir, Some("topic_x")),
+            topic_path
+        );
+        assert_eq!(
+            progress_file_path_for_branch(&feature_dir, None),
+            feature_dir.join("progress.json")
+        );
+
+        let mut main_state = ProgressState::with_initial_progress(10, 2);
+        main_state.save_to_path(&main_path).unwrap();
+        let mut topic_state = ProgressState::with_initial_progress(5, 4);
+        topic_state.save_to_path(&topic_path).unwrap();
+
+        let loaded = ProgressState::load_from_path(&main_path).unwrap().unwrap();
+        assert_eq!((loaded.processed_count, loaded.total_count), (2, 10));
+        let loaded = ProgressState::load_from_path(&topic_path).unwrap().unwrap();
+        assert_eq!((loaded.processed_count, loaded.total_count), (4, 5));
+    }
+
+    #[test]
+    fn test_current_git_branch_reads_symbolic_ref() {
+        let temp_dir = tempdir().unwrap();
+        let repo = crate::test_support::TestRepo::init(temp_dir.path());
+        repo.git(&["symbolic-ref", "HEAD", "refs/heads/topic/a"]);
+        assert_eq!(
+            current_git_branch(temp_dir.path()).as_deref(),
+            Some("topic/a")
+        );
+    }
+
+    #[test]
+    fn test_progress_state_load_rejects_corrupt_file() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("progress.json");
+        fs::write(&path, "not json").unwrap();
+
+        let err = ProgressState::load_from_path(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to parse progress file"));
+    }
+
     // ========================================================================
     // TranslationStats Tests
     // ========================================================================
@@ -939,6 +1758,21 @@ mod tests {
         assert!(stats.skipped_files.is_empty());
     }
 
+    #[test]
+    fn test_record_file_oversized_is_kept_apart_from_skipped() {
+        let mut stats = TranslationStats::new();
+        stats.record_file_phase("big.rs", FilePhase::Translated);
+
+        stats.record_file_oversized("big.rs".to_string());
+        stats.record_file_oversized("big.rs".to_string());
+        assert_eq!(stats.oversized_files, vec!["src/big.rs"]);
+        assert!(stats.skipped_files.is_empty());
+        assert!(stats.file_phase("big.rs").is_none());
+
+        stats.clear_target_history("src/big.rs");
+        assert!(stats.oversized_files.is_empty());
+    }
+
     // ========================================================================
     // TranslationStats Persistence Tests
     // ========================================================================
@@ -1053,6 +1887,55 @@ mod tests {
         assert!(entry.had_restart);
     }
 
+    #[test]
+    fn test_file_phase_lifecycle_and_json_roundtrip() {
+        let mut stats = TranslationStats::new();
+        stats.record_file_phase("fun_a.rs", FilePhase::Translated);
+        stats.record_file_phase("src/fun_a.rs", FilePhase::Built);
+        stats.record_file_phase("src/fun_b.rs", FilePhase::Tested);
+        assert_eq!(stats.file_phase("src/fun_a.rs"), Some(FilePhase::Built));
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains("\"built\""));
+        let restored: TranslationStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.interrupted_files(),
+            vec![
+                ("src/fun_a.rs".to_string(), FilePhase::Built),
+                ("src/fun_b.rs".to_string(), FilePhase::Tested),
+            ]
+        );
+
+        stats.record_file_completion("src/fun_a.rs".to_string(), 1, false, 0);
+        stats.record_file_skipped("src/fun_b.rs".to_string());
+        assert!(stats.interrupted_files().is_empty());
+
+        // Stats files written before phase markers existed still load.
+        let legacy: TranslationStats = serde_json::from_str(
+            r#"{"total_files":0,"success_first_try":0,"success_retry_1":0,"success_retry_2":0,
+               "success_retry_3_plus":0,"restart_count":0,"file_attempts":{},"skipped_files":[]}"#,
+        )
+        .unwrap();
+        assert!(legacy.file_phases.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_with_workspace_skips_interrupted_files() {
+        let temp_dir = tempdir().unwrap();
+        let rust_dir = temp_dir.path().join("rust").join("src");
+        fs::create_dir_all(&rust_dir).unwrap();
+        fs::write(rust_dir.join("fun_half.rs"), "pub fn half() {}\n").unwrap();
+
+        let mut stats = TranslationStats::new();
+        stats.record_file_phase("src/fun_half.rs", FilePhase::Built);
+
+        let changed = stats.reconcile_with_workspace(&rust_dir).unwrap();
+
+        assert!(!changed);
+        assert!(stats.file_attempts.is_empty());
+        assert_eq!(stats.file_phase("src/fun_half.rs"), Some(FilePhase::Built));
+    }
+
     #[test]
     fn test_reconcile_with_workspace_merges_legacy_and_canonical_keys() {
         let temp_dir = tempdir().unwrap();
@@ -1110,4 +1993,77 @@ mod tests {
         assert_eq!(canonicalize_stats_file_key("./mod_a/fun_done.rs"), "src/mod_a/fun_done.rs");
         assert_eq!(canonicalize_stats_file_key(r"mod_a\fun_done.rs"), "src/mod_a/fun_done.rs");
     }
+
+    #[test]
+    fn test_prune_log_dir_removes_oldest_until_under_budget() {
+        use std::time::{Duration, SystemTime};
+
+        let temp_dir = tempdir().unwrap();
+        let logs = temp_dir.path().join("logs");
+        fs::create_dir_all(logs.join("records")).unwrap();
+        let now = SystemTime::now();
+        let write = |name: &str, size: usize, age_secs: u64| {
+            let path = logs.join(name);
+            fs::write(&path, vec![b'x'; size]).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age_secs))
+                .unwrap();
+        };
+        write("oldest.error.txt", 400, 300);
+        write("records/older.json", 400, 200);
+        write("newer.error.txt", 400, 100);
+        write("newest.error.txt", 400, 0);
+
+        // 1600 bytes against a 1000-byte budget: the two oldest files go.
+        assert_eq!(prune_log_dir(&logs, 1000, None, now).unwrap(), 2);
+        assert!(!logs.join("oldest.error.txt").exists());
+        assert!(!logs.join("records/older.json").exists());
+        assert!(logs.join("newer.error.txt").exists());
+        assert!(logs.join("newest.error.txt").exists());
+
+        // Within budget, but older than the age limit.
+        assert_eq!(
+            prune_log_dir(&logs, 1000, Some(Duration::from_secs(50)), now).unwrap(),
+            1
+        );
+        assert!(logs.join("newest.error.txt").exists());
+
+        assert_eq!(
+            prune_log_dir(&temp_dir.path().join("missing"), 0, None, now).unwrap(),
+            0
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_missing_tools_reports_hints_and_honors_env_override() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        for tool in ["git", "cargo", "code_analyse", "c2rust-config", "bindgen"] {
+            let path = dir.path().join(tool);
+            fs::write(&path, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let path_var = std::ffi::OsString::from(dir.path());
+
+        let missing = missing_tools(Some(&path_var), |_| None);
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].starts_with("clang not found: install clang"));
+
+        let custom_clang = dir.path().join("clang-17");
+        let env = |var: &str| {
+            (var == "C2RUST_CLANG").then(|| custom_clang.to_string_lossy().into_owned())
+        };
+        let missing = missing_tools(Some(&path_var), env);
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].contains("clang-17 (from $C2RUST_CLANG) not found"));
+
+        fs::write(&custom_clang, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&custom_clang, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(missing_tools(Some(&path_var), env).is_empty());
+    }
 }
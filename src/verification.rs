@@ -1,7 +1,8 @@
-use crate::{analyzer, builder, diff_display, interaction, suggestion, translator};
+use crate::{analyzer, builder, diff_display, interaction, suggestion, translator, ui};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 /// Signal type returned when a file is skipped, either by the user interactively
 /// or automatically (e.g., when `C2RUST_AUTO_RETRY_ON_MAX_FIX` is set and the
@@ -41,6 +42,106 @@ impl std::fmt::Display for TranslationFailedSignal {
 
 impl std::error::Error for TranslationFailedSignal {}
 
+/// How compiler warnings in translated code are handled (`--warnings`).
+///
+/// - `Fix` (default): the error phase suppresses warnings; once the build is clean a
+///   warning-only pass (Phase 2) feeds remaining warnings to the fixer.
+/// - `Ignore`: warnings are suppressed and never fixed (Phase 2 is skipped).
+/// - `Fail`: warnings are treated as build failures in the error phase and routed
+///   into the error-fix loop; Phase 2 is skipped because nothing is left for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WarningMode {
+    #[default]
+    Fix,
+    Ignore,
+    Fail,
+}
+
+impl WarningMode {
+    /// Whether the error phase builds with `-A warnings`.
+    pub fn suppresses_warnings_in_error_phase(self) -> bool {
+        !matches!(self, WarningMode::Fail)
+    }
+
+    /// Whether the dedicated warning-fix phase (Phase 2) should run.
+    pub fn runs_warning_phase(self) -> bool {
+        matches!(self, WarningMode::Fix)
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            WarningMode::Fix => 0,
+            WarningMode::Ignore => 1,
+            WarningMode::Fail => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => WarningMode::Ignore,
+            2 => WarningMode::Fail,
+            _ => WarningMode::Fix,
+        }
+    }
+}
+
+impl std::str::FromStr for WarningMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fix" => Ok(WarningMode::Fix),
+            "ignore" => Ok(WarningMode::Ignore),
+            "fail" => Ok(WarningMode::Fail),
+            other => Err(format!(
+                "`{other}` is not a valid warning mode (expected fix, ignore or fail)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for WarningMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WarningMode::Fix => "fix",
+            WarningMode::Ignore => "ignore",
+            WarningMode::Fail => "fail",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Process-wide warning mode, set once at the start of `translate_feature`.
+static WARNING_MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the warning mode used by the check/fix loops.
+pub fn set_warning_mode(mode: WarningMode) {
+    WARNING_MODE.store(mode.to_u8(), Ordering::Relaxed);
+}
+
+/// Current warning mode.
+pub fn warning_mode() -> WarningMode {
+    WarningMode::from_u8(WARNING_MODE.load(Ordering::Relaxed))
+}
+
+/// Turn a `cargo check` result from the error phase into a build error to fix, if any.
+///
+/// Under [`WarningMode::Fail`] a successful check that still reported warnings is
+/// converted into an error so it is routed into the error-fix loop.
+fn check_result_to_build_error(
+    mode: WarningMode,
+    check_result: Result<Option<String>>,
+) -> Option<anyhow::Error> {
+    match check_result {
+        Ok(Some(warnings)) if mode == WarningMode::Fail => Some(anyhow::anyhow!(
+            "Warnings treated as errors (--warnings fail):\n{}",
+            warnings
+        )),
+        Ok(_) => None,
+        Err(e) => Some(e),
+    }
+}
+
 /// Outcome of the automatic retry decision when `C2RUST_AUTO_RETRY_ON_MAX_FIX` is set.
 #[derive(Debug, PartialEq)]
 enum AutoRetryOutcome {
@@ -69,7 +170,7 @@ fn resolve_auto_retry_outcome(is_last_attempt: bool) -> Option<AutoRetryOutcome>
 
 /// Display warning message about retry directly operation
 pub fn display_retry_directly_warning() {
-    println!("│");
+    ui::blank();
     println!("│ {}", "⚠ Warning: This will:".bright_yellow().bold());
     println!(
         "│ {}",
@@ -80,18 +181,17 @@ pub fn display_retry_directly_warning() {
         "  • Re-translate from C source completely".bright_yellow()
     );
     println!("│ {}", "  • Clear all previous suggestions".bright_yellow());
-    println!("│");
+    ui::blank();
 }
 
 /// Group messages (errors or warnings) by file and apply a fix to each affected file.
 ///
-/// This is the shared logic used by both `build_and_fix_loop` (errors) and
-/// `build_and_fix_warnings_loop` (warnings). `is_warning` controls whether
+/// This is the shared logic used by both `execute_code_error_check_with_fix_loop` (errors)
+/// and `execute_code_warning_check_with_fix_loop` (warnings). `is_warning` controls whether
 /// `apply_warning_fix` (true) or `apply_error_fix` (false) is called for each fix.
 ///
 /// Returns the number of fixes applied in this call.
 fn apply_fixes_for_messages<F>(
-    message: &str,
     fallback_error: &anyhow::Error,
     feature: &str,
     file_type: &str,
@@ -105,13 +205,15 @@ where
 {
     let mut count = 0usize;
 
-    let file_messages = match crate::error_handler::group_errors_by_file(message, feature) {
+    // cargo 的 JSON 诊断可直接按文件分组；没有时回退到从文本中解析
+    let grouped = match crate::diagnostics::diagnostics_of(fallback_error) {
+        Some(diagnostics) => crate::error_handler::group_diagnostics_by_file(diagnostics, feature),
+        None => crate::error_handler::group_errors_by_file(&fallback_error.to_string(), feature),
+    };
+    let file_messages = match grouped {
         Ok(v) => v,
         Err(e) => {
-            println!(
-                "│ {}",
-                format!("⚠ Failed to group messages by file: {}", e).yellow()
-            );
+            ui::warning(format!("Failed to group messages by file: {}", e));
             vec![]
         }
     };
@@ -129,10 +231,10 @@ where
         }
         for (msg_file, file_msg) in &file_messages {
             let Some(file_stem) = msg_file.file_stem().and_then(|s| s.to_str()) else {
-                println!(
-                    "│ {}",
-                    format!("⚠ Skipping file with invalid name: {}", msg_file.display()).yellow()
-                );
+                ui::warning(format!(
+                    "Skipping file with invalid name: {}",
+                    msg_file.display()
+                ));
                 continue;
             };
             let (msg_file_type, _) =
@@ -154,10 +256,7 @@ where
                 ) {
                     Ok(()) => count += 1,
                     Err(e) => {
-                        println!(
-                            "│ {}",
-                            format!("⚠ Warning fix failed, continuing: {:#}", e).yellow()
-                        );
+                        ui::warning(format!("Warning fix failed, continuing: {:#}", e));
                     }
                 }
             } else {
@@ -171,10 +270,7 @@ where
                 ) {
                     Ok(()) => count += 1,
                     Err(e) => {
-                        println!(
-                            "│ {}",
-                            format!("⚠ Error fix failed, continuing: {:#}", e).yellow()
-                        );
+                        ui::warning(format!("Error fix failed, continuing: {:#}", e));
                     }
                 }
             }
@@ -192,10 +288,7 @@ where
             ) {
                 Ok(()) => count += 1,
                 Err(e) => {
-                    println!(
-                        "│ {}",
-                        format!("⚠ Warning fix failed, continuing: {:#}", e).yellow()
-                    );
+                    ui::warning(format!("Warning fix failed, continuing: {:#}", e));
                 }
             }
         } else {
@@ -209,10 +302,7 @@ where
             ) {
                 Ok(()) => count += 1,
                 Err(e) => {
-                    println!(
-                        "│ {}",
-                        format!("⚠ Error fix failed, continuing: {:#}", e).yellow()
-                    );
+                    ui::warning(format!("Error fix failed, continuing: {:#}", e));
                 }
             }
         }
@@ -221,6 +311,83 @@ where
     Ok(count)
 }
 
+/// [`build_and_fix_loop`] 中一次修复的结果
+pub(crate) enum FixStep {
+    /// 已应用修复，重新构建
+    Applied,
+    /// 修复失败（如修复脚本出错），给出警告后重新构建
+    Failed(anyhow::Error),
+    /// 无法继续修复（如错误不在当前文件中），结束循环
+    Stop,
+}
+
+/// 构建并修复错误，直到构建通过或已修复 `max_fix_attempts` 次（不重新翻译，供 `fix` 子命令使用）
+///
+/// 每次修复后都会重新构建，最后一次修复的结果同样得到验证。`build` 与 `fix` 返回的错误
+/// 直接向上传递；修复本身失败时应返回 [`FixStep::Failed`]。
+///
+/// 返回 Ok((build_passes, fixes_applied))
+pub(crate) fn build_and_fix_loop<B, F>(
+    max_fix_attempts: usize,
+    mut build: B,
+    mut fix: F,
+) -> Result<(bool, usize)>
+where
+    B: FnMut() -> Result<()>,
+    F: FnMut(&anyhow::Error) -> Result<FixStep>,
+{
+    let mut fixes_applied = 0usize;
+    for fix_attempt in 0..=max_fix_attempts {
+        ui::blank();
+        let title = if fix_attempt == 0 {
+            "Building Rust project".to_string()
+        } else {
+            format!(
+                "Building Rust project (after fix {}/{})",
+                fix_attempt, max_fix_attempts
+            )
+        };
+        println!("│ {}", title.bright_blue().bold());
+        let build_error = match build() {
+            Ok(()) => {
+                println!("│ {}", "✓ Build successful!".bright_green().bold());
+                return Ok((true, fixes_applied));
+            }
+            Err(e) => e,
+        };
+        // 构建机故障不是代码问题，交给修复器没有意义
+        if build_error
+            .downcast_ref::<builder::TransientBuildError>()
+            .is_some()
+        {
+            return Err(build_error);
+        }
+        if fix_attempt == max_fix_attempts {
+            break;
+        }
+        match fix(&build_error)? {
+            FixStep::Applied => fixes_applied += 1,
+            FixStep::Failed(e) => ui::warning(format!("Error fix failed, continuing: {:#}", e)),
+            FixStep::Stop => break,
+        }
+    }
+    Ok((false, fixes_applied))
+}
+
+/// 错误修复循环所处的翻译尝试及其上限
+#[derive(Debug, Clone, Copy)]
+pub struct FixLoopLimits {
+    /// 当前是第几次翻译（从 1 开始）
+    pub attempt_number: usize,
+    /// 是否已是最后一次翻译机会
+    pub is_last_attempt: bool,
+    pub max_error_fix_attempts: usize,
+    pub max_translation_attempts: usize,
+    pub show_full_output: bool,
+    /// 测试配置不可用，构建/测试流程中跳过测试阶段
+    pub skip_test: bool,
+}
+
 /// 在循环中构建并修复错误
 ///
 /// 返回 Ok((build_successful, fix_attempts, had_restart))：
@@ -233,22 +400,23 @@ pub fn execute_code_error_check_with_fix_loop<F>(
     rs_file: &Path,
     file_name: &str,
     format_progress: &F,
-    is_last_attempt: bool,
-    attempt_number: usize,
-    max_error_fix_attempts: usize,
-    show_full_output: bool,
-    skip_test: bool,
+    limits: FixLoopLimits,
 ) -> Result<(bool, usize, bool)>
 where
     F: Fn(&str) -> String,
 {
+    let FixLoopLimits {
+        max_error_fix_attempts,
+        show_full_output,
+        ..
+    } = limits;
     let mut fix_attempts = 0usize;
-    println!("│ {}", "Updating code analysis...".bright_blue());
-    analyzer::update_code_analysis(feature)?;
-    println!("│ {}", "✓ Code analysis updated".bright_green());
+    ui::info("Updating code analysis...");
+    let updated = analyzer::update_code_analysis(feature)?;
+    ui::success(analyzer::update_status_message(updated));
     for attempt in 1..=max_error_fix_attempts {
-        println!("│");
-        println!("│ {}", format_progress("Check").bright_magenta().bold());
+        ui::blank();
+        ui::header(format_progress("Check"));
         println!(
             "│ {}",
             format!(
@@ -259,25 +427,34 @@ where
             .bold()
         );
 
-        match builder::cargo_check(feature, true, show_full_output) {
-            Ok(_) => {
+        let mode = warning_mode();
+        let check_result = builder::cargo_check(
+            feature,
+            mode.suppresses_warnings_in_error_phase(),
+            show_full_output,
+        );
+        match check_result_to_build_error(mode, check_result) {
+            None => {
                 println!("│ {}", "✓ Check successful!".bright_green().bold());
                 return Ok((true, fix_attempts, false));
             }
-            Err(build_error) => {
+            Some(build_error) => {
+                // 构建机故障不是代码问题，交给修复器没有意义
+                if build_error
+                    .downcast_ref::<builder::TransientBuildError>()
+                    .is_some()
+                {
+                    return Err(build_error);
+                }
                 if attempt == max_error_fix_attempts {
                     let (build_successful, extra_fix_attempts, had_restart) =
                         handle_max_fix_attempts_reached(
                             build_error,
                             file_name,
                             rs_file,
-                            is_last_attempt,
-                            attempt_number,
-                            max_error_fix_attempts,
                             feature,
                             file_type,
-                            show_full_output,
-                            skip_test,
+                            limits,
                         )?;
                     return Ok((
                         build_successful,
@@ -287,7 +464,6 @@ where
                 } else {
                     // Apply fixes using the shared helper (error phase, is_warning=false)
                     fix_attempts += apply_fixes_for_messages(
-                        &build_error.to_string(),
                         &build_error,
                         feature,
                         file_type,
@@ -300,9 +476,9 @@ where
             }
         }
 
-        println!("│ {}", "Updating code analysis...".bright_blue());
-        analyzer::update_code_analysis(feature)?;
-        println!("│ {}", "✓ Code analysis updated".bright_green());
+        ui::info("Updating code analysis...");
+        let updated = analyzer::update_code_analysis(feature)?;
+        ui::success(analyzer::update_status_message(updated));
     }
 
     Ok((false, fix_attempts, false))
@@ -332,11 +508,8 @@ where
 {
     let mut fix_attempts = 0usize;
     for attempt in 1..=max_warning_fix_attempts {
-        println!("│");
-        println!(
-            "│ {}",
-            format_progress("Warning Check").bright_magenta().bold()
-        );
+        ui::blank();
+        ui::header(format_progress("Warning Check"));
         println!(
             "│ {}",
             format!(
@@ -355,7 +528,6 @@ where
             Ok(Some(warnings)) => {
                 let warning_error = anyhow::anyhow!("{}", warnings);
                 fix_attempts += apply_fixes_for_messages(
-                    &warnings,
                     &warning_error,
                     feature,
                     file_type,
@@ -368,23 +540,20 @@ where
             Err(e) => {
                 // Check failed during warning phase -- unexpected since errors were already fixed.
                 // Treat as non-fatal: log and stop the warning loop but do not abort file processing.
-                println!(
-                    "│ {}",
-                    format!("✗ Unexpected check error during warning phase: {}", e).red()
-                );
+                ui::error(format!(
+                    "Unexpected check error during warning phase: {}",
+                    e
+                ));
                 return Ok(fix_attempts);
             }
         }
 
-        println!("│ {}", "Updating code analysis...".bright_blue());
-        analyzer::update_code_analysis(feature)?;
-        println!("│ {}", "✓ Code analysis updated".bright_green());
+        ui::info("Updating code analysis...");
+        let updated = analyzer::update_code_analysis(feature)?;
+        ui::success(analyzer::update_status_message(updated));
     }
 
-    println!(
-        "│ {}",
-        "⚠ Maximum warning fix attempts reached, continuing with remaining warnings.".yellow()
-    );
+    ui::warning("Maximum warning fix attempts reached, continuing with remaining warnings.");
     Ok(fix_attempts)
 }
 
@@ -397,15 +566,19 @@ fn handle_max_fix_attempts_reached(
     build_error: anyhow::Error,
     file_name: &str,
     rs_file: &Path,
-    is_last_attempt: bool,
-    attempt_number: usize,
-    max_error_fix_attempts: usize,
     feature: &str,
     file_type: &str,
-    show_full_output: bool,
-    skip_test: bool,
+    limits: FixLoopLimits,
 ) -> Result<(bool, usize, bool)> {
-    println!("│");
+    let FixLoopLimits {
+        attempt_number,
+        is_last_attempt,
+        max_error_fix_attempts,
+        max_translation_attempts,
+        skip_test,
+        ..
+    } = limits;
+    ui::blank();
     println!("│ {}", "⚠ Maximum error-fix attempts reached!".red().bold());
     println!(
         "│ {}",
@@ -423,7 +596,10 @@ fn handle_max_fix_attempts_reached(
     interaction::display_file_paths(Some(&c_file), rs_file);
 
     // 使用差异显示进行更好的比较
-    let error_message = format!("✗ Build Error:\n{}", build_error);
+    let error_message = format!(
+        "✗ Build Error:\n{}",
+        crate::diagnostics::display_text(&build_error)
+    );
     if let Err(e) = diff_display::display_code_comparison(
         &c_file,
         rs_file,
@@ -442,7 +618,10 @@ fn handle_max_fix_attempts_reached(
         translator::display_code(rs_file, "─ Rust Code ─", usize::MAX, true);
 
         println!("│ {}", "═══ Build Error ═══".bright_red().bold());
-        println!("│ {}", build_error);
+        println!("│ {}", crate::diagnostics::display_text(&build_error));
+    }
+    if let Some(diagnostics) = crate::diagnostics::diagnostics_of(&build_error) {
+        crate::diagnostics::display_snippets(diagnostics, rs_file);
     }
 
     // 当设置了 C2RUST_AUTO_RETRY_ON_MAX_FIX 时，根据是否还有重试机会，
@@ -463,7 +642,12 @@ fn handle_max_fix_attempts_reached(
                     "Auto-retry enabled (C2RUST_AUTO_RETRY_ON_MAX_FIX): retrying translation automatically."
                         .bright_cyan()
                 );
-                return handle_retry_directly(attempt_number, is_last_attempt, true);
+                return handle_retry_directly(
+                    attempt_number,
+                    max_translation_attempts,
+                    is_last_attempt,
+                    true,
+                );
             }
         }
     }
@@ -472,21 +656,15 @@ fn handle_max_fix_attempts_reached(
     let choice = interaction::prompt_compile_failure_choice()?;
 
     match choice {
-        interaction::FailureChoice::RetryDirectly => {
-            handle_retry_directly(attempt_number, is_last_attempt, false)
-        }
-        interaction::FailureChoice::AddSuggestion => handle_add_suggestion(
-            feature,
-            file_type,
-            rs_file,
-            &build_error,
-            is_last_attempt,
+        interaction::FailureChoice::RetryDirectly => handle_retry_directly(
             attempt_number,
-            file_name,
-            max_error_fix_attempts,
-            show_full_output,
-            skip_test,
+            max_translation_attempts,
+            is_last_attempt,
+            false,
         ),
+        interaction::FailureChoice::AddSuggestion => {
+            handle_add_suggestion(feature, file_type, rs_file, file_name, limits)
+        }
         interaction::FailureChoice::ManualFix => {
             handle_manual_fix(feature, file_type, rs_file, &build_error, skip_test)
         }
@@ -511,12 +689,11 @@ fn handle_max_fix_attempts_reached(
 /// 处理直接重试选项
 fn handle_retry_directly(
     attempt_number: usize,
+    max_translation_attempts: usize,
     is_last_attempt: bool,
     auto_triggered: bool,
 ) -> Result<(bool, usize, bool)> {
-    use crate::util::MAX_TRANSLATION_ATTEMPTS;
-
-    println!("│");
+    ui::blank();
     if auto_triggered {
         println!(
             "│ {}",
@@ -548,7 +725,7 @@ fn handle_retry_directly(
     }
 
     // 重新翻译（清空并重新生成 rs 文件）
-    let remaining_retries = MAX_TRANSLATION_ATTEMPTS - attempt_number;
+    let remaining_retries = max_translation_attempts.saturating_sub(attempt_number);
     println!(
         "│ {}",
         format!(
@@ -557,11 +734,8 @@ fn handle_retry_directly(
         )
         .bright_cyan()
     );
-    println!(
-        "│ {}",
-        "Note: The translator will overwrite the existing file content.".bright_blue()
-    );
-    println!("│ {}", "✓ Retry scheduled".bright_green());
+    ui::info("Note: The translator will overwrite the existing file content.");
+    ui::success("Retry scheduled");
     Ok((false, 0, true)) // 发出重试信号，且使用了重来功能
 }
 
@@ -570,17 +744,17 @@ fn handle_add_suggestion(
     feature: &str,
     file_type: &str,
     rs_file: &Path,
-    _build_error: &anyhow::Error,
-    is_last_attempt: bool,
-    attempt_number: usize,
     file_name: &str,
-    max_error_fix_attempts: usize,
-    show_full_output: bool,
-    skip_test: bool,
+    limits: FixLoopLimits,
 ) -> Result<(bool, usize, bool)> {
-    use crate::util::MAX_TRANSLATION_ATTEMPTS;
-
-    println!("│");
+    let FixLoopLimits {
+        attempt_number,
+        is_last_attempt,
+        max_error_fix_attempts,
+        max_translation_attempts,
+        ..
+    } = limits;
+    ui::blank();
     println!(
         "│ {}",
         "You chose: Add fix suggestion for AI to modify".bright_cyan()
@@ -601,7 +775,7 @@ fn handle_add_suggestion(
 
     // 如果我们仍然可以重试翻译，则执行
     if !is_last_attempt {
-        let remaining_retries = MAX_TRANSLATION_ATTEMPTS - attempt_number;
+        let remaining_retries = max_translation_attempts.saturating_sub(attempt_number);
         println!(
             "│ {}",
             format!(
@@ -610,26 +784,23 @@ fn handle_add_suggestion(
             )
             .bright_cyan()
         );
-        println!(
-            "│ {}",
-            "Note: The translator will overwrite the existing file content.".bright_blue()
-        );
-        println!("│ {}", "✓ Retry scheduled".bright_green());
+        ui::info("Note: The translator will overwrite the existing file content.");
+        ui::success("Retry scheduled");
         Ok((false, 0, false)) // 发出重试信号，未使用重来功能
     } else {
         // 没有更多翻译重试，但用户输入了新建议
         // 不清空 .rs 文件，而是用新建议重新开始完整的修复循环
-        println!("│");
+        ui::blank();
         println!("│ {}", "No translation retries remaining.".bright_yellow());
         println!(
             "│ {}",
             "Starting new fix-and-verify cycle with your suggestion...".bright_cyan()
         );
-        println!(
-            "│ {}",
-            format!("(You will have {} error-fix attempts)", max_error_fix_attempts).bright_blue()
-        );
-        println!("│");
+        ui::info(format!(
+            "(You will have {} error-fix attempts)",
+            max_error_fix_attempts
+        ));
+        ui::blank();
 
         // 调用 execute_code_error_check_with_fix_loop 重新开始完整的修复循环
         // 注意：这里传入 is_last_attempt=true 表示这是最后一次翻译机会
@@ -642,11 +813,11 @@ fn handle_add_suggestion(
                 rs_file,
                 file_name,
                 &|op: &str| format!("Suggestion-based fix - {}", op),
-                true, // is_last_attempt: 翻译层面确实是最后一次了
-                attempt_number,
-                max_error_fix_attempts,
-                show_full_output,
-                skip_test,
+                // is_last_attempt: 翻译层面确实是最后一次了
+                FixLoopLimits {
+                    is_last_attempt: true,
+                    ..limits
+                },
             )?;
 
         Ok((build_successful, recursive_fix_attempts, had_restart))
@@ -670,20 +841,17 @@ fn handle_manual_fix(
     build_error: &anyhow::Error,
     skip_test: bool,
 ) -> Result<(bool, usize, bool)> {
-    println!("│");
+    ui::blank();
     println!("│ {}", "You chose: Manual fix".bright_cyan());
 
     // 尝试打开文件（多文件时展示选择界面）
     match interaction::open_files_for_manual_fix(&collect_fix_files(feature, rs_file, build_error))
     {
         Ok(_) => {
-            // Vim 编辑后，重复尝试构建并允许用户决定是重试还是退出
+            // 编辑器编辑后，重复尝试构建并允许用户决定是重试还是退出
             loop {
-                println!("│");
-                println!(
-                    "│ {}",
-                    "Vim editing completed. Running full build and test...".bright_blue()
-                );
+                ui::blank();
+                ui::info("Editing completed. Running full build and test...");
 
                 // 手动编辑后执行完整构建流程
                 match builder::run_full_build_and_test_interactive(feature, file_type, rs_file, skip_test) {
@@ -697,14 +865,11 @@ fn handle_manual_fix(
                         return Ok((true, 0, false));
                     }
                     Err(e) => {
-                        println!(
-                            "│ {}",
-                            "✗ Build or tests still failing after manual fix".red()
-                        );
+                        ui::error("Build or tests still failing after manual fix");
 
                         // 从新的错误中提取涉及的文件列表
                         let fix_files = collect_fix_files(feature, rs_file, &e);
-                        println!("│");
+                        ui::blank();
                         println!(
                             "│ {}",
                             format!("Found {} file(s) with errors:", fix_files.len())
@@ -716,7 +881,7 @@ fn handle_manual_fix(
                         }
 
                         // 询问用户是否想再试一次
-                        println!("│");
+                        ui::blank();
                         println!(
                             "│ {}",
                             "Build or tests still have errors. What would you like to do?".yellow()
@@ -727,7 +892,7 @@ fn handle_manual_fix(
                             interaction::FailureChoice::ManualFix => {
                                 println!(
                                     "│ {}",
-                                    "Opening Vim again for another manual fix attempt..."
+                                    "Opening editor again for another manual fix attempt..."
                                         .bright_cyan()
                                 );
                                 // 使用已提取的文件列表（不重新解析）
@@ -760,9 +925,9 @@ fn handle_manual_fix(
             }
         }
         Err(e) => {
-            println!("│ {}", format!("Failed to open Vim: {}", e).red());
+            println!("│ {}", format!("Failed to open editor: {}", e).red());
             Err(e).context(format!(
-                "Failed to open file {} in Vim for manual editing",
+                "Failed to open file {} in editor for manual editing",
                 rs_file.display()
             ))
         }
@@ -773,6 +938,84 @@ fn handle_manual_fix(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_warning_mode_selection() {
+        assert_eq!("fix".parse::<WarningMode>(), Ok(WarningMode::Fix));
+        assert_eq!("Ignore".parse::<WarningMode>(), Ok(WarningMode::Ignore));
+        assert_eq!("FAIL".parse::<WarningMode>(), Ok(WarningMode::Fail));
+        assert!("deny".parse::<WarningMode>().is_err());
+        assert_eq!(WarningMode::default(), WarningMode::Fix);
+
+        assert!(WarningMode::Fix.suppresses_warnings_in_error_phase());
+        assert!(WarningMode::Fix.runs_warning_phase());
+        assert!(WarningMode::Ignore.suppresses_warnings_in_error_phase());
+        assert!(!WarningMode::Ignore.runs_warning_phase());
+        assert!(!WarningMode::Fail.suppresses_warnings_in_error_phase());
+        assert!(!WarningMode::Fail.runs_warning_phase());
+    }
+
+    #[test]
+    fn test_build_and_fix_loop_verifies_the_last_fix() {
+        // The build passes once two fixes were applied; both fit in two attempts.
+        let fixes = std::cell::Cell::new(0);
+        let result = build_and_fix_loop(
+            2,
+            || {
+                if fixes.get() < 2 {
+                    anyhow::bail!("error[E0308]: mismatched types")
+                }
+                Ok(())
+            },
+            |_| {
+                fixes.set(fixes.get() + 1);
+                Ok(FixStep::Applied)
+            },
+        )
+        .unwrap();
+        assert_eq!(result, (true, 2));
+
+        let builds = std::cell::Cell::new(0);
+        let result = build_and_fix_loop(
+            3,
+            || {
+                builds.set(builds.get() + 1);
+                anyhow::bail!("error[E0308]: mismatched types")
+            },
+            |_| Ok(FixStep::Stop),
+        )
+        .unwrap();
+        assert_eq!(result, (false, 0));
+        assert_eq!(builds.get(), 1, "Stop ends the loop without rebuilding");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_set_warning_mode_round_trips() {
+        for mode in [WarningMode::Ignore, WarningMode::Fail, WarningMode::Fix] {
+            set_warning_mode(mode);
+            assert_eq!(warning_mode(), mode);
+        }
+    }
+
+    #[test]
+    fn test_fail_mode_routes_warnings_into_fix_path() {
+        let warnings = "warning: unused variable: `x`\n  --> src/fun_a.rs:1:5".to_string();
+
+        let err = check_result_to_build_error(WarningMode::Fail, Ok(Some(warnings.clone())))
+            .expect("warnings must become a build error in fail mode");
+        assert!(err.to_string().contains("unused variable"));
+
+        assert!(
+            check_result_to_build_error(WarningMode::Fix, Ok(Some(warnings.clone()))).is_none()
+        );
+        assert!(check_result_to_build_error(WarningMode::Ignore, Ok(Some(warnings))).is_none());
+        assert!(check_result_to_build_error(WarningMode::Fail, Ok(None)).is_none());
+        assert!(
+            check_result_to_build_error(WarningMode::Ignore, Err(anyhow::anyhow!("E0308")))
+                .is_some()
+        );
+    }
+
     /// Save the current value of an environment variable and return a `scopeguard`
     /// that restores it (or removes it if it was absent) when dropped.
     fn env_guard(key: &'static str) -> impl Drop {
@@ -835,8 +1078,7 @@ mod tests {
         let rs_file = feature_src_dir.join("nonexistent.rs");
 
         let result = apply_fixes_for_messages(
-            "warning: unused\n  --> src/nonexistent.rs:1:1",
-            &anyhow::anyhow!("dummy"),
+            &anyhow::anyhow!("warning: unused\n  --> src/nonexistent.rs:1:1"),
             feature,
             "var",
             &rs_file,